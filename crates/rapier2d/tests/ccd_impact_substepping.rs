@@ -0,0 +1,174 @@
+//! `CcdMode::ImpactSubstepping`: fast bodies resolve their impacts within the step they happen
+//! in — they bounce and slide for the rest of the step instead of stopping at the impact like
+//! with the default `CcdMode::MotionClamping`.
+
+use rapier2d::prelude::*;
+
+fn world(mode: CcdMode) -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    // No gravity: keep the fast bodies on clean straight paths.
+    world.gravity = Vector::ZERO;
+    world.integration_parameters.ccd_mode = mode;
+    world
+}
+
+/// A thin fixed wall at x = 0, its faces at x = ±0.1.
+fn add_fixed_wall(world: &mut PhysicsWorld, restitution: Real) {
+    world
+        .colliders
+        .insert(ColliderBuilder::cuboid(0.1, 10.0).restitution(restitution));
+}
+
+/// A ball of radius 0.2 at `translation`, moving at `linvel`.
+fn add_fast_ball(
+    world: &mut PhysicsWorld,
+    translation: Vector,
+    linvel: Vector,
+    collider: ColliderBuilder,
+) -> RigidBodyHandle {
+    let (ball, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(translation)
+            .linvel(linvel),
+        collider,
+    );
+    ball
+}
+
+#[test]
+fn fast_ball_bounces_off_a_wall_within_the_step() {
+    let mut world = world(CcdMode::ImpactSubstepping);
+    add_fixed_wall(&mut world, 1.0);
+    let ball = add_fast_ball(
+        &mut world,
+        Vector::new(-3.0, 0.0),
+        Vector::new(200.0, 0.0),
+        ColliderBuilder::ball(0.2).restitution(1.0),
+    );
+
+    // 3.33 m of motion: the ball touches the wall after 2.7 m, then flies back for 0.63 m.
+    world.step();
+    let x = world.bodies[ball].translation().x;
+    let vx = world.bodies[ball].linvel().x;
+    assert!((-1.0..-0.8).contains(&x), "x = {x}");
+    assert!((vx + 200.0).abs() < 1.0, "vx = {vx}");
+
+    world.step();
+    assert!(world.bodies[ball].translation().x < -4.0);
+}
+
+#[test]
+fn motion_clamping_stops_the_ball_at_the_wall() {
+    let mut world = world(CcdMode::MotionClamping);
+    add_fixed_wall(&mut world, 1.0);
+    let ball = add_fast_ball(
+        &mut world,
+        Vector::new(-3.0, 0.0),
+        Vector::new(200.0, 0.0),
+        ColliderBuilder::ball(0.2).restitution(1.0),
+    );
+
+    world.step();
+    let x = world.bodies[ball].translation().x;
+    assert!((-0.35..-0.25).contains(&x), "x = {x}");
+    // The velocity is untouched.
+    let vx = world.bodies[ball].linvel().x;
+    assert!((vx - 200.0).abs() < 1.0e-3, "vx = {vx}");
+}
+
+#[test]
+fn fast_ball_slides_along_the_ground() {
+    let slide = |mode, max_ccd_substeps| {
+        let mut world = world(mode);
+        world.integration_parameters.max_ccd_substeps = max_ccd_substeps;
+        // The ground's surface is at y = 0.
+        world.colliders.insert(
+            ColliderBuilder::cuboid(50.0, 0.1)
+                .translation(Vector::new(0.0, -0.1))
+                .friction(0.0),
+        );
+        let ball = add_fast_ball(
+            &mut world,
+            Vector::new(0.0, 2.0),
+            Vector::new(200.0, -200.0),
+            ColliderBuilder::ball(0.2).friction(0.0).restitution(0.0),
+        );
+        world.step();
+        (
+            world.bodies[ball].translation(),
+            world.bodies[ball].linvel(),
+        )
+    };
+
+    // The ball lands after 1.8 m along x, and keeps sliding for the rest of the step.
+    let (pos, vel) = slide(CcdMode::ImpactSubstepping, 4);
+    assert!(pos.x > 3.0, "{pos:?}");
+    assert!((0.1..0.4).contains(&pos.y), "{pos:?}");
+    assert!((vel.x - 200.0).abs() < 1.0, "{vel:?}");
+    assert!(vel.y.abs() < 1.0, "{vel:?}");
+
+    // Without substeps, the motion clamping drops the rest of the step at the impact.
+    let (pos, _) = slide(CcdMode::MotionClamping, 1);
+    assert!(pos.x < 2.0, "{pos:?}");
+}
+
+#[test]
+fn bullets_transfer_their_momentum_to_dynamic_targets() {
+    let mut world = world(CcdMode::ImpactSubstepping);
+    let (target, _) = world.insert(
+        RigidBodyBuilder::dynamic(),
+        ColliderBuilder::cuboid(0.2, 0.2).restitution(1.0),
+    );
+    let (bullet, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(-3.0, 0.0))
+            .linvel(Vector::new(200.0, 0.0))
+            .ccd_enabled(true),
+        ColliderBuilder::ball(0.2).restitution(1.0),
+    );
+    let momentum = |world: &PhysicsWorld| {
+        [bullet, target]
+            .iter()
+            .map(|h| world.bodies[*h].mass() * world.bodies[*h].linvel().x)
+            .sum::<Real>()
+    };
+    let initial_momentum = momentum(&world);
+
+    world.step();
+    // The lighter bullet bounces back, the target flies away.
+    assert!(world.bodies[bullet].linvel().x < 0.0);
+    assert!(world.bodies[target].linvel().x > 150.0);
+    assert!((momentum(&world) - initial_momentum).abs() < 0.01 * initial_momentum);
+}
+
+#[test]
+fn simultaneous_impacts_on_one_target_are_solved_together() {
+    let mut world = world(CcdMode::ImpactSubstepping);
+    let (target, _) = world.insert(
+        RigidBodyBuilder::dynamic(),
+        ColliderBuilder::cuboid(0.2, 0.2).restitution(1.0),
+    );
+    // Two identical bullets hitting the target from both sides at the same time.
+    let bullets = [-1.0, 1.0].map(|side: Real| {
+        world
+            .insert(
+                RigidBodyBuilder::dynamic()
+                    .translation(Vector::new(3.0 * side, 0.0))
+                    .linvel(Vector::new(-200.0 * side, 0.0))
+                    .ccd_enabled(true),
+                ColliderBuilder::ball(0.2).restitution(1.0),
+            )
+            .0
+    });
+
+    world.step();
+    // Both bullets bounce back, and the total momentum stays zero.
+    assert!(world.bodies[bullets[0]].linvel().x < -50.0);
+    assert!(world.bodies[bullets[1]].linvel().x > 50.0);
+    let momentum: Real = [target, bullets[0], bullets[1]]
+        .iter()
+        .map(|h| world.bodies[*h].mass() * world.bodies[*h].linvel().x)
+        .sum();
+    let bullet_momentum = world.bodies[bullets[0]].mass() * 200.0;
+    assert!(momentum.abs() < 0.01 * bullet_momentum, "{momentum}");
+}
@@ -0,0 +1,163 @@
+//! Anisotropic friction: `ColliderMaterial::anisotropic_friction` makes the friction differ
+//! along a direction of the collider's local frame and orthogonally to it, with both
+//! friction models and for multibody links.
+
+use rapier3d::prelude::*;
+
+mod common;
+
+use common::make_multibody_root;
+
+const FRICTION_MODELS: [FrictionModel; 2] = [FrictionModel::Simplified, FrictionModel::Coulomb];
+
+fn world(friction_model: FrictionModel, ground: ColliderBuilder) -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world.integration_parameters.friction_model = friction_model;
+    world.insert(
+        RigidBodyBuilder::fixed(),
+        ground.friction_combine_rule(CoefficientCombineRule::Min),
+    );
+    world
+}
+
+/// A box resting on the ground, sliding diagonally.
+fn sliding_box(
+    world: &mut PhysicsWorld,
+    body: RigidBodyBuilder,
+    collider: ColliderBuilder,
+) -> RigidBodyHandle {
+    world
+        .insert(
+            body.translation(Vector::new(0.0, 0.6, 0.0))
+                .linvel(Vector::new(3.0, 0.0, 3.0)),
+            collider.friction_combine_rule(CoefficientCombineRule::Min),
+        )
+        .0
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+#[test]
+fn anisotropic_ground_only_resists_the_secondary_direction() {
+    for friction_model in FRICTION_MODELS {
+        // Frictionless along X, rough along Z.
+        let ground = ColliderBuilder::cuboid(50.0, 0.1, 50.0)
+            .friction(0.0)
+            .anisotropic_friction(Vector::X, 1.0);
+        let mut world = world(friction_model, ground);
+        let handle = sliding_box(
+            &mut world,
+            RigidBodyBuilder::dynamic().lock_rotations(),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5).friction(1.0),
+        );
+        run(&mut world, 60);
+
+        let linvel = world.bodies[handle].linvel();
+        assert!(linvel.x > 2.9, "{friction_model:?}: {linvel:?}");
+        assert!(linvel.z.abs() < 0.05, "{friction_model:?}: {linvel:?}");
+    }
+}
+
+#[test]
+fn anisotropic_ground_only_resists_the_secondary_direction_of_multibodies() {
+    // Frictionless along X, rough along Z.
+    let ground = ColliderBuilder::cuboid(50.0, 0.1, 50.0)
+        .friction(0.0)
+        .anisotropic_friction(Vector::X, 1.0);
+    let mut world = world(FrictionModel::default(), ground);
+    // Rotations aren't locked on multibodies: keep the friction low enough not to tip the box.
+    let handle = sliding_box(
+        &mut world,
+        RigidBodyBuilder::dynamic(),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5).friction(0.4),
+    );
+    make_multibody_root(&mut world, handle);
+    run(&mut world, 60);
+
+    let linvel = world.bodies[handle].linvel();
+    assert!(linvel.x > 2.9, "{linvel:?}");
+    assert!(linvel.z.abs() < 0.05, "{linvel:?}");
+}
+
+#[test]
+fn anisotropic_direction_follows_the_collider_rotation() {
+    for friction_model in FRICTION_MODELS {
+        let ground = ColliderBuilder::cuboid(50.0, 0.1, 50.0).friction(1.0);
+        let mut world = world(friction_model, ground);
+        // A "ski" gliding along its local X axis, rotated so that it points along world Z.
+        let handle = sliding_box(
+            &mut world,
+            RigidBodyBuilder::dynamic()
+                .rotation(Vector::new(0.0, -std::f32::consts::FRAC_PI_2, 0.0))
+                .lock_rotations(),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5)
+                .friction(0.0)
+                .anisotropic_friction(Vector::X, 1.0),
+        );
+        run(&mut world, 60);
+
+        let linvel = world.bodies[handle].linvel();
+        assert!(linvel.x.abs() < 0.05, "{friction_model:?}: {linvel:?}");
+        assert!(linvel.z > 2.9, "{friction_model:?}: {linvel:?}");
+    }
+}
+
+#[test]
+fn anisotropic_friction_coefficients_are_combined_per_direction() {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world.insert(
+        RigidBodyBuilder::fixed(),
+        ColliderBuilder::cuboid(50.0, 0.1, 50.0)
+            .friction(0.2)
+            .anisotropic_friction(Vector::X, 0.6)
+            .friction_combine_rule(CoefficientCombineRule::Max),
+    );
+    let handle = world
+        .insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.6, 0.0)),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5).friction(0.4),
+        )
+        .1;
+    run(&mut world, 2);
+
+    let pair = world
+        .narrow_phase
+        .contact_pairs()
+        .find(|pair| pair.collider1 == handle || pair.collider2 == handle)
+        .unwrap();
+    let manifold = &pair.manifolds[0];
+    assert!(manifold.data.is_friction_anisotropic());
+    assert!((manifold.data.friction_dir.x.abs() - 1.0).abs() < 1.0e-5);
+    assert_eq!(manifold.data.friction, 0.4);
+    assert_eq!(manifold.data.secondary_friction, 0.6);
+}
+
+#[test]
+fn isotropic_contacts_have_no_friction_direction() {
+    let mut world = world(
+        FrictionModel::default(),
+        ColliderBuilder::cuboid(50.0, 0.1, 50.0),
+    );
+    let handle = world
+        .insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.6, 0.0)),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5).friction(0.7),
+        )
+        .1;
+    run(&mut world, 2);
+
+    let pair = world
+        .narrow_phase
+        .contact_pairs()
+        .find(|pair| pair.collider1 == handle || pair.collider2 == handle)
+        .unwrap();
+    let manifold = &pair.manifolds[0];
+    assert!(!manifold.data.is_friction_anisotropic());
+    assert_eq!(manifold.data.secondary_friction, manifold.data.friction);
+}
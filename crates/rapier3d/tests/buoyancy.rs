@@ -0,0 +1,207 @@
+//! Buoyancy and fluid drag: `BuoyancyController` makes light bodies float at the surface of
+//! its fluid volumes, heavy bodies sink, and the fluid flow drags floating bodies along.
+
+use rapier3d::control::{
+    BuoyancyController, DynamicCharacterController, FluidRegion, FluidVolume, submerged_part,
+};
+use rapier3d::prelude::*;
+
+const WATER_DENSITY: Real = 1000.0;
+
+fn world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world
+}
+
+fn ocean() -> FluidVolume {
+    let mut volume = FluidVolume::new(
+        FluidRegion::HalfSpace {
+            point: Vector::ZERO,
+            normal: Vector::Y,
+        },
+        WATER_DENSITY,
+    );
+    volume.linear_drag = 1000.0;
+    volume.angular_drag = 100.0;
+    volume
+}
+
+/// A ball of radius 0.5 and the given density.
+fn ball(world: &mut PhysicsWorld, y: Real, density: Real) -> RigidBodyHandle {
+    world
+        .insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(0.0, y, 0.0)),
+            ColliderBuilder::ball(0.5).density(density),
+        )
+        .0
+}
+
+fn run(world: &mut PhysicsWorld, controller: &mut BuoyancyController, steps: usize) {
+    for _ in 0..steps {
+        controller.update(
+            world.integration_parameters.dt,
+            world.gravity,
+            &mut world.bodies,
+            &world.colliders,
+            &world.broad_phase,
+            &world.narrow_phase,
+        );
+        world.step();
+    }
+}
+
+#[test]
+fn light_ball_floats_half_submerged() {
+    let mut world = world();
+    let mut controller = BuoyancyController::new();
+    controller.add_volume(ocean());
+    // Half the density of water: the ball floats with its center on the surface.
+    let handle = ball(&mut world, -1.0, WATER_DENSITY / 2.0);
+
+    run(&mut world, &mut controller, 600);
+
+    let rb = &world.bodies[handle];
+    assert!(rb.translation().y.abs() < 0.05, "{:?}", rb.translation());
+    assert!(rb.linvel().length() < 0.05, "{:?}", rb.linvel());
+}
+
+#[test]
+fn rising_fluid_wakes_sleeping_bodies_up() {
+    let mut world = world();
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -3.5, 0.0)),
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+    );
+    let handle = ball(&mut world, -2.5, WATER_DENSITY / 2.0);
+    let mut controller = BuoyancyController::new();
+    run(&mut world, &mut controller, 300);
+    assert!(world.bodies[handle].is_sleeping());
+
+    // The ball rests on the sea floor when the sea fills up.
+    controller.add_volume(ocean());
+    run(&mut world, &mut controller, 600);
+
+    let y = world.bodies[handle].translation().y;
+    assert!(y.abs() < 0.05, "{y}");
+}
+
+#[test]
+fn heavy_ball_sinks() {
+    let mut world = world();
+    let mut controller = BuoyancyController::new();
+    controller.add_volume(FluidVolume::new(
+        FluidRegion::HalfSpace {
+            point: Vector::ZERO,
+            normal: Vector::Y,
+        },
+        WATER_DENSITY,
+    ));
+    let handle = ball(&mut world, 0.0, WATER_DENSITY * 2.0);
+
+    run(&mut world, &mut controller, 120);
+
+    // Sinks with half the gravity acceleration: ~9.8 m in two seconds.
+    let y = world.bodies[handle].translation().y;
+    assert!(y < -8.0 && y > -11.0, "{y}");
+}
+
+#[test]
+fn sensor_pool_surface_is_the_top_of_the_sensor() {
+    let mut world = world();
+    // A pool filling `y ∈ [-10, 0]`.
+    let (_, pool) = world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -5.0, 0.0)),
+        ColliderBuilder::cuboid(10.0, 5.0, 10.0).sensor(true),
+    );
+    let mut volume = ocean();
+    volume.region = FluidRegion::Collider(pool);
+    let mut controller = BuoyancyController::new();
+    controller.add_volume(volume);
+    let handle = ball(&mut world, -1.0, WATER_DENSITY / 2.0);
+
+    run(&mut world, &mut controller, 600);
+
+    let y = world.bodies[handle].translation().y;
+    assert!(y.abs() < 0.05, "{y}");
+}
+
+#[test]
+fn flow_drags_floating_bodies_along() {
+    let mut world = world();
+    let mut volume = ocean();
+    volume.flow_velocity = Vector::new(2.0, 0.0, 0.0);
+    let mut controller = BuoyancyController::new();
+    controller.add_volume(volume);
+    let handle = ball(&mut world, 0.0, WATER_DENSITY / 2.0);
+
+    run(&mut world, &mut controller, 600);
+
+    let linvel = world.bodies[handle].linvel();
+    assert!((linvel.x - 2.0).abs() < 0.1, "{linvel:?}");
+}
+
+#[test]
+fn forces_of_other_controllers_are_kept() {
+    let mut world = world();
+    world.insert_collider(
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+    // A character wading in water up to its waist.
+    let (body, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(0.0, 1.1, 0.0))
+            .lock_rotations(),
+        ColliderBuilder::capsule_y(0.5, 0.3)
+            .density(2.0 * WATER_DENSITY)
+            .friction(0.0),
+    );
+    let mut character = DynamicCharacterController::new(body);
+    let mut buoyancy = BuoyancyController::new();
+    let surface = Vector::new(0.0, 1.0, 0.0);
+    buoyancy.add_volume(FluidVolume::new(
+        FluidRegion::HalfSpace {
+            point: surface,
+            normal: Vector::Y,
+        },
+        WATER_DENSITY,
+    ));
+
+    for _ in 0..240 {
+        buoyancy.update(
+            world.integration_parameters.dt,
+            world.gravity,
+            &mut world.bodies,
+            &world.colliders,
+            &world.broad_phase,
+            &world.narrow_phase,
+        );
+        let queries = world.broad_phase.as_query_pipeline_mut(
+            world.narrow_phase.query_dispatcher(),
+            &mut world.bodies,
+            &mut world.colliders,
+            QueryFilter::default(),
+        );
+        character.update(
+            world.integration_parameters.dt,
+            world.gravity,
+            queries,
+            Vector::ZERO,
+        );
+        world.step();
+    }
+
+    // The body holds the force of each controller: the character's, and the buoyancy of the
+    // submerged part of the character at rest.
+    let rb = &world.bodies[body];
+    assert!(rb.linvel().length() < 1.0e-3);
+    let co = &world.colliders[rb.colliders()[0]];
+    let submerged = submerged_part(co.shape(), co.position(), surface, Vector::Y).unwrap();
+    let buoyancy_force = -world.gravity * (WATER_DENSITY * submerged.volume);
+    let other_forces = rb.user_force() - character.applied_force();
+    assert!(
+        (other_forces - buoyancy_force).length() < 1.0e-2 * buoyancy_force.length(),
+        "{other_forces:?} {buoyancy_force:?}"
+    );
+}
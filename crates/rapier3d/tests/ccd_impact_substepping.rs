@@ -0,0 +1,115 @@
+//! `CcdMode::ImpactSubstepping` in 3D: fast bodies resolve their impacts within the step they
+//! happen in, and only their islands are substepped — the rest of the world takes a single
+//! solve over the whole step.
+
+use rapier3d::prelude::*;
+
+fn world(mode: CcdMode) -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    // No gravity: keep the fast bodies on clean straight paths.
+    world.gravity = Vector::ZERO;
+    world.integration_parameters.ccd_mode = mode;
+    world
+}
+
+/// A thin fixed wall at x = 0, its faces at x = ±0.1.
+fn add_fixed_wall(world: &mut PhysicsWorld) {
+    world
+        .colliders
+        .insert(ColliderBuilder::cuboid(0.1, 10.0, 10.0).restitution(1.0));
+}
+
+/// A bouncy ball of radius 0.2 at `translation`, moving at `linvel`.
+fn add_fast_ball(world: &mut PhysicsWorld, translation: Vector, linvel: Vector) -> RigidBodyHandle {
+    let (ball, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(translation)
+            .linvel(linvel),
+        ColliderBuilder::ball(0.2).restitution(1.0),
+    );
+    ball
+}
+
+#[test]
+fn fast_ball_bounces_off_a_wall_within_the_step() {
+    let mut world = world(CcdMode::ImpactSubstepping);
+    add_fixed_wall(&mut world);
+    let ball = add_fast_ball(
+        &mut world,
+        Vector::new(-3.0, 0.0, 0.0),
+        Vector::new(200.0, 0.0, 0.0),
+    );
+
+    // 3.33 m of motion: the ball touches the wall after 2.7 m, then flies back for 0.63 m.
+    world.step();
+    let x = world.bodies[ball].translation().x;
+    let vx = world.bodies[ball].linvel().x;
+    assert!((-1.0..-0.8).contains(&x), "x = {x}");
+    assert!((vx + 200.0).abs() < 1.0, "vx = {vx}");
+}
+
+#[test]
+fn bullets_transfer_their_momentum_to_dynamic_targets() {
+    let mut world = world(CcdMode::ImpactSubstepping);
+    let (target, _) = world.insert(
+        RigidBodyBuilder::dynamic(),
+        ColliderBuilder::cuboid(0.2, 0.2, 0.2).restitution(1.0),
+    );
+    let (bullet, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(-3.0, 0.0, 0.0))
+            .linvel(Vector::new(200.0, 0.0, 0.0))
+            .ccd_enabled(true),
+        ColliderBuilder::ball(0.2).restitution(1.0),
+    );
+    let momentum = |world: &PhysicsWorld| {
+        [bullet, target]
+            .iter()
+            .map(|h| world.bodies[*h].mass() * world.bodies[*h].linvel().x)
+            .sum::<Real>()
+    };
+    let initial_momentum = momentum(&world);
+
+    world.step();
+    // The lighter bullet bounces back, the target flies away.
+    assert!(world.bodies[bullet].linvel().x < 0.0);
+    assert!(world.bodies[target].linvel().x > 120.0);
+    assert!((momentum(&world) - initial_momentum).abs() < 0.01 * initial_momentum);
+}
+
+#[test]
+fn bodies_away_from_the_impacts_take_a_single_solve() {
+    // A box falling far from the wall, whose step must not be split by the ball's impact.
+    let falling_box = |with_ball: bool| {
+        let mut world = world(CcdMode::ImpactSubstepping);
+        world.gravity = Vector::new(0.0, -9.81, 0.0);
+        add_fixed_wall(&mut world);
+        let (falling_box, _) = world.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::new(-50.0, 0.0, 0.0))
+                .linvel(Vector::new(0.0, 3.0, 0.0)),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+        let ball = with_ball.then(|| {
+            add_fast_ball(
+                &mut world,
+                Vector::new(-3.0, 0.0, 0.0),
+                Vector::new(200.0, 0.0, 0.0),
+            )
+        });
+        world.step();
+        let ball_x = ball.map(|ball| world.bodies[ball].translation().x);
+        (world.bodies[falling_box].translation(), ball_x)
+    };
+
+    let (pos, ball_x) = falling_box(true);
+    let (expected, _) = falling_box(false);
+    // The ball did bounce within the step...
+    let ball_x = ball_x.unwrap();
+    assert!((-1.0..-0.8).contains(&ball_x), "ball x = {ball_x}");
+    // ... while the box moved exactly as in a step without any impact.
+    assert!(
+        (pos - expected).length() < 1.0e-6,
+        "{pos:?} != {expected:?}"
+    );
+}
@@ -0,0 +1,117 @@
+//! `KinematicCharacterController`: ground reporting and `apply_platform_motion`, which carries
+//! characters with the full rigid motion (including yaw) of the platform they stand on.
+
+use rapier3d::control::{EffectiveCharacterMovement, KinematicCharacterController};
+use rapier3d::prelude::*;
+
+/// A 6x6 kinematic platform whose top is at `y = 0.1`.
+fn platform(linvel: Vector, angvel: Vector) -> (PhysicsWorld, ColliderHandle) {
+    let mut world = PhysicsWorld::new();
+    let (_, collider) = world.insert(
+        RigidBodyBuilder::kinematic_velocity_based()
+            .linvel(linvel)
+            .angvel(angvel),
+        ColliderBuilder::cuboid(3.0, 0.1, 3.0),
+    );
+    // Build the broad-phase BVH the scene queries run on.
+    world.step();
+    (world, collider)
+}
+
+/// Moves a ball standing on the platform at `(x, _, 0)` with a zero desired translation for
+/// `steps` steps, returning its final pose and last movement.
+fn ride(
+    world: &mut PhysicsWorld,
+    controller: &KinematicCharacterController,
+    x: Real,
+    steps: usize,
+) -> (Pose, EffectiveCharacterMovement) {
+    let shape = Ball::new(0.5);
+    let mut pose = Pose::translation(x, 0.62, 0.0);
+    let mut movement = EffectiveCharacterMovement::default();
+    for _ in 0..steps {
+        movement = controller.move_shape(
+            world.integration_parameters.dt,
+            &world.query_pipeline(),
+            &shape,
+            &pose,
+            Vector::ZERO,
+            |_| {},
+        );
+        pose.translation += movement.translation;
+        pose.rotation = movement.rotation * pose.rotation;
+        world.step();
+    }
+    (pose, movement)
+}
+
+#[test]
+fn the_ground_and_its_velocity_are_reported() {
+    let (mut world, platform_collider) = platform(Vector::new(2.0, 0.0, 0.0), Vector::ZERO);
+    let controller = KinematicCharacterController::default();
+
+    let (_, movement) = ride(&mut world, &controller, 0.0, 1);
+    assert!(movement.grounded);
+    assert_eq!(movement.ground, Some(platform_collider));
+    assert!((movement.ground_normal - Vector::Y).length() < 1.0e-3);
+    // The velocity to add to the character's when it jumps off.
+    assert!((movement.ground_linvel - Vector::new(2.0, 0.0, 0.0)).length() < 1.0e-3);
+    assert_eq!(movement.ground_angvel, Vector::ZERO);
+    assert_eq!(movement.rotation, Rotation::IDENTITY);
+
+    // Nothing is reported in the air.
+    let movement = controller.move_shape(
+        world.integration_parameters.dt,
+        &world.query_pipeline(),
+        &Ball::new(0.5),
+        &Pose::translation(0.0, 5.0, 0.0),
+        Vector::ZERO,
+        |_| {},
+    );
+    assert!(!movement.grounded);
+    assert_eq!(movement.ground, None);
+    assert_eq!(movement.ground_linvel, Vector::ZERO);
+}
+
+#[test]
+fn characters_are_carried_by_translating_platforms() {
+    let (mut world, _) = platform(Vector::new(2.0, 0.0, 0.0), Vector::ZERO);
+    let controller = KinematicCharacterController {
+        apply_platform_motion: true,
+        ..Default::default()
+    };
+
+    let (pose, movement) = ride(&mut world, &controller, 0.0, 60);
+    assert!(movement.grounded);
+    // One second at 2 m/s.
+    assert!((pose.translation.x - 2.0).abs() < 0.02);
+    assert!((pose.translation.y - 0.62).abs() < 0.02);
+}
+
+#[test]
+fn characters_are_carried_and_turned_by_rotating_platforms() {
+    let (mut world, _) = platform(Vector::ZERO, Vector::Y);
+    let controller = KinematicCharacterController {
+        apply_platform_motion: true,
+        ..Default::default()
+    };
+
+    let steps = 90;
+    let (pose, movement) = ride(&mut world, &controller, 1.0, steps);
+    let angle = steps as Real * world.integration_parameters.dt;
+    assert!(movement.grounded);
+    assert!((movement.ground_angvel - Vector::Y).length() < 1.0e-3);
+    // The point under the character moves at `angvel × arm`.
+    let expected_linvel = Vector::Y.cross(pose.translation);
+    assert!((movement.ground_linvel - expected_linvel).length() < 0.05);
+
+    // The character went around the platform's center, facing the same way relative to it.
+    let expected = Vector::new(angle.cos(), 0.62, -angle.sin());
+    assert!(
+        (pose.translation - expected).length() < 0.02,
+        "{:?} != {expected:?}",
+        pose.translation
+    );
+    let facing = pose.rotation * Vector::X;
+    assert!((facing - Vector::new(angle.cos(), 0.0, -angle.sin())).length() < 1.0e-3);
+}
@@ -0,0 +1,161 @@
+//! `KinematicCharacterController::try_resize_shape` (crouching and standing up) and
+//! `KinematicCharacterController::find_ledge` (ledge grabbing and mantling).
+
+use rapier3d::control::{CharacterShapeAnchor, KinematicCharacterController};
+use rapier3d::prelude::*;
+
+/// A ground whose top is at `y = 0`, a low ceiling at `y = 1.5` over `x < 0`, a wall whose
+/// face is at `x = 3` over `z < 0`, and a 1.5 high block whose face is at `x = 3` over `z > 0`.
+fn world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(
+        ColliderBuilder::cuboid(20.0, 0.5, 20.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+    world.insert_collider(
+        ColliderBuilder::cuboid(5.0, 0.5, 5.0).translation(Vector::new(-5.0, 2.0, 0.0)),
+        None,
+    );
+    world.insert_collider(
+        ColliderBuilder::cuboid(1.0, 5.0, 5.0).translation(Vector::new(4.0, 5.0, -5.0)),
+        None,
+    );
+    world.insert_collider(
+        ColliderBuilder::cuboid(1.0, 0.75, 5.0).translation(Vector::new(4.0, 0.75, 5.0)),
+        None,
+    );
+    // Build the broad-phase BVH the scene queries run on.
+    world.step();
+    world
+}
+
+fn standing() -> Capsule {
+    Capsule::new_y(0.6, 0.3)
+}
+
+fn crouching() -> Capsule {
+    Capsule::new_y(0.2, 0.3)
+}
+
+#[test]
+fn crouching_and_standing_up_keep_the_feet_in_place() {
+    let world = world();
+    let controller = KinematicCharacterController::default();
+    let queries = world.query_pipeline();
+
+    // Crouching is always possible; the feet stay 0.05 above the ground.
+    let standing_pos = Pose::translation(2.0, 0.95, 0.0);
+    let crouching_pos = controller
+        .try_resize_shape(
+            &queries,
+            &standing(),
+            &crouching(),
+            &standing_pos,
+            CharacterShapeAnchor::Bottom,
+        )
+        .unwrap();
+    assert!((crouching_pos.translation - Vector::new(2.0, 0.55, 0.0)).length() < 1.0e-5);
+
+    // Standing back up is possible in the open.
+    let standing_again = controller
+        .try_resize_shape(
+            &queries,
+            &crouching(),
+            &standing(),
+            &crouching_pos,
+            CharacterShapeAnchor::Bottom,
+        )
+        .unwrap();
+    assert!((standing_again.translation - standing_pos.translation).length() < 1.0e-5);
+
+    // But not under the low ceiling.
+    let under_ceiling = Pose::translation(-2.0, 0.55, 0.0);
+    assert!(
+        controller
+            .try_resize_shape(
+                &queries,
+                &crouching(),
+                &standing(),
+                &under_ceiling,
+                CharacterShapeAnchor::Bottom,
+            )
+            .is_none()
+    );
+    // Keeping the top of the shape in place instead hits the ground.
+    assert!(
+        controller
+            .try_resize_shape(
+                &queries,
+                &crouching(),
+                &standing(),
+                &under_ceiling,
+                CharacterShapeAnchor::Top,
+            )
+            .is_none()
+    );
+}
+
+#[test]
+fn resizing_next_to_a_wall_corrects_the_position() {
+    let world = world();
+    let controller = KinematicCharacterController::default();
+    let queries = world.query_pipeline();
+
+    // A thin character growing wider right next to the wall is pushed away from it, keeping
+    // the controller's offset.
+    let thin = Capsule::new_y(0.6, 0.2);
+    let pos = Pose::translation(2.75, 0.95, -2.0);
+    let resized = controller
+        .try_resize_shape(
+            &queries,
+            &thin,
+            &standing(),
+            &pos,
+            CharacterShapeAnchor::Center,
+        )
+        .unwrap();
+    let offset = 0.01 * 1.8;
+    assert!((resized.translation.x - (3.0 - 0.3 - offset)).abs() < 1.0e-3);
+    assert!((resized.translation.y - 0.95).abs() < 1.0e-3);
+}
+
+#[test]
+fn ledges_are_found_on_top_of_reachable_walls() {
+    let world = world();
+    let controller = KinematicCharacterController::default();
+    let queries = world.query_pipeline();
+    let offset = 0.01 * 1.8;
+
+    // The block is 1.5 high: reachable with a 2.0 reach.
+    let pos = Pose::translation(2.0, 0.95, 2.0);
+    let ledge = controller
+        .find_ledge(&queries, &standing(), &pos, Vector::X, 1.0, 2.0)
+        .unwrap();
+    assert!((ledge.height - (1.5 + offset - 0.05)).abs() < 1.0e-3);
+    assert!((ledge.edge - Vector::new(3.0, 1.5, 2.0)).length() < 0.05);
+    assert!((ledge.wall_normal - -Vector::X).length() < 1.0e-3);
+    assert!((ledge.top_normal - Vector::Y).length() < 1.0e-3);
+    // Standing on top of the block, past its edge.
+    assert!((ledge.mantle_pos.translation.y - (1.5 + 0.9 + offset)).abs() < 1.0e-3);
+    assert!(ledge.mantle_pos.translation.x - 0.3 > 3.0);
+
+    // Out of reach.
+    assert!(
+        controller
+            .find_ledge(&queries, &standing(), &pos, Vector::X, 1.0, 1.0)
+            .is_none()
+    );
+    // Too far.
+    assert!(
+        controller
+            .find_ledge(&queries, &standing(), &pos, Vector::X, 0.5, 2.0)
+            .is_none()
+    );
+    // The wall is too high.
+    let pos = Pose::translation(2.0, 0.95, -2.0);
+    assert!(
+        controller
+            .find_ledge(&queries, &standing(), &pos, Vector::X, 1.0, 2.0)
+            .is_none()
+    );
+}
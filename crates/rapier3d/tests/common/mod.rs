@@ -0,0 +1,92 @@
+//! Multibody fixtures shared by the articulation tests: planar chains of unit-length links
+//! hinged about Z, and free-floating multibody roots.
+
+// Each test crate only uses some of these.
+#![allow(dead_code)]
+
+use rapier3d::prelude::*;
+
+/// The tip of a link, in its local frame: its body is at the middle of the link.
+pub const LINK_TIP: Vector = Vector::new(0.5, 0.0, 0.0);
+
+/// Inserts a unit-length link hinged about Z at `(anchor1, 0, 0)` on `parent`, laid out along X.
+/// The link is a ball of radius 0.1 at its middle, with the given `density`.
+pub fn insert_hinged_link(
+    world: &mut PhysicsWorld,
+    parent: RigidBodyHandle,
+    anchor1: Real,
+    density: Real,
+    limits: Option<[Real; 2]>,
+) -> (RigidBodyHandle, MultibodyJointHandle) {
+    let anchor1 = Vector::new(anchor1, 0.0, 0.0);
+    let position = world.bodies[parent].position() * (anchor1 + LINK_TIP);
+    let (link, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(position),
+        ColliderBuilder::ball(0.1).density(density),
+    );
+    let mut joint = RevoluteJointBuilder::new(Vector::Z)
+        .local_anchor1(anchor1)
+        .local_anchor2(-LINK_TIP);
+    if let Some(limits) = limits {
+        joint = joint.limits(limits);
+    }
+    let handle = world.insert_multibody_joint(parent, link, joint).unwrap();
+    (link, handle)
+}
+
+/// A planar arm of `num_links` links starting from a fixed base at the origin, stepped once
+/// without gravity to let its fixed root collapse to zero degrees of freedom.
+///
+/// Returns the bodies and joint handles of every link, from the base to the tip.
+pub fn planar_arm(
+    world: &mut PhysicsWorld,
+    num_links: usize,
+    density: Real,
+) -> (Vec<RigidBodyHandle>, Vec<MultibodyJointHandle>) {
+    let mut parent = world.insert_body(RigidBodyBuilder::fixed());
+    let mut links = vec![];
+    let mut joints = vec![];
+    for i in 0..num_links {
+        let anchor1 = if i == 0 { 0.0 } else { LINK_TIP.x };
+        let (link, joint) = insert_hinged_link(world, parent, anchor1, density, None);
+        links.push(link);
+        joints.push(joint);
+        parent = link;
+    }
+
+    let gravity = world.gravity;
+    world.gravity = Vector::ZERO;
+    world.step();
+    world.gravity = gravity;
+    (links, joints)
+}
+
+/// Bends the multibody of `joint` by the given generalized `displacements`, and moves its
+/// links accordingly.
+pub fn bend(world: &mut PhysicsWorld, joint: MultibodyJointHandle, displacements: &[Real]) {
+    let (multibody, _) = world.multibody_joints.get_mut(joint).unwrap();
+    multibody.apply_displacements(displacements);
+    multibody.forward_kinematics(&world.bodies, false);
+    multibody.update_rigid_bodies(&mut world.bodies, true);
+}
+
+/// Makes `body` the dynamic root of a multibody, by hinging a small link at its center: its
+/// contacts are then solved as multibody contacts. The root keeps the body's velocity, without
+/// the default angular damping of multibodies.
+pub fn make_multibody_root(world: &mut PhysicsWorld, body: RigidBodyHandle) {
+    let (link, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(world.bodies[body].translation()),
+        ColliderBuilder::ball(0.05),
+    );
+    let joint = RevoluteJointBuilder::new(Vector::Y).contacts_enabled(false);
+    let joint = world.insert_multibody_joint(body, link, joint).unwrap();
+
+    let (linvel, angvel) = (world.bodies[body].linvel(), world.bodies[body].angvel());
+    let (multibody, _) = world.multibody_joints.get_mut(joint).unwrap();
+    multibody.damping_mut().fill(0.0);
+    let mut velocity = multibody.generalized_velocity_mut();
+    for i in 0..3 {
+        velocity[i] = linvel[i];
+        velocity[3 + i] = angvel[i];
+    }
+}
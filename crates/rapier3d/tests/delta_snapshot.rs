@@ -0,0 +1,268 @@
+//! Delta snapshots: `PhysicsWorld::delta_snapshot` and `PhysicsWorld::apply_delta`.
+//!
+//! As for full snapshots (see `snapshot_roundtrip.rs`), the contract is byte equality: a
+//! world rebuilt from a keyframe and the deltas following it must serialize to the same bytes
+//! as the world they were taken from, and keep doing so as both continue stepping.
+#![cfg(feature = "serde-serialize")]
+
+use rapier3d::prelude::*;
+
+fn save(world: &PhysicsWorld) -> Vec<u8> {
+    bincode::serialize(world).unwrap()
+}
+
+/// A pile of boxes settling on a ground, plus a ball rolling next to it.
+fn pile() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -0.5, 0.0)),
+        ColliderBuilder::cuboid(20.0, 0.5, 20.0),
+    );
+    for i in 0..4 {
+        for j in 0..3 {
+            for k in 0..4 {
+                world.insert(
+                    RigidBodyBuilder::dynamic().translation(Vector::new(
+                        i as Real * 1.05 - 2.0,
+                        j as Real * 1.05 + 0.55,
+                        k as Real * 1.05 - 2.0,
+                    )),
+                    ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+                );
+            }
+        }
+    }
+    world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(-8.0, 0.4, 8.0))
+            .linvel(Vector::new(2.0, 0.0, 0.0))
+            .can_sleep(false),
+        ColliderBuilder::ball(0.4),
+    );
+    world
+}
+
+/// Structural changes, applied before `step`: bodies are added and removed, and a joint is
+/// created, so the deltas have to handle freed and reused slots.
+fn edit(world: &mut PhysicsWorld, step: usize, spawned: &mut Vec<RigidBodyHandle>) {
+    match step {
+        20 | 25 | 30 => {
+            let (body, _) = world.insert(
+                RigidBodyBuilder::dynamic().translation(Vector::new(step as Real * 0.1, 6.0, 0.0)),
+                ColliderBuilder::ball(0.3),
+            );
+            spawned.push(body);
+        }
+        40 => {
+            let handle = spawned.remove(1);
+            world.remove_body(handle);
+        }
+        45 => {
+            let (body, _) = world.insert(
+                RigidBodyBuilder::dynamic().translation(Vector::new(5.0, 3.0, 5.0)),
+                ColliderBuilder::capsule_y(0.3, 0.2),
+            );
+            world.insert_impulse_joint(
+                spawned[0],
+                body,
+                SphericalJointBuilder::new().local_anchor2(Vector::new(0.0, 1.0, 0.0)),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Records one snapshot per step, with the world's bytes after each of them.
+fn record(
+    world: &mut PhysicsWorld,
+    num_steps: usize,
+    spawned: &mut Vec<RigidBodyHandle>,
+) -> (Vec<PhysicsWorldDelta>, Vec<Vec<u8>>) {
+    let mut tracker = PhysicsWorldDeltaTracker::new();
+    let mut deltas = vec![world.delta_snapshot(&mut tracker)];
+    let mut bytes = vec![save(world)];
+    for step in 0..num_steps {
+        edit(world, step, spawned);
+        world.step();
+        deltas.push(world.delta_snapshot(&mut tracker));
+        bytes.push(save(world));
+    }
+    (deltas, bytes)
+}
+
+#[test]
+fn a_chain_of_deltas_restores_the_exact_world() {
+    let mut world = pile();
+    let (deltas, bytes) = record(&mut world, 60, &mut Vec::new());
+    assert!(deltas[0].is_keyframe());
+    assert!(deltas[1..].iter().all(|delta| !delta.is_keyframe()));
+
+    let mut restored = PhysicsWorld::new();
+    for (i, delta) in deltas.iter().enumerate() {
+        assert_eq!(delta.index(), i as u64);
+        restored.apply_delta(delta);
+        assert!(
+            save(&restored) == bytes[i],
+            "the world restored at step {i} isn't byte-identical"
+        );
+    }
+}
+
+#[test]
+fn a_restored_world_continues_identically() {
+    let mut spawned = Vec::new();
+    let mut world = pile();
+    let (deltas, _) = record(&mut world, 35, &mut spawned);
+
+    let mut restored = PhysicsWorld::new();
+    for delta in &deltas {
+        restored.apply_delta(delta);
+    }
+    let mut spawned_restored = spawned.clone();
+    for step in 35..80 {
+        edit(&mut world, step, &mut spawned);
+        world.step();
+        edit(&mut restored, step, &mut spawned_restored);
+        restored.step();
+        assert!(
+            save(&restored) == save(&world),
+            "the restored world diverged at step {step}"
+        );
+    }
+}
+
+#[test]
+fn rolling_back_reapplies_the_keyframe() {
+    let mut world = pile();
+    let (deltas, bytes) = record(&mut world, 50, &mut Vec::new());
+
+    // Roll the world that produced the deltas back to step 32, then step it forward again:
+    // it must retrace the recorded run.
+    for delta in &deltas[..=32] {
+        world.apply_delta(delta);
+    }
+    assert!(save(&world) == bytes[32]);
+    for step in 32..39 {
+        // No edit happens during these steps: the spawn list isn't needed.
+        edit(&mut world, step, &mut Vec::new());
+        world.step();
+        assert!(
+            save(&world) == bytes[step + 1],
+            "the rolled-back world diverged at step {step}"
+        );
+    }
+}
+
+#[test]
+fn deltas_only_hold_what_changed() {
+    let mut world = pile();
+    let mut tracker = PhysicsWorldDeltaTracker::new();
+    let keyframe = world.delta_snapshot(&mut tracker);
+    let num_bodies = world.bodies.len();
+    // The keyframe holds every slot, including the arenas' free ones.
+    assert!(keyframe.bodies().num_changed_bodies() >= num_bodies);
+    assert!(keyframe.colliders().num_changed_colliders() >= world.colliders.len());
+
+    // Nothing changes without a step.
+    let delta = world.delta_snapshot(&mut tracker);
+    assert_eq!(delta.bodies().num_changed_bodies(), 0);
+    assert_eq!(delta.colliders().num_changed_colliders(), 0);
+    assert_eq!(delta.narrow_phase().num_changed_contact_pairs(), 0);
+    assert!(!delta.broad_phase().has_tree());
+    assert_eq!(delta.broad_phase().num_changed_colliders(), 0);
+
+    // Once the pile is asleep, only the rolling ball and its pairs change.
+    for _ in 0..300 {
+        world.step();
+    }
+    world.delta_snapshot(&mut tracker);
+    world.step();
+    let delta = world.delta_snapshot(&mut tracker);
+    assert_eq!(delta.bodies().num_changed_bodies(), 1);
+    assert!(delta.colliders().num_changed_colliders() <= 1);
+    assert!(delta.broad_phase().num_changed_colliders() <= 2);
+    assert!(
+        delta.narrow_phase().num_changed_contact_pairs()
+            < world.narrow_phase.contact_pairs().count() / 4
+    );
+    assert!(
+        bincode::serialize(&delta).unwrap().len() * 4
+            < bincode::serialize(&keyframe).unwrap().len()
+    );
+}
+
+#[test]
+fn deltas_survive_serialization() {
+    let mut world = pile();
+    let (deltas, bytes) = record(&mut world, 50, &mut Vec::new());
+
+    let mut restored = PhysicsWorld::new();
+    for delta in &deltas {
+        let delta: PhysicsWorldDelta =
+            bincode::deserialize(&bincode::serialize(delta).unwrap()).unwrap();
+        restored.apply_delta(&delta);
+    }
+    assert!(save(&restored) == bytes[50]);
+}
+
+#[test]
+fn shape_changes_are_part_of_the_delta() {
+    let mut world = pile();
+    let mut tracker = PhysicsWorldDeltaTracker::new();
+    let mut restored = PhysicsWorld::new();
+    restored.apply_delta(&world.delta_snapshot(&mut tracker));
+
+    let (handle, _) = world.colliders.iter().nth(3).unwrap();
+    world.colliders[handle].set_shape(SharedShape::ball(0.7));
+    world.step();
+    let delta = world.delta_snapshot(&mut tracker);
+    restored.apply_delta(&delta);
+    assert!(save(&restored) == save(&world));
+
+    // Modifying the shape in place is seen too, even though nothing else holds it.
+    world.colliders[handle]
+        .shape_mut()
+        .as_ball_mut()
+        .unwrap()
+        .radius = 0.8;
+    let delta = world.delta_snapshot(&mut tracker);
+    assert_eq!(delta.colliders().num_changed_colliders(), 1);
+    restored.apply_delta(&delta);
+    assert!(save(&restored) == save(&world));
+}
+
+#[test]
+fn worlds_record_their_changes_independently() {
+    let mut worlds = [pile(), pile()];
+    let mut trackers = [
+        PhysicsWorldDeltaTracker::new(),
+        PhysicsWorldDeltaTracker::new(),
+    ];
+    let mut restored = [PhysicsWorld::new(), PhysicsWorld::new()];
+    let mut spawned = [Vec::new(), Vec::new()];
+
+    // Interleaved snapshots of two worlds stepping in the same thread.
+    for step in 0..50 {
+        for i in 0..2 {
+            edit(&mut worlds[i], step, &mut spawned[i]);
+            worlds[i].step();
+            restored[i].apply_delta(&worlds[i].delta_snapshot(&mut trackers[i]));
+            assert!(
+                save(&restored[i]) == save(&worlds[i]),
+                "world {i} restored at step {step} isn't byte-identical"
+            );
+        }
+    }
+
+    // Stepping one world doesn't show up in the deltas of the other.
+    for _ in 0..10 {
+        worlds[1].step();
+        worlds[1].delta_snapshot(&mut trackers[1]);
+    }
+    let delta = worlds[0].delta_snapshot(&mut trackers[0]);
+    assert_eq!(delta.bodies().num_changed_bodies(), 0);
+    assert_eq!(delta.colliders().num_changed_colliders(), 0);
+    assert_eq!(delta.broad_phase().num_changed_colliders(), 0);
+    restored[0].apply_delta(&delta);
+    assert!(save(&restored[0]) == save(&worlds[0]));
+}
@@ -0,0 +1,218 @@
+//! `DynamicCharacterController`: a dynamic capsule floating at its ride height, walking,
+//! stepping, sliding, and reacting to impulses and joints like any other dynamic body.
+
+use rapier3d::control::{
+    CharacterAutostep, CharacterLength, DynamicCharacterController, DynamicCharacterMovement,
+};
+use rapier3d::prelude::*;
+
+/// The capsule is 1.6 tall, so the default ride height is `0.15 * 1.6`.
+const RIDE_HEIGHT: Real = 0.24;
+/// The height of the capsule's center when it floats at its ride height above `y = 0`.
+const REST_Y: Real = RIDE_HEIGHT + 0.8;
+
+/// A flat ground whose top is at `y = 0`, and a character standing above the origin.
+fn world() -> (PhysicsWorld, DynamicCharacterController) {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(
+        ColliderBuilder::cuboid(20.0, 0.5, 20.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+    let controller = character(&mut world, Vector::new(0.0, 1.5, 0.0));
+    (world, controller)
+}
+
+fn character(world: &mut PhysicsWorld, pos: Vector) -> DynamicCharacterController {
+    let (body, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(pos)
+            .lock_rotations(),
+        ColliderBuilder::capsule_y(0.5, 0.3).friction(0.0),
+    );
+    DynamicCharacterController::new(body)
+}
+
+fn run(
+    world: &mut PhysicsWorld,
+    controller: &mut DynamicCharacterController,
+    desired_velocity: Vector,
+    steps: usize,
+) -> DynamicCharacterMovement {
+    let mut movement = DynamicCharacterMovement::default();
+    for _ in 0..steps {
+        let queries = world.broad_phase.as_query_pipeline_mut(
+            world.narrow_phase.query_dispatcher(),
+            &mut world.bodies,
+            &mut world.colliders,
+            QueryFilter::default(),
+        );
+        movement = controller.update(
+            world.integration_parameters.dt,
+            world.gravity,
+            queries,
+            desired_velocity,
+        );
+        world.step();
+    }
+    movement
+}
+
+#[test]
+fn floats_at_ride_height_and_walks_at_the_desired_velocity() {
+    let (mut world, mut controller) = world();
+    let movement = run(&mut world, &mut controller, Vector::ZERO, 120);
+    let body = &world.bodies[controller.body];
+    assert!(movement.grounded);
+    assert!(!movement.is_sliding_down_slope);
+    assert!((movement.ground_distance - RIDE_HEIGHT).abs() < 0.01);
+    assert!((body.translation().y - REST_Y).abs() < 0.01);
+    // The character floats: it doesn't touch the ground.
+    assert_eq!(
+        world
+            .narrow_phase
+            .contact_pairs()
+            .filter(|p| p.has_any_active_contact())
+            .count(),
+        0
+    );
+
+    let movement = run(&mut world, &mut controller, Vector::new(3.0, 0.0, 0.0), 60);
+    let body = &world.bodies[controller.body];
+    assert!(movement.grounded);
+    assert!((body.linvel() - Vector::new(3.0, 0.0, 0.0)).length() < 0.05);
+    assert!((body.translation().y - REST_Y).abs() < 0.02);
+
+    // The character brakes when there is no input.
+    run(&mut world, &mut controller, Vector::ZERO, 60);
+    assert!(world.bodies[controller.body].linvel().length() < 0.05);
+}
+
+#[test]
+fn reacts_to_impulses_and_recovers() {
+    let (mut world, mut controller) = world();
+    run(&mut world, &mut controller, Vector::ZERO, 60);
+
+    // An explosion throws the character up and away: it leaves the ground and keeps its
+    // momentum in the air.
+    let body = &mut world.bodies[controller.body];
+    body.apply_impulse(Vector::new(3.0, 5.0, 0.0) * body.mass(), true);
+    let movement = run(&mut world, &mut controller, Vector::ZERO, 10);
+    assert!(!movement.grounded);
+    let body = &world.bodies[controller.body];
+    assert!(body.translation().y > REST_Y + 0.5);
+    assert!(body.linvel().x > 1.0);
+
+    // It lands, then stops.
+    let movement = run(&mut world, &mut controller, Vector::ZERO, 240);
+    let body = &world.bodies[controller.body];
+    assert!(movement.grounded);
+    assert!(body.translation().x > 1.0);
+    assert!(body.linvel().length() < 0.05);
+    assert!((body.translation().y - REST_Y).abs() < 0.02);
+}
+
+#[test]
+fn keeps_the_other_forces_applied_to_the_body() {
+    let (mut world, mut controller) = world();
+    run(&mut world, &mut controller, Vector::ZERO, 60);
+
+    // A wind stronger than the controller's maximum acceleration blows the character away.
+    let body = &mut world.bodies[controller.body];
+    let wind = Vector::new(2.0 * body.mass() * controller.max_acceleration, 0.0, 0.0);
+    body.add_force(wind, true);
+    run(&mut world, &mut controller, Vector::ZERO, 30);
+    let body = &world.bodies[controller.body];
+    assert!(body.translation().x > 1.0);
+    assert!((body.user_force() - controller.applied_force() - wind).length() < 1.0e-3);
+
+    // Once the wind stops, the character brakes.
+    world.bodies[controller.body].add_force(-wind, true);
+    run(&mut world, &mut controller, Vector::ZERO, 120);
+    let body = &world.bodies[controller.body];
+    assert!(body.linvel().length() < 0.05);
+    assert!((body.user_force() - controller.applied_force()).length() < 1.0e-3);
+}
+
+#[test]
+fn joints_hold_the_character_back() {
+    let (mut world, mut controller) = world();
+    let anchor =
+        world.insert_body(RigidBodyBuilder::fixed().translation(Vector::new(0.0, REST_Y, 0.0)));
+    world.insert_impulse_joint(anchor, controller.body, RopeJointBuilder::new(2.0));
+
+    run(&mut world, &mut controller, Vector::new(4.0, 0.0, 0.0), 180);
+    let x = world.bodies[controller.body].translation().x;
+    assert!(
+        x > 1.8 && x < 2.1,
+        "the rope didn't stop the character at {x}"
+    );
+}
+
+#[test]
+fn walks_onto_steps_only_with_autostep() {
+    for autostep in [false, true] {
+        let (mut world, mut controller) = world();
+        // A 0.5 high step starting at `x = 1.5`, taller than the ride height.
+        world.insert_collider(
+            ColliderBuilder::cuboid(5.0, 0.25, 5.0).translation(Vector::new(6.5, 0.25, 0.0)),
+            None,
+        );
+        if autostep {
+            controller.autostep = Some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(0.6),
+                min_width: CharacterLength::Absolute(0.3),
+                include_dynamic_bodies: false,
+            });
+        }
+
+        run(&mut world, &mut controller, Vector::ZERO, 60);
+        run(&mut world, &mut controller, Vector::new(2.0, 0.0, 0.0), 120);
+        let body = &world.bodies[controller.body];
+        if autostep {
+            assert!(body.translation().x > 3.0);
+            assert!((body.translation().y - (REST_Y + 0.5)).abs() < 0.05);
+        } else {
+            // Blocked by the step, which the capsule touches at its radius.
+            assert!(body.translation().x < 1.5 - 0.25);
+            assert!((body.translation().y - REST_Y).abs() < 0.05);
+        }
+    }
+}
+
+#[test]
+fn slides_down_steep_slopes_only() {
+    let slopes: [(Real, bool); 2] = [(20.0, false), (60.0, true)];
+    for (angle, steep) in slopes {
+        let mut world = PhysicsWorld::new();
+        // The slope's surface passes above the origin and rises toward +X.
+        world.insert_collider(
+            ColliderBuilder::cuboid(20.0, 0.5, 20.0).rotation(Vector::new(
+                0.0,
+                0.0,
+                angle.to_radians(),
+            )),
+            None,
+        );
+        let mut controller = character(&mut world, Vector::new(0.0, 3.0, 0.0));
+
+        let movement = run(&mut world, &mut controller, Vector::ZERO, 120);
+        assert!(movement.grounded);
+        assert_eq!(movement.is_sliding_down_slope, steep);
+        let x = world.bodies[controller.body].translation().x;
+        if steep {
+            assert!(x < -1.0, "the character didn't slide down: {x}");
+        } else {
+            assert!(x.abs() < 0.05, "the character slid on a gentle slope: {x}");
+        }
+
+        // Walking uphill only works on the gentle slope.
+        let start = world.bodies[controller.body].translation().x;
+        run(&mut world, &mut controller, Vector::new(2.0, 0.0, 0.0), 60);
+        let moved = world.bodies[controller.body].translation().x - start;
+        if steep {
+            assert!(moved < 0.0);
+        } else {
+            assert!(moved > 1.0);
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! Force fields: the built-in fields of the pipeline's `ForceFieldSet` and user-defined
+//! `ForceField`s, evaluated at each solver substep.
+
+use rapier3d::prelude::*;
+use std::sync::Mutex;
+
+fn world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::ZERO;
+    world
+}
+
+fn ball(world: &mut PhysicsWorld, translation: Vector) -> RigidBodyHandle {
+    world
+        .insert(
+            RigidBodyBuilder::dynamic().translation(translation),
+            ColliderBuilder::ball(0.5),
+        )
+        .0
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+#[test]
+fn point_gravity_pulls_toward_the_center() {
+    let mut world = world();
+    world
+        .force_fields_mut()
+        .insert(PointGravityField::new(Vector::ZERO, 100.0));
+    let handle = ball(&mut world, Vector::new(10.0, 0.0, 0.0));
+
+    run(&mut world, 1);
+
+    // Acceleration of `100 / 10² = 1` toward the origin.
+    let linvel = world.bodies[handle].linvel();
+    let expected = -world.integration_parameters.dt;
+    assert!((linvel.x - expected).abs() < 1.0e-4, "{linvel:?}");
+    assert!(linvel.y.abs() < 1.0e-6 && linvel.z.abs() < 1.0e-6);
+}
+
+#[test]
+fn wind_drags_bodies_to_its_velocity() {
+    let mut world = world();
+    let wind = world
+        .force_fields_mut()
+        .insert(WindField::new(Vector::new(5.0, 0.0, 0.0), 2.0));
+    let handle = ball(&mut world, Vector::ZERO);
+
+    run(&mut world, 300);
+    let linvel = world.bodies[handle].linvel();
+    assert!((linvel.x - 5.0).abs() < 0.01, "{linvel:?}");
+
+    // Turn the wind around through the typed accessor.
+    world
+        .force_fields_mut()
+        .field_mut::<WindField>(wind)
+        .unwrap()
+        .velocity = Vector::new(0.0, 0.0, 5.0);
+    run(&mut world, 300);
+    let linvel = world.bodies[handle].linvel();
+    assert!(
+        linvel.x.abs() < 0.01 && (linvel.z - 5.0).abs() < 0.01,
+        "{linvel:?}"
+    );
+
+    // Removing the field stops its effect.
+    assert!(world.force_fields_mut().remove(wind).is_some());
+    world.bodies[handle].set_linvel(Vector::ZERO, true);
+    run(&mut world, 10);
+    assert_eq!(world.bodies[handle].linvel(), Vector::ZERO);
+}
+
+#[test]
+fn stiff_drag_is_stable_thanks_to_substepping() {
+    let mut world = world();
+    // `drag * dt / mass ≈ 3.2`: an explicit once-per-step evaluation would overshoot and
+    // diverge; per-substep evaluation keeps it stable.
+    let handle = ball(&mut world, Vector::ZERO);
+    let mass = world.bodies[handle].mass();
+    let drag = 3.2 * mass / world.integration_parameters.dt;
+    world
+        .force_fields_mut()
+        .insert(WindField::new(Vector::new(1.0, 0.0, 0.0), drag));
+
+    for _ in 0..60 {
+        world.step();
+        let linvel = world.bodies[handle].linvel();
+        assert!(linvel.x >= 0.0 && linvel.x <= 1.0 + 1.0e-4, "{linvel:?}");
+    }
+    assert!((world.bodies[handle].linvel().x - 1.0).abs() < 1.0e-3);
+}
+
+#[test]
+fn vortex_makes_bodies_swirl_around_its_axis() {
+    let mut world = world();
+    let mut vortex = VortexField::new(Vector::ZERO, Vector::Y, 10.0);
+    vortex.tangential_acceleration = 5.0;
+    vortex.axial_acceleration = 1.0;
+    world.force_fields_mut().insert(vortex);
+    let inside = ball(&mut world, Vector::new(5.0, 0.0, 0.0));
+    let outside = ball(&mut world, Vector::new(20.0, 0.0, 0.0));
+
+    run(&mut world, 10);
+
+    // Counterclockwise around +Y: at +X, the tangent is -Z.
+    let linvel = world.bodies[inside].linvel();
+    assert!(linvel.z < 0.0 && linvel.y > 0.0, "{linvel:?}");
+    assert!(linvel.z.abs() > linvel.y.abs(), "{linvel:?}");
+    assert_eq!(world.bodies[outside].linvel(), Vector::ZERO);
+}
+
+#[test]
+fn aerodynamic_drag_gives_a_terminal_velocity() {
+    let mut world = world();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    let mut aero = AerodynamicField::new(1.2);
+    aero.default_profile = Some(AerodynamicProfile::new(0.5));
+    world.force_fields_mut().insert(aero);
+    let handle = ball(&mut world, Vector::ZERO);
+
+    run(&mut world, 900);
+
+    // `v = sqrt(2 m g / (ρ C_d A))` with `A = π r²`.
+    let mass = world.bodies[handle].mass();
+    let area = std::f32::consts::PI * 0.25;
+    let terminal = (2.0 * mass * 9.81 / (1.2 * 0.5 * area)).sqrt();
+    let linvel = world.bodies[handle].linvel();
+    assert!(
+        (linvel.y + terminal).abs() < 0.01 * terminal,
+        "{linvel:?} {terminal}"
+    );
+}
+
+#[test]
+fn aerodynamic_lift_depends_on_the_incidence() {
+    let mut world = world();
+    let mut aero = AerodynamicField::new(1.2);
+    aero.wind_velocity = Vector::new(-10.0, 0.0, 0.0);
+    let mut plate = |angle: Real, z: Real| {
+        let (body, collider) = world.insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::new(0.0, 0.0, z))
+                .rotation(Vector::new(0.0, 0.0, angle))
+                .lock_rotations(),
+            ColliderBuilder::cuboid(1.0, 0.02, 1.0),
+        );
+        aero.set_profile(
+            collider,
+            AerodynamicProfile::new(0.1).with_lift(1.0, Vector::Y),
+        );
+        body
+    };
+    // Leading edge up and down, facing the wind.
+    let up = plate(0.3, -5.0);
+    let down = plate(-0.3, 0.0);
+    let flat = plate(0.0, 5.0);
+    world.force_fields_mut().insert(aero);
+
+    run(&mut world, 5);
+
+    let up = world.bodies[up].linvel();
+    let down = world.bodies[down].linvel();
+    let flat = world.bodies[flat].linvel();
+    assert!(up.y > 0.0 && up.x < 0.0, "{up:?}");
+    assert!((down.y + up.y).abs() < 1.0e-4 * up.y, "{down:?} {up:?}");
+    assert!(flat.y.abs() < 1.0e-6 && flat.x < 0.0, "{flat:?}");
+}
+
+/// Records the bodies it is evaluated on.
+struct RecordingField(Mutex<Vec<RigidBodyHandle>>);
+
+impl ForceField for RecordingField {
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce) {
+        self.0.lock().unwrap().push(body.handle);
+        force.add_force_at_point(Vector::X, body.center_of_mass + Vector::Y);
+    }
+}
+
+#[test]
+fn custom_fields_are_evaluated_at_each_substep_of_the_body_group() {
+    let mut world = world();
+    let field = world
+        .force_fields_mut()
+        .insert(RecordingField(Mutex::new(vec![])));
+    let regular = ball(&mut world, Vector::ZERO);
+    let elevated = world
+        .insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::new(10.0, 0.0, 0.0))
+                .additional_solver_iterations(2),
+            ColliderBuilder::ball(0.5),
+        )
+        .0;
+    let fixed = world
+        .insert(
+            RigidBodyBuilder::fixed().translation(Vector::new(-10.0, 0.0, 0.0)),
+            ColliderBuilder::ball(0.5),
+        )
+        .0;
+
+    run(&mut world, 1);
+
+    let evaluated = std::mem::take(
+        &mut *world
+            .force_fields()
+            .field::<RecordingField>(field)
+            .unwrap()
+            .0
+            .lock()
+            .unwrap(),
+    );
+    let count = |handle| evaluated.iter().filter(|h| **h == handle).count();
+    let substeps = world.integration_parameters.num_solver_iterations;
+    assert_eq!(count(regular), substeps);
+    assert_eq!(count(elevated), substeps + 2);
+    assert_eq!(count(fixed), 0);
+
+    // Both bodies received the same total impulse, whatever their number of substeps.
+    let dt = world.integration_parameters.dt;
+    for handle in [regular, elevated] {
+        let rb = &world.bodies[handle];
+        let linvel = rb.linvel();
+        assert!((linvel.x * rb.mass() - dt).abs() < 1.0e-5, "{linvel:?}");
+        // The force is applied above the center of mass: it spins the body around -Z.
+        assert!(rb.angvel().z < 0.0, "{:?}", rb.angvel());
+    }
+}
@@ -0,0 +1,193 @@
+//! Per-body gravity: gravity overrides, gravity providers combining several sources, and the
+//! wake-up of the sleeping bodies whose gravity direction changed.
+
+use rapier3d::prelude::*;
+
+fn free_ball(
+    world: &mut PhysicsWorld,
+    translation: Vector,
+    body: RigidBodyBuilder,
+) -> RigidBodyHandle {
+    let (handle, _) = world.insert(body.translation(translation), ColliderBuilder::ball(0.5));
+    handle
+}
+
+/// A unit cube resting asleep on a fixed ground whose surface is at `y = 0`.
+fn sleeping_cube_on_ground(world: &mut PhysicsWorld) -> RigidBodyHandle {
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -0.5, 0.0)),
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+    );
+    let (cube, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(0.0, 0.5, 0.0))
+            .sleeping(true),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    cube
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+#[test]
+fn gravity_override_replaces_the_world_gravity() {
+    let mut world = PhysicsWorld::new();
+    let sideways = Vector::new(5.0, 0.0, 0.0);
+    let overridden = free_ball(
+        &mut world,
+        Vector::ZERO,
+        RigidBodyBuilder::dynamic().gravity_override(sideways),
+    );
+    // The gravity scale still applies to the override.
+    let scaled = free_ball(
+        &mut world,
+        Vector::new(10.0, 0.0, 0.0),
+        RigidBodyBuilder::dynamic()
+            .gravity_override(sideways)
+            .gravity_scale(2.0),
+    );
+    let falling = free_ball(
+        &mut world,
+        Vector::new(-10.0, 0.0, 0.0),
+        RigidBodyBuilder::dynamic(),
+    );
+    assert_eq!(world.bodies[overridden].applied_gravity(), None);
+
+    // One second.
+    run(&mut world, 60);
+    let pos = world.bodies[overridden].translation();
+    assert!(pos.x > 2.3 && pos.x < 2.8, "{pos:?}");
+    assert!(pos.y.abs() < 1.0e-5, "{pos:?}");
+    let pos = world.bodies[scaled].translation();
+    assert!(pos.x > 14.6 && pos.x < 15.6, "{pos:?}");
+    assert!(world.bodies[falling].translation().y < -4.0);
+
+    assert_eq!(world.bodies[overridden].applied_gravity(), Some(sideways));
+    assert_eq!(world.bodies[falling].applied_gravity(), Some(world.gravity));
+
+    // Removing the override restores the world gravity.
+    world.bodies[overridden].set_gravity_override(None, true);
+    world.step();
+    assert_eq!(
+        world.bodies[overridden].applied_gravity(),
+        Some(world.gravity)
+    );
+}
+
+#[test]
+fn gravity_sources_add_up_and_yield_to_overrides() {
+    let mut world = PhysicsWorld::new();
+    let planet = PointGravityField::new(Vector::ZERO, 100.0);
+    let moon = PointGravityField::new(Vector::new(0.0, 0.0, 20.0), 25.0);
+    world.set_gravity_provider(GravitySources::new().with(planet).with(moon));
+
+    let east = free_ball(
+        &mut world,
+        Vector::new(10.0, 0.0, 0.0),
+        RigidBodyBuilder::dynamic(),
+    );
+    let between = free_ball(
+        &mut world,
+        Vector::new(0.0, 0.0, 10.0),
+        RigidBodyBuilder::dynamic(),
+    );
+    let overridden = free_ball(
+        &mut world,
+        Vector::new(0.0, 10.0, 0.0),
+        RigidBodyBuilder::dynamic().gravity_override(Vector::ZERO),
+    );
+    world.step();
+
+    // The world gravity is ignored: each source pulls with `strength / d²`.
+    let moon_pull = |p: Vector| {
+        (Vector::new(0.0, 0.0, 20.0) - p)
+            * (25.0 / (p - Vector::new(0.0, 0.0, 20.0)).length().powi(3))
+    };
+    let expected = Vector::new(-1.0, 0.0, 0.0) + moon_pull(Vector::new(10.0, 0.0, 0.0));
+    let gravity = world.bodies[east].applied_gravity().unwrap();
+    assert!((gravity - expected).length() < 1.0e-4, "{gravity:?}");
+    // Pulled toward the planet and the moon alike: the planet wins.
+    let gravity = world.bodies[between].applied_gravity().unwrap();
+    assert!(
+        (gravity - Vector::new(0.0, 0.0, -0.75)).length() < 1.0e-4,
+        "{gravity:?}"
+    );
+    assert_eq!(
+        world.bodies[overridden].applied_gravity(),
+        Some(Vector::ZERO)
+    );
+
+    // Adding the world gravity.
+    let mut sources = GravitySources::new().with(planet);
+    sources.include_world_gravity = true;
+    world.set_gravity_provider(sources);
+    world.step();
+    let gravity = world.bodies[between].applied_gravity().unwrap();
+    assert!(
+        (gravity - Vector::new(0.0, -9.81, -1.0)).length() < 0.05,
+        "{gravity:?}"
+    );
+
+    // Closures are providers too.
+    world.set_gravity_provider(
+        |_: RigidBodyHandle, body: &RigidBody, g: Vector| {
+            if body.translation().x > 5.0 { -g } else { g }
+        },
+    );
+    world.step();
+    assert_eq!(world.bodies[east].applied_gravity(), Some(-world.gravity));
+    assert_eq!(world.bodies[between].applied_gravity(), Some(world.gravity));
+
+    assert!(world.remove_gravity_provider().is_some());
+    world.step();
+    assert_eq!(world.bodies[east].applied_gravity(), Some(world.gravity));
+}
+
+#[test]
+fn sleeping_bodies_wake_up_when_the_world_gravity_turns() {
+    let mut world = PhysicsWorld::new();
+    let cube = sleeping_cube_on_ground(&mut world);
+    world.step();
+    assert!(world.bodies[cube].is_sleeping());
+
+    // Stronger gravity, same direction.
+    world.gravity = Vector::new(0.0, -20.0, 0.0);
+    world.step();
+    assert!(world.bodies[cube].is_sleeping());
+
+    // Turning less than `gravity_wake_angle`.
+    let angle: Real = 0.01;
+    world.gravity = Vector::new(angle.sin(), -angle.cos(), 0.0) * 9.81;
+    world.step();
+    assert!(world.bodies[cube].is_sleeping());
+
+    // Turning more than that.
+    let angle: Real = 0.3;
+    world.gravity = Vector::new(angle.sin(), -angle.cos(), 0.0) * 9.81;
+    world.step();
+    assert!(!world.bodies[cube].is_sleeping());
+}
+
+#[test]
+fn sleeping_bodies_wake_up_when_their_provided_gravity_turns() {
+    let mut world = PhysicsWorld::new();
+    let cube = sleeping_cube_on_ground(&mut world);
+    world.set_gravity_provider(|_: RigidBodyHandle, _: &RigidBody, g: Vector| g * 0.5);
+    run(&mut world, 10);
+    assert!(world.bodies[cube].is_sleeping());
+
+    // The provider is consulted every step, even though the world gravity doesn't change.
+    world.set_gravity_provider(|_: RigidBodyHandle, _: &RigidBody, _: Vector| {
+        Vector::new(9.81, 0.0, 0.0)
+    });
+    world.step();
+    assert!(!world.bodies[cube].is_sleeping());
+    assert_eq!(
+        world.bodies[cube].applied_gravity(),
+        Some(Vector::new(9.81, 0.0, 0.0))
+    );
+}
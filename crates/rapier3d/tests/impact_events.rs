@@ -0,0 +1,130 @@
+//! Impact events: colliders with `ActiveEvents::IMPACT_EVENTS` report the pre-solve impact
+//! speed, the first-step normal impulse, the deepest contact and both materials when they start
+//! touching another collider.
+
+use std::sync::mpsc::{Receiver, channel};
+
+use rapier3d::prelude::*;
+
+fn collector() -> (ChannelEventCollector, Receiver<ImpactEvent>) {
+    let (collision_send, _) = channel();
+    let (contact_force_send, _) = channel();
+    let (impact_send, impact_recv) = channel();
+    let events = ChannelEventCollector::new(collision_send, contact_force_send)
+        .with_impact_event_sender(impact_send);
+    (events, impact_recv)
+}
+
+/// A ball of radius 0.5 dropped from `height` above a fixed ground whose surface is at `y = 0`.
+///
+/// Returns the ground and ball colliders, and the ball body.
+fn drop_ball(
+    world: &mut PhysicsWorld,
+    height: Real,
+    ball: ColliderBuilder,
+) -> (ColliderHandle, ColliderHandle, RigidBodyHandle) {
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    let (_, ground) = world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -0.5, 0.0)),
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0).friction(0.9),
+    );
+    let (body, ball) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.5 + height, 0.0)),
+        ball,
+    );
+    (ground, ball, body)
+}
+
+/// Steps `world` for two seconds, and returns the impact events it emitted.
+fn run(
+    world: &mut PhysicsWorld,
+    events: &ChannelEventCollector,
+    recv: &Receiver<ImpactEvent>,
+) -> Vec<ImpactEvent> {
+    for _ in 0..120 {
+        world.step_with_events(&(), events);
+    }
+    recv.try_iter().collect()
+}
+
+#[test]
+fn impact_reports_the_pre_solve_speed_and_the_stopping_impulse() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    let ball = ColliderBuilder::ball(0.5).active_events(ActiveEvents::IMPACT_EVENTS);
+    let (ground, ball, body) = drop_ball(&mut world, 2.0, ball);
+
+    let impacts = run(&mut world, &events, &recv);
+    // The ball doesn't bounce, so it hits the ground only once.
+    assert_eq!(impacts.len(), 1, "{impacts:?}");
+    let impact = impacts[0];
+    assert!(
+        (impact.collider1, impact.collider2) == (ground, ball)
+            || (impact.collider1, impact.collider2) == (ball, ground)
+    );
+
+    // The speed reached after a 2 m fall, give or take a step of gravity.
+    let speed = Real::sqrt(2.0 * 9.81 * 2.0);
+    assert!(
+        (-impact.normal_relative_velocity - speed).abs() < 0.3,
+        "{impact:?}"
+    );
+    // The solver stopped the ball over that first step.
+    let momentum = world.bodies[body].mass() * speed;
+    assert!(
+        impact.normal_impulse > 0.5 * momentum && impact.normal_impulse < 2.0 * momentum,
+        "{impact:?}"
+    );
+    assert!(impact.normal.y.abs() > 0.99, "{impact:?}");
+    assert!(
+        impact.point.y.abs() < 0.2 && impact.point.x.abs() < 1.0e-3,
+        "{impact:?}"
+    );
+}
+
+#[test]
+fn impact_reports_the_materials_of_both_colliders() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    // The impact flag on the ground is enough.
+    let ball = ColliderBuilder::ball(0.5).friction(0.3).restitution(0.6);
+    let (ground, ball, _) = drop_ball(&mut world, 1.0, ball);
+    world.colliders[ground].set_active_events(ActiveEvents::IMPACT_EVENTS);
+
+    let impacts = run(&mut world, &events, &recv);
+    // The ball bounces a few times.
+    assert!(impacts.len() > 1, "{impacts:?}");
+    let impact = impacts[0];
+    let (friction, restitution) = if impact.collider1 == ball {
+        (
+            [impact.friction1, impact.friction2],
+            [impact.restitution1, impact.restitution2],
+        )
+    } else {
+        (
+            [impact.friction2, impact.friction1],
+            [impact.restitution2, impact.restitution1],
+        )
+    };
+    assert_eq!(friction, [0.3, 0.9]);
+    assert_eq!(restitution, [0.6, 0.0]);
+    // Both are averaged by default.
+    assert!((impact.combined_friction - 0.6).abs() < 1.0e-5);
+    assert!((impact.combined_restitution - 0.3).abs() < 1.0e-5);
+
+    // Each bounce is slower than the previous one.
+    for pair in impacts.windows(2) {
+        assert!(pair[1].normal_relative_velocity > pair[0].normal_relative_velocity);
+        assert!(pair[1].normal_relative_velocity < 0.0);
+    }
+}
+
+#[test]
+fn colliders_without_impact_events_stay_silent() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    let ball = ColliderBuilder::ball(0.5).active_events(ActiveEvents::COLLISION_EVENTS);
+    drop_ball(&mut world, 1.0, ball);
+
+    assert!(run(&mut world, &events, &recv).is_empty());
+}
@@ -0,0 +1,150 @@
+//! Breakable impulse joints: a joint whose reaction force or torque exceeds its
+//! `break_force`/`break_torque` is disabled (or removed) at the end of the step, and a
+//! `JointBreakEvent` reports it.
+
+use std::sync::mpsc::{Receiver, channel};
+
+use rapier3d::prelude::*;
+
+/// A 10 kg cube hanging from a fixed anchor through a fixed joint, its center of mass
+/// `offset` away from the joint anchor.
+fn hanging_cube(
+    world: &mut PhysicsWorld,
+    offset: Vector,
+    joint: FixedJointBuilder,
+) -> ImpulseJointHandle {
+    let anchor = world.insert_body(RigidBodyBuilder::fixed());
+    let (cube, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(offset)
+            .can_sleep(false),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5).density(10.0),
+    );
+    world.insert_impulse_joint(anchor, cube, joint.local_anchor2(-offset))
+}
+
+fn collector() -> (ChannelEventCollector, Receiver<JointBreakEvent>) {
+    let (collision_send, _) = channel();
+    let (contact_force_send, _) = channel();
+    let (joint_break_send, joint_break_recv) = channel();
+    let events = ChannelEventCollector::new(collision_send, contact_force_send)
+        .with_joint_break_event_sender(joint_break_send);
+    (events, joint_break_recv)
+}
+
+fn world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world
+}
+
+#[test]
+fn joint_breaks_once_its_force_exceeds_the_threshold() {
+    let mut world = world();
+    let (events, recv) = collector();
+    // The cube weighs ~98.1 N.
+    let weak = hanging_cube(
+        &mut world,
+        Vector::new(0.0, -1.0, 0.0),
+        FixedJointBuilder::new().break_force(50.0),
+    );
+    let strong = hanging_cube(
+        &mut world,
+        Vector::new(5.0, -1.0, 0.0),
+        FixedJointBuilder::new().break_force(500.0),
+    );
+
+    for _ in 0..10 {
+        world.step_with_events(&(), &events);
+    }
+
+    let broken: Vec<_> = recv.try_iter().collect();
+    assert_eq!(broken.len(), 1, "exactly one joint must break: {broken:?}");
+    let event = broken[0];
+    assert_eq!(event.joint, weak);
+    assert!(!event.removed);
+    assert!(event.force.length() > 50.0, "{event:?}");
+    assert!(
+        (event.linear_impulse - event.force * world.integration_parameters.dt).length() < 1.0e-4
+    );
+
+    // The broken joint was disabled, not removed; the strong one is untouched.
+    assert!(!world.impulse_joints.get(weak).unwrap().data.is_enabled());
+    assert!(world.impulse_joints.get(strong).unwrap().data.is_enabled());
+}
+
+#[test]
+fn joint_breaks_once_its_torque_exceeds_the_threshold() {
+    let mut world = world();
+    let (events, recv) = collector();
+    // Two cantilevers: the weight (~98.1 N) 2 m away from the anchor needs ~196 N.m there.
+    let weak = hanging_cube(
+        &mut world,
+        Vector::new(2.0, 0.0, 0.0),
+        FixedJointBuilder::new().break_torque(100.0),
+    );
+    let strong = hanging_cube(
+        &mut world,
+        Vector::new(0.0, 0.0, 2.0),
+        FixedJointBuilder::new().break_torque(400.0),
+    );
+
+    for _ in 0..10 {
+        world.step_with_events(&(), &events);
+    }
+
+    let broken: Vec<_> = recv.try_iter().collect();
+    assert_eq!(broken.len(), 1, "exactly one joint must break: {broken:?}");
+    assert_eq!(broken[0].joint, weak);
+    assert!(broken[0].torque.length() > 100.0, "{:?}", broken[0]);
+    assert!(world.impulse_joints.get(strong).unwrap().data.is_enabled());
+}
+
+#[test]
+fn broken_joint_can_be_removed() {
+    let mut world = world();
+    let (events, recv) = collector();
+    let joint = hanging_cube(
+        &mut world,
+        Vector::new(0.0, -1.0, 0.0),
+        FixedJointBuilder::new()
+            .break_force(50.0)
+            .break_action(JointBreakAction::Remove),
+    );
+
+    world.step_with_events(&(), &events);
+    world.step_with_events(&(), &events);
+
+    let broken: Vec<_> = recv.try_iter().collect();
+    assert_eq!(broken.len(), 1);
+    assert!(broken[0].removed);
+    assert!(world.impulse_joints.get(joint).is_none());
+
+    // The cube is now free-falling.
+    for _ in 0..30 {
+        world.step_with_events(&(), &events);
+    }
+    let (_, cube) = world
+        .rigid_bodies()
+        .find(|(_, rb)| rb.is_dynamic())
+        .unwrap();
+    assert!(cube.translation().y < -1.5);
+}
+
+#[test]
+fn unbreakable_joints_never_break() {
+    let mut world = world();
+    let (events, recv) = collector();
+    let joint = hanging_cube(
+        &mut world,
+        Vector::new(2.0, 0.0, 0.0),
+        FixedJointBuilder::new(),
+    );
+
+    for _ in 0..30 {
+        world.step_with_events(&(), &events);
+    }
+
+    assert!(recv.try_recv().is_err());
+    assert!(world.impulse_joints.get(joint).unwrap().data.is_enabled());
+}
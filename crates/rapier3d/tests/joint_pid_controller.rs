@@ -0,0 +1,198 @@
+//! `JointPidController` and `MultibodyPidController`: PID control of joint coordinates, with
+//! anti-windup, derivative filtering and output saturation.
+
+use rapier3d::control::{JointPidController, MultibodyPidController};
+use rapier3d::prelude::*;
+
+mod common;
+
+const DT: Real = 1.0 / 60.0;
+
+#[test]
+fn the_integral_does_not_wind_up_while_saturated() {
+    let mut pid = JointPidController::new(10.0, 10.0, 0.0);
+    pid.max_output = 1.0;
+
+    for _ in 0..120 {
+        assert_eq!(pid.correction(DT, 1.0, 0.0), 1.0);
+    }
+    assert_eq!(pid.integral, 0.0);
+    // The output reverses as soon as the error does.
+    assert_eq!(pid.correction(DT, -1.0, 0.0), -1.0);
+
+    let mut pid = JointPidController::new(0.0, 1.0, 0.0);
+    pid.max_integral = 0.5;
+    for _ in 0..120 {
+        pid.correction(DT, 1.0, 0.0);
+    }
+    assert!((pid.correction(DT, 1.0, 0.0) - 0.5).abs() < 1.0e-5);
+    // Once bounded, the integral unwinds quickly.
+    for _ in 0..30 {
+        pid.correction(DT, -1.0, 0.0);
+    }
+    assert!(pid.integral.abs() < 1.0e-5);
+}
+
+#[test]
+fn the_derivative_filter_smooths_velocity_errors() {
+    let mut unfiltered = JointPidController::new(0.0, 0.0, 1.0);
+    assert_eq!(unfiltered.correction(DT, 0.0, 1.0), 1.0);
+
+    let mut filtered = unfiltered;
+    filtered.reset();
+    filtered.derivative_filter = 0.1;
+    let first = filtered.correction(DT, 0.0, 1.0);
+    assert!((first - DT / (0.1 + DT)).abs() < 1.0e-5);
+    for _ in 0..60 {
+        filtered.correction(DT, 0.0, 1.0);
+    }
+    assert!((filtered.filtered_derivative - 1.0).abs() < 1.0e-3);
+}
+
+#[test]
+fn an_impulse_joint_is_driven_to_its_target_angle() {
+    let mut world = PhysicsWorld::new();
+    let base = world.insert_body(RigidBodyBuilder::fixed());
+    let (bar, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(1.0, 0.0, 0.0)),
+        ColliderBuilder::cuboid(1.0, 0.1, 0.1),
+    );
+    let hinge = RevoluteJointBuilder::new(Vector::Z).local_anchor2(Vector::new(-1.0, 0.0, 0.0));
+    let handle = world.insert_impulse_joint(base, bar, hinge);
+
+    // Raise the bar against gravity.
+    let mut pid = JointPidController::default();
+    for _ in 0..180 {
+        let joint = world.impulse_joints.get_mut(handle, true).unwrap();
+        pid.drive_impulse_joint(DT, &world.bodies, joint, JointAxis::AngX, 0.8, 0.0);
+        world.step();
+    }
+
+    let joint = world.impulse_joints.get(handle).unwrap();
+    let angle = joint.axis_position(&world.bodies, JointAxis::AngX);
+    assert!((angle - 0.8).abs() < 0.02, "{angle}");
+    assert!(joint.axis_velocity(&world.bodies, JointAxis::AngX).abs() < 0.05);
+    let revolute_angle = joint
+        .data
+        .as_revolute()
+        .unwrap()
+        .angle(world.bodies[base].rotation(), world.bodies[bar].rotation());
+    assert!((angle - revolute_angle).abs() < 1.0e-5);
+    // The anchors stay together along the locked axes.
+    assert!(joint.axis_position(&world.bodies, JointAxis::LinX).abs() < 0.01);
+}
+
+#[test]
+fn the_axis_velocity_is_the_derivative_of_the_axis_position() {
+    // Both bodies move and spin freely, so the axes of the first joint frame rotate too. They
+    // start at the origin, where their center of mass is before any step is taken.
+    let (linvel1, angvel1) = (Vector::new(0.3, -0.2, 0.5), Vector::new(0.4, 1.2, -0.7));
+    let (linvel2, angvel2) = (Vector::new(-0.6, 0.1, 0.2), Vector::new(-0.5, 0.3, 0.9));
+    let joint_at = |t: Real| {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = ImpulseJointSet::new();
+        let body = |linvel: Vector, angvel: Vector| {
+            RigidBodyBuilder::dynamic()
+                .translation(linvel * t)
+                .rotation(angvel * t)
+                .linvel(linvel)
+                .angvel(angvel)
+        };
+        let body1 = bodies.insert(body(linvel1, angvel1));
+        let body2 = bodies.insert(body(linvel2, angvel2));
+        let prismatic = PrismaticJointBuilder::new(Vector::X)
+            .local_anchor1(Vector::new(0.8, 0.4, 0.0))
+            .local_anchor2(Vector::new(-0.3, 0.0, 0.1));
+        let handle = joints.insert(body1, body2, prismatic, true);
+        (bodies, joints, handle)
+    };
+
+    let h = 1.0e-3;
+    let (bodies, joints, handle) = joint_at(0.0);
+    let (bodies_before, joints_before, _) = joint_at(-h);
+    let (bodies_after, joints_after, _) = joint_at(h);
+    for axis in [JointAxis::LinX, JointAxis::LinY, JointAxis::LinZ] {
+        let derivative = (joints_after
+            .get(handle)
+            .unwrap()
+            .axis_position(&bodies_after, axis)
+            - joints_before
+                .get(handle)
+                .unwrap()
+                .axis_position(&bodies_before, axis))
+            / (2.0 * h);
+        let velocity = joints.get(handle).unwrap().axis_velocity(&bodies, axis);
+        assert!(
+            (velocity - derivative).abs() < 1.0e-2,
+            "{velocity} vs. {derivative}"
+        );
+    }
+}
+
+/// A two-link arm hanging from a fixed base, with revolute joints about Z.
+fn arm(world: &mut PhysicsWorld) -> [MultibodyJointHandle; 2] {
+    let (_, joints) = common::planar_arm(world, 2, 1.0);
+    [joints[0], joints[1]]
+}
+
+#[test]
+fn every_multibody_dof_is_driven_to_its_target() {
+    let mut world = PhysicsWorld::new();
+    let [_, tip] = arm(&mut world);
+    let targets = [0.5, -0.3];
+
+    let ndofs = world.multibody_joints.get(tip).unwrap().0.ndofs();
+    assert_eq!(ndofs, 2);
+    let mut pid = MultibodyPidController::new(ndofs, JointPidController::default());
+    for _ in 0..180 {
+        let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+        pid.update(DT, &mut world.bodies, multibody, &targets, &[0.0; 2]);
+        world.step();
+    }
+
+    let (multibody, _) = world.multibody_joints.get(tip).unwrap();
+    let coords = multibody.generalized_coordinates();
+    for (coord, target) in coords.iter().zip(targets) {
+        assert!((coord - target).abs() < 0.02, "{coord} vs. {target}");
+    }
+    assert!(multibody.generalized_velocity().amax() < 0.05);
+}
+
+#[test]
+fn a_single_multibody_joint_is_driven_to_its_target() {
+    let mut world = PhysicsWorld::new();
+    let [_, tip] = arm(&mut world);
+    world.gravity = Vector::ZERO;
+
+    let mut pid = JointPidController::default();
+    for _ in 0..120 {
+        let (multibody, link_id) = world.multibody_joints.get_mut(tip).unwrap();
+        pid.drive_multibody_joint(
+            DT,
+            &mut world.bodies,
+            multibody,
+            link_id,
+            JointAxis::AngX,
+            1.0,
+            0.0,
+        );
+        // Locked axes can't be driven.
+        assert_eq!(
+            pid.drive_multibody_joint(
+                DT,
+                &mut world.bodies,
+                multibody,
+                link_id,
+                JointAxis::LinX,
+                1.0,
+                0.0
+            ),
+            0.0
+        );
+        world.step();
+    }
+
+    let (multibody, tip_link) = world.multibody_joints.get(tip).unwrap();
+    let tip_angle = multibody.link(tip_link).unwrap().joint().coords()[3];
+    assert!((tip_angle - 1.0).abs() < 0.02, "{tip_angle}");
+}
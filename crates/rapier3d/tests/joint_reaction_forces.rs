@@ -0,0 +1,167 @@
+//! Joint reaction read-back: the force and torque an impulse or multibody joint applied
+//! during the last step, and the generalized forces of the motors and limits of impulse and
+//! multibody joints.
+
+use rapier3d::prelude::*;
+
+const WEIGHT: Real = 10.0 * 9.81;
+
+fn world() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world
+}
+
+/// Inserts a fixed anchor and a 10 kg cube with its center of mass `offset` away from it.
+fn anchor_and_cube(world: &mut PhysicsWorld, offset: Vector) -> (RigidBodyHandle, RigidBodyHandle) {
+    let anchor = world.insert_body(RigidBodyBuilder::fixed());
+    let (cube, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(offset)
+            .can_sleep(false),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5).density(10.0),
+    );
+    (anchor, cube)
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+fn assert_close(actual: Real, expected: Real) {
+    assert!(
+        (actual - expected).abs() < expected.abs() * 0.02 + 1.0e-3,
+        "expected {expected}, got {actual}"
+    );
+}
+
+#[test]
+fn fixed_joint_reaction_balances_the_weight() {
+    let mut world = world();
+    let offset = Vector::new(0.0, -1.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    let joint = world.insert_impulse_joint(
+        anchor,
+        cube,
+        FixedJointBuilder::new().local_anchor2(-offset),
+    );
+
+    run(&mut world, 30);
+
+    let joint = world.impulse_joints.get(joint).unwrap();
+    let force = joint.reaction_force();
+    assert_close(force.y, WEIGHT);
+    assert!(
+        force.x.abs() < 1.0e-2 && force.z.abs() < 1.0e-2,
+        "{force:?}"
+    );
+    assert!(joint.reaction_torque().length() < 1.0e-2);
+}
+
+#[test]
+fn fixed_joint_reaction_torque_is_measured_at_the_anchor() {
+    let mut world = world();
+    // A cantilever: the weight 2 m away from the anchor along +X.
+    let offset = Vector::new(2.0, 0.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    let joint = world.insert_impulse_joint(
+        anchor,
+        cube,
+        FixedJointBuilder::new().local_anchor2(-offset),
+    );
+
+    run(&mut world, 30);
+
+    let joint = world.impulse_joints.get(joint).unwrap();
+    assert_close(joint.reaction_force().y, WEIGHT);
+    assert_close(joint.reaction_torque().z, 2.0 * WEIGHT);
+}
+
+#[test]
+fn local_reaction_is_expressed_in_the_second_joint_frame() {
+    let mut world = world();
+    let offset = Vector::new(0.0, -1.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    // The joint frame on the cube is rotated so that its local X axis points up.
+    let frame2 = Pose::new(-offset, Vector::new(0.0, 0.0, std::f32::consts::FRAC_PI_2));
+    let mut joint = FixedJoint::new();
+    joint.set_local_frame1(frame2).set_local_frame2(frame2);
+    let joint = world.insert_impulse_joint(anchor, cube, joint);
+
+    run(&mut world, 30);
+
+    let joint = world.impulse_joints.get(joint).unwrap();
+    let local_force = joint.local_reaction_force(&world.bodies);
+    assert_close(local_force.x, WEIGHT);
+    assert!(local_force.y.abs() < 1.0e-2, "{local_force:?}");
+}
+
+#[test]
+fn prismatic_motor_force_holds_the_weight() {
+    let mut world = world();
+    let offset = Vector::new(0.0, -1.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    let joint = world.insert_impulse_joint(
+        anchor,
+        cube,
+        PrismaticJointBuilder::new(Vector::Y)
+            .local_anchor2(-offset)
+            .motor_velocity(0.0, 1.0e4),
+    );
+
+    run(&mut world, 60);
+
+    let joint = world.impulse_joints.get(joint).unwrap();
+    assert_close(joint.motor_force(JointAxis::LinX).abs(), WEIGHT);
+    assert_eq!(joint.limit_force(JointAxis::LinX), 0.0);
+    // The motor is the only thing holding the cube along the sliding axis.
+    assert_close(joint.reaction_force().y, WEIGHT);
+}
+
+#[test]
+fn multibody_motor_force_holds_the_weight() {
+    let mut world = world();
+    let offset = Vector::new(0.0, -1.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    let joint = world
+        .insert_multibody_joint(
+            anchor,
+            cube,
+            PrismaticJointBuilder::new(Vector::Y)
+                .local_anchor2(-offset)
+                .motor_velocity(0.0, 1.0e4),
+        )
+        .unwrap();
+
+    run(&mut world, 60);
+
+    let (multibody, link_id) = world.multibody_joints.get(joint).unwrap();
+    let joint = multibody.link(link_id).unwrap().joint();
+    assert_close(joint.motor_force(JointAxis::LinX).abs(), WEIGHT);
+    assert_eq!(joint.limit_force(JointAxis::LinX), 0.0);
+    assert_close(joint.reaction_force().y, WEIGHT);
+}
+
+#[test]
+fn multibody_reaction_torque_is_measured_at_the_anchor() {
+    let mut world = world();
+    let offset = Vector::new(2.0, 0.0, 0.0);
+    let (anchor, cube) = anchor_and_cube(&mut world, offset);
+    let joint = world
+        .insert_multibody_joint(
+            anchor,
+            cube,
+            FixedJointBuilder::new().local_anchor2(-offset),
+        )
+        .unwrap();
+
+    run(&mut world, 30);
+
+    let (multibody, link_id) = world.multibody_joints.get(joint).unwrap();
+    let link = multibody.link(link_id).unwrap();
+    assert_close(link.joint().reaction_force().y, WEIGHT);
+    assert_close(link.joint().reaction_torque().z, 2.0 * WEIGHT);
+    assert_close(link.local_reaction_force().y, WEIGHT);
+}
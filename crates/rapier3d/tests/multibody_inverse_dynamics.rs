@@ -0,0 +1,118 @@
+//! `Multibody::inverse_dynamics`, `Multibody::coriolis_torques` and
+//! `Multibody::gravity_compensation_torques`, checked against the forward dynamics of the
+//! simulation.
+
+use rapier3d::prelude::*;
+
+mod common;
+
+/// A planar arm of heavy links bent by `angles`, with undamped joints.
+///
+/// Returns the joint handle of the tip link, and the bodies of every link.
+fn arm(world: &mut PhysicsWorld, angles: &[Real]) -> (MultibodyJointHandle, Vec<RigidBodyHandle>) {
+    let (links, joints) = common::planar_arm(world, angles.len(), 1000.0);
+    let tip = *joints.last().unwrap();
+    // Keep the steps short so that they match the continuous-time dynamics closely.
+    world.integration_parameters.dt = 1.0e-4;
+    let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+    multibody.damping_mut().fill(0.0);
+    common::bend(world, tip, angles);
+    (tip, links)
+}
+
+/// Steps `world` after applying the generalized forces `torques`, if any, to the multibody, and
+/// returns its resulting generalized accelerations.
+///
+/// The torques are applied by adding `M⁻¹·τ·dt` to the generalized velocities.
+fn step_with_torques(
+    world: &mut PhysicsWorld,
+    tip: MultibodyJointHandle,
+    torques: Option<&DVector>,
+) -> DVector {
+    let dt = world.integration_parameters.dt;
+    let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+    let before = multibody.generalized_velocity().clone_owned();
+    if let Some(torques) = torques {
+        let mass = multibody.mass_matrix(&world.bodies);
+        let mut vel_change = torques * dt;
+        assert!(mass.lu().solve_mut(&mut vel_change));
+        let mut velocities = multibody.generalized_velocity_mut();
+        velocities += vel_change;
+    }
+
+    world.step();
+    let (multibody, _) = world.multibody_joints.get(tip).unwrap();
+    (multibody.generalized_velocity() - before) / dt
+}
+
+#[test]
+fn the_mass_matrix_maps_accelerations_to_applied_torques() {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::ZERO;
+    let (tip, links) = arm(&mut world, &[0.3, 0.6, 0.6]);
+
+    // A torque on the tip is felt by every joint, since they share its axis.
+    world.bodies[links[2]].add_torque(Vector::Z, true);
+    let accelerations = step_with_torques(&mut world, tip, None);
+
+    let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+    let torques = multibody.inverse_dynamics(&world.bodies, Vector::ZERO, accelerations.as_slice());
+    let from_mass_matrix = multibody.mass_matrix(&world.bodies) * &accelerations;
+    for i in 0..3 {
+        assert!((torques[i] - 1.0).abs() < 1.0e-2, "{torques}");
+        assert!(
+            (from_mass_matrix[i] - 1.0).abs() < 1.0e-2,
+            "{from_mass_matrix}"
+        );
+    }
+}
+
+#[test]
+fn gravity_compensation_holds_the_arm_still() {
+    let hold = |compensate: bool| {
+        let mut world = PhysicsWorld::new();
+        let (tip, _) = arm(&mut world, &[0.3, 0.6, 0.6]);
+        for _ in 0..5000 {
+            let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+            let torques = multibody.gravity_compensation_torques(&world.bodies, world.gravity);
+            step_with_torques(&mut world, tip, compensate.then_some(&torques));
+        }
+        let (multibody, _) = world.multibody_joints.get(tip).unwrap();
+        multibody.generalized_coordinates()
+    };
+
+    let initial = DVector::from_row_slice(&[0.3, 0.6, 0.6]);
+    let held = hold(true);
+    assert!((&held - &initial).amax() < 1.0e-3, "{held}");
+    // Without compensation, the arm falls.
+    let fallen = hold(false);
+    assert!((&fallen - &initial).amax() > 0.1, "{fallen}");
+}
+
+#[test]
+fn inverse_dynamics_torques_produce_the_requested_accelerations() {
+    let mut world = PhysicsWorld::new();
+    let (tip, _) = arm(&mut world, &[0.3, 0.6, 0.6]);
+    let target = [2.0, -1.0, 0.5];
+
+    let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+    multibody
+        .generalized_velocity_mut()
+        .copy_from_slice(&[1.0, -2.0, 1.5]);
+    // The arm moves fast enough for the velocity-dependent forces to matter.
+    assert!(multibody.coriolis_torques(&world.bodies).amax() > 1.0);
+
+    let torques = multibody.inverse_dynamics(&world.bodies, world.gravity, &target);
+    let expected = multibody.mass_matrix(&world.bodies) * DVector::from_row_slice(&target)
+        + multibody.coriolis_torques(&world.bodies)
+        + multibody.gravity_compensation_torques(&world.bodies, world.gravity);
+    assert!(
+        (&torques - &expected).amax() < 1.0e-3,
+        "{torques} vs. {expected}"
+    );
+
+    let accelerations = step_with_torques(&mut world, tip, Some(&torques));
+    for (acceleration, target) in accelerations.iter().zip(target) {
+        assert!((acceleration - target).abs() < 0.05, "{accelerations}");
+    }
+}
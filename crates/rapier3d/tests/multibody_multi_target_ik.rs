@@ -0,0 +1,282 @@
+//! `Multibody::inverse_kinematics_multi`: several targets with priority weights, joint limits,
+//! a secondary posture objective, and `ImpulseJointTree` to solve impulse-joint trees.
+
+use rapier3d::prelude::*;
+
+mod common;
+
+use common::LINK_TIP as TIP;
+
+fn options() -> InverseKinematicsOption {
+    InverseKinematicsOption {
+        damping: 0.1,
+        max_iters: 200,
+        ..Default::default()
+    }
+}
+
+fn tip_target(link_id: usize, position: Vector) -> InverseKinematicsTarget {
+    InverseKinematicsTarget {
+        local_frame: Pose::from_translation(TIP),
+        constrained_axes: JointAxesMask::LIN_X | JointAxesMask::LIN_Y,
+        ..InverseKinematicsTarget::new(link_id, Pose::from_translation(position))
+    }
+}
+
+/// The tips of the trunk and of one branch of a `y_tree` with the given joint angles.
+fn tips(trunk_angle: Real, branch_angle: Real) -> (Vector, Vector) {
+    let angle = trunk_angle + branch_angle;
+    let trunk_tip = Vector::new(trunk_angle.cos(), trunk_angle.sin(), 0.0);
+    (
+        trunk_tip,
+        trunk_tip + Vector::new(angle.cos(), angle.sin(), 0.0),
+    )
+}
+
+/// A planar “Y” of unit-length links hinged about Z: a trunk starting from a fixed base at the
+/// origin, and two branches attached to the trunk’s tip.
+///
+/// Returns the joint handle and link id of the trunk, and of the left and right branches.
+fn y_tree(
+    world: &mut PhysicsWorld,
+    trunk_limits: Option<[Real; 2]>,
+) -> [(MultibodyJointHandle, usize); 3] {
+    let base = world.insert_body(RigidBodyBuilder::fixed());
+    let (trunk, trunk_joint) = common::insert_hinged_link(world, base, 0.0, 1.0, trunk_limits);
+    let (_, left_joint) = common::insert_hinged_link(world, trunk, TIP.x, 1.0, None);
+    let (_, right_joint) = common::insert_hinged_link(world, trunk, TIP.x, 1.0, None);
+
+    // Let the fixed root collapse to zero degrees of freedom.
+    let (multibody, _) = world.multibody_joints.get_mut(trunk_joint).unwrap();
+    multibody.forward_kinematics(&world.bodies, true);
+    assert_eq!(multibody.ndofs(), 3);
+
+    [trunk_joint, left_joint, right_joint].map(|handle| {
+        let (_, link_id) = world.multibody_joints.get(handle).unwrap();
+        (handle, link_id)
+    })
+}
+
+/// The world-space tip of the link `link_id` after applying `displacements` to `multibody`.
+fn displaced_tip(
+    world: &PhysicsWorld,
+    handle: MultibodyJointHandle,
+    link_id: usize,
+    displacements: &DVector,
+) -> Vector {
+    let (multibody, _) = world.multibody_joints.get(handle).unwrap();
+    let mut multibody = multibody.clone();
+    multibody.apply_displacements(displacements.as_slice());
+    multibody.forward_kinematics(&world.bodies, false);
+    multibody.link(link_id).unwrap().local_to_world() * TIP
+}
+
+#[test]
+fn several_links_reach_their_targets_simultaneously() {
+    let mut world = PhysicsWorld::new();
+    let [(handle, _), (_, left), (_, right)] = y_tree(&mut world, None);
+    let (_, left_tip) = tips(0.4, 0.5);
+    let (_, right_tip) = tips(0.4, -0.7);
+
+    let (multibody, _) = world.multibody_joints.get(handle).unwrap();
+    let mut displacements = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics_multi(
+        &world.bodies,
+        &[tip_target(left, left_tip), tip_target(right, right_tip)],
+        None,
+        &options(),
+        |_| true,
+        &mut displacements,
+    );
+
+    for (link_id, target) in [(left, left_tip), (right, right_tip)] {
+        let tip = displaced_tip(&world, handle, link_id, &displacements);
+        assert!((tip - target).length() < 1.0e-2, "{tip} vs. {target}");
+    }
+}
+
+#[test]
+fn conflicting_targets_are_balanced_by_their_weights() {
+    let mut world = PhysicsWorld::new();
+    let [(handle, _), (_, left), _] = y_tree(&mut world, None);
+    let (_, first) = tips(0.4, 0.5);
+    let second = first - Vector::new(0.0, 0.4, 0.0);
+    let targets = [
+        tip_target(left, first),
+        InverseKinematicsTarget {
+            weight: 3.0,
+            ..tip_target(left, second)
+        },
+    ];
+
+    let (multibody, _) = world.multibody_joints.get(handle).unwrap();
+    let mut displacements = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics_multi(
+        &world.bodies,
+        &targets,
+        None,
+        &options(),
+        |_| true,
+        &mut displacements,
+    );
+
+    // The weighted least-squares solution lies three quarters of the way to the second target.
+    let expected = first.lerp(second, 0.75);
+    let tip = displaced_tip(&world, handle, left, &displacements);
+    assert!((tip - expected).length() < 1.0e-2, "{tip} vs. {expected}");
+}
+
+#[test]
+fn the_solution_is_clamped_to_the_joint_limits() {
+    let mut world = PhysicsWorld::new();
+    let [(handle, _), (_, left), _] = y_tree(&mut world, Some([-0.2, 0.2]));
+    // Reaching this target exactly requires a trunk angle of either 0.4 or 0.9.
+    let (_, target) = tips(0.4, 0.5);
+
+    let (multibody, _) = world.multibody_joints.get(handle).unwrap();
+    let trunk_angle =
+        |displacements: &DVector| multibody.generalized_coordinates()[0] + displacements[0];
+
+    let mut unlimited = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics_multi(
+        &world.bodies,
+        &[tip_target(left, target)],
+        None,
+        &options(),
+        |_| true,
+        &mut unlimited,
+    );
+    assert!(trunk_angle(&unlimited) > 0.3);
+    let tip = displaced_tip(&world, handle, left, &unlimited);
+    assert!((tip - target).length() < 1.0e-2);
+
+    let limited_options = InverseKinematicsOption {
+        enforce_joint_limits: true,
+        ..options()
+    };
+    let mut limited = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics_multi(
+        &world.bodies,
+        &[tip_target(left, target)],
+        None,
+        &limited_options,
+        |_| true,
+        &mut limited,
+    );
+    assert!(trunk_angle(&limited).abs() <= 0.2 + 1.0e-5);
+    // The closest reachable point is on the circle of the branch around the trunk’s tip.
+    let (trunk_tip, _) = tips(0.2, 0.0);
+    let tip = displaced_tip(&world, handle, left, &limited);
+    let expected_distance = (target - trunk_tip).length() - 1.0;
+    assert!(((tip - target).length() - expected_distance).abs() < 1.0e-2);
+
+    // The single-target solver enforces the limits too.
+    let mut single = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics(
+        &world.bodies,
+        left,
+        &InverseKinematicsOption {
+            constrained_axes: JointAxesMask::LIN_X | JointAxesMask::LIN_Y,
+            ..limited_options
+        },
+        &Pose::from_translation(target),
+        |_| true,
+        &mut single,
+    );
+    assert!(trunk_angle(&single).abs() <= 0.2 + 1.0e-5);
+}
+
+#[test]
+fn the_posture_is_reached_in_the_null_space_of_the_targets() {
+    let mut world = PhysicsWorld::new();
+    let [(handle, _), (_, left), (_, right)] = y_tree(&mut world, None);
+    let (_, target) = tips(0.4, 0.5);
+
+    let (multibody, _) = world.multibody_joints.get(handle).unwrap();
+    let mut displacements = DVector::zeros(multibody.ndofs());
+    multibody.inverse_kinematics_multi(
+        &world.bodies,
+        &[tip_target(left, target)],
+        Some(&[0.0, 0.0, 0.8]),
+        &options(),
+        |_| true,
+        &mut displacements,
+    );
+
+    let tip = displaced_tip(&world, handle, left, &displacements);
+    assert!((tip - target).length() < 1.0e-2, "{tip} vs. {target}");
+    // The right branch doesn’t affect the target, so it follows the posture entirely.
+    let right_angle = multibody.generalized_coordinates()[2] + displacements[2];
+    assert!((right_angle - 0.8).abs() < 1.0e-2, "{right_angle}");
+    let (_, right_tip) = tips(0.4, 0.8);
+    let tip = displaced_tip(&world, handle, right, &displacements);
+    assert!((tip - right_tip).length() < 0.1);
+}
+
+#[test]
+fn an_impulse_joint_tree_is_solved_and_moved() {
+    let mut world = PhysicsWorld::new();
+    let base = world.insert_body(RigidBodyBuilder::fixed());
+    let insert_link = |world: &mut PhysicsWorld, center: Real| {
+        let (link, _) = world.insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(center, 0.0, 0.0)),
+            ColliderBuilder::ball(0.1),
+        );
+        link
+    };
+    let trunk = insert_link(&mut world, 0.5);
+    let left = insert_link(&mut world, 1.5);
+    let right = insert_link(&mut world, 1.5);
+
+    let hinge = |anchor1: Vector, anchor2: Vector| {
+        RevoluteJointBuilder::new(Vector::Z)
+            .local_anchor1(anchor1)
+            .local_anchor2(anchor2)
+    };
+    world.insert_impulse_joint(base, trunk, hinge(Vector::ZERO, -TIP));
+    let left_joint = world.insert_impulse_joint(trunk, left, hinge(TIP, -TIP));
+    // Joints don’t need to point away from the root.
+    world.insert_impulse_joint(right, trunk, hinge(-TIP, TIP));
+
+    let mut tree = ImpulseJointTree::new(&world.bodies, &world.impulse_joints, base);
+    assert_eq!(tree.multibody().ndofs(), 3);
+    let [left_id, right_id] = [left, right].map(|body| tree.link_id(body).unwrap());
+    assert_eq!(tree.joint(left_id), Some(left_joint));
+    assert_eq!(tree.link_id(base), Some(0));
+    assert_eq!(tree.joint(0), None);
+
+    let (_, left_tip) = tips(0.4, 0.5);
+    let (_, right_tip) = tips(0.4, -0.7);
+    let mut displacements = DVector::zeros(3);
+    tree.multibody().inverse_kinematics_multi(
+        &world.bodies,
+        &[
+            tip_target(left_id, left_tip),
+            tip_target(right_id, right_tip),
+        ],
+        None,
+        &options(),
+        |_| true,
+        &mut displacements,
+    );
+    tree.apply_displacements(&mut world.bodies, displacements.as_slice());
+
+    for (body, target) in [(left, left_tip), (right, right_tip)] {
+        let tip = world.bodies[body].position() * TIP;
+        assert!((tip - target).length() < 1.0e-2, "{tip} vs. {target}");
+    }
+    // The joint anchors still coincide.
+    let anchor = world.bodies[trunk].position() * TIP;
+    for body in [left, right] {
+        assert!((world.bodies[body].position() * -TIP - anchor).length() < 1.0e-4);
+    }
+
+    // A tree mirroring the moved bodies finds the same coordinates.
+    let moved = ImpulseJointTree::new(&world.bodies, &world.impulse_joints, base);
+    let coords = tree.multibody().generalized_coordinates();
+    let moved_coords = moved.multibody().generalized_coordinates();
+    assert!(
+        (&coords - &moved_coords).amax() < 1.0e-4,
+        "{coords} vs. {moved_coords}"
+    );
+}
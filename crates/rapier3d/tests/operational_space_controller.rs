@@ -0,0 +1,159 @@
+//! `OperationalSpaceController`: task-space control of a multibody link, with a secondary posture
+//! objective in the task's null-space and gravity compensation, and `Multibody::mass_matrix`.
+
+use rapier3d::control::{OperationalSpaceController, OperationalSpaceTarget};
+use rapier3d::prelude::*;
+
+mod common;
+
+const DT: Real = 1.0 / 60.0;
+
+/// A planar arm of heavy links bent by `angles`, without gravity.
+///
+/// Returns the joint handle of the tip link, and the bodies of every link.
+fn arm(world: &mut PhysicsWorld, angles: &[Real]) -> (MultibodyJointHandle, Vec<RigidBodyHandle>) {
+    world.gravity = Vector::ZERO;
+    let (links, joints) = common::planar_arm(world, angles.len(), 1000.0);
+    let tip = *joints.last().unwrap();
+    common::bend(world, tip, angles);
+    (tip, links)
+}
+
+/// The controller of the arm's tip position.
+fn tip_controller() -> OperationalSpaceController {
+    OperationalSpaceController {
+        local_frame: Pose::from_translation(common::LINK_TIP),
+        constrained_axes: JointAxesMask::LIN_X | JointAxesMask::LIN_Y,
+        ..OperationalSpaceController::default()
+    }
+}
+
+fn reach(
+    world: &mut PhysicsWorld,
+    tip: MultibodyJointHandle,
+    target: &OperationalSpaceTarget,
+    steps: usize,
+) {
+    let controller = tip_controller();
+    for _ in 0..steps {
+        let (multibody, link_id) = world.multibody_joints.get_mut(tip).unwrap();
+        controller.apply(
+            DT,
+            &mut world.bodies,
+            world.gravity,
+            multibody,
+            link_id,
+            target,
+        );
+        world.step();
+    }
+}
+
+#[test]
+fn the_mass_matrix_of_a_pendulum_is_its_inertia_about_the_hinge() {
+    let mut world = PhysicsWorld::new();
+    let (tip, links) = arm(&mut world, &[0.0]);
+    let mass = world.bodies[links[0]].mass();
+
+    let (multibody, _) = world.multibody_joints.get_mut(tip).unwrap();
+    let mass_matrix = multibody.mass_matrix(&world.bodies);
+    assert_eq!(mass_matrix.shape(), (1, 1));
+    // Ball inertia about its center, shifted to the hinge.
+    let expected = 0.4 * mass * 0.1 * 0.1 + mass * 0.5 * 0.5;
+    assert!((mass_matrix[(0, 0)] - expected).abs() < 1.0e-4 * expected);
+}
+
+#[test]
+fn the_feed_forward_force_is_mapped_to_joint_torques() {
+    let mut world = PhysicsWorld::new();
+    let (tip, _) = arm(&mut world, &[0.0; 3]);
+    let controller = OperationalSpaceController {
+        local_frame: Pose::from_translation(common::LINK_TIP),
+        lin_kp: 0.0,
+        lin_kd: 0.0,
+        ang_kp: 0.0,
+        ang_kd: 0.0,
+        ..OperationalSpaceController::default()
+    };
+    let mut target = OperationalSpaceTarget::new(Pose::IDENTITY);
+    target.force = Vector::Y;
+
+    let (multibody, link_id) = world.multibody_joints.get_mut(tip).unwrap();
+    let torques =
+        controller.joint_torques(&world.bodies, world.gravity, multibody, link_id, &target);
+    // The tip is at (3, 0, 0): each joint feels the force's moment about its hinge.
+    for (torque, expected) in torques.iter().zip([3.0, 2.0, 1.0]) {
+        assert!((torque - expected).abs() < 1.0e-4, "{torques}");
+    }
+}
+
+#[test]
+fn the_tip_reaches_its_target() {
+    let mut world = PhysicsWorld::new();
+    let (tip, links) = arm(&mut world, &[0.3, 0.6, 0.6]);
+    let target = OperationalSpaceTarget::new(Pose::from_translation(Vector::new(2.0, 1.0, 0.0)));
+    reach(&mut world, tip, &target, 180);
+
+    let tip_pos = world.bodies[links[2]].position() * common::LINK_TIP;
+    assert!(
+        (tip_pos - target.pose.translation).length() < 0.02,
+        "{tip_pos}"
+    );
+    assert!(world.bodies[links[2]].linvel().length() < 0.05);
+}
+
+#[test]
+fn the_posture_is_controlled_in_the_null_space_of_the_task() {
+    let reach_with_posture = |posture: [Real; 3]| {
+        let mut world = PhysicsWorld::new();
+        let (tip, links) = arm(&mut world, &[0.3, 0.6, 0.6]);
+        let mut target =
+            OperationalSpaceTarget::new(Pose::from_translation(Vector::new(2.0, 1.0, 0.0)));
+        target.posture = Some(DVector::from_row_slice(&posture));
+        reach(&mut world, tip, &target, 240);
+
+        let tip_pos = world.bodies[links[2]].position() * common::LINK_TIP;
+        assert!(
+            (tip_pos - target.pose.translation).length() < 0.02,
+            "{tip_pos}"
+        );
+        let (multibody, _) = world.multibody_joints.get(tip).unwrap();
+        multibody.generalized_coordinates()
+    };
+
+    // The two postures bend the arm in opposite directions, leading to different configurations.
+    let first = reach_with_posture([0.0, 1.0, -0.5]);
+    let second = reach_with_posture([1.0, -0.5, -0.5]);
+    assert!((first[0] - second[0]).abs() > 0.1, "{first} vs. {second}");
+}
+
+#[test]
+fn the_tip_reaches_its_target_under_gravity() {
+    let mut world = PhysicsWorld::new();
+    let (tip, links) = arm(&mut world, &[0.3, 0.6, 0.6]);
+    // Gravity in the plane of the arm: without compensation, the tip sags by about `g / lin_kp`.
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    let mut target =
+        OperationalSpaceTarget::new(Pose::from_translation(Vector::new(2.0, 1.0, 0.0)));
+    target.posture = Some(DVector::from_row_slice(&[0.0, 1.0, -0.5]));
+    reach(&mut world, tip, &target, 240);
+
+    let tip_pos = world.bodies[links[2]].position() * common::LINK_TIP;
+    assert!(
+        (tip_pos - target.pose.translation).length() < 0.02,
+        "{tip_pos}"
+    );
+    assert!(world.bodies[links[2]].linvel().length() < 0.05);
+}
+
+#[test]
+fn applying_the_torques_wakes_the_arm_up() {
+    let mut world = PhysicsWorld::new();
+    let (tip, links) = arm(&mut world, &[0.3, 0.6, 0.6]);
+    for link in &links {
+        world.bodies[*link].sleep();
+    }
+    let target = OperationalSpaceTarget::new(Pose::from_translation(Vector::new(2.0, 1.0, 0.0)));
+    reach(&mut world, tip, &target, 1);
+    assert!(links.iter().all(|link| !world.bodies[*link].is_sleeping()));
+}
@@ -0,0 +1,209 @@
+//! Replays: recording the inputs applied to a `PhysicsWorld` and re-simulating them.
+
+use rapier3d::prelude::*;
+
+/// Records a short scene exercising every kind of input.
+fn record() -> (Replay, PhysicsWorld) {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(ColliderBuilder::cuboid(20.0, 0.1, 20.0), None);
+    let mut recorder = ReplayRecorder::new(&world);
+
+    let mut balls = vec![];
+    for i in 0..4 {
+        let ball = recorder.insert_body(
+            &mut world,
+            RigidBodyBuilder::dynamic().translation(Vector::new(i as Real, 1.0 + i as Real, 0.0)),
+        );
+        recorder.insert_collider(&mut world, ColliderBuilder::ball(0.4), Some(ball));
+        balls.push(ball);
+    }
+    let joint = recorder.insert_impulse_joint(
+        &mut world,
+        balls[0],
+        balls[1],
+        SphericalJointBuilder::new().local_anchor2(Vector::new(-1.0, 0.0, 0.0)),
+    );
+
+    for step in 0..120 {
+        match step {
+            10 => recorder.record(
+                &mut world,
+                ReplayInput::ApplyImpulse {
+                    body: balls[2],
+                    impulse: Vector::new(1.0, 3.0, 0.0),
+                    wake_up: true,
+                },
+            ),
+            20..40 => recorder.record(
+                &mut world,
+                ReplayInput::AddForceAtPoint {
+                    body: balls[3],
+                    force: Vector::new(0.0, 0.0, 2.0),
+                    point: Vector::new(3.0, 5.0, 0.3),
+                    wake_up: true,
+                },
+            ),
+            40 => recorder.record(
+                &mut world,
+                ReplayInput::ResetForces {
+                    body: balls[3],
+                    wake_up: true,
+                },
+            ),
+            50 => recorder.record(
+                &mut world,
+                ReplayInput::RemoveImpulseJoint { handle: joint },
+            ),
+            60 => recorder.record(&mut world, ReplayInput::RemoveBody { handle: balls[2] }),
+            70 => {
+                // Reuses the slot freed by the removal.
+                let ball = recorder.insert_body(
+                    &mut world,
+                    RigidBodyBuilder::dynamic()
+                        .translation(Vector::new(0.0, 4.0, 0.0))
+                        .angvel(Vector::new(0.0, 5.0, 0.0)),
+                );
+                recorder.insert_collider(
+                    &mut world,
+                    ColliderBuilder::cuboid(0.3, 0.3, 0.3),
+                    Some(ball),
+                );
+            }
+            _ => {}
+        }
+        recorder.step(&mut world);
+    }
+
+    (recorder.finish(), world)
+}
+
+#[test]
+fn replay_reproduces_the_recorded_simulation() {
+    let (replay, world) = record();
+    assert_eq!(replay.num_steps(), 120);
+
+    let replayed = replay.run().unwrap();
+    assert_eq!(replayed.bodies.len(), world.bodies.len());
+    for (handle, rb) in world.bodies.iter() {
+        let replayed = &replayed.bodies[handle];
+        assert_eq!(replayed.position(), rb.position());
+        assert_eq!(replayed.linvel(), rb.linvel());
+        assert_eq!(replayed.angvel(), rb.angvel());
+    }
+}
+
+#[test]
+fn replay_reports_the_first_diverging_step() {
+    let (mut replay, _) = record();
+
+    // Drop the impulse applied before step 10.
+    replay.frames[10].inputs.clear();
+    match replay.run() {
+        Err(ReplayError::StateMismatch { step, .. }) => assert_eq!(step, 10),
+        result => panic!("unexpected replay result: {:?}", result.err()),
+    }
+
+    // A different initial state diverges from the first step.
+    let (mut replay, _) = record();
+    replay.initial_state.gravity = Vector::new(0.0, -1.0, 0.0);
+    let mut replayer = replay.replayer();
+    assert!(matches!(
+        replayer.step(),
+        Err(ReplayError::StateMismatch { step: 0, .. })
+    ));
+}
+
+#[test]
+fn replay_reports_handle_mismatches() {
+    let (mut replay, _) = record();
+
+    // An extra body makes the following insertions get other handles.
+    replay.initial_state.insert_body(RigidBodyBuilder::fixed());
+    assert_eq!(
+        replay.run().err(),
+        Some(ReplayError::HandleMismatch { step: 0 })
+    );
+}
+
+#[test]
+fn replay_copies_the_force_fields_and_records_the_multibodies() {
+    let mut world = PhysicsWorld::new();
+    let wind = world
+        .force_fields_mut()
+        .insert(WindField::new(Vector::new(3.0, 0.0, 0.0), 0.5));
+    world.set_gravity_provider(
+        GravitySources::new().with(PointGravityField::new(Vector::new(0.0, -50.0, 0.0), 2.0e4)),
+    );
+    let mut recorder = ReplayRecorder::new(&world);
+
+    // A free ball pushed by the wind, and a pendulum of two links swinging under gravity.
+    let ball = recorder.insert_body(
+        &mut world,
+        RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.0, 3.0)),
+    );
+    recorder.insert_collider(&mut world, ColliderBuilder::ball(0.4), Some(ball));
+    let mut parent = recorder.insert_body(&mut world, RigidBodyBuilder::fixed());
+    let mut joints = vec![];
+    for i in 1..=2 {
+        let link = recorder.insert_body(
+            &mut world,
+            RigidBodyBuilder::dynamic().translation(Vector::new(i as Real, 0.0, 0.0)),
+        );
+        recorder.insert_collider(&mut world, ColliderBuilder::ball(0.1), Some(link));
+        let joint = RevoluteJointBuilder::new(Vector::Z).local_anchor2(Vector::new(-1.0, 0.0, 0.0));
+        joints.push(
+            recorder
+                .insert_multibody_joint(&mut world, parent, link, joint)
+                .unwrap(),
+        );
+        parent = link;
+    }
+
+    for step in 0..60 {
+        if step == 30 {
+            recorder.record(
+                &mut world,
+                ReplayInput::RemoveMultibodyJoint { handle: joints[1] },
+            );
+        }
+        recorder.step(&mut world);
+    }
+    let mut replay = recorder.finish();
+
+    // The wind kept its handle in the initial state.
+    assert!(
+        replay
+            .initial_state
+            .force_fields()
+            .field::<WindField>(wind)
+            .is_some()
+    );
+    let replayed = replay.run().unwrap();
+    for (handle, rb) in world.bodies.iter() {
+        assert_eq!(replayed.bodies[handle].position(), rb.position());
+    }
+    assert_eq!(
+        replayed.multibody_joints.state_hash(),
+        world.multibody_joints.state_hash()
+    );
+
+    // Without the wind, the ball drifts from the first step.
+    replay.initial_state.force_fields_mut().clear();
+    assert!(matches!(
+        replay.run(),
+        Err(ReplayError::StateMismatch { step: 0, .. })
+    ));
+}
+
+#[cfg(feature = "serde-serialize")]
+#[test]
+fn replay_survives_serialization() {
+    let (replay, world) = record();
+    let bytes = bincode::serialize(&replay).unwrap();
+    let replay: Replay = bincode::deserialize(&bytes).unwrap();
+
+    let replayed = replay.run().unwrap();
+    for (handle, rb) in world.bodies.iter() {
+        assert_eq!(replayed.bodies[handle].position(), rb.position());
+    }
+}
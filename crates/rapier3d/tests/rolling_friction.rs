@@ -0,0 +1,141 @@
+//! Rolling and spinning resistance: `ColliderMaterial::rolling_friction` slows down rolling
+//! balls, `ColliderMaterial::spinning_friction` slows down balls spinning around the contact
+//! normal, and neither affects a body when left to zero. Multibody links get the same
+//! resistance as rigid bodies.
+
+use rapier3d::prelude::*;
+
+mod common;
+
+use common::make_multibody_root;
+
+fn world_with_ground(rolling_friction: Real, spinning_friction: Real) -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world.insert(
+        RigidBodyBuilder::fixed(),
+        ColliderBuilder::cuboid(50.0, 0.1, 50.0)
+            .rolling_friction(rolling_friction)
+            .spinning_friction(spinning_friction),
+    );
+    world
+}
+
+/// A ball of radius 0.5 resting on the ground.
+fn ball(
+    world: &mut PhysicsWorld,
+    linvel: Vector,
+    angvel: Vector,
+    rolling_friction: Real,
+    spinning_friction: Real,
+) -> RigidBodyHandle {
+    world
+        .insert(
+            RigidBodyBuilder::dynamic()
+                .translation(Vector::new(0.0, 0.6, 0.0))
+                .linvel(linvel)
+                .angvel(angvel),
+            ColliderBuilder::ball(0.5)
+                .rolling_friction(rolling_friction)
+                .spinning_friction(spinning_friction),
+        )
+        .0
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+#[test]
+fn rolling_friction_stops_a_rolling_ball() {
+    // Rolling without slipping along +X.
+    let linvel = Vector::new(2.0, 0.0, 0.0);
+    let angvel = Vector::new(0.0, 0.0, -4.0);
+
+    let mut world = world_with_ground(0.05, 0.0);
+    let handle = ball(&mut world, linvel, angvel, 0.05, 0.0);
+    run(&mut world, 300);
+    let rb = &world.bodies[handle];
+    assert!(rb.linvel().length() < 0.05, "{:?}", rb.linvel());
+    assert!(rb.angvel().length() < 0.1, "{:?}", rb.angvel());
+
+    let mut world = world_with_ground(0.0, 0.0);
+    let handle = ball(&mut world, linvel, angvel, 0.0, 0.0);
+    run(&mut world, 300);
+    let rb = &world.bodies[handle];
+    assert!(rb.linvel().x > 1.9, "{:?}", rb.linvel());
+}
+
+#[test]
+fn rolling_and_spinning_friction_stop_a_multibody_ball() {
+    // Rolling without slipping along +X, and spinning around the contact normal.
+    let linvel = Vector::new(2.0, 0.0, 0.0);
+    let angvel = Vector::new(0.0, 10.0, -4.0);
+
+    let mut world = world_with_ground(0.05, 0.05);
+    let handle = ball(&mut world, linvel, angvel, 0.05, 0.05);
+    make_multibody_root(&mut world, handle);
+    run(&mut world, 300);
+    let rb = &world.bodies[handle];
+    assert!(rb.linvel().length() < 0.05, "{:?}", rb.linvel());
+    assert!(rb.angvel().length() < 0.1, "{:?}", rb.angvel());
+
+    let mut world = world_with_ground(0.0, 0.0);
+    let handle = ball(&mut world, linvel, angvel, 0.0, 0.0);
+    make_multibody_root(&mut world, handle);
+    run(&mut world, 300);
+    let rb = &world.bodies[handle];
+    assert!(rb.linvel().x > 1.9, "{:?}", rb.linvel());
+    assert!(rb.angvel().y > 9.9, "{:?}", rb.angvel());
+}
+
+#[test]
+fn spinning_friction_stops_a_spinning_ball() {
+    let angvel = Vector::new(0.0, 10.0, 0.0);
+
+    let mut world = world_with_ground(0.0, 0.05);
+    let handle = ball(&mut world, Vector::ZERO, angvel, 0.0, 0.05);
+    run(&mut world, 300);
+    assert!(world.bodies[handle].angvel().y.abs() < 0.1);
+
+    let mut world = world_with_ground(0.0, 0.0);
+    let handle = ball(&mut world, Vector::ZERO, angvel, 0.0, 0.0);
+    run(&mut world, 300);
+    assert!(world.bodies[handle].angvel().y > 9.9);
+}
+
+#[test]
+fn rolling_friction_does_not_affect_bodies_in_the_air() {
+    let mut world = PhysicsWorld::new();
+    let angvel = Vector::new(0.0, 0.0, -4.0);
+    let handle = ball(&mut world, Vector::ZERO, angvel, 0.05, 0.05);
+    run(&mut world, 60);
+    let new_angvel = world.bodies[handle].angvel();
+    assert!((new_angvel - angvel).length() < 1.0e-4, "{new_angvel:?}");
+}
+
+#[test]
+fn rolling_friction_coefficients_are_combined_like_friction() {
+    let mut world = world_with_ground(0.0, 0.0);
+    let handle = world
+        .insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.6, 0.0)),
+            ColliderBuilder::ball(0.5)
+                .rolling_friction(0.04)
+                .spinning_friction(0.02)
+                .friction_combine_rule(CoefficientCombineRule::Max),
+        )
+        .1;
+    run(&mut world, 2);
+
+    let pair = world
+        .narrow_phase
+        .contact_pairs()
+        .find(|pair| pair.collider1 == handle || pair.collider2 == handle)
+        .unwrap();
+    let manifold = &pair.manifolds[0];
+    assert_eq!(manifold.data.rolling_friction, 0.04);
+    assert_eq!(manifold.data.spinning_friction, 0.02);
+}
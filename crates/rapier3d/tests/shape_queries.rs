@@ -0,0 +1,120 @@
+//! Scene queries returning every hit: `QueryPipeline::cast_shape_all` and
+//! `QueryPipeline::contacts_with_shape`.
+
+use rapier3d::parry::query::ShapeCastOptions;
+use rapier3d::prelude::*;
+
+/// Five balls in a row along +X, the middle one being a sensor, plus one off to the side.
+fn row() -> (PhysicsWorld, Vec<ColliderHandle>) {
+    let mut world = PhysicsWorld::new();
+    let mut handles = Vec::new();
+    // Inserted out of order so that the handle order isn't the hit order.
+    for i in [3, 0, 4, 2, 1] {
+        let (_, co) = world.insert(
+            RigidBodyBuilder::fixed().translation(Vector::new(i as Real * 2.0, 0.0, 0.0)),
+            ColliderBuilder::ball(0.5).sensor(i == 2),
+        );
+        handles.push(co);
+    }
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(4.0, 5.0, 0.0)),
+        ColliderBuilder::ball(0.5),
+    );
+    world.step();
+    (world, handles)
+}
+
+#[test]
+fn cast_shape_all_reports_every_hit_sorted_by_time_of_impact() {
+    let (world, handles) = row();
+    let shape = Ball::new(0.25);
+    let options = ShapeCastOptions::with_max_time_of_impact(100.0);
+    let start = Pose::translation(-5.0, 0.0, 0.0);
+
+    let hits = world
+        .query_pipeline()
+        .cast_shape_all(&start, Vector::X, &shape, options);
+    let hit_handles: Vec<_> = hits.iter().map(|(h, _)| *h).collect();
+    // The balls at x = 0, 2, 4, 6 and 8.
+    assert_eq!(
+        hit_handles,
+        vec![handles[1], handles[4], handles[3], handles[0], handles[2]]
+    );
+    for (i, (_, hit)) in hits.iter().enumerate() {
+        // The surfaces touch when the centers are 0.75 apart.
+        let expected = 5.0 + i as Real * 2.0 - 0.75;
+        assert!((hit.time_of_impact - expected).abs() < 1.0e-4);
+        // Witness and normal on the hit collider are in world-space.
+        assert!((hit.witness1 - Vector::new(i as Real * 2.0 - 0.5, 0.0, 0.0)).length() < 1.0e-4);
+        assert!((hit.normal1 - -Vector::X).length() < 1.0e-4);
+    }
+
+    // The first hit is the one `cast_shape` reports.
+    let (first, _) = world
+        .query_pipeline()
+        .cast_shape(&start, Vector::X, &shape, options)
+        .unwrap();
+    assert_eq!(first, hit_handles[0]);
+
+    // The maximum time of impact and the filter are honored.
+    let short = ShapeCastOptions::with_max_time_of_impact(8.0);
+    let hits = world.cast_shape_all(
+        &start,
+        Vector::X,
+        &shape,
+        short,
+        QueryFilter::default().exclude_sensors(),
+    );
+    let hit_handles: Vec<_> = hits.iter().map(|(h, _)| *h).collect();
+    assert_eq!(hit_handles, vec![handles[1], handles[4]]);
+
+    // A shape starting inside colliders hits them at time zero, then sorted by handle.
+    let big = Ball::new(3.2);
+    let hits = world.query_pipeline().cast_shape_all(
+        &Pose::translation(3.0, 0.0, 0.0),
+        Vector::X,
+        &big,
+        ShapeCastOptions::with_max_time_of_impact(0.0),
+    );
+    let hit_handles: Vec<_> = hits.iter().map(|(h, _)| *h).collect();
+    assert_eq!(hits.len(), 4);
+    assert!(hits.iter().all(|(_, hit)| hit.time_of_impact == 0.0));
+    assert!(hit_handles.windows(2).all(|w| w[0].0 < w[1].0));
+}
+
+#[test]
+fn contacts_with_shape_reports_penetrations() {
+    let (world, handles) = row();
+    // Overlaps balls 1 and 2 (a sensor) by 0.25 each, and touches nothing else.
+    let shape = Cuboid::new(Vector::new(0.75, 0.5, 0.5));
+    let pos = Pose::translation(3.0, 0.0, 0.0);
+
+    let mut contacts: Vec<_> = world
+        .query_pipeline()
+        .contacts_with_shape(pos, &shape)
+        .map(|(h, _, c)| (h, c))
+        .collect();
+    contacts.sort_by_key(|(handle, _)| handle.0);
+    assert_eq!(contacts.len(), 2);
+    for (handle, contact) in &contacts {
+        let center_x: Real = if *handle == handles[4] { 2.0 } else { 4.0 };
+        assert!((contact.penetration_depth - 0.25).abs() < 1.0e-4);
+        let toward = Vector::new(center_x - 3.0, 0.0, 0.0);
+        assert!((contact.normal - toward).length() < 1.0e-4);
+        assert!((contact.point_on_collider.x - (center_x - toward.x * 0.5)).abs() < 1.0e-4);
+        assert!((contact.point_on_shape.x - (3.0 + toward.x * 0.75)).abs() < 1.0e-4);
+    }
+
+    // Same colliders as `intersect_shape`, with the same filtering.
+    let filter = QueryFilter::default().exclude_sensors();
+    let contacts: Vec<_> = world
+        .contacts_with_shape(pos, &shape, filter)
+        .map(|(h, _, _)| h)
+        .collect();
+    let intersections: Vec<_> = world
+        .intersect_shape(pos, &shape, filter)
+        .map(|(h, _)| h)
+        .collect();
+    assert_eq!(contacts, intersections);
+    assert_eq!(contacts, vec![handles[4]]);
+}
@@ -0,0 +1,161 @@
+//! Sleep control: forcing a whole island to sleep with `IslandManager::sleep_island_of`, and
+//! keeping sleeping bodies asleep when touched by bodies that don't wake their neighbors or
+//! lightly enough (`RigidBodyActivation::wake_impulse_threshold`).
+
+use rapier3d::prelude::*;
+
+fn world_with_ground() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -0.5, 0.0)),
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+    );
+    world
+}
+
+/// A unit cube resting asleep on the ground, at `x`.
+fn sleeping_cube(world: &mut PhysicsWorld, x: Real, builder: RigidBodyBuilder) -> RigidBodyHandle {
+    let (cube, _) = world.insert(
+        builder.translation(Vector::new(x, 0.5, 0.0)).sleeping(true),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    cube
+}
+
+/// A ball of the given radius and density, dropped from `height` above the cube at `x`.
+fn drop_ball(
+    world: &mut PhysicsWorld,
+    x: Real,
+    height: Real,
+    radius: Real,
+    density: Real,
+    builder: RigidBodyBuilder,
+) -> RigidBodyHandle {
+    let (ball, _) = world.insert(
+        builder.translation(Vector::new(x, 1.0 + radius + height, 0.0)),
+        ColliderBuilder::ball(radius).density(density),
+    );
+    ball
+}
+
+fn run(world: &mut PhysicsWorld, steps: usize) {
+    for _ in 0..steps {
+        world.step();
+    }
+}
+
+#[test]
+fn sleep_island_of_puts_the_whole_island_to_sleep() {
+    let mut world = world_with_ground();
+    let (bottom, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.5, 0.0)),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    let (top, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 1.5, 0.0)),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    // Not touching the stack.
+    let (apart, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(5.0, 0.5, 0.0)),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+
+    // Let the contacts (and so the islands) form.
+    world.step();
+    world.sleep_island_of(top);
+    world.step();
+
+    assert!(world.bodies[top].is_sleeping());
+    assert!(world.bodies[bottom].is_sleeping());
+    assert!(!world.bodies[apart].is_sleeping());
+    let active: Vec<_> = world.islands.active_bodies().collect();
+    assert_eq!(active, [apart]);
+
+    // The stack stays where it was, and wakes up as a whole.
+    run(&mut world, 10);
+    assert!(world.bodies[top].is_sleeping());
+    world.wake_up(bottom, true);
+    world.step();
+    assert!(!world.bodies[top].is_sleeping());
+}
+
+#[test]
+fn sleep_island_of_ignores_fixed_and_sleeping_bodies() {
+    let mut world = world_with_ground();
+    let cube = sleeping_cube(&mut world, 0.0, RigidBodyBuilder::dynamic());
+    world.step();
+
+    world.sleep_island_of(cube);
+    let ground = world.bodies.iter().find(|(_, rb)| rb.is_fixed()).unwrap().0;
+    world.sleep_island_of(ground);
+    world.step();
+    assert!(world.bodies[cube].is_sleeping());
+    assert!(!world.bodies[ground].is_sleeping());
+}
+
+#[test]
+fn bodies_that_dont_wake_neighbors_leave_sleeping_bodies_asleep() {
+    let mut world = world_with_ground();
+    let cube = sleeping_cube(&mut world, 0.0, RigidBodyBuilder::dynamic());
+    let ball = drop_ball(
+        &mut world,
+        0.0,
+        0.5,
+        0.5,
+        1.0,
+        RigidBodyBuilder::dynamic().wakes_neighbors(false),
+    );
+    // The same fall wakes up a cube with the default settings.
+    let woken_cube = sleeping_cube(&mut world, 4.0, RigidBodyBuilder::dynamic());
+    drop_ball(&mut world, 4.0, 0.5, 0.5, 1.0, RigidBodyBuilder::dynamic());
+
+    let mut woken_cube_woke_up = false;
+    for _ in 0..120 {
+        world.step();
+        woken_cube_woke_up |= !world.bodies[woken_cube].is_sleeping();
+        assert!(world.bodies[cube].is_sleeping());
+    }
+    assert!(woken_cube_woke_up);
+    // The sleeping cube held the ball like a fixed body would.
+    let ball_y = world.bodies[ball].translation().y;
+    assert!((ball_y - 1.5).abs() < 0.05, "{ball_y}");
+    assert_ne!(
+        world.islands.persistent_island_of(&world.bodies, ball),
+        world.islands.persistent_island_of(&world.bodies, cube),
+    );
+
+    // Once the cube is woken up by something else, the two bodies share an island again.
+    world.wake_up(cube, true);
+    world.step();
+    assert!(
+        world
+            .islands
+            .persistent_island_of(&world.bodies, cube)
+            .is_some()
+    );
+    assert_eq!(
+        world.islands.persistent_island_of(&world.bodies, ball),
+        world.islands.persistent_island_of(&world.bodies, cube),
+    );
+}
+
+#[test]
+fn only_hard_enough_touches_wake_bodies_with_a_wake_impulse_threshold() {
+    let mut world = world_with_ground();
+    let pile = RigidBodyBuilder::dynamic().wake_impulse_threshold(2.0);
+    let light_target = sleeping_cube(&mut world, 0.0, pile.clone());
+    let heavy_target = sleeping_cube(&mut world, 4.0, pile);
+    // A ~0.07 kg ball landing at ~3 m/s, then a ~5 kg ball landing at ~4.4 m/s.
+    drop_ball(&mut world, 0.0, 0.5, 0.25, 1.0, RigidBodyBuilder::dynamic());
+    drop_ball(&mut world, 4.0, 1.0, 0.5, 10.0, RigidBodyBuilder::dynamic());
+
+    let mut heavy_target_woke_up = false;
+    for _ in 0..120 {
+        world.step();
+        heavy_target_woke_up |= !world.bodies[heavy_target].is_sleeping();
+        assert!(world.bodies[light_target].is_sleeping());
+    }
+    assert!(heavy_target_woke_up);
+}
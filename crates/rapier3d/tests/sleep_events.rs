@@ -0,0 +1,111 @@
+//! Sleep events: rigid-bodies with `RigidBodyActiveEvents::SLEEP_EVENTS` report falling
+//! asleep and waking up (with their whole island) through the `EventHandler`.
+
+use std::sync::mpsc::{Receiver, channel};
+
+use rapier3d::prelude::*;
+
+fn collector() -> (ChannelEventCollector, Receiver<SleepEvent>) {
+    let (collision_send, _) = channel();
+    let (contact_force_send, _) = channel();
+    let (sleep_send, sleep_recv) = channel();
+    let events = ChannelEventCollector::new(collision_send, contact_force_send)
+        .with_sleep_event_sender(sleep_send);
+    (events, sleep_recv)
+}
+
+/// A ground with two cubes stacked on it. Only the bottom cube reports sleep events.
+///
+/// Returns the bottom and top cubes.
+fn stack(world: &mut PhysicsWorld) -> (RigidBodyHandle, RigidBodyHandle) {
+    world.gravity = Vector::new(0.0, -9.81, 0.0);
+    world.insert(
+        RigidBodyBuilder::fixed().translation(Vector::new(0.0, -0.5, 0.0)),
+        ColliderBuilder::cuboid(10.0, 0.5, 10.0),
+    );
+    let (bottom, _) = world.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(0.0, 0.5, 0.0))
+            .active_events(RigidBodyActiveEvents::SLEEP_EVENTS),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    let (top, _) = world.insert(
+        RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 1.5, 0.0)),
+        ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+    );
+    (bottom, top)
+}
+
+/// Steps `world` until `body` falls asleep.
+fn step_until_asleep(
+    world: &mut PhysicsWorld,
+    events: &ChannelEventCollector,
+    body: RigidBodyHandle,
+) {
+    for _ in 0..400 {
+        world.step_with_events(&(), events);
+        if world.bodies[body].is_sleeping() {
+            return;
+        }
+    }
+    panic!("the body never fell asleep");
+}
+
+#[test]
+fn sleeping_and_waking_up_are_reported_for_opted_in_bodies() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    let (bottom, top) = stack(&mut world);
+
+    step_until_asleep(&mut world, &events, bottom);
+    assert!(world.bodies[top].is_sleeping());
+    // The top cube doesn't have sleep events enabled.
+    let slept: Vec<_> = recv.try_iter().collect();
+    assert_eq!(slept, [SleepEvent::FellAsleep(bottom)]);
+
+    // Waking the top cube wakes its whole island, bottom cube included.
+    world.islands.wake_up(&mut world.bodies, top, true);
+    world.step_with_events(&(), &events);
+    assert!(!world.bodies[bottom].is_sleeping());
+    let woken: Vec<_> = recv.try_iter().collect();
+    assert_eq!(woken, [SleepEvent::WokeUp(bottom)]);
+    assert!(!woken[0].fell_asleep());
+
+    // It falls asleep again, reporting it only once.
+    step_until_asleep(&mut world, &events, bottom);
+    for _ in 0..10 {
+        world.step_with_events(&(), &events);
+    }
+    let slept: Vec<_> = recv.try_iter().collect();
+    assert_eq!(slept, [SleepEvent::FellAsleep(bottom)]);
+}
+
+#[test]
+fn bodies_without_sleep_events_stay_silent() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    let (bottom, _) = stack(&mut world);
+    world.bodies[bottom].set_active_events(RigidBodyActiveEvents::empty());
+
+    step_until_asleep(&mut world, &events, bottom);
+    world.islands.wake_up(&mut world.bodies, bottom, true);
+    world.step_with_events(&(), &events);
+    assert_eq!(recv.try_iter().count(), 0);
+}
+
+#[test]
+fn bodies_that_cannot_sleep_never_report_sleeping() {
+    let mut world = PhysicsWorld::new();
+    let (events, recv) = collector();
+    world.insert(
+        RigidBodyBuilder::dynamic()
+            .can_sleep(false)
+            .active_events(RigidBodyActiveEvents::SLEEP_EVENTS),
+        ColliderBuilder::ball(0.5),
+    );
+
+    for _ in 0..200 {
+        world.step_with_events(&(), &events);
+    }
+    assert_eq!(recv.try_iter().count(), 0);
+}
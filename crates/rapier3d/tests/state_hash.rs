@@ -0,0 +1,120 @@
+//! State hashing: `PhysicsWorld::state_hash` and the per-set hashes it combines.
+
+use rapier3d::prelude::*;
+
+fn stack() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    world.insert(
+        RigidBodyBuilder::fixed(),
+        ColliderBuilder::cuboid(10.0, 0.1, 10.0),
+    );
+    for i in 0..3 {
+        world.insert(
+            RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.6 + i as Real, 0.0)),
+            ColliderBuilder::cuboid(0.5, 0.5, 0.5),
+        );
+    }
+    let body1 =
+        world.insert_body(RigidBodyBuilder::dynamic().translation(Vector::new(5.0, 3.0, 0.0)));
+    let body2 =
+        world.insert_body(RigidBodyBuilder::dynamic().translation(Vector::new(6.0, 3.0, 0.0)));
+    world.insert_impulse_joint(
+        body1,
+        body2,
+        SphericalJointBuilder::new().local_anchor2(Vector::new(-1.0, 0.0, 0.0)),
+    );
+    world
+}
+
+#[test]
+fn the_rigid_body_hash_follows_its_documented_definition() {
+    // Golden values: changing them breaks the stability promised across releases.
+    assert_eq!(RigidBodySet::new().state_hash(), 0xa8c7_f832_281a_39c5);
+
+    let mut bodies = RigidBodySet::new();
+    bodies.insert(
+        RigidBodyBuilder::dynamic()
+            .translation(Vector::new(1.0, 2.0, 3.0))
+            .linvel(Vector::new(4.0, 5.0, 6.0)),
+    );
+    assert_eq!(bodies.state_hash(), 0xc21f_631c_6133_c4c4);
+}
+
+#[test]
+fn identical_simulations_have_identical_hashes() {
+    let mut world1 = stack();
+    let mut world2 = stack();
+    for _ in 0..60 {
+        world1.step();
+        world2.step();
+        assert_eq!(world1.state_hash(), world2.state_hash());
+    }
+    assert!(
+        world1
+            .narrow_phase
+            .contact_pairs()
+            .any(|p| p.has_any_active_contact())
+    );
+}
+
+#[test]
+fn each_part_of_the_state_changes_the_hash() {
+    let mut world = stack();
+    for _ in 0..10 {
+        world.step();
+    }
+    let hash = world.state_hash();
+    let (bodies, contacts, joints) = (
+        world.bodies.state_hash(),
+        world.narrow_phase.state_hash(),
+        world.impulse_joints.state_hash(),
+    );
+
+    // Velocities.
+    let handle = world.bodies.iter().nth(1).unwrap().0;
+    let linvel = world.bodies[handle].linvel();
+    world.bodies[handle].set_linvel(linvel + Vector::new(0.0, 1.0e-6, 0.0), false);
+    assert_ne!(world.bodies.state_hash(), bodies);
+    assert_ne!(world.state_hash(), hash);
+    world.bodies[handle].set_linvel(linvel, false);
+    assert_eq!(world.state_hash(), hash);
+
+    // Sleeping flags.
+    world.bodies[handle].sleep();
+    assert_ne!(world.bodies.state_hash(), bodies);
+    world.bodies[handle].wake_up(true);
+
+    // Contact warm-start impulses: the same contacts pushed harder.
+    let mut heavier = stack();
+    heavier.gravity *= 2.0;
+    for _ in 0..10 {
+        heavier.step();
+    }
+    assert_ne!(heavier.narrow_phase.state_hash(), contacts);
+    assert_ne!(
+        contacts,
+        PhysicsWorld::new().narrow_phase.state_hash(),
+        "the stack should be resting on the ground"
+    );
+
+    // Joint impulses.
+    let (_, joint) = world.impulse_joints.iter_mut().next().unwrap();
+    joint.impulses[0] += 1.0;
+    assert_ne!(world.impulse_joints.state_hash(), joints);
+}
+
+#[cfg(feature = "serde-serialize")]
+#[test]
+fn the_hash_survives_serialization() {
+    let mut world = stack();
+    for _ in 0..30 {
+        world.step();
+    }
+    let bytes = bincode::serialize(&world).unwrap();
+    let mut restored: PhysicsWorld = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(restored.state_hash(), world.state_hash());
+
+    world.step();
+    restored.step();
+    assert_eq!(restored.state_hash(), world.state_hash());
+}
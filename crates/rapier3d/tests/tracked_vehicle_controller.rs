@@ -0,0 +1,150 @@
+//! `TrackedVehicleController`: tracks made of rows of suspension contacts sharing one belt
+//! speed, driving and skid-steering a tank.
+
+use rapier3d::control::{TrackTuning, TrackedVehicleController};
+use rapier3d::prelude::*;
+
+/// A 1.2 ton tank on a flat ground, driving along +X, with five road wheels per track.
+fn tank(tuning: &TrackTuning) -> (PhysicsWorld, TrackedVehicleController) {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(
+        ColliderBuilder::cuboid(100.0, 0.5, 100.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+
+    let chassis = RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.9, 0.0));
+    let collider = ColliderBuilder::cuboid(2.5, 0.4, 1.5).density(100.0);
+    let (chassis, _) = world.insert(chassis, collider);
+
+    let mut vehicle = TrackedVehicleController::new(chassis);
+    for z in [-1.2, 1.2] {
+        let road_wheels: Vec<_> = (-2..=2).map(|x| Vector::new(x as Real, -0.4, z)).collect();
+        vehicle.add_track(&road_wheels, 0.3, 0.2, tuning);
+    }
+
+    // Settle on the suspension.
+    update(&mut world, &mut vehicle, 60);
+    (world, vehicle)
+}
+
+fn update(world: &mut PhysicsWorld, vehicle: &mut TrackedVehicleController, steps: usize) {
+    for _ in 0..steps {
+        let q = world.broad_phase.as_query_pipeline_mut(
+            world.narrow_phase.query_dispatcher(),
+            &mut world.bodies,
+            &mut world.colliders,
+            QueryFilter::exclude_dynamic().exclude_rigid_body(vehicle.chassis),
+        );
+        vehicle.update_vehicle(world.integration_parameters.dt, q);
+        world.step();
+    }
+}
+
+#[test]
+fn the_tracks_carry_the_tank() {
+    let (world, vehicle) = tank(&TrackTuning::default());
+    let mass = world.bodies[vehicle.chassis].mass();
+
+    let mut total_load = 0.0;
+    for track in vehicle.tracks() {
+        assert_eq!(track.contacts().len(), 5);
+        for contact in track.contacts() {
+            assert!(contact.is_in_contact);
+            assert!(contact.ground_object.is_some());
+            assert!((contact.contact_normal_ws - Vector::Y).length() < 1.0e-3);
+            total_load += contact.load;
+        }
+    }
+    assert!((total_load - mass * 9.81).abs() < 0.05 * mass * 9.81);
+    // The chassis floats on its suspension.
+    assert!(world.bodies[vehicle.chassis].translation().y > 0.8);
+}
+
+#[test]
+fn both_tracks_at_the_same_speed_drive_straight() {
+    let (mut world, mut vehicle) = tank(&TrackTuning::default());
+    vehicle.skid_steer(3.0, 0.0);
+    for track in vehicle.tracks() {
+        assert_eq!(track.speed, 3.0);
+    }
+    update(&mut world, &mut vehicle, 120);
+
+    let chassis = &world.bodies[vehicle.chassis];
+    assert!(
+        (chassis.linvel().x - 3.0).abs() < 0.1,
+        "{}",
+        chassis.linvel()
+    );
+    assert!(chassis.linvel().z.abs() < 0.05);
+    assert!(chassis.angvel().y.abs() < 0.05);
+}
+
+#[test]
+fn the_drive_force_is_limited() {
+    let tuning = TrackTuning {
+        max_drive_force: 1000.0,
+        ..TrackTuning::default()
+    };
+    let (mut world, mut vehicle) = tank(&tuning);
+    let mass = world.bodies[vehicle.chassis].mass();
+    vehicle.skid_steer(10.0, 0.0);
+    update(&mut world, &mut vehicle, 60);
+
+    // One second at `2 * max_drive_force / mass`.
+    let expected_speed = 2.0 * 1000.0 / mass;
+    let speed = world.bodies[vehicle.chassis].linvel().x;
+    assert!((speed - expected_speed).abs() < 0.1 * expected_speed);
+    for track in vehicle.tracks() {
+        assert!((track.drive_force() - 1000.0).abs() < 10.0);
+    }
+}
+
+#[test]
+fn opposite_track_speeds_turn_in_place() {
+    let (mut world, mut vehicle) = tank(&TrackTuning::default());
+    vehicle.skid_steer(0.0, 1.0);
+    // The right track (along +Z) drives forward, the left one backward.
+    assert!((vehicle.tracks()[0].speed + 1.2).abs() < 1.0e-5);
+    assert!((vehicle.tracks()[1].speed - 1.2).abs() < 1.0e-5);
+    update(&mut world, &mut vehicle, 60);
+
+    let chassis = &world.bodies[vehicle.chassis];
+    // The tracks slide sideways, so the tank turns slower than requested.
+    assert!(chassis.angvel().y > 0.2 && chassis.angvel().y < 1.0);
+    let drift = Vector::new(chassis.translation().x, 0.0, chassis.translation().z);
+    assert!(drift.length() < 0.2);
+}
+
+#[test]
+fn a_parked_tank_sleeps_until_its_tracks_move() {
+    let (mut world, mut vehicle) = tank(&TrackTuning::default());
+    world.bodies[vehicle.chassis].sleep();
+    let height = world.bodies[vehicle.chassis].translation().y;
+    let loads: Vec<_> = vehicle.tracks()[0]
+        .contacts()
+        .iter()
+        .map(|contact| contact.load)
+        .collect();
+
+    update(&mut world, &mut vehicle, 60);
+    let chassis = &world.bodies[vehicle.chassis];
+    assert!(chassis.is_sleeping());
+    // The suspension loads didn't pile up in the velocity of the sleeping chassis...
+    assert_eq!(chassis.linvel(), Vector::ZERO);
+    // ... and the contacts kept their last loads.
+    for (contact, load) in vehicle.tracks()[0].contacts().iter().zip(&loads) {
+        assert_eq!(contact.load, *load);
+    }
+
+    // Moving the tracks wakes the tank up, and it drives off without jumping.
+    vehicle.skid_steer(3.0, 0.0);
+    update(&mut world, &mut vehicle, 120);
+    let chassis = &world.bodies[vehicle.chassis];
+    assert!(!chassis.is_sleeping());
+    assert!(
+        (chassis.linvel().x - 3.0).abs() < 0.1,
+        "{}",
+        chassis.linvel()
+    );
+    assert!((chassis.translation().y - height).abs() < 0.05);
+}
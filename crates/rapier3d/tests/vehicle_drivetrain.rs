@@ -0,0 +1,187 @@
+//! `Drivetrain`: an engine, clutch, gearbox, differentials and handbrake driving the wheels of a
+//! `DynamicRayCastVehicleController`.
+
+use rapier3d::control::{
+    Differential, DrivenAxle, Drivetrain, DynamicRayCastVehicleController, Gearbox, GearboxMode,
+    TireModel, VehicleEngine, WheelTuning,
+};
+use rapier3d::prelude::*;
+
+const RADIUS: Real = 0.35;
+
+/// A one ton, rear-wheel drive car driving along +X on a flat ground.
+///
+/// The wheels are ordered front-left, front-right, rear-left, rear-right.
+fn car(differential: Differential) -> (PhysicsWorld, DynamicRayCastVehicleController) {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(
+        ColliderBuilder::cuboid(500.0, 0.5, 500.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+
+    let chassis = RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 0.9, 0.0));
+    let collider = ColliderBuilder::cuboid(2.0, 0.3, 0.9).density(250.0);
+    let (chassis, _) = world.insert(chassis, collider);
+
+    let tuning = WheelTuning {
+        suspension_stiffness: 30.0,
+        suspension_compression: 3.0,
+        suspension_damping: 3.0,
+        tire_model: TireModel::magic_formula(),
+        ..WheelTuning::default()
+    };
+    let mut vehicle = DynamicRayCastVehicleController::new(chassis);
+    for pos in [
+        Vector::new(1.4, -0.3, -0.8),
+        Vector::new(1.4, -0.3, 0.8),
+        Vector::new(-1.4, -0.3, -0.8),
+        Vector::new(-1.4, -0.3, 0.8),
+    ] {
+        vehicle.add_wheel(pos, -Vector::Y, Vector::Z, 0.3, RADIUS, &tuning);
+    }
+
+    let mut drivetrain = Drivetrain::new(
+        VehicleEngine::default(),
+        Gearbox::default(),
+        vec![DrivenAxle::new(2, 3, differential)],
+    );
+    drivetrain.handbrake_wheels = vec![2, 3];
+    vehicle.drivetrain = Some(drivetrain);
+
+    // Settle on the suspension.
+    update(&mut world, &mut vehicle, 60);
+    (world, vehicle)
+}
+
+fn update(world: &mut PhysicsWorld, vehicle: &mut DynamicRayCastVehicleController, steps: usize) {
+    for _ in 0..steps {
+        let q = world.broad_phase.as_query_pipeline_mut(
+            world.narrow_phase.query_dispatcher(),
+            &mut world.bodies,
+            &mut world.colliders,
+            QueryFilter::exclude_dynamic().exclude_rigid_body(vehicle.chassis),
+        );
+        vehicle.update_vehicle(world.integration_parameters.dt, q);
+        world.step();
+    }
+}
+
+fn drivetrain_mut(vehicle: &mut DynamicRayCastVehicleController) -> &mut Drivetrain {
+    vehicle.drivetrain.as_mut().unwrap()
+}
+
+#[test]
+fn the_automatic_gearbox_shifts_up_while_accelerating() {
+    let (mut world, mut vehicle) = car(Differential::Open);
+    assert!((drivetrain_mut(&mut vehicle).rpm() - 800.0).abs() < 1.0);
+
+    let drivetrain = drivetrain_mut(&mut vehicle);
+    drivetrain.gearbox.gear = 1;
+    drivetrain.throttle = 1.0;
+    update(&mut world, &mut vehicle, 240);
+
+    let speed = world.bodies[vehicle.chassis].linvel().x;
+    let drivetrain = drivetrain_mut(&mut vehicle);
+    assert!(speed > 8.0, "{speed}");
+    assert!(drivetrain.gearbox.gear >= 2);
+    assert!(drivetrain.rpm() >= 800.0 && drivetrain.rpm() <= 7000.0);
+    assert!(drivetrain.clutch_torque() > 0.0);
+    // The driven wheels push, the others just roll.
+    for wheel in vehicle.wheels() {
+        assert!(wheel.angular_velocity > 0.0);
+    }
+    assert!(vehicle.wheels()[2].engine_force > 0.0);
+    assert_eq!(vehicle.wheels()[0].engine_force, 0.0);
+}
+
+#[test]
+fn the_engine_revs_freely_in_neutral_or_with_the_clutch_pressed() {
+    for (gear, clutch) in [(0, 1.0), (1, 0.0)] {
+        let (mut world, mut vehicle) = car(Differential::Open);
+        let drivetrain = drivetrain_mut(&mut vehicle);
+        drivetrain.gearbox.gear = gear;
+        drivetrain.clutch = clutch;
+        drivetrain.throttle = 1.0;
+        let start = world.bodies[vehicle.chassis].translation();
+        update(&mut world, &mut vehicle, 60);
+
+        assert!(drivetrain_mut(&mut vehicle).rpm() > 6500.0);
+        let moved = world.bodies[vehicle.chassis].translation() - start;
+        assert!(moved.x.abs() < 0.01 && moved.z.abs() < 0.01);
+    }
+}
+
+#[test]
+fn the_reverse_gear_drives_backward() {
+    let (mut world, mut vehicle) = car(Differential::Open);
+    let drivetrain = drivetrain_mut(&mut vehicle);
+    drivetrain.gearbox.mode = GearboxMode::Manual;
+    drivetrain.gearbox.gear = -1;
+    drivetrain.throttle = 0.5;
+    update(&mut world, &mut vehicle, 120);
+
+    assert!(world.bodies[vehicle.chassis].linvel().x < -1.0);
+    assert_eq!(drivetrain_mut(&mut vehicle).gearbox.gear, -1);
+}
+
+#[test]
+fn locking_differentials_keep_traction_with_a_wheel_on_ice() {
+    let drive_with_a_wheel_on_ice = |differential| {
+        let (mut world, mut vehicle) = car(differential);
+        if let TireModel::MagicFormula {
+            longitudinal,
+            lateral,
+        } = &mut vehicle.wheels_mut()[2].tire_model
+        {
+            longitudinal.peak = 0.05;
+            lateral.peak = 0.05;
+        }
+        let drivetrain = drivetrain_mut(&mut vehicle);
+        drivetrain.gearbox.mode = GearboxMode::Manual;
+        drivetrain.gearbox.gear = 1;
+        drivetrain.throttle = 0.3;
+        update(&mut world, &mut vehicle, 120);
+
+        let wheels = vehicle.wheels();
+        let spin_difference = wheels[2].angular_velocity - wheels[3].angular_velocity;
+        (world.bodies[vehicle.chassis].linvel().x, spin_difference)
+    };
+
+    // With an open differential, the torque is limited by the wheel on ice, which spins freely.
+    let (open_speed, open_spin_difference) = drive_with_a_wheel_on_ice(Differential::Open);
+    assert!(open_spin_difference > 30.0, "{open_spin_difference}");
+
+    let (limited_slip_speed, limited_slip_spin_difference) =
+        drive_with_a_wheel_on_ice(Differential::LimitedSlip { stiffness: 10.0 });
+    assert!(limited_slip_spin_difference < open_spin_difference);
+    assert!(limited_slip_speed > open_speed);
+
+    let (locked_speed, _) = drive_with_a_wheel_on_ice(Differential::Locked);
+    assert!(
+        locked_speed > 2.0 * open_speed,
+        "{locked_speed} vs. {open_speed}"
+    );
+    assert!(locked_speed >= limited_slip_speed * 0.95);
+}
+
+#[test]
+fn the_handbrake_locks_the_rear_wheels() {
+    let (mut world, mut vehicle) = car(Differential::Open);
+    world.bodies[vehicle.chassis].set_linvel(Vector::new(10.0, 0.0, 0.0), true);
+    for wheel in vehicle.wheels_mut() {
+        wheel.angular_velocity = 10.0 / RADIUS;
+    }
+    drivetrain_mut(&mut vehicle).handbrake = 1.0;
+    update(&mut world, &mut vehicle, 30);
+
+    let speed = world.bodies[vehicle.chassis].linvel().x;
+    assert!(speed > 5.0 && speed < 9.5, "{speed}");
+    let wheels = vehicle.wheels();
+    for rear in &wheels[2..] {
+        assert_eq!(rear.angular_velocity, 0.0);
+        assert!((rear.slip_ratio + 1.0).abs() < 1.0e-3);
+    }
+    for front in &wheels[..2] {
+        assert!((front.angular_velocity * RADIUS - speed).abs() < 0.1 * speed);
+    }
+}
@@ -0,0 +1,137 @@
+//! `DynamicRayCastVehicleController::trailer_hitch`: a two-axle trailer towed by a truck
+//! through a spherical joint with a limited hitch angle.
+
+use rapier3d::control::{DynamicRayCastVehicleController, WheelTuning};
+use rapier3d::prelude::*;
+
+const TRUCK_HITCH: Vector = Vector::new(-2.25, -0.1, 0.0);
+const TRAILER_HITCH: Vector = Vector::new(2.75, -0.1, 0.0);
+const MAX_YAW: Real = 0.5;
+
+struct Convoy {
+    world: PhysicsWorld,
+    truck: DynamicRayCastVehicleController,
+    trailer: DynamicRayCastVehicleController,
+}
+
+fn vehicle(
+    world: &mut PhysicsWorld,
+    x: Real,
+    half_length: Real,
+    wheels_x: &[Real],
+) -> DynamicRayCastVehicleController {
+    let chassis = RigidBodyBuilder::dynamic().translation(Vector::new(x, 0.9, 0.0));
+    let collider = ColliderBuilder::cuboid(half_length, 0.3, 0.9).density(250.0);
+    let (chassis, _) = world.insert(chassis, collider);
+
+    let tuning = WheelTuning {
+        suspension_stiffness: 30.0,
+        suspension_compression: 3.0,
+        suspension_damping: 3.0,
+        ..WheelTuning::default()
+    };
+    let mut vehicle = DynamicRayCastVehicleController::new(chassis);
+    for &wheel_x in wheels_x {
+        for z in [-0.8, 0.8] {
+            let pos = Vector::new(wheel_x, -0.3, z);
+            vehicle.add_wheel(pos, -Vector::Y, Vector::Z, 0.3, 0.35, &tuning);
+        }
+    }
+    vehicle
+}
+
+/// A truck at the origin, and a trailer with two rear axles hitched behind it.
+fn convoy() -> Convoy {
+    let mut world = PhysicsWorld::new();
+    world.insert_collider(
+        ColliderBuilder::cuboid(100.0, 0.5, 100.0).translation(Vector::new(0.0, -0.5, 0.0)),
+        None,
+    );
+    let truck = vehicle(&mut world, 0.0, 2.0, &[1.4, -1.4]);
+    let trailer = vehicle(&mut world, -5.0, 2.5, &[-1.2, -2.0]);
+    let hitch = truck.trailer_hitch(TRUCK_HITCH, TRAILER_HITCH, MAX_YAW, 0.3, 0.2);
+    world.insert_impulse_joint(truck.chassis, trailer.chassis, hitch);
+
+    let mut convoy = Convoy {
+        world,
+        truck,
+        trailer,
+    };
+    // Settle on the suspensions.
+    convoy.update(60);
+    convoy
+}
+
+impl Convoy {
+    fn update(&mut self, steps: usize) {
+        for _ in 0..steps {
+            for vehicle in [&mut self.truck, &mut self.trailer] {
+                let q = self.world.broad_phase.as_query_pipeline_mut(
+                    self.world.narrow_phase.query_dispatcher(),
+                    &mut self.world.bodies,
+                    &mut self.world.colliders,
+                    QueryFilter::exclude_dynamic(),
+                );
+                vehicle.update_vehicle(self.world.integration_parameters.dt, q);
+            }
+            self.world.step();
+        }
+    }
+
+    fn hitch_gap(&self) -> Real {
+        let truck = &self.world.bodies[self.truck.chassis];
+        let trailer = &self.world.bodies[self.trailer.chassis];
+        ((truck.position() * TRUCK_HITCH) - (trailer.position() * TRAILER_HITCH)).length()
+    }
+
+    fn hitch_yaw(&self) -> Real {
+        let truck = &self.world.bodies[self.truck.chassis];
+        let trailer = &self.world.bodies[self.trailer.chassis];
+        (truck.rotation().inverse() * *trailer.rotation())
+            .to_scaled_axis()
+            .y
+    }
+}
+
+#[test]
+fn the_trailer_follows_the_truck() {
+    let mut convoy = convoy();
+    let trailer_start = convoy.world.bodies[convoy.trailer.chassis].translation();
+    assert!(convoy.hitch_gap() < 0.02);
+
+    for wheel in &mut convoy.truck.wheels_mut()[2..] {
+        wheel.engine_force = 1500.0;
+    }
+    convoy.update(180);
+
+    let truck = &convoy.world.bodies[convoy.truck.chassis];
+    let trailer = &convoy.world.bodies[convoy.trailer.chassis];
+    assert!(truck.translation().x > 3.0);
+    assert!(trailer.translation().x - trailer_start.x > 3.0);
+    assert!((truck.linvel() - trailer.linvel()).length() < 0.1);
+    assert!(convoy.hitch_gap() < 0.02);
+    // The trailer's wheels roll along.
+    for wheel in convoy.trailer.wheels() {
+        assert!(wheel.raycast_info().is_in_contact);
+        assert!(wheel.rotation.abs() > 1.0);
+    }
+}
+
+#[test]
+fn the_hitch_angle_is_limited() {
+    let mut convoy = convoy();
+    // Swing the trailer around the hitch.
+    convoy.world.bodies[convoy.trailer.chassis].set_angvel(Vector::new(0.0, 10.0, 0.0), true);
+
+    let mut max_yaw: Real = 0.0;
+    for _ in 0..60 {
+        convoy.update(1);
+        max_yaw = max_yaw.max(convoy.hitch_yaw().abs());
+        assert!(convoy.hitch_gap() < 0.05);
+    }
+    assert!(max_yaw > 0.4, "the trailer didn't swing: {max_yaw}");
+    assert!(
+        max_yaw < MAX_YAW + 0.05,
+        "the hitch angle isn't limited: {max_yaw}"
+    );
+}
@@ -0,0 +1,228 @@
+//! `DynamicRayCastVehicleController`: detecting the ground with a shape cast per wheel
+//! (`WheelCastShape`), and the magic formula tire model (`TireModel::MagicFormula`) with its
+//! per-wheel slip ratio, slip angle and load.
+
+use rapier3d::control::{DynamicRayCastVehicleController, TireModel, WheelCastShape, WheelTuning};
+use rapier3d::prelude::*;
+
+const HW: Real = 0.3;
+const HH: Real = 0.15;
+const RADIUS: Real = 0.1;
+
+/// Adds a vehicle driving along +X to the world, its front wheels at `x + 0.45`.
+fn spawn_vehicle(
+    world: &mut PhysicsWorld,
+    x: Real,
+    tuning: &WheelTuning,
+) -> DynamicRayCastVehicleController {
+    let chassis = RigidBodyBuilder::dynamic().translation(Vector::new(x, 0.3, 0.0));
+    let collider = ColliderBuilder::cuboid(HW * 2.0, HH, HW).density(100.0);
+    let (vehicle_handle, _) = world.insert(chassis, collider);
+
+    let mut vehicle = DynamicRayCastVehicleController::new(vehicle_handle);
+    for pos in [
+        Vector::new(HW * 1.5, -HH, HW),
+        Vector::new(HW * 1.5, -HH, -HW),
+        Vector::new(-HW * 1.5, -HH, HW),
+        Vector::new(-HW * 1.5, -HH, -HW),
+    ] {
+        vehicle.add_wheel(pos, -Vector::Y, Vector::Z, HH, RADIUS, tuning);
+    }
+    vehicle
+}
+
+fn tuning() -> WheelTuning {
+    WheelTuning {
+        suspension_stiffness: 100.0,
+        suspension_damping: 10.0,
+        ..WheelTuning::default()
+    }
+}
+
+fn update(world: &mut PhysicsWorld, vehicle: &mut DynamicRayCastVehicleController, steps: usize) {
+    for _ in 0..steps {
+        let q = world.broad_phase.as_query_pipeline_mut(
+            world.narrow_phase.query_dispatcher(),
+            &mut world.bodies,
+            &mut world.colliders,
+            QueryFilter::exclude_dynamic().exclude_rigid_body(vehicle.chassis),
+        );
+        vehicle.update_vehicle(world.integration_parameters.dt, q);
+        world.step();
+    }
+}
+
+/// A flat ground whose top is at `y = 0`, with a 0.1 wide, 1.0 deep crack along `x = 0`.
+fn cracked_ground() -> PhysicsWorld {
+    let mut world = PhysicsWorld::new();
+    for x in [-10.05, 10.05] {
+        world.insert_collider(
+            ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(Vector::new(x, -0.5, 0.0)),
+            None,
+        );
+    }
+    world
+}
+
+#[test]
+fn wheel_shapes_bridge_cracks_rays_fall_into() {
+    let shapes = [
+        WheelCastShape::Ray,
+        WheelCastShape::Ball,
+        WheelCastShape::Cylinder { half_width: 0.05 },
+    ];
+
+    for cast_shape in shapes {
+        let tuning = WheelTuning {
+            cast_shape,
+            ..tuning()
+        };
+        let mut world = cracked_ground();
+        // The front wheels are right above the crack.
+        let mut vehicle = spawn_vehicle(&mut world, -HW * 1.5, &tuning);
+        // Build the broad-phase BVH the wheels are cast against. The chassis falls by less
+        // than `3.0e-3` meanwhile.
+        world.step();
+        update(&mut world, &mut vehicle, 1);
+
+        let front = vehicle.wheels()[0].raycast_info();
+        let rear = vehicle.wheels()[2].raycast_info();
+        // The hard points are at `y = 0.15`, the wheels reach `y = -0.1` at rest.
+        assert!(rear.is_in_contact);
+        assert!((rear.suspension_length - 0.05).abs() < 5.0e-3);
+        assert!((rear.contact_normal_ws - Vector::Y).length() < 1.0e-3);
+        assert!(rear.contact_point_ws.y.abs() < 1.0e-3);
+
+        if cast_shape == WheelCastShape::Ray {
+            assert!(!front.is_in_contact);
+        } else {
+            // The wheel rests on the edges of the crack.
+            let center_height = (RADIUS * RADIUS - 0.05 * 0.05).sqrt();
+            assert!(front.is_in_contact, "{cast_shape:?}");
+            assert!((front.suspension_length - (0.15 - center_height)).abs() < 5.0e-3);
+            assert!((front.contact_point_ws.x.abs() - 0.05).abs() < 1.0e-3);
+            assert!(front.contact_normal_ws.y > 0.8);
+        }
+    }
+}
+
+#[test]
+fn the_friction_slip_model_reports_slip_angles_and_loads() {
+    let mut world = cracked_ground();
+    let mut vehicle = spawn_vehicle(&mut world, 3.0, &tuning());
+    // The suspension of a sleeping chassis no longer balances its weight.
+    *world.bodies[vehicle.chassis].activation_mut() = RigidBodyActivation::cannot_sleep();
+    update(&mut world, &mut vehicle, 120);
+
+    // The wheels carry the chassis’ weight.
+    let mass = world.bodies[vehicle.chassis].mass();
+    let total_load: Real = vehicle.wheels().iter().map(|w| w.load).sum();
+    assert!((total_load - mass * 9.81).abs() < 0.05 * mass * 9.81);
+
+    // Drifting sideways.
+    world.bodies[vehicle.chassis].set_linvel(Vector::new(5.0, 0.0, 1.0), true);
+    update(&mut world, &mut vehicle, 1);
+    for wheel in vehicle.wheels() {
+        assert_eq!(wheel.slip_ratio, 0.0);
+        assert!(wheel.slip_angle > 0.1 && wheel.slip_angle < 0.25);
+        assert!((wheel.angular_velocity * RADIUS - 5.0).abs() < 0.5);
+    }
+}
+
+#[test]
+fn magic_formula_wheels_spin_and_lock() {
+    let tuning = WheelTuning {
+        tire_model: TireModel::magic_formula(),
+        ..tuning()
+    };
+
+    // Flooring it from a standstill spins the wheels faster than the vehicle moves.
+    let mut world = cracked_ground();
+    let mut vehicle = spawn_vehicle(&mut world, 3.0, &tuning);
+    update(&mut world, &mut vehicle, 60);
+    for wheel in vehicle.wheels_mut() {
+        wheel.engine_force = 1.0e4;
+    }
+    update(&mut world, &mut vehicle, 30);
+    let speed = world.bodies[vehicle.chassis].linvel().x;
+    assert!(speed > 1.0);
+    for wheel in vehicle.wheels() {
+        assert!(wheel.slip_ratio > 0.1, "{}", wheel.slip_ratio);
+        assert!(wheel.angular_velocity * RADIUS > speed);
+        assert!(wheel.rotation > 0.0);
+    }
+
+    // Slamming the brakes at 10 m/s locks the wheels: the vehicle slides, slowing down at
+    // about the friction coefficient at full slip times the gravity.
+    let stopping_speed = |peak: Real| {
+        let mut tuning = tuning;
+        if let TireModel::MagicFormula {
+            longitudinal,
+            lateral,
+        } = &mut tuning.tire_model
+        {
+            longitudinal.peak = peak;
+            lateral.peak = peak;
+        }
+        let mut world = cracked_ground();
+        let mut vehicle = spawn_vehicle(&mut world, 3.0, &tuning);
+        update(&mut world, &mut vehicle, 60);
+        world.bodies[vehicle.chassis].set_linvel(Vector::new(10.0, 0.0, 0.0), true);
+        for wheel in vehicle.wheels_mut() {
+            wheel.angular_velocity = 10.0 / RADIUS;
+            wheel.brake = 1.0e9;
+        }
+        update(&mut world, &mut vehicle, 30);
+        for wheel in vehicle.wheels() {
+            assert_eq!(wheel.angular_velocity, 0.0);
+            assert!((wheel.slip_ratio + 1.0).abs() < 1.0e-3);
+        }
+        let speed = world.bodies[vehicle.chassis].linvel().x;
+        assert!(speed > 0.0);
+        speed
+    };
+    let TireModel::MagicFormula { longitudinal, .. } = TireModel::magic_formula() else {
+        unreachable!()
+    };
+    let expected = 10.0 - longitudinal.eval(-1.0).abs() * 9.81 * 0.5;
+    let grippy = stopping_speed(1.0);
+    assert!((grippy - expected).abs() < 1.0, "{grippy} != {expected}");
+    // Less grip, longer braking distances.
+    assert!(stopping_speed(0.3) > grippy + 2.0);
+}
+
+#[test]
+fn magic_formula_wheels_corner_with_slip_angles() {
+    let tuning = WheelTuning {
+        tire_model: TireModel::magic_formula(),
+        ..tuning()
+    };
+    let mut world = cracked_ground();
+    let mut vehicle = spawn_vehicle(&mut world, 3.0, &tuning);
+    update(&mut world, &mut vehicle, 60);
+
+    world.bodies[vehicle.chassis].set_linvel(Vector::new(5.0, 0.0, 0.0), true);
+    for (i, wheel) in vehicle.wheels_mut().iter_mut().enumerate() {
+        wheel.angular_velocity = 5.0 / RADIUS;
+        if i < 2 {
+            wheel.steering = 0.2;
+        }
+    }
+    update(&mut world, &mut vehicle, 1);
+    // Only the steered wheels slip sideways at first, pushing the front toward the turn.
+    for (i, wheel) in vehicle.wheels().iter().enumerate() {
+        assert!(wheel.load > 0.0);
+        assert!(wheel.slip_ratio.abs() < 0.05);
+        if i < 2 {
+            assert!((wheel.slip_angle.abs() - 0.2).abs() < 0.05);
+        } else {
+            assert!(wheel.slip_angle.abs() < 0.05);
+        }
+    }
+
+    // Positive steering turns counterclockwise around +Y.
+    update(&mut world, &mut vehicle, 30);
+    let chassis = &world.bodies[vehicle.chassis];
+    assert!(chassis.angvel().y > 0.1);
+    assert!(chassis.linvel().z < -0.1);
+}
@@ -804,6 +804,7 @@ impl InverseKinematicsOption {
             constrained_axes: self.constrained_axes.0,
             epsilon_linear: self.epsilon_linear,
             epsilon_angular: self.epsilon_angular,
+            ..Default::default()
         }
     }
 }
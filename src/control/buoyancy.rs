@@ -0,0 +1,612 @@
+//! Buoyancy and fluid drag applied by fluid volumes to the dynamic bodies they contain.
+use crate::alloc_prelude::*;
+
+#[cfg(not(feature = "std"))]
+use simba::scalar::ComplexField;
+
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::geometry::{BroadPhaseBvh, ColliderHandle, ColliderSet, NarrowPhase, Shape, TypedShape};
+use crate::math::{AngVector, Pose, Real, Vector};
+#[cfg(feature = "dim3")]
+use crate::na::RealField;
+use crate::utils::CrossProduct;
+use parry::partitioning::BvhNode;
+
+/// Number of subdivisions used to triangulate the curved shapes (capsules, cylinders,
+/// cones) before clipping them against the fluid surface.
+const CURVED_SHAPE_SUBDIVISIONS: u32 = 16;
+
+/// The region of space filled by a [`FluidVolume`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FluidRegion {
+    /// All the space below a plane, e.g., an ocean.
+    HalfSpace {
+        /// Any point of the fluid surface.
+        point: Vector,
+        /// The unit normal of the fluid surface, pointing out of the fluid (usually up).
+        normal: Vector,
+    },
+    /// The inside of a sensor collider, e.g., a pool.
+    ///
+    /// The fluid fills the collider up to its surface: the plane orthogonal to the gravity
+    /// touching the top of the collider's world-space AABB. The affected bodies are the ones
+    /// intersecting this sensor according to the narrow-phase, and their submerged part is
+    /// only clipped against the fluid surface (not against the sides or the bottom of the
+    /// sensor).
+    Collider(ColliderHandle),
+}
+
+/// A volume of fluid applying buoyancy and drag to the dynamic bodies it contains.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FluidVolume {
+    /// The region of space filled by the fluid.
+    pub region: FluidRegion,
+    /// The fluid density (mass per unit volume, e.g., `1000.0` for water in SI units).
+    ///
+    /// A body floats if its density is smaller than this.
+    pub density: Real,
+    /// The linear drag coefficient: the drag force grows linearly with the velocity of the
+    /// submerged part relative to the fluid.
+    pub linear_drag: Real,
+    /// The quadratic drag coefficient: the drag force grows with the square of the velocity
+    /// of the submerged part relative to the fluid.
+    pub quadratic_drag: Real,
+    /// The angular drag coefficient: the drag torque grows linearly with the angular velocity
+    /// of the body.
+    pub angular_drag: Real,
+    /// The velocity of the fluid (a river's current, for example).
+    pub flow_velocity: Vector,
+}
+
+impl FluidVolume {
+    /// A still fluid with the given density, without drag.
+    pub fn new(region: FluidRegion, density: Real) -> Self {
+        Self {
+            region,
+            density,
+            linear_drag: 0.0,
+            quadratic_drag: 0.0,
+            angular_drag: 0.0,
+            flow_velocity: Vector::ZERO,
+        }
+    }
+
+    /// The fluid surface as a point and a unit normal pointing out of the fluid, if it exists.
+    fn surface(&self, gravity: Vector, colliders: &ColliderSet) -> Option<(Vector, Vector)> {
+        match self.region {
+            FluidRegion::HalfSpace { point, normal } => Some((point, normal)),
+            FluidRegion::Collider(handle) => {
+                let aabb = colliders.get(handle)?.compute_aabb();
+                let up = (-gravity).try_normalize().unwrap_or(Vector::Y);
+                // The AABB vertex furthest along `up`.
+                let top = Vector::from_array(core::array::from_fn(|i| {
+                    if up[i] >= 0.0 {
+                        aabb.maxs[i]
+                    } else {
+                        aabb.mins[i]
+                    }
+                }));
+                Some((top, up))
+            }
+        }
+    }
+}
+
+/// The part of a shape below a fluid surface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SubmergedPart {
+    /// The submerged volume (area in 2D).
+    pub volume: Real,
+    /// The world-space centroid of the submerged part, where the buoyancy force applies.
+    pub centroid: Vector,
+}
+
+/// Computes the part of `shape`, at the world-space pose `pos`, below the plane passing through
+/// `surface_point` with the unit normal `surface_normal` (pointing out of the fluid).
+///
+/// Balls are clipped exactly. Other convex shapes, compound shapes, and closed triangle meshes
+/// are triangulated, then their triangles (segments in 2D) are clipped against the plane. The
+/// border of round shapes is ignored. Returns `None` if the shape isn't submerged, or isn't
+/// supported (half-spaces, heightfields, polylines, voxels, and custom shapes).
+pub fn submerged_part(
+    shape: &dyn Shape,
+    pos: &Pose,
+    surface_point: Vector,
+    surface_normal: Vector,
+) -> Option<SubmergedPart> {
+    let mut acc = SubmergedAccumulator::default();
+    acc.add_shape(shape, pos, surface_point, surface_normal);
+    acc.finish()
+}
+
+/// Accumulates the volume and first moment of the submerged parts of several shapes.
+#[derive(Default)]
+struct SubmergedAccumulator {
+    volume: Real,
+    moment: Vector,
+}
+
+impl SubmergedAccumulator {
+    fn finish(self) -> Option<SubmergedPart> {
+        (self.volume > Real::EPSILON).then(|| SubmergedPart {
+            volume: self.volume,
+            centroid: self.moment / self.volume,
+        })
+    }
+
+    /// Adds the signed volume and moment of one shape. Its triangulation may be oriented
+    /// inward, in which case both have the wrong sign.
+    fn add_signed(&mut self, volume: Real, moment: Vector) {
+        if volume < 0.0 {
+            self.volume -= volume;
+            self.moment -= moment;
+        } else {
+            self.volume += volume;
+            self.moment += moment;
+        }
+    }
+
+    fn add_shape(&mut self, shape: &dyn Shape, pos: &Pose, point: Vector, normal: Vector) {
+        // Quick reject: the part of the shape's AABB below the surface is empty.
+        let aabb = shape.compute_aabb(pos);
+        let lowest = aabb.center().dot(normal) - aabb.half_extents().dot(normal.abs());
+        if lowest >= point.dot(normal) {
+            return;
+        }
+
+        match shape.as_typed_shape() {
+            TypedShape::Ball(ball) => {
+                self.add_ball(pos.translation, ball.radius, point, normal);
+            }
+            TypedShape::Compound(compound) => {
+                for (sub_pos, sub_shape) in compound.shapes() {
+                    self.add_shape(&**sub_shape, &(*pos * *sub_pos), point, normal);
+                }
+            }
+            #[cfg(feature = "dim2")]
+            TypedShape::Cuboid(cuboid) => {
+                self.add_polygon(&cuboid.to_polyline(), pos, point, normal);
+            }
+            #[cfg(feature = "dim2")]
+            TypedShape::Capsule(capsule) => {
+                let vertices = capsule.to_polyline(CURVED_SHAPE_SUBDIVISIONS);
+                self.add_polygon(&vertices, pos, point, normal);
+            }
+            #[cfg(feature = "dim2")]
+            TypedShape::ConvexPolygon(polygon) => {
+                self.add_polygon(polygon.points(), pos, point, normal);
+            }
+            #[cfg(feature = "dim2")]
+            TypedShape::Triangle(tri) => {
+                self.add_polygon(&[tri.a, tri.b, tri.c], pos, point, normal);
+            }
+            #[cfg(feature = "dim2")]
+            TypedShape::RoundCuboid(s) => self.add_shape(&s.inner_shape, pos, point, normal),
+            #[cfg(feature = "dim2")]
+            TypedShape::RoundConvexPolygon(s) => self.add_shape(&s.inner_shape, pos, point, normal),
+            #[cfg(feature = "dim3")]
+            TypedShape::Cuboid(cuboid) => {
+                let (vertices, indices) = cuboid.to_trimesh();
+                self.add_trimesh(&vertices, &indices, pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::Capsule(capsule) => {
+                let (vertices, indices) =
+                    capsule.to_trimesh(CURVED_SHAPE_SUBDIVISIONS, CURVED_SHAPE_SUBDIVISIONS / 2);
+                self.add_trimesh(&vertices, &indices, pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::Cylinder(cylinder) => {
+                let (vertices, indices) = cylinder.to_trimesh(CURVED_SHAPE_SUBDIVISIONS);
+                self.add_trimesh(&vertices, &indices, pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::Cone(cone) => {
+                let (vertices, indices) = cone.to_trimesh(CURVED_SHAPE_SUBDIVISIONS);
+                self.add_trimesh(&vertices, &indices, pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::ConvexPolyhedron(poly) => {
+                let (vertices, indices) = poly.to_trimesh();
+                self.add_trimesh(&vertices, &indices, pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::TriMesh(mesh) => {
+                self.add_trimesh(mesh.vertices(), mesh.indices(), pos, point, normal);
+            }
+            #[cfg(feature = "dim3")]
+            TypedShape::RoundCuboid(s) => self.add_shape(&s.inner_shape, pos, point, normal),
+            #[cfg(feature = "dim3")]
+            TypedShape::RoundCylinder(s) => self.add_shape(&s.inner_shape, pos, point, normal),
+            #[cfg(feature = "dim3")]
+            TypedShape::RoundCone(s) => self.add_shape(&s.inner_shape, pos, point, normal),
+            #[cfg(feature = "dim3")]
+            TypedShape::RoundConvexPolyhedron(s) => {
+                self.add_shape(&s.inner_shape, pos, point, normal)
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds the exact submerged cap of a ball.
+    fn add_ball(&mut self, center: Vector, radius: Real, point: Vector, normal: Vector) {
+        // Height of the ball's center above the surface, clamped to the ball.
+        let d = (center - point).dot(normal).clamp(-radius, radius);
+        if d >= radius {
+            return;
+        }
+
+        #[cfg(feature = "dim2")]
+        let (volume, depth) = {
+            // Circular segment below a chord at the distance `d` from the center.
+            let half_chord = (radius * radius - d * d).max(0.0).sqrt();
+            let area = radius * radius * (d / radius).acos() - d * half_chord;
+            let depth = if area > 0.0 {
+                2.0 * half_chord * half_chord * half_chord / (3.0 * area)
+            } else {
+                radius
+            };
+            (area, depth)
+        };
+        #[cfg(feature = "dim3")]
+        let (volume, depth) = {
+            // Spherical cap of height `h`.
+            let h = radius - d;
+            let volume = Real::pi() * h * h * (3.0 * radius - h) / 3.0;
+            let depth = 3.0 * (2.0 * radius - h) * (2.0 * radius - h) / (4.0 * (3.0 * radius - h));
+            (volume, depth)
+        };
+
+        self.volume += volume;
+        self.moment += (center - normal * depth) * volume;
+    }
+
+    /// Adds the submerged part of a closed, consistently oriented polygon.
+    #[cfg(feature = "dim2")]
+    fn add_polygon(&mut self, vertices: &[Vector], pos: &Pose, point: Vector, normal: Vector) {
+        // Each clipped edge forms a triangle with a reference point on the surface; the edges
+        // closing the clipped polygon lie on the surface and form degenerate triangles, so they
+        // can be skipped. The reference point is projected close to the shape for accuracy.
+        let reference = project_on_plane(pos.translation, point, normal);
+        let (mut total_area, mut moment) = (0.0, Vector::ZERO);
+        for i in 0..vertices.len() {
+            let a = *pos * vertices[i];
+            let b = *pos * vertices[(i + 1) % vertices.len()];
+            let mut clipped = [Vector::ZERO; 2];
+            if clip_polygon(&[a, b], &mut clipped, point, normal) == 2 {
+                let (u, v) = (clipped[0] - reference, clipped[1] - reference);
+                let area = u.perp_dot(v) / 2.0;
+                total_area += area;
+                moment += (reference * 3.0 + u + v) / 3.0 * area;
+            }
+        }
+        self.add_signed(total_area, moment);
+    }
+
+    /// Adds the submerged part of a closed, consistently oriented triangle mesh.
+    #[cfg(feature = "dim3")]
+    fn add_trimesh(
+        &mut self,
+        vertices: &[Vector],
+        indices: &[[u32; 3]],
+        pos: &Pose,
+        point: Vector,
+        normal: Vector,
+    ) {
+        // Each clipped triangle forms a tetrahedron with a reference point on the surface; the
+        // cap closing the clipped mesh lies on the surface and forms degenerate tetrahedra, so it
+        // can be skipped. The reference point is projected close to the shape for accuracy.
+        let reference = project_on_plane(pos.translation, point, normal);
+        let (mut total_volume, mut moment) = (0.0, Vector::ZERO);
+        for idx in indices {
+            let tri = idx.map(|i| *pos * vertices[i as usize]);
+            let mut clipped = [Vector::ZERO; 4];
+            let len = clip_polygon(&tri, &mut clipped, point, normal);
+            for k in 1..len.saturating_sub(1) {
+                let (u, v, w) = (
+                    clipped[0] - reference,
+                    clipped[k] - reference,
+                    clipped[k + 1] - reference,
+                );
+                let volume = u.dot(v.cross(w)) / 6.0;
+                total_volume += volume;
+                moment += (reference * 4.0 + u + v + w) / 4.0 * volume;
+            }
+        }
+        self.add_signed(total_volume, moment);
+    }
+}
+
+fn project_on_plane(pt: Vector, point: Vector, normal: Vector) -> Vector {
+    pt - normal * (pt - point).dot(normal)
+}
+
+/// Clips a convex polygon (or a segment if it has two vertices) against the half-space below
+/// the plane, writing the result to `out` and returning its number of vertices.
+fn clip_polygon(polygon: &[Vector], out: &mut [Vector], point: Vector, normal: Vector) -> usize {
+    let mut len = 0;
+    let mut push = |pt: Vector| {
+        if len < out.len() {
+            out[len] = pt;
+            len += 1;
+        }
+    };
+    let is_segment = polygon.len() == 2;
+    let num_edges = if is_segment { 1 } else { polygon.len() };
+
+    for i in 0..num_edges {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % polygon.len()];
+        let da = (a - point).dot(normal);
+        let db = (b - point).dot(normal);
+
+        if da <= 0.0 {
+            push(a);
+        }
+        if (da < 0.0 && db > 0.0) || (da > 0.0 && db < 0.0) {
+            push(a + (b - a) * (da / (da - db)));
+        }
+        if is_segment && db <= 0.0 {
+            push(b);
+        }
+    }
+
+    len
+}
+
+/// Applies the buoyancy and drag of a set of [`FluidVolume`]s to the dynamic bodies they contain.
+///
+/// Call [`Self::update`] once before each simulation step. Each collider partially or fully
+/// inside a fluid volume receives:
+/// - An Archimedes force, opposite to the gravity and proportional to its submerged volume,
+///   applied at the centroid of its submerged part (so floating bodies right themselves).
+/// - A drag force opposing the velocity of its submerged centroid relative to the fluid flow,
+///   and a drag torque opposing its angular velocity, both scaled by its submerged fraction.
+///
+/// A collider inside several overlapping volumes is affected by each of them. The forces are
+/// [added](crate::dynamics::RigidBody::add_force) to the bodies, so the solver integrates them
+/// at each substep along with the gravity, and the ones added by the previous update are removed
+/// first (the other forces added to the bodies are kept). A sleeping body is woken up only if
+/// the fluid forces acting on it changed, e.g., when the fluid rises around it.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct BuoyancyController {
+    volumes: Vec<FluidVolume>,
+    /// The force and torque added to each body by the last update, sorted by handle.
+    applied: Vec<(RigidBodyHandle, Vector, AngVector)>,
+}
+
+impl BuoyancyController {
+    /// A buoyancy controller without any fluid volume.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a fluid volume, returning its index.
+    pub fn add_volume(&mut self, volume: FluidVolume) -> usize {
+        self.volumes.push(volume);
+        self.volumes.len() - 1
+    }
+
+    /// Removes the fluid volume at the given index, shifting the following ones.
+    pub fn remove_volume(&mut self, index: usize) -> FluidVolume {
+        self.volumes.remove(index)
+    }
+
+    /// The fluid volumes of this controller.
+    pub fn volumes(&self) -> &[FluidVolume] {
+        &self.volumes
+    }
+
+    /// The fluid volumes of this controller, for modification.
+    pub fn volumes_mut(&mut self) -> &mut [FluidVolume] {
+        &mut self.volumes
+    }
+
+    /// Sets the buoyancy and drag forces of every fluid volume for the next step of length `dt`.
+    ///
+    /// The forces added by the previous call are removed from the bodies first. The colliders
+    /// below a [`FluidRegion::HalfSpace`] surface are found with the broad-phase, and the ones
+    /// inside a [`FluidRegion::Collider`] with the narrow-phase, so the colliders inserted since
+    /// the last simulation step aren't affected yet.
+    #[profiling::function]
+    pub fn update(
+        &mut self,
+        dt: Real,
+        gravity: Vector,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        broad_phase: &BroadPhaseBvh,
+        narrow_phase: &NarrowPhase,
+    ) {
+        let mut forces = Vec::new();
+
+        for volume in &self.volumes {
+            let Some((point, normal)) = volume.surface(gravity, colliders) else {
+                continue;
+            };
+
+            match volume.region {
+                FluidRegion::HalfSpace { .. } => {
+                    let below_surface = |node: &BvhNode| {
+                        let aabb = node.aabb();
+                        (aabb.center() - point).dot(normal) <= aabb.half_extents().dot(normal.abs())
+                    };
+                    for leaf in broad_phase.tree.leaves(below_surface) {
+                        let Some((_, handle)) = colliders.get_unknown_gen(leaf) else {
+                            continue;
+                        };
+                        add_fluid_forces(
+                            volume,
+                            handle,
+                            point,
+                            normal,
+                            dt,
+                            gravity,
+                            bodies,
+                            colliders,
+                            &mut forces,
+                        );
+                    }
+                }
+                FluidRegion::Collider(sensor) => {
+                    for (h1, h2, intersecting) in narrow_phase.intersection_pairs_with(sensor) {
+                        if intersecting {
+                            let handle = if h1 == sensor { h2 } else { h1 };
+                            add_fluid_forces(
+                                volume,
+                                handle,
+                                point,
+                                normal,
+                                dt,
+                                gravity,
+                                bodies,
+                                colliders,
+                                &mut forces,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        // Sum the forces of each body in a deterministic order (the stable sort keeps the
+        // order of the volumes and colliders they come from).
+        forces.sort_by_key(|(handle, ..)| handle.into_raw_parts().0);
+        forces.dedup_by(|(handle, force, torque), (kept, kept_force, kept_torque)| {
+            let same_body = *handle == *kept;
+            if same_body {
+                *kept_force += *force;
+                *kept_torque += *torque;
+            }
+            same_body
+        });
+
+        let previous = core::mem::take(&mut self.applied);
+        for (handle, force, torque) in &previous {
+            if let Some(rb) = bodies.get_mut(*handle) {
+                rb.add_force(-*force, false);
+                rb.add_torque(-*torque, false);
+            }
+        }
+
+        for (handle, force, torque) in forces {
+            let rb = &mut bodies[handle];
+            // A sleeping body in equilibrium receives the same forces as before it fell asleep.
+            let unchanged = previous
+                .binary_search_by_key(&handle.into_raw_parts().0, |(h, ..)| h.into_raw_parts().0)
+                .is_ok_and(|i| previous[i] == (handle, force, torque));
+            let wake_up = !rb.is_sleeping() || !unchanged;
+            rb.add_force(force, wake_up);
+            rb.add_torque(torque, wake_up);
+            self.applied.push((handle, force, torque));
+        }
+    }
+}
+
+/// Pushes to `forces` the force and torque applied by `volume` to the body of `handle`.
+#[allow(clippy::too_many_arguments)]
+fn add_fluid_forces(
+    volume: &FluidVolume,
+    handle: ColliderHandle,
+    point: Vector,
+    normal: Vector,
+    dt: Real,
+    gravity: Vector,
+    bodies: &RigidBodySet,
+    colliders: &ColliderSet,
+    forces: &mut Vec<(RigidBodyHandle, Vector, AngVector)>,
+) {
+    let Some(co) = colliders.get(handle) else {
+        return;
+    };
+    if co.is_sensor() || !co.is_enabled() {
+        return;
+    }
+    let Some(body) = co.parent() else {
+        return;
+    };
+    let Some(rb) = bodies.get(body).filter(|rb| rb.is_dynamic()) else {
+        return;
+    };
+    let Some(submerged) = submerged_part(co.shape(), co.position(), point, normal) else {
+        return;
+    };
+
+    let fraction = (submerged.volume / co.volume()).min(1.0);
+    let buoyancy = -gravity * (volume.density * submerged.volume);
+
+    let rel_vel = rb.velocity_at_point(submerged.centroid) - volume.flow_velocity;
+    let speed = rel_vel.length();
+    let drag = -rel_vel * (fraction * (volume.linear_drag + volume.quadratic_drag * speed));
+    // The drag is integrated explicitly: cap it so it can't reverse the relative velocity.
+    let max_drag = rb.mass() * speed * crate::utils::inv(dt);
+    let drag = if drag.length() > max_drag {
+        drag.normalize_or_zero() * max_drag
+    } else {
+        drag
+    };
+
+    let force = buoyancy + drag;
+    let angular_drag = -rb.angvel() * (fraction * volume.angular_drag);
+    let torque = (submerged.centroid - rb.center_of_mass()).gcross(force) + angular_drag;
+    forces.push((body, force, torque));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::{Ball, Cuboid};
+    use crate::na::RealField;
+
+    fn assert_close(a: Real, b: Real) {
+        assert!((a - b).abs() < 1.0e-4 * b.abs().max(1.0), "{a} != {b}");
+    }
+
+    #[test]
+    fn half_submerged_cuboid() {
+        let cuboid = Cuboid::new(Vector::splat(0.5));
+        let pos = Pose::from_translation(Vector::Y * 0.25);
+        let part = submerged_part(&cuboid, &pos, Vector::ZERO, Vector::Y).unwrap();
+        // The unit cuboid spans `y ∈ [-0.25, 0.75]`: the submerged part is `y ∈ [-0.25, 0]`.
+        assert_close(part.volume, 0.25);
+        assert_close(part.centroid.y, -0.125);
+        assert_close(part.centroid.x, 0.0);
+    }
+
+    #[test]
+    fn half_submerged_ball() {
+        let ball = Ball::new(1.0);
+        let part = submerged_part(&ball, &Pose::IDENTITY, Vector::ZERO, Vector::Y).unwrap();
+        let pi = Real::pi();
+        #[cfg(feature = "dim2")]
+        {
+            assert_close(part.volume, pi / 2.0);
+            assert_close(part.centroid.y, -4.0 / (3.0 * pi));
+        }
+        #[cfg(feature = "dim3")]
+        {
+            assert_close(part.volume, 2.0 * pi / 3.0);
+            assert_close(part.centroid.y, -3.0 / 8.0);
+        }
+    }
+
+    #[test]
+    fn shapes_above_the_surface_are_not_submerged() {
+        let cuboid = Cuboid::new(Vector::splat(0.5));
+        let pos = Pose::from_translation(Vector::Y * 2.0);
+        assert!(submerged_part(&cuboid, &pos, Vector::ZERO, Vector::Y).is_none());
+        assert!(submerged_part(&Ball::new(1.0), &pos, Vector::ZERO, Vector::Y).is_none());
+    }
+
+    #[test]
+    fn fully_submerged_cuboid() {
+        let cuboid = Cuboid::new(Vector::splat(0.5));
+        let pos = Pose::from_translation(Vector::Y * -3.0);
+        let part = submerged_part(&cuboid, &pos, Vector::ZERO, Vector::Y).unwrap();
+        assert_close(part.volume, 1.0);
+        assert_close(part.centroid.y, -3.0);
+    }
+}
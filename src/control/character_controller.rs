@@ -1,6 +1,6 @@
 use crate::alloc_prelude::*;
 use crate::geometry::{ColliderHandle, ContactManifold, Shape, ShapeCastHit};
-use crate::math::{Pose, Real, Vector};
+use crate::math::{AngVector, Pose, Real, Rotation, Vector, rotation_from_angle};
 use crate::pipeline::{QueryFilterFlags, QueryPipeline, QueryPipelineMut};
 use crate::utils;
 use na::{RealField, Vector2};
@@ -47,7 +47,7 @@ impl CharacterLength {
         }
     }
 
-    fn eval(self, value: Real) -> Real {
+    pub(crate) fn eval(self, value: Real) -> Real {
         match self {
             Self::Relative(x) => value * x,
             Self::Absolute(x) => x,
@@ -55,6 +55,14 @@ impl CharacterLength {
     }
 }
 
+/// The ground a character stands on.
+#[derive(Copy, Clone, Debug)]
+struct GroundContact {
+    handle: ColliderHandle,
+    normal: Vector,
+    point: Vector,
+}
+
 #[derive(Debug)]
 struct HitInfo {
     toi: ShapeCastHit,
@@ -114,6 +122,37 @@ pub struct CharacterCollision {
     pub hit: ShapeCastHit,
 }
 
+/// The point of a character shape that stays in place when it is resized with
+/// [`KinematicCharacterController::try_resize_shape`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CharacterShapeAnchor {
+    /// The bottom of the shape (along the `up` direction) stays in place, e.g., to crouch or
+    /// stand up on the ground.
+    Bottom,
+    /// The center of the shape stays in place, e.g., to curl up while in the air.
+    Center,
+    /// The top of the shape stays in place, e.g., when hanging from a ledge.
+    Top,
+}
+
+/// A ledge found by [`KinematicCharacterController::find_ledge`].
+#[derive(Copy, Clone, Debug)]
+pub struct CharacterLedge {
+    /// The collider forming the top of the ledge.
+    pub handle: ColliderHandle,
+    /// The point of the ledge's edge in front of the character, e.g., to place its hands.
+    pub edge: Vector,
+    /// The normal of the wall below the edge, pointing toward the character.
+    pub wall_normal: Vector,
+    /// The normal of the top of the ledge.
+    pub top_normal: Vector,
+    /// The height of the top of the ledge above the character's current position.
+    pub height: Real,
+    /// The pose of the character standing on top of the ledge, e.g., at the end of a mantle.
+    pub mantle_pos: Pose,
+}
+
 /// A kinematic character controller for player/NPC movement (walking, climbing, sliding).
 ///
 /// This provides classic game character movement: walking on floors, sliding on slopes,
@@ -203,6 +242,15 @@ pub struct KinematicCharacterController {
     /// This value should remain fairly small since it can introduce artificial "bumps" when sliding
     /// along a flat surface.
     pub normal_nudge_factor: Real,
+    /// Should the character follow the full rigid motion of the body it stands on?
+    ///
+    /// When enabled, the displacement of the character's position due to the linear and angular
+    /// velocities of the ground's rigid-body over `dt` is added to the desired translation, and
+    /// the rotation of the ground around `up` (in 3D) is reported in
+    /// [`EffectiveCharacterMovement::rotation`], so that characters are carried by rotating
+    /// platforms. When disabled (the default), only the translation of kinematic platforms is
+    /// followed.
+    pub apply_platform_motion: bool,
 }
 
 impl Default for KinematicCharacterController {
@@ -216,6 +264,7 @@ impl Default for KinematicCharacterController {
             min_slope_slide_angle: Real::frac_pi_4(),
             snap_to_ground: Some(CharacterLength::Relative(0.2)),
             normal_nudge_factor: 1.0e-4,
+            apply_platform_motion: false,
         }
     }
 }
@@ -225,10 +274,41 @@ impl Default for KinematicCharacterController {
 pub struct EffectiveCharacterMovement {
     /// The movement to apply.
     pub translation: Vector,
+    /// The rotation to apply to the character, around its own position.
+    ///
+    /// This is the identity unless [`KinematicCharacterController::apply_platform_motion`] is
+    /// enabled and the character stands on a body rotating around `up`.
+    pub rotation: Rotation,
     /// Is the character touching the ground after applying `EffectiveKineamticMovement::translation`?
     pub grounded: bool,
     /// Is the character sliding down a slope due to slope angle being larger than `min_slope_slide_angle`?
     pub is_sliding_down_slope: bool,
+    /// The collider the character is standing on, if it is grounded.
+    pub ground: Option<ColliderHandle>,
+    /// The normal of the ground, pointing toward the character, or zero if it isn't grounded.
+    pub ground_normal: Vector,
+    /// The velocity of the ground at the point the character stands on.
+    ///
+    /// Add it to the character's velocity when it jumps off a moving platform. Zero if the
+    /// character isn't grounded, or stands on a collider without parent.
+    pub ground_linvel: Vector,
+    /// The angular velocity of the rigid-body the character stands on.
+    pub ground_angvel: AngVector,
+}
+
+impl Default for EffectiveCharacterMovement {
+    fn default() -> Self {
+        Self {
+            translation: Vector::ZERO,
+            rotation: Rotation::IDENTITY,
+            grounded: false,
+            is_sliding_down_slope: false,
+            ground: None,
+            ground_normal: Vector::ZERO,
+            ground_linvel: Vector::ZERO,
+            ground_angvel: AngVector::default(),
+        }
+    }
 }
 
 impl KinematicCharacterController {
@@ -303,11 +383,7 @@ impl KinematicCharacterController {
         desired_translation: Vector,
         mut events: impl FnMut(CharacterCollision),
     ) -> EffectiveCharacterMovement {
-        let mut result = EffectiveCharacterMovement {
-            translation: Vector::ZERO,
-            grounded: false,
-            is_sliding_down_slope: false,
-        };
+        let mut result = EffectiveCharacterMovement::default();
         let dims = self.compute_dims(character_shape);
 
         // 1. Depenetrate, but only when there is no desired movement: the shape-casting
@@ -323,9 +399,7 @@ impl KinematicCharacterController {
             );
         }
 
-        let mut translation_remaining = desired_translation;
-
-        let grounded_at_starting_pos = self.detect_grounded_status_and_apply_friction(
+        let mut ground = self.detect_grounded_status_and_apply_friction(
             dt,
             queries,
             character_shape,
@@ -334,6 +408,17 @@ impl KinematicCharacterController {
             None,
             None,
         );
+        let grounded_at_starting_pos = ground.is_some();
+
+        let mut translation_remaining = desired_translation;
+        if self.apply_platform_motion
+            && let Some(ground) = ground
+        {
+            let (platform_translation, platform_rotation) =
+                self.platform_motion(dt, queries, &ground, character_pos);
+            translation_remaining += platform_translation;
+            result.rotation = platform_rotation;
+        }
 
         let mut max_iters = 20;
         let mut kinematic_friction_translation = Vector::ZERO;
@@ -401,7 +486,7 @@ impl KinematicCharacterController {
             } else {
                 // No interference along the path.
                 result.translation += translation_remaining;
-                result.grounded = self.detect_grounded_status_and_apply_friction(
+                ground = self.detect_grounded_status_and_apply_friction(
                     dt,
                     queries,
                     character_shape,
@@ -412,14 +497,15 @@ impl KinematicCharacterController {
                 );
                 break;
             }
-            result.grounded = self.detect_grounded_status_and_apply_friction(
+            // The platform's motion is already part of the translation if it is applied fully.
+            ground = self.detect_grounded_status_and_apply_friction(
                 dt,
                 queries,
                 character_shape,
                 &(Pose::from_translation(result.translation) * *character_pos),
                 dims,
-                Some(&mut kinematic_friction_translation),
-                Some(&mut translation_remaining),
+                (!self.apply_platform_motion).then_some(&mut kinematic_friction_translation),
+                (!self.apply_platform_motion).then_some(&mut translation_remaining),
             );
 
             if !self.slide {
@@ -429,7 +515,7 @@ impl KinematicCharacterController {
         // When not moving, `detect_grounded_status_and_apply_friction` is not reached
         // so we call it explicitly here.
         if !is_moving {
-            result.grounded = self.detect_grounded_status_and_apply_friction(
+            ground = self.detect_grounded_status_and_apply_friction(
                 dt,
                 queries,
                 character_shape,
@@ -440,20 +526,82 @@ impl KinematicCharacterController {
             );
         }
         // If needed, and if we are not already grounded, snap to the ground.
-        if grounded_at_starting_pos {
-            self.snap_to_ground(
+        if grounded_at_starting_pos
+            && let Some((handle, hit)) = self.snap_to_ground(
                 queries,
                 character_shape,
                 &(Pose::from_translation(result.translation) * *character_pos),
                 dims,
                 &mut result,
-            );
+            )
+        {
+            ground = Some(GroundContact {
+                handle,
+                normal: hit.normal1,
+                point: hit.witness1,
+            });
         }
 
+        self.report_ground(queries, ground, &mut result);
+
         // Return the result.
         result
     }
 
+    /// The translation of the character, and its rotation around `up` (in 3D), due to the rigid
+    /// motion of the body it stands on over `dt`.
+    fn platform_motion(
+        &self,
+        dt: Real,
+        queries: &QueryPipeline,
+        ground: &GroundContact,
+        character_pos: &Pose,
+    ) -> (Vector, Rotation) {
+        let Some(platform) = queries
+            .colliders
+            .get(ground.handle)
+            .and_then(|co| co.parent())
+            .and_then(|parent| queries.bodies.get(parent))
+        else {
+            return (Vector::ZERO, Rotation::IDENTITY);
+        };
+
+        // Rotate the character's position around the platform's center of mass.
+        let arm = character_pos.translation - platform.center_of_mass();
+        let translation =
+            platform.linvel() * dt + rotation_from_angle(platform.angvel() * dt) * arm - arm;
+        // Only the rotation around `up` applies to the character, to keep it upright.
+        #[cfg(feature = "dim2")]
+        let rotation = Rotation::IDENTITY;
+        #[cfg(feature = "dim3")]
+        let rotation = rotation_from_angle(self.up * (platform.angvel().dot(self.up) * dt));
+        (translation, rotation)
+    }
+
+    fn report_ground(
+        &self,
+        queries: &QueryPipeline,
+        ground: Option<GroundContact>,
+        result: &mut EffectiveCharacterMovement,
+    ) {
+        result.grounded = ground.is_some();
+        let Some(ground) = ground else {
+            return;
+        };
+
+        result.ground = Some(ground.handle);
+        result.ground_normal = ground.normal;
+        if let Some(platform) = queries
+            .colliders
+            .get(ground.handle)
+            .and_then(|co| co.parent())
+            .and_then(|parent| queries.bodies.get(parent))
+        {
+            result.ground_linvel = platform.velocity_at_point(ground.point);
+            result.ground_angvel = platform.angvel();
+        }
+    }
+
     fn snap_to_ground(
         &self,
         queries: &QueryPipeline,
@@ -502,7 +650,7 @@ impl KinematicCharacterController {
         dims: Vector2<Real>,
         mut kinematic_friction_translation: Option<&mut Vector>,
         mut translation_remaining: Option<&mut Vector>,
-    ) -> bool {
+    ) -> Option<GroundContact> {
         let prediction = self.predict_ground(dims.y);
 
         // TODO: allow custom dispatchers.
@@ -513,9 +661,9 @@ impl KinematicCharacterController {
             .compute_aabb(character_pos)
             .loosened(prediction);
 
-        let mut grounded = false;
+        let mut ground = None;
 
-        'outer: for (_, collider) in queries.intersect_aabb_conservative(character_aabb) {
+        'outer: for (handle, collider) in queries.intersect_aabb_conservative(character_aabb) {
             manifolds.clear();
             let pos12 = character_pos.inv_mul(collider.position());
             let _ = dispatcher.contact_manifolds(
@@ -538,8 +686,14 @@ impl KinematicCharacterController {
                     .filter(|rb| rb.is_kinematic());
 
                 for m in &manifolds {
-                    if self.is_grounded_at_contact_manifold(m, character_pos, dims) {
-                        grounded = true;
+                    if ground.is_none() {
+                        ground = self.ground_contact_at_manifold(
+                            m,
+                            handle,
+                            collider.position(),
+                            character_pos,
+                            dims,
+                        );
                     }
 
                     if let Some(kinematic_parent) = kinematic_parent {
@@ -589,22 +743,30 @@ impl KinematicCharacterController {
                     *kinematic_friction_translation - init_kinematic_friction_translation;
             } else {
                 for m in &manifolds {
-                    if self.is_grounded_at_contact_manifold(m, character_pos, dims) {
-                        grounded = true;
+                    ground = self.ground_contact_at_manifold(
+                        m,
+                        handle,
+                        collider.position(),
+                        character_pos,
+                        dims,
+                    );
+                    if ground.is_some() {
                         break 'outer; // We can stop the search early.
                     }
                 }
             }
         }
-        grounded
+        ground
     }
 
-    fn is_grounded_at_contact_manifold(
+    fn ground_contact_at_manifold(
         &self,
         manifold: &ContactManifold,
+        handle: ColliderHandle,
+        collider_pos: &Pose,
         character_pos: &Pose,
         dims: Vector2<Real>,
-    ) -> bool {
+    ) -> Option<GroundContact> {
         let normal = -(character_pos.rotation * manifold.local_n1);
 
         // For the controller to be grounded, the angle between the contact normal and the up vector
@@ -613,11 +775,15 @@ impl KinematicCharacterController {
             let prediction = self.predict_ground(dims.y);
             for contact in &manifold.points {
                 if contact.dist <= prediction {
-                    return true;
+                    return Some(GroundContact {
+                        handle,
+                        normal,
+                        point: *collider_pos * contact.local_p2,
+                    });
                 }
             }
         }
-        false
+        None
     }
 
     fn handle_slopes(
@@ -872,6 +1038,168 @@ impl KinematicCharacterController {
         true
     }
 
+    /// Checks whether the character can switch from the shape `from` to the shape `to`, e.g., to
+    /// stand up after crouching, and returns the pose of the resized character if it can.
+    ///
+    /// The new shape is first placed so that its `anchor` matches the one of `from` at
+    /// `character_pos`. If it then overlaps, or is closer than [`Self::offset`] to, a collider
+    /// tested by [`Self::move_shape`] (the colliders passing the query filter, except sensors),
+    /// it is pushed away by at most a quarter of its height. Returns `None` if that isn't enough
+    /// to make room for it, e.g., when standing up under a low ceiling.
+    #[profiling::function]
+    pub fn try_resize_shape(
+        &self,
+        queries: &QueryPipeline,
+        from: &dyn Shape,
+        to: &dyn Shape,
+        character_pos: &Pose,
+        anchor: CharacterShapeAnchor,
+    ) -> Option<Pose> {
+        // The bottom, center, and top of a shape along `up`, relative to its position.
+        let up_local = character_pos.rotation.inverse() * self.up;
+        let span_along_up = |shape: &dyn Shape| {
+            let aabb = shape.compute_local_aabb();
+            let center = aabb.center().dot(up_local);
+            let half_extent = aabb.half_extents().dot(up_local.abs());
+            [center - half_extent, center, center + half_extent]
+        };
+        let anchor_index = match anchor {
+            CharacterShapeAnchor::Bottom => 0,
+            CharacterShapeAnchor::Center => 1,
+            CharacterShapeAnchor::Top => 2,
+        };
+        let shift = span_along_up(from)[anchor_index] - span_along_up(to)[anchor_index];
+        let mut pos = Pose::from_translation(self.up * shift) * *character_pos;
+
+        let dims = self.compute_dims(to);
+        let offset = self.offset.eval(dims.y);
+        let max_correction = dims.y * 0.25;
+        let mut applied = 0.0;
+
+        // Run a few passes so that getting pushed away from one collider doesn’t leave the
+        // shape too close to another one.
+        for pass in 0..=4 {
+            let Some(correction) = self.clearance_correction(queries, to, &pos, offset) else {
+                return Some(pos);
+            };
+            applied += correction.length();
+            if pass == 4 || correction == Vector::ZERO || applied > max_correction {
+                return None;
+            }
+            pos = Pose::from_translation(correction) * pos;
+        }
+
+        None
+    }
+
+    /// The translation moving `shape` at `pos` at least [`Self::offset`] away from the colliders
+    /// it is too close to, or `None` if it is far enough from all of them.
+    ///
+    /// The translation can be zero if the colliders push the shape in opposite directions.
+    fn clearance_correction(
+        &self,
+        queries: &QueryPipeline,
+        shape: &dyn Shape,
+        pos: &Pose,
+        offset: Real,
+    ) -> Option<Vector> {
+        let aabb = shape.compute_aabb(pos).loosened(offset);
+        let mut correction = None;
+
+        for (_, collider) in queries.intersect_aabb_conservative(aabb) {
+            if collider.is_sensor() {
+                continue;
+            }
+
+            let pos12 = pos.inv_mul(collider.position());
+            if let Ok(Some(contact)) =
+                queries
+                    .dispatcher
+                    .contact(&pos12, shape, collider.shape(), offset)
+                && contact.dist < offset - 1.0e-5
+            {
+                // `normal1` (expressed in the shape’s local frame) points towards the obstacle.
+                *correction.get_or_insert(Vector::ZERO) -=
+                    (pos.rotation * contact.normal1) * (offset - contact.dist);
+            }
+        }
+
+        correction
+    }
+
+    /// Looks for a ledge the character can grab or climb onto, in front of it along `direction`.
+    ///
+    /// A ledge is the top of a wall (a surface steeper than [`Self::max_slope_climb_angle`])
+    /// at most `max_distance` away, with room for the character on top of it, at most
+    /// `max_height` above the character's current position. The free space is checked by
+    /// shape-casting the character, with the same filtering and [`Self::offset`] as
+    /// [`Self::move_shape`]: up to the height of the ledge, then forward over it, then down
+    /// onto its top.
+    ///
+    /// The returned [`CharacterLedge::mantle_pos`] can be fed to
+    /// [`Self::try_resize_shape`] to mantle onto a low-ceiling ledge while crouching.
+    #[profiling::function]
+    pub fn find_ledge(
+        &self,
+        queries: &QueryPipeline,
+        character_shape: &dyn Shape,
+        character_pos: &Pose,
+        direction: Vector,
+        max_distance: Real,
+        max_height: Real,
+    ) -> Option<CharacterLedge> {
+        let dims = self.compute_dims(character_shape);
+        let offset = self.offset.eval(dims.y);
+        let [_, horizontal_dir] = self.split_into_components(direction);
+        let dir = horizontal_dir.try_normalize()?;
+        let cast = |pos: &Pose, dir: Vector, max_dist: Real| {
+            queries.cast_shape(
+                pos,
+                dir,
+                character_shape,
+                ShapeCastOptions {
+                    target_distance: offset,
+                    stop_at_penetration: false,
+                    max_time_of_impact: max_dist,
+                    compute_impact_geometry_on_penetration: true,
+                },
+            )
+        };
+
+        // 1. A wall in front of the character.
+        let (_, wall) = cast(character_pos, dir, max_distance)?;
+        if !self.compute_hit_info(wall).is_wall {
+            return None;
+        }
+
+        // 2. Room to rise, then to move over the ledge by the character’s width.
+        let rise = cast(character_pos, self.up, max_height)
+            .map(|(_, hit)| hit.time_of_impact)
+            .unwrap_or(max_height);
+        let raised_pos = Pose::from_translation(self.up * rise) * *character_pos;
+        let reach = wall.time_of_impact + dims.x;
+        if cast(&raised_pos, dir, reach).is_some() {
+            return None;
+        }
+
+        // 3. The top of the ledge, above the character’s position and flat enough to stand on.
+        let above_ledge = Pose::from_translation(dir * reach) * raised_pos;
+        let (handle, top) = cast(&above_ledge, -self.up, rise)?;
+        let height = rise - top.time_of_impact;
+        if height <= offset || self.compute_hit_info(top).is_wall {
+            return None;
+        }
+
+        Some(CharacterLedge {
+            handle,
+            edge: wall.witness1 + self.up * (top.witness1 - wall.witness1).dot(self.up),
+            wall_normal: wall.normal1,
+            top_normal: top.normal1,
+            height,
+            mantle_pos: Pose::from_translation(-self.up * top.time_of_impact) * above_ledge,
+        })
+    }
+
     /// For the given collisions between a character and its environment, this method will apply
     /// impulses to the rigid-bodies surrounding the character shape at the time of the collisions.
     /// Note that the impulse calculation is only approximate as it is not based on a global
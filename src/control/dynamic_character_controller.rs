@@ -0,0 +1,440 @@
+//! A character controller driving a dynamic rigid-body with forces.
+use crate::control::{CharacterAutostep, CharacterLength};
+use crate::dynamics::RigidBodyHandle;
+use crate::geometry::{ColliderHandle, Shape, ShapeCastHit};
+use crate::math::{Pose, Real, Vector};
+use crate::pipeline::{QueryFilterFlags, QueryPipeline, QueryPipelineMut};
+use na::RealField;
+use parry::query::details::ShapeCastOptions;
+
+/// A character controller driving a dynamic rigid-body, usually a capsule with locked rotations.
+///
+/// Unlike the [`KinematicCharacterController`](super::KinematicCharacterController), which
+/// computes a translation outside of the simulation, this controller only applies forces to
+/// a regular dynamic body. The character is therefore part of the constraints solver: it gets
+/// pushed by other bodies, thrown by explosions, held back by joints, and pushes dynamic
+/// bodies (including the one it stands on) with its actual mass.
+///
+/// Every step, [`Self::update`] shape-casts the body's collider down to find the ground, then:
+/// - Keeps the collider floating [`Self::ride_height`] above the ground with a damped spring.
+///   The spring compensates the gravity, absorbs bumps and steps lower than the ride height,
+///   and lets the character fall when it is launched up or walks off a ledge.
+/// - Accelerates the character toward the desired velocity, relative to the ground (so it is
+///   carried by moving platforms), by at most [`Self::max_acceleration`].
+/// - Stops supporting the character on slopes steeper than [`Self::min_slope_slide_angle`], so
+///   it slides down, and prevents it from walking up slopes steeper than
+///   [`Self::max_slope_climb_angle`].
+/// - Lifts the character onto steps taller than the ride height, if [`Self::autostep`] is set.
+///
+/// The body should have locked rotations, and its collider a low friction so that it doesn't
+/// stick to walls: the friction with the ground is handled by the controller.
+///
+/// # Example
+///
+/// ```
+/// # use rapier3d::prelude::*;
+/// # use rapier3d::control::DynamicCharacterController;
+/// # let mut world = PhysicsWorld::new();
+/// # world.insert_collider(ColliderBuilder::cuboid(10.0, 0.1, 10.0), None);
+/// let (body, _) = world.insert(
+///     RigidBodyBuilder::dynamic()
+///         .translation(Vector::new(0.0, 1.5, 0.0))
+///         .lock_rotations(),
+///     ColliderBuilder::capsule_y(0.5, 0.3).friction(0.0),
+/// );
+/// let mut controller = DynamicCharacterController::new(body);
+///
+/// // In your game loop:
+/// let desired_velocity = Vector::new(2.0, 0.0, 0.0);
+/// let queries = world.broad_phase.as_query_pipeline_mut(
+///     world.narrow_phase.query_dispatcher(),
+///     &mut world.bodies,
+///     &mut world.colliders,
+///     QueryFilter::default(),
+/// );
+/// let movement = controller.update(
+///     world.integration_parameters.dt,
+///     world.gravity,
+///     queries,
+///     desired_velocity,
+/// );
+/// if movement.grounded {
+///     // Allow jumping, etc.
+/// }
+/// world.step();
+/// ```
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug)]
+pub struct DynamicCharacterController {
+    /// Handle of the character's rigid-body. Its first non-sensor collider is the character shape.
+    pub body: RigidBodyHandle,
+    /// The direction that goes "up". Used to determine where the floor is, and the floor’s angle.
+    pub up: Vector,
+    /// The gap kept between the character's collider and the ground.
+    ///
+    /// Obstacles lower than this are walked over without any contact.
+    pub ride_height: CharacterLength,
+    /// How much further than the ride height the ground is still detected.
+    ///
+    /// The character is grounded (and fully controlled) while it is within this distance, for
+    /// example when walking down a slope or over a small gap.
+    pub ground_margin: CharacterLength,
+    /// The natural frequency (in Hz) of the spring holding the character at its ride height.
+    pub ride_frequency: Real,
+    /// The damping ratio of the spring holding the character at its ride height.
+    ///
+    /// `1.0` (critical damping) brings it back to the ride height without oscillating.
+    pub ride_damping_ratio: Real,
+    /// The maximum acceleration applied to reach the desired velocity on the ground.
+    ///
+    /// This also determines how fast the character recovers from being pushed.
+    pub max_acceleration: Real,
+    /// The maximum acceleration applied to reach the desired velocity while airborne.
+    ///
+    /// The character isn't slowed down in the air when the desired velocity is zero, so it
+    /// keeps the momentum of a jump or an explosion.
+    pub max_air_acceleration: Real,
+    /// The maximum angle (radians) between the floor’s normal and the `up` vector that the
+    /// character is able to climb.
+    pub max_slope_climb_angle: Real,
+    /// The minimum angle (radians) between the floor’s normal and the `up` vector before the
+    /// character stops being supported and starts to slide down.
+    pub min_slope_slide_angle: Real,
+    /// Should the character automatically step over obstacles taller than its ride height?
+    /// (disabled by default)
+    pub autostep: Option<CharacterAutostep>,
+    /// The force added to the body by the previous call to [`Self::update`].
+    applied_force: Vector,
+}
+
+/// The state of a character after a call to [`DynamicCharacterController::update`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct DynamicCharacterMovement {
+    /// Is the character on the ground, i.e., within the ride height plus the ground margin
+    /// from a non-vertical surface?
+    pub grounded: bool,
+    /// Is the character sliding down a slope due to slope angle being larger than
+    /// `min_slope_slide_angle`?
+    pub is_sliding_down_slope: bool,
+    /// The collider the character stands on, if it is grounded.
+    pub ground: Option<ColliderHandle>,
+    /// The normal of the ground at the point below the character, or zero if it isn't grounded.
+    pub ground_normal: Vector,
+    /// The distance between the character's collider and the ground, if it is grounded.
+    pub ground_distance: Real,
+    /// The height the character was lifted by to step onto an obstacle, or zero.
+    pub step_height: Real,
+}
+
+impl DynamicCharacterController {
+    /// A controller for the given dynamic body, with the default settings.
+    pub fn new(body: RigidBodyHandle) -> Self {
+        Self {
+            body,
+            up: Vector::Y,
+            ride_height: CharacterLength::Relative(0.15),
+            ground_margin: CharacterLength::Relative(0.1),
+            ride_frequency: 5.0,
+            ride_damping_ratio: 1.0,
+            max_acceleration: 40.0,
+            max_air_acceleration: 5.0,
+            max_slope_climb_angle: Real::frac_pi_4(),
+            min_slope_slide_angle: Real::frac_pi_4(),
+            autostep: None,
+            applied_force: Vector::ZERO,
+        }
+    }
+
+    /// Sets the forces moving the character toward `desired_velocity` during the next step.
+    ///
+    /// Call this once before each simulation step, with the world's gravity. The force
+    /// [added](crate::dynamics::RigidBody::add_force) to the body by the previous call is
+    /// replaced, and the other forces added to the body are left untouched. If the body's
+    /// forces are [reset](crate::dynamics::RigidBody::reset_forces), call
+    /// [`Self::forget_applied_force`] too. The component of `desired_velocity` along
+    /// [`Self::up`] is ignored: apply an impulse to the body to jump.
+    /// The character's own colliders and sensors are always excluded from the queries, which
+    /// replaces the filter's `exclude_rigid_body`.
+    ///
+    /// A sleeping character is left asleep while `desired_velocity` is zero.
+    #[profiling::function]
+    pub fn update(
+        &mut self,
+        dt: Real,
+        gravity: Vector,
+        mut queries: QueryPipelineMut,
+        desired_velocity: Vector,
+    ) -> DynamicCharacterMovement {
+        let mut result = DynamicCharacterMovement::default();
+        let Some(rb) = queries.bodies.get(self.body) else {
+            return result;
+        };
+        let Some(co) = rb
+            .colliders()
+            .iter()
+            .filter_map(|handle| queries.colliders.get(*handle))
+            .find(|co| !co.is_sensor())
+        else {
+            return result;
+        };
+        let shape = co.shared_shape().clone();
+        let shape_pos = *co.position();
+        queries.filter.exclude_rigid_body = Some(self.body);
+        queries.filter.flags |= QueryFilterFlags::EXCLUDE_SENSORS;
+
+        let extents = shape.compute_aabb(&shape_pos).extents();
+        let height = extents.dot(self.up.abs());
+        let width = (extents - self.up.abs() * height).length();
+        let ride_height = self.ride_height.eval(height);
+        let max_ground_distance = ride_height + self.ground_margin.eval(height);
+
+        // 1. Find the ground.
+        let ground = self
+            .cast(
+                &queries.as_ref(),
+                &*shape,
+                &shape_pos,
+                -self.up,
+                max_ground_distance,
+            )
+            .filter(|(_, hit)| hit.normal1.dot(self.up) >= 1.0e-3);
+        let mut supported = false;
+        let mut ground_vel = Vector::ZERO;
+        if let Some((handle, hit)) = ground {
+            result.grounded = true;
+            result.ground = Some(handle);
+            result.ground_normal = hit.normal1;
+            result.ground_distance = hit.time_of_impact;
+            result.is_sliding_down_slope =
+                self.slope_angle(hit.normal1) > self.min_slope_slide_angle;
+            supported = !result.is_sliding_down_slope;
+            ground_vel = queries
+                .colliders
+                .get(handle)
+                .and_then(|co| co.parent())
+                .and_then(|parent| queries.bodies.get(parent))
+                .map(|ground_rb| ground_rb.velocity_at_point(hit.witness1))
+                .unwrap_or_default();
+        }
+
+        let rb = &queries.bodies[self.body];
+        let wants_to_move = desired_velocity != Vector::ZERO;
+        if rb.is_sleeping() && !wants_to_move {
+            return result;
+        }
+
+        // 2. Compute the horizontal target velocity.
+        let mut target = desired_velocity - self.up * desired_velocity.dot(self.up);
+        if let Some((_, hit)) = ground
+            && self.slope_angle(hit.normal1) > self.max_slope_climb_angle
+        {
+            // The horizontal part of the normal of a slope points downhill: remove the
+            // uphill part of the target.
+            let downhill = (hit.normal1 - self.up * hit.normal1.dot(self.up)).normalize_or_zero();
+            target -= downhill * target.dot(downhill).min(0.0);
+        }
+
+        // 3. Step onto obstacles.
+        let mut step = Vector::ZERO;
+        if supported
+            && let Some(autostep) = self.autostep
+            && let Some(dir) = target.try_normalize()
+            && let Some(step_up) = self.step_up(
+                queries.as_ref(),
+                &*shape,
+                &shape_pos,
+                dir,
+                ride_height,
+                [width, height],
+                &autostep,
+            )
+        {
+            step = step_up;
+            result.step_height = step_up.dot(self.up);
+        }
+
+        let mass = rb.mass();
+        let linvel = rb.linvel();
+        let gravity_along_up = -(gravity * rb.gravity_scale()).dot(self.up);
+        let rel_vel = linvel - ground_vel;
+
+        // 4. The vertical force holding the character at its ride height.
+        let mut vertical_force = 0.0;
+        if supported && let Some((_, hit)) = ground {
+            let rel_vel_up = rel_vel.dot(self.up);
+            let accel = if step != Vector::ZERO {
+                // The ground distance is outdated after the step: just stop falling.
+                gravity_along_up - rel_vel_up.min(0.0) / dt
+            } else {
+                // Implicit damped spring, stable for any frequency.
+                let omega = Real::two_pi() * self.ride_frequency;
+                let stiffness = omega * omega;
+                let damping = 2.0 * self.ride_damping_ratio * omega;
+                let error = ride_height - hit.time_of_impact;
+                gravity_along_up
+                    + (stiffness * error - damping * rel_vel_up)
+                        / (1.0 + damping * dt + stiffness * dt * dt)
+            };
+            // Never pull the character down: it must be free to jump or be thrown away.
+            vertical_force = accel.max(0.0) * mass;
+        }
+
+        // 5. The horizontal force toward the target velocity.
+        let max_accel = if supported {
+            target += ground_vel - self.up * ground_vel.dot(self.up);
+            self.max_acceleration
+        } else if target != Vector::ZERO {
+            self.max_air_acceleration
+        } else {
+            0.0
+        };
+        let current = linvel - self.up * linvel.dot(self.up);
+        let mut delta_vel = target - current;
+        let max_delta_vel = max_accel * dt;
+        if delta_vel.length() > max_delta_vel {
+            delta_vel = delta_vel.normalize_or_zero() * max_delta_vel;
+        }
+        let force = self.up * vertical_force + delta_vel * (mass * crate::utils::inv(dt));
+
+        let rb = &mut queries.bodies[self.body];
+        if step != Vector::ZERO {
+            rb.set_translation(rb.translation() + step, true);
+        }
+        // Forces are integrated along with the gravity at every substep: an impulse applied
+        // before the step would leave the velocity short of the gravity of the substeps.
+        // Only the force added by the previous call is replaced, the other forces added to the
+        // body (by the user or other controllers) are kept.
+        if force != self.applied_force && rb.is_dynamic() {
+            rb.add_force(force - self.applied_force, false);
+            self.applied_force = force;
+        }
+        if force != Vector::ZERO {
+            rb.wake_up(true);
+        }
+
+        // 6. Push the ground back.
+        if supported
+            && let Some((handle, hit)) = ground
+            && let Some(ground_rb) = queries
+                .colliders
+                .get(handle)
+                .and_then(|co| co.parent())
+                .and_then(|parent| queries.bodies.get_mut(parent))
+            && ground_rb.is_dynamic()
+        {
+            ground_rb.apply_impulse_at_point(-force * dt, hit.witness1, true);
+        }
+
+        result
+    }
+
+    /// The force added to the body by the previous call to [`Self::update`].
+    pub fn applied_force(&self) -> Vector {
+        self.applied_force
+    }
+
+    /// Forgets the force added to the body by the previous call to [`Self::update`], after
+    /// the body's forces were [reset](crate::dynamics::RigidBody::reset_forces).
+    pub fn forget_applied_force(&mut self) {
+        self.applied_force = Vector::ZERO;
+    }
+
+    #[cfg(feature = "dim2")]
+    fn slope_angle(&self, normal: Vector) -> Real {
+        self.up.angle_to(normal).abs()
+    }
+
+    #[cfg(feature = "dim3")]
+    fn slope_angle(&self, normal: Vector) -> Real {
+        self.up.angle_between(normal)
+    }
+
+    fn cast(
+        &self,
+        queries: &QueryPipeline,
+        shape: &dyn Shape,
+        shape_pos: &Pose,
+        dir: Vector,
+        max_dist: Real,
+    ) -> Option<(ColliderHandle, ShapeCastHit)> {
+        queries.cast_shape(
+            shape_pos,
+            dir,
+            shape,
+            ShapeCastOptions {
+                target_distance: 0.0,
+                stop_at_penetration: false,
+                max_time_of_impact: max_dist,
+                compute_impact_geometry_on_penetration: true,
+            },
+        )
+    }
+
+    /// The translation moving the character onto the obstacle in front of it, floating at its
+    /// ride height, if there is room to stand on it.
+    ///
+    /// As with the kinematic character controller, the character is also moved forward by the
+    /// step's minimum width so that it doesn't fall back against the step's edge.
+    #[allow(clippy::too_many_arguments)]
+    fn step_up(
+        &self,
+        mut queries: QueryPipeline,
+        shape: &dyn Shape,
+        shape_pos: &Pose,
+        dir: Vector,
+        ride_height: Real,
+        [width, height]: [Real; 2],
+        autostep: &CharacterAutostep,
+    ) -> Option<Vector> {
+        let max_height = autostep.max_height.eval(height);
+        let min_width = autostep.min_width.eval(width);
+
+        // Only step onto walls: lower slopes are handled by the ride spring.
+        let (wall_handle, wall) = self.cast(&queries, shape, shape_pos, dir, min_width)?;
+        if self.slope_angle(wall.normal1) < self.max_slope_climb_angle {
+            return None;
+        }
+        if !autostep.include_dynamic_bodies {
+            let wall_body = queries
+                .colliders
+                .get(wall_handle)
+                .and_then(|co| co.parent())
+                .and_then(|parent| queries.bodies.get(parent));
+            if wall_body.is_some_and(|rb| rb.is_dynamic()) {
+                return None;
+            }
+            queries.filter.flags |= QueryFilterFlags::EXCLUDE_DYNAMIC;
+        }
+
+        // There must be room above the character, then above the step.
+        if self
+            .cast(&queries, shape, shape_pos, self.up, max_height)
+            .is_some()
+        {
+            return None;
+        }
+        let raised_pos = Pose::from_translation(self.up * max_height) * *shape_pos;
+        if self
+            .cast(&queries, shape, &raised_pos, dir, min_width)
+            .is_some()
+        {
+            return None;
+        }
+
+        // Find the top of the step, which must not be too steep to stand on.
+        let above_step = Pose::from_translation(dir * min_width) * raised_pos;
+        let (_, top) = self.cast(
+            &queries,
+            shape,
+            &above_step,
+            -self.up,
+            max_height + ride_height,
+        )?;
+        if self.slope_angle(top.normal1) > self.max_slope_climb_angle {
+            return None;
+        }
+
+        let lift = ride_height + max_height - top.time_of_impact;
+        (lift > 0.0 && lift <= max_height).then_some(self.up * lift + dir * min_width)
+    }
+}
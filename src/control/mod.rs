@@ -1,20 +1,52 @@
 //! Utilities for controlling the trajectories of objects in a non-physical way.
 
+#[cfg(feature = "alloc")]
+pub use self::buoyancy::{
+    BuoyancyController, FluidRegion, FluidVolume, SubmergedPart, submerged_part,
+};
 #[cfg(feature = "alloc")]
 pub use self::character_controller::{
-    CharacterAutostep, CharacterCollision, CharacterLength, EffectiveCharacterMovement,
-    KinematicCharacterController,
+    CharacterAutostep, CharacterCollision, CharacterLedge, CharacterLength, CharacterShapeAnchor,
+    EffectiveCharacterMovement, KinematicCharacterController,
+};
+#[cfg(feature = "alloc")]
+pub use self::dynamic_character_controller::{
+    DynamicCharacterController, DynamicCharacterMovement,
 };
 #[cfg(feature = "alloc")]
-pub use self::pid_controller::{PdController, PdErrors, PidController};
+pub use self::operational_space_controller::{OperationalSpaceController, OperationalSpaceTarget};
+#[cfg(feature = "alloc")]
+pub use self::pid_controller::{
+    JointPidController, MultibodyPidController, PdController, PdErrors, PidController,
+};
 
 #[cfg(all(feature = "dim3", feature = "alloc"))]
-pub use self::ray_cast_vehicle_controller::{DynamicRayCastVehicleController, Wheel, WheelTuning};
+pub use self::ray_cast_vehicle_controller::{
+    DynamicRayCastVehicleController, MagicFormula, TireModel, Wheel, WheelCastShape, WheelTuning,
+};
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub use self::tracked_vehicle_controller::{
+    Track, TrackContact, TrackTuning, TrackedVehicleController,
+};
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+pub use self::vehicle_drivetrain::{
+    Differential, DrivenAxle, Drivetrain, Gearbox, GearboxMode, VehicleEngine,
+};
 
+#[cfg(feature = "alloc")]
+mod buoyancy;
 #[cfg(feature = "alloc")]
 mod character_controller;
+#[cfg(feature = "alloc")]
+mod dynamic_character_controller;
 
+#[cfg(feature = "alloc")]
+mod operational_space_controller;
 #[cfg(feature = "alloc")]
 mod pid_controller;
 #[cfg(all(feature = "dim3", feature = "alloc"))]
 mod ray_cast_vehicle_controller;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+mod tracked_vehicle_controller;
+#[cfg(all(feature = "dim3", feature = "alloc"))]
+mod vehicle_drivetrain;
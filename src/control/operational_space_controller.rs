@@ -0,0 +1,234 @@
+use super::pid_controller::wake_up_links;
+use crate::alloc_prelude::*;
+use crate::dynamics::{JointAxesMask, Multibody, RigidBodySet, RigidBodyVelocity};
+use crate::math::{AngVector, DIM, DVector, Pose, Real, SPATIAL_DIM, Vector};
+use crate::utils::{CrossProductMatrix, vect_to_na};
+use na::{DMatrix, SMatrix, SVector};
+
+/// An operational-space (task-space) controller driving a multibody link toward a target pose.
+///
+/// The controller computes the joint torques `τ = Jᵀ·F + C(q, q̇)·q̇ + g(q)` making a frame
+/// attached to the link behave like a mass-spring-damper around the target pose, where
+/// `F = Λ·(kp·e + kd·ė) + F_ff`, `J` is the jacobian of that frame, `Λ = (J·M⁻¹·Jᵀ)⁻¹` is the
+/// task-space inertia derived from the multibody’s [mass matrix](Multibody::mass_matrix) `M`, and
+/// `F_ff` a feed-forward wrench. The velocity-dependent and gravity forces `C(q, q̇)·q̇ + g(q)`
+/// are compensated with the multibody’s [inverse dynamics](Multibody::inverse_dynamics), so
+/// the link follows its target under gravity too.
+///
+/// If the multibody is redundant with respect to the task, the joint motions that don’t move the
+/// controlled frame are damped, and a secondary posture objective can be given. Their joint
+/// torques `M·q̈₀` are projected onto the null-space of the task, so they don’t disturb the
+/// end-effector.
+///
+/// The torques can either be read with [`Self::joint_torques`], or applied to the multibody for
+/// the next simulation step with [`Self::apply`]. There is no joint motor output: the motors of
+/// a multibody are solved joint by joint, with their own stiffness and force limit, which would
+/// undo the coupling between the joints that the task-space inertia accounts for.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OperationalSpaceController {
+    /// The controlled frame, relative to the link’s rigid-body.
+    pub local_frame: Pose,
+    /// The stiffness, in `1/s²`, pulling the frame’s origin toward the target position.
+    pub lin_kp: Real,
+    /// The damping, in `1/s`, pulling the frame’s linear velocity toward the target velocity.
+    pub lin_kd: Real,
+    /// The stiffness, in `1/s²`, pulling the frame’s orientation toward the target orientation.
+    pub ang_kp: Real,
+    /// The damping, in `1/s`, pulling the frame’s angular velocity toward the target velocity.
+    pub ang_kd: Real,
+    /// The task-space axes controlled by this controller.
+    ///
+    /// The other axes are left free, and can be used by the posture objective.
+    pub constrained_axes: JointAxesMask,
+    /// The stiffness, in `1/s²`, pulling the generalized coordinates toward the target posture.
+    pub posture_kp: Real,
+    /// The damping, in `1/s`, of the generalized velocities in the null-space of the task, with
+    /// or without a posture objective.
+    pub posture_kd: Real,
+    /// A damping coefficient regularizing the task-space inertia near singular configurations.
+    pub damping: Real,
+    /// The maximum magnitude of each joint torque.
+    pub max_torque: Real,
+}
+
+impl Default for OperationalSpaceController {
+    fn default() -> Self {
+        Self {
+            local_frame: Pose::IDENTITY,
+            lin_kp: 100.0,
+            lin_kd: 20.0,
+            ang_kp: 100.0,
+            ang_kd: 20.0,
+            constrained_axes: JointAxesMask::all(),
+            posture_kp: 25.0,
+            posture_kd: 10.0,
+            damping: 1.0e-3,
+            max_torque: Real::MAX,
+        }
+    }
+}
+
+/// The target of an [`OperationalSpaceController`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationalSpaceTarget {
+    /// The target world-space pose of the controlled frame.
+    pub pose: Pose,
+    /// The target world-space velocity of the controlled frame’s origin.
+    pub velocity: RigidBodyVelocity<Real>,
+    /// The feed-forward force applied by the controlled frame’s origin.
+    pub force: Vector,
+    /// The feed-forward torque applied by the controlled frame.
+    pub torque: AngVector,
+    /// The target generalized coordinates of the secondary posture objective, if any.
+    ///
+    /// Its length must match the multibody’s number of degrees of freedom.
+    pub posture: Option<DVector>,
+}
+
+impl OperationalSpaceTarget {
+    /// A target at rest at the given pose, without any feed-forward wrench or posture objective.
+    pub fn new(pose: Pose) -> Self {
+        Self {
+            pose,
+            velocity: RigidBodyVelocity::zero(),
+            force: Vector::ZERO,
+            torque: Default::default(),
+            posture: None,
+        }
+    }
+}
+
+impl OperationalSpaceController {
+    /// Computes the joint torques driving the link `link_id` of `multibody` toward `target`,
+    /// under the given `gravity`.
+    ///
+    /// The returned vector follows the ordering of the multibody’s
+    /// [generalized velocities](Multibody::generalized_velocity).
+    pub fn joint_torques(
+        &self,
+        bodies: &RigidBodySet,
+        gravity: Vector,
+        multibody: &mut Multibody,
+        link_id: usize,
+        target: &OperationalSpaceTarget,
+    ) -> DVector {
+        self.solve(bodies, gravity, multibody, link_id, target).0
+    }
+
+    /// Computes the joint torques driving the link `link_id` of `multibody` toward `target`
+    /// under the given `gravity`, and applies them for a step of length `dt`.
+    ///
+    /// The torques are applied by adding `M⁻¹·τ·dt` to the multibody’s generalized velocities,
+    /// so this must be called before each step. The links are woken up, and the torques are
+    /// returned.
+    pub fn apply(
+        &self,
+        dt: Real,
+        bodies: &mut RigidBodySet,
+        gravity: Vector,
+        multibody: &mut Multibody,
+        link_id: usize,
+        target: &OperationalSpaceTarget,
+    ) -> DVector {
+        let (torques, mass) = self.solve(bodies, gravity, multibody, link_id, target);
+        let mut vel_change = &torques * dt;
+        if mass.lu().solve_mut(&mut vel_change) {
+            let mut velocities = multibody.generalized_velocity_mut();
+            velocities += vel_change;
+        }
+        wake_up_links(bodies, multibody);
+        torques
+    }
+
+    fn solve(
+        &self,
+        bodies: &RigidBodySet,
+        gravity: Vector,
+        multibody: &mut Multibody,
+        link_id: usize,
+        target: &OperationalSpaceTarget,
+    ) -> (DVector, DMatrix<Real>) {
+        let ndofs = multibody.ndofs();
+        // Also refreshes the link poses and jacobians read below.
+        let bias = multibody.inverse_dynamics(bodies, gravity, &vec![0.0; ndofs]);
+        let mass = multibody.mass_matrix(bodies);
+        let Some(link) = multibody.link(link_id) else {
+            return (DVector::zeros(ndofs), mass);
+        };
+        let Some(inv_mass) = mass.clone().try_inverse() else {
+            return (DVector::zeros(ndofs), mass);
+        };
+
+        // Move the link’s jacobian from its center of mass to the controlled frame.
+        let pose = *link.local_to_world() * self.local_frame;
+        let com = link.local_to_world() * bodies[link.rigid_body_handle()].local_center_of_mass();
+        let mut jacobian = multibody.body_jacobian(link_id).clone();
+        {
+            let (mut jacobian_v, jacobian_w) =
+                jacobian.rows_range_pair_mut(0..DIM, DIM..SPATIAL_DIM);
+            let shift_tr = vect_to_na(pose.translation - com).gcross_matrix_tr();
+            jacobian_v.gemm(1.0, &shift_tr, &jacobian_w, 1.0);
+        }
+
+        let delta_lin = target.pose.translation - pose.translation;
+        #[cfg(feature = "dim2")]
+        let delta_ang = (target.pose.rotation * pose.rotation.inverse()).angle();
+        #[cfg(feature = "dim3")]
+        let delta_ang = (target.pose.rotation * pose.rotation.inverse()).to_scaled_axis();
+
+        let velocities = multibody.generalized_velocity();
+        let pos_error = spatial(delta_lin, delta_ang);
+        let vel_error =
+            spatial(target.velocity.linvel, target.velocity.angvel) - &jacobian * velocities;
+        let mut acceleration = SVector::<Real, SPATIAL_DIM>::zeros();
+        let mut wrench = spatial(target.force, target.torque);
+
+        for i in 0..SPATIAL_DIM {
+            if (self.constrained_axes.bits() >> i) & 1 == 0 {
+                jacobian.row_mut(i).fill(0.0);
+                wrench[i] = 0.0;
+            } else if i < DIM {
+                acceleration[i] = self.lin_kp * pos_error[i] + self.lin_kd * vel_error[i];
+            } else {
+                acceleration[i] = self.ang_kp * pos_error[i] + self.ang_kd * vel_error[i];
+            }
+        }
+
+        // Task-space inertia Λ = (J·M⁻¹·Jᵀ)⁻¹.
+        let jacobian_inv_mass = &jacobian * &inv_mass;
+        let identity = SMatrix::<Real, SPATIAL_DIM, SPATIAL_DIM>::identity();
+        let inv_lambda =
+            &jacobian_inv_mass * jacobian.transpose() + identity * (self.damping * self.damping);
+        let lambda = inv_lambda.pseudo_inverse(1.0e-6).unwrap_or(identity);
+
+        let mut torques = jacobian.tr_mul(&(lambda * acceleration + wrench));
+
+        // The posture damping applies even without a target posture: otherwise, the joint motions
+        // that don’t move the controlled frame would never be damped.
+        let mut posture_acceleration = velocities * -self.posture_kd;
+        if let Some(posture) = &target.posture {
+            assert_eq!(posture.len(), ndofs);
+            let coords = multibody.generalized_coordinates();
+            posture_acceleration += (posture - coords) * self.posture_kp;
+        }
+        let posture_torques = &mass * posture_acceleration;
+        // Null-space projection (I − Jᵀ·Λ·J·M⁻¹)·τ₀: removes the part of the posture
+        // torques that would accelerate the controlled frame.
+        torques +=
+            &posture_torques - jacobian.tr_mul(&(lambda * (&jacobian_inv_mass * &posture_torques)));
+        torques += bias;
+
+        torques.apply(|t| *t = t.clamp(-self.max_torque, self.max_torque));
+        (torques, mass)
+    }
+}
+
+fn spatial(linear: Vector, angular: AngVector) -> SVector<Real, SPATIAL_DIM> {
+    #[cfg(feature = "dim2")]
+    return na::vector![linear.x, linear.y, angular];
+    #[cfg(feature = "dim3")]
+    return na::vector![
+        linear.x, linear.y, linear.z, angular.x, angular.y, angular.z
+    ];
+}
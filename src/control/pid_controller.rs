@@ -1,5 +1,9 @@
-use crate::dynamics::{AxesMask, RigidBody, RigidBodyPosition, RigidBodyVelocity};
-use crate::math::{AngVector, Pose, Real, Rotation, Vector};
+use crate::alloc_prelude::*;
+use crate::dynamics::{
+    AxesMask, ImpulseJoint, JointAxis, Multibody, RigidBody, RigidBodyPosition, RigidBodySet,
+    RigidBodyVelocity,
+};
+use crate::math::{AngVector, DVector, Pose, Real, Rotation, Vector};
 
 /// A Proportional-Derivative (PD) controller.
 ///
@@ -420,3 +424,235 @@ impl PidController {
         RigidBodyVelocity { linvel, angvel }
     }
 }
+
+/// A Proportional-Integral-Derivative (PID) controller acting on a single degree of freedom.
+///
+/// This is useful for driving a joint so its coordinate matches a target: a
+/// [`MultibodyJoint`](crate::dynamics::MultibodyJoint) generalized coordinate (see
+/// [`Self::drive_multibody_joint`] and [`MultibodyPidController`] to drive all of them at once),
+/// or an [`ImpulseJoint`] axis (see [`Self::drive_impulse_joint`]).
+///
+/// Like [`PidController`], its output is a velocity change if `kp` is proportional to the inverse
+/// of the simulation step. It also implements:
+/// - Anti-windup: the integral term is bounded by [`Self::max_integral`], and doesn’t accumulate
+///   while the output is saturated in the direction of the error.
+/// - Derivative filtering: the velocity error can be smoothed by a first-order low-pass filter
+///   with the time constant [`Self::derivative_filter`], to reduce the effect of noisy velocities.
+/// - Output saturation: the output magnitude is limited to [`Self::max_output`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct JointPidController {
+    /// The Proportional gain applied to the instantaneous position error.
+    ///
+    /// This is usually set to a multiple of the inverse of simulation step time
+    /// (e.g. `60` if the delta-time is `1.0 / 60.0`).
+    pub kp: Real,
+    /// The Integral gain applied to the position error accumulated through time.
+    pub ki: Real,
+    /// The Derivative gain applied to the (filtered) velocity error.
+    ///
+    /// This is usually set to a value in `[0.0, 1.0]` where `0.0` implies no damping
+    /// (no correction of velocity errors) and `1.0` implies complete damping (velocity errors
+    /// are corrected in a single simulation step).
+    pub kd: Real,
+    /// The maximum magnitude of the Integral part’s contribution to the output.
+    pub max_integral: Real,
+    /// The time constant, in seconds, of the low-pass filter applied to the velocity error.
+    ///
+    /// Set to `0.0` to disable filtering.
+    pub derivative_filter: Real,
+    /// The maximum magnitude of the output.
+    pub max_output: Real,
+    /// The position error accumulated through time for the Integral part of the controller.
+    pub integral: Real,
+    /// The velocity error smoothed by the derivative filter.
+    pub filtered_derivative: Real,
+}
+
+impl Default for JointPidController {
+    fn default() -> Self {
+        Self::new(60.0, 1.0, 0.8)
+    }
+}
+
+impl JointPidController {
+    /// Initializes the PID controller with the given gains, and without any integral bound,
+    /// derivative filtering or output saturation.
+    pub fn new(kp: Real, ki: Real, kd: Real) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            max_integral: Real::MAX,
+            derivative_filter: 0.0,
+            max_output: Real::MAX,
+            integral: 0.0,
+            filtered_derivative: 0.0,
+        }
+    }
+
+    /// Resets to zero the accumulated position error and the filtered velocity error.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.filtered_derivative = 0.0;
+    }
+
+    /// Calculates the correction from the given position and velocity errors.
+    ///
+    /// The unit of the returned value depends on the gain values. In general, `kp` is proportional
+    /// to the inverse of the simulation step so the returned value is a velocity change.
+    pub fn correction(&mut self, dt: Real, pos_error: Real, vel_error: Real) -> Real {
+        let alpha = if self.derivative_filter > 0.0 {
+            dt / (self.derivative_filter + dt)
+        } else {
+            1.0
+        };
+        self.filtered_derivative += (vel_error - self.filtered_derivative) * alpha;
+
+        let pd = self.kp * pos_error + self.kd * self.filtered_derivative;
+        let max_integral = self.max_integral * crate::utils::inv(self.ki.abs());
+        let integral = (self.integral + pos_error * dt).clamp(-max_integral, max_integral);
+        let unsaturated = pd + self.ki * integral;
+
+        // Don’t wind up the integral while the output is saturated by the error.
+        if unsaturated.abs() <= self.max_output || unsaturated * pos_error <= 0.0 {
+            self.integral = integral;
+        }
+
+        (pd + self.ki * self.integral).clamp(-self.max_output, self.max_output)
+    }
+
+    /// Drives the generalized coordinate along `axis` of the joint of the multibody link
+    /// `link_id` toward `target_pos` and `target_vel`.
+    ///
+    /// The correction is the velocity change of the driven coordinate: it is applied as the
+    /// generalized force `I·correction / dt` during the step, `I` being the coordinate’s effective
+    /// inertia, so the rest of the multibody reacts to it like to a joint torque. The links are
+    /// woken up, and the correction is returned. Returns `0.0` and does nothing if the link
+    /// doesn’t exist or if `axis` is locked.
+    pub fn drive_multibody_joint(
+        &mut self,
+        dt: Real,
+        bodies: &mut RigidBodySet,
+        multibody: &mut Multibody,
+        link_id: usize,
+        axis: JointAxis,
+        target_pos: Real,
+        target_vel: Real,
+    ) -> Real {
+        let Some(link) = multibody.link(link_id) else {
+            return 0.0;
+        };
+        let locked = link.joint.data.locked_axes.bits();
+        let axis = axis as usize;
+        if (locked >> axis) & 1 != 0 {
+            return 0.0;
+        }
+
+        let dof = link.assembly_id() + (!locked & ((1 << axis) - 1)).count_ones() as usize;
+        let pos = link.joint.coords()[axis];
+        let vel = multibody.generalized_velocity()[dof];
+        let correction = self.correction(dt, target_pos - pos, target_vel - vel);
+        let mut velocity_changes = DVector::zeros(multibody.ndofs());
+        velocity_changes[dof] = correction;
+        multibody.apply_velocity_changes_as_impulses(&velocity_changes);
+        wake_up_links(bodies, multibody);
+        correction
+    }
+
+    /// Drives the impulse joint along `axis` toward `target_pos` and `target_vel`.
+    ///
+    /// The joint’s position and velocity along `axis` are measured with
+    /// [`ImpulseJoint::axis_position`] and [`ImpulseJoint::axis_velocity`]. The correction is then
+    /// applied by setting the joint’s motor on `axis` to the corrected target velocity, so the
+    /// motor’s `max_force` still limits the force it applies. The correction is returned.
+    pub fn drive_impulse_joint(
+        &mut self,
+        dt: Real,
+        bodies: &RigidBodySet,
+        joint: &mut ImpulseJoint,
+        axis: JointAxis,
+        target_pos: Real,
+        target_vel: Real,
+    ) -> Real {
+        let pos = joint.axis_position(bodies, axis);
+        let vel = joint.axis_velocity(bodies, axis);
+        let correction = self.correction(dt, target_pos - pos, target_vel - vel);
+        joint
+            .data
+            .set_motor_velocity(axis, vel + correction, IMPULSE_JOINT_MOTOR_DAMPING);
+        correction
+    }
+}
+
+/// The motor damping used by [`JointPidController::drive_impulse_joint`], high enough for the
+/// motor to reach its target velocity within a step.
+const IMPULSE_JOINT_MOTOR_DAMPING: Real = 1.0e4;
+
+/// Wakes up the links of a multibody driven by a controller, which would otherwise fall asleep
+/// while the controller keeps changing its velocity.
+pub(super) fn wake_up_links(bodies: &mut RigidBodySet, multibody: &Multibody) {
+    for link in multibody.links() {
+        if let Some(rb) = bodies.get_mut(link.rigid_body_handle()) {
+            rb.wake_up(true);
+        }
+    }
+}
+
+/// A batch of [`JointPidController`]s driving every degree of freedom of a [`Multibody`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MultibodyPidController {
+    /// The controllers of each degree of freedom, in the same order as the multibody’s
+    /// generalized coordinates.
+    pub dofs: Vec<JointPidController>,
+}
+
+impl MultibodyPidController {
+    /// Initializes the controllers of `ndofs` degrees of freedom with copies of `controller`.
+    pub fn new(ndofs: usize, controller: JointPidController) -> Self {
+        Self {
+            dofs: vec![controller; ndofs],
+        }
+    }
+
+    /// Resets the accumulated errors of every controller.
+    pub fn reset(&mut self) {
+        self.dofs.iter_mut().for_each(JointPidController::reset);
+    }
+
+    /// Drives every degree of freedom of `multibody` toward the given targets.
+    ///
+    /// The targets are given in the same order as the multibody’s
+    /// [generalized coordinates](Multibody::generalized_coordinates). The corrections are
+    /// applied like with [`JointPidController::drive_multibody_joint`]: as generalized forces
+    /// scaled by the effective inertia of each coordinate, during the step.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of controllers or targets doesn’t match the multibody’s number of
+    /// degrees of freedom.
+    pub fn update(
+        &mut self,
+        dt: Real,
+        bodies: &mut RigidBodySet,
+        multibody: &mut Multibody,
+        target_positions: &[Real],
+        target_velocities: &[Real],
+    ) {
+        let ndofs = multibody.ndofs();
+        assert_eq!(self.dofs.len(), ndofs);
+        assert_eq!(target_positions.len(), ndofs);
+        assert_eq!(target_velocities.len(), ndofs);
+
+        let positions = multibody.generalized_coordinates();
+        let velocities = multibody.generalized_velocity();
+        let velocity_changes = DVector::from_fn(ndofs, |i, _| {
+            let pos_error = target_positions[i] - positions[i];
+            let vel_error = target_velocities[i] - velocities[i];
+            self.dofs[i].correction(dt, pos_error, vel_error)
+        });
+        multibody.apply_velocity_changes_as_impulses(&velocity_changes);
+        wake_up_links(bodies, multibody);
+    }
+}
@@ -4,12 +4,17 @@ use crate::alloc_prelude::*;
 #[cfg(not(feature = "std"))]
 use simba::scalar::ComplexField;
 
-use crate::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
-use crate::geometry::{ColliderHandle, ColliderSet, Ray};
-use crate::math::{Real, Vector, VectorExt, rotation_from_angle};
+use crate::control::Drivetrain;
+use crate::dynamics::{JointAxis, RigidBody, RigidBodyHandle, RigidBodySet, SphericalJointBuilder};
+use crate::geometry::{Ball, ColliderHandle, ColliderSet, Cylinder, Ray, Shape};
+use crate::math::{Matrix, Pose, Real, Rotation, Vector, VectorExt, rotation_from_angle};
 use crate::pipeline::QueryPipeline;
 use crate::prelude::QueryPipelineMut;
 use crate::utils::{CrossProduct, DotProduct};
+use parry::query::details::ShapeCastOptions;
+
+/// Below this speed, the slip ratio is relative to this speed instead of the wheel’s speed.
+const MIN_SLIP_SPEED: Real = 0.1;
 
 /// A character controller to simulate vehicles using ray-casting for the wheels.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -27,6 +32,11 @@ pub struct DynamicRayCastVehicleController {
     pub index_up_axis: usize,
     /// The chassis’ local _forward_ direction (`0 = x, 1 = y, 2 = z`)
     pub index_forward_axis: usize,
+    /// The engine, gearbox and differentials driving the wheels, if any.
+    ///
+    /// When set, it overwrites every wheel’s [`Wheel::engine_force`] and [`Wheel::brake`] at
+    /// each [`Self::update_vehicle`].
+    pub drivetrain: Option<Drivetrain>,
 }
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -54,6 +64,15 @@ pub struct WheelTuning {
     pub friction_slip: Real,
     /// The maximum force applied by the suspension.
     pub max_suspension_force: Real,
+    /// How the wheel detects the ground.
+    pub cast_shape: WheelCastShape,
+    /// How the tire’s friction forces are computed.
+    pub tire_model: TireModel,
+    /// The wheel’s angular inertia around its axle.
+    ///
+    /// Only used when the wheel’s spin is simulated: with [`TireModel::MagicFormula`], or when
+    /// the vehicle has a [`DynamicRayCastVehicleController::drivetrain`].
+    pub wheel_inertia: Real,
 }
 
 impl Default for WheelTuning {
@@ -66,10 +85,105 @@ impl Default for WheelTuning {
             side_friction_stiffness: 1.0,
             friction_slip: 10.5,
             max_suspension_force: 6000.0,
+            cast_shape: WheelCastShape::Ray,
+            tire_model: TireModel::FrictionSlip,
+            wheel_inertia: 1.0,
         }
     }
 }
 
+/// The geometry swept along a wheel’s suspension to detect the ground.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum WheelCastShape {
+    /// A single ray through the wheel’s center.
+    ///
+    /// This is the cheapest option, but the wheel can fall into cracks narrower than its
+    /// width and snag on small steps the ray doesn’t see coming.
+    #[default]
+    Ray,
+    /// A ball with the wheel’s radius.
+    Ball,
+    /// A cylinder with the wheel’s radius, oriented along its axle.
+    Cylinder {
+        /// Half the width of the tire.
+        half_width: Real,
+    },
+}
+
+/// The model computing the friction forces between a tire and the ground.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum TireModel {
+    /// Bullet’s model: the impulses cancelling the wheel’s sliding are clamped to
+    /// [`Wheel::friction_slip`] times the suspension force.
+    ///
+    /// [`Wheel::engine_force`] directly pushes the chassis, and the wheel’s spin just follows
+    /// the ground. With a [`DynamicRayCastVehicleController::drivetrain`], the spin is instead
+    /// integrated from the drive and brake torques and the friction impulse, and the grip left
+    /// within the friction budget drags it toward the ground speed: the wheel only spins up or
+    /// locks while sliding.
+    #[default]
+    FrictionSlip,
+    /// Pacejka’s “magic formula”: the friction forces are functions of the wheel’s slip ratio
+    /// and slip angle, scaled by the load on the wheel, and limited by a friction ellipse.
+    ///
+    /// The wheel’s spin is simulated: [`Wheel::engine_force`] spins the wheel up (it is the
+    /// drive torque divided by the wheel’s radius), [`Wheel::brake`] slows it down, and the
+    /// tire forces pull the wheel’s spin and the chassis velocity toward each other. This
+    /// lets wheels spin, lock, and drift.
+    MagicFormula {
+        /// The curve giving the traction/braking friction coefficient from the slip ratio.
+        longitudinal: MagicFormula,
+        /// The curve giving the cornering friction coefficient from the slip angle (in
+        /// radians).
+        lateral: MagicFormula,
+    },
+}
+
+impl TireModel {
+    /// The magic formula tire model with coefficients typical of a road tire on dry asphalt.
+    pub fn magic_formula() -> Self {
+        Self::MagicFormula {
+            longitudinal: MagicFormula {
+                stiffness: 10.0,
+                shape: 1.9,
+                peak: 1.0,
+                curvature: 0.97,
+            },
+            lateral: MagicFormula {
+                stiffness: 10.0,
+                shape: 1.3,
+                peak: 1.0,
+                curvature: 0.97,
+            },
+        }
+    }
+}
+
+/// The coefficients of Pacejka’s “magic formula” `D sin(C atan(Bx - E (Bx - atan(Bx))))`,
+/// giving a friction coefficient from a slip ratio or a slip angle `x`.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MagicFormula {
+    /// The stiffness factor `B`: the larger, the steeper the curve near zero slip.
+    pub stiffness: Real,
+    /// The shape factor `C`, controlling how much grip is lost past the peak.
+    pub shape: Real,
+    /// The peak factor `D`: the maximum friction coefficient.
+    pub peak: Real,
+    /// The curvature factor `E`, controlling the slip at which the peak is reached.
+    pub curvature: Real,
+}
+
+impl MagicFormula {
+    /// The friction coefficient for the given slip.
+    pub fn eval(&self, slip: Real) -> Real {
+        let bx = self.stiffness * slip;
+        self.peak * (self.shape * (bx - self.curvature * (bx - bx.atan())).atan()).sin()
+    }
+}
+
 /// Objects used to initialize a wheel.
 struct WheelDesc {
     /// The position of the wheel, relative to the chassis.
@@ -106,6 +220,12 @@ struct WheelDesc {
     pub max_suspension_force: Real,
     /// The multiplier of friction between a tire and the collider it's on top of.
     pub side_friction_stiffness: Real,
+    /// How the wheel detects the ground.
+    pub cast_shape: WheelCastShape,
+    /// How the tire’s friction forces are computed.
+    pub tire_model: TireModel,
+    /// The wheel’s angular inertia around its axle.
+    pub inertia: Real,
 }
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -173,6 +293,34 @@ pub struct Wheel {
     /// The force applied by the suspension.
     pub wheel_suspension_force: Real,
     skid_info: Real,
+
+    /// How the wheel detects the ground.
+    pub cast_shape: WheelCastShape,
+    /// How the tire’s friction forces are computed.
+    pub tire_model: TireModel,
+    /// The wheel’s angular inertia around its axle.
+    ///
+    /// Only used when the wheel’s spin is simulated: with [`TireModel::MagicFormula`], or when
+    /// the vehicle has a [`DynamicRayCastVehicleController::drivetrain`].
+    pub inertia: Real,
+    /// The wheel’s spin around its axle, positive when rolling forward.
+    ///
+    /// Unless the wheel’s spin is simulated, this just matches the ground speed.
+    pub angular_velocity: Real,
+    /// The longitudinal slip ratio: the difference between the tire’s rolling speed and the
+    /// ground speed, relative to the largest of both.
+    ///
+    /// Positive when the wheel spins faster than the ground moves (traction), negative when
+    /// it spins slower (braking), `-1` when locked. Always zero with
+    /// [`TireModel::FrictionSlip`].
+    pub slip_ratio: Real,
+    /// The angle (in radians) between the wheel’s rolling direction and the velocity of its
+    /// contact point.
+    pub slip_angle: Real,
+    /// The normal force pressing the tire on the ground: the spring and damper force the
+    /// suspension applied to the chassis during the last update, clamped to
+    /// [`Wheel::max_suspension_force`].
+    pub load: Real,
 }
 
 impl Wheel {
@@ -206,6 +354,13 @@ impl Wheel {
             side_impulse: 0.0,
             forward_impulse: 0.0,
             side_friction_stiffness: info.side_friction_stiffness,
+            cast_shape: info.cast_shape,
+            tire_model: info.tire_model,
+            inertia: info.inertia,
+            angular_velocity: 0.0,
+            slip_ratio: 0.0,
+            slip_angle: 0.0,
+            load: 0.0,
         }
     }
 
@@ -229,6 +384,19 @@ impl Wheel {
     pub fn axle(&self) -> Vector {
         self.wheel_axle_ws
     }
+
+    /// The largest friction impulse of [`TireModel::FrictionSlip`] over a step of length `dt`.
+    ///
+    /// It grows with the suspension force before its clamping to
+    /// [`Self::max_suspension_force`], unlike the [`Self::load`] of [`TireModel::MagicFormula`].
+    fn friction_slip_impulse(&self, dt: Real) -> Real {
+        self.wheel_suspension_force * dt * self.friction_slip
+    }
+
+    /// Is the wheel’s spin integrated from its torques rather than following the ground?
+    fn simulates_spin(&self, has_drivetrain: bool) -> bool {
+        has_drivetrain || matches!(self.tire_model, TireModel::MagicFormula { .. })
+    }
 }
 
 /// Information about suspension and the ground obtained from the ray-casting
@@ -263,6 +431,7 @@ impl DynamicRayCastVehicleController {
             chassis,
             index_up_axis: 1,
             index_forward_axis: 0,
+            drivetrain: None,
         }
     }
 
@@ -292,6 +461,9 @@ impl DynamicRayCastVehicleController {
             max_suspension_travel: tuning.max_suspension_travel,
             max_suspension_force: tuning.max_suspension_force,
             side_friction_stiffness: tuning.side_friction_stiffness,
+            cast_shape: tuning.cast_shape,
+            tire_model: tuning.tire_model,
+            inertia: tuning.wheel_inertia,
         };
 
         let wheel_id = self.wheels.len();
@@ -333,39 +505,24 @@ impl DynamicRayCastVehicleController {
     #[profiling::function]
     fn ray_cast(&mut self, queries: &QueryPipeline, chassis: &RigidBody, wheel_id: usize) {
         let wheel = &mut self.wheels[wheel_id];
-        let raylen = wheel.suspension_rest_length + wheel.radius;
-        let rayvector = wheel.wheel_direction_ws * raylen;
         let source = wheel.raycast_info.hard_point_ws;
-        wheel.raycast_info.contact_point_ws = source + rayvector;
-        let ray = Ray::new(source, rayvector);
-        let hit = queries.cast_ray_and_get_normal(&ray, 1.0, true);
-
+        wheel.raycast_info.contact_point_ws =
+            source + wheel.wheel_direction_ws * (wheel.suspension_rest_length + wheel.radius);
         wheel.raycast_info.ground_object = None;
 
-        if let Some((collider_hit, mut hit)) = hit {
-            if hit.time_of_impact == 0.0 {
-                let collider = &queries.colliders[collider_hit];
-                let up_ray = Ray::new(source + rayvector, -rayvector);
-                if let Some(hit2) =
-                    collider
-                        .shape
-                        .cast_ray_and_get_normal(collider.position(), &up_ray, 1.0, false)
-                {
-                    hit.normal = -hit2.normal;
-                }
-
-                if hit.normal == Vector::ZERO {
-                    // If the hit is still not defined, set the normal.
-                    hit.normal = -wheel.wheel_direction_ws;
-                }
+        let hit = match wheel.cast_shape {
+            WheelCastShape::Ray => cast_wheel_ray(queries, wheel),
+            WheelCastShape::Ball => cast_wheel_shape(queries, wheel, &Ball::new(wheel.radius)),
+            WheelCastShape::Cylinder { half_width } => {
+                cast_wheel_shape(queries, wheel, &Cylinder::new(half_width, wheel.radius))
             }
+        };
 
+        if let Some(hit) = hit {
             wheel.raycast_info.contact_normal_ws = hit.normal;
             wheel.raycast_info.is_in_contact = true;
-            wheel.raycast_info.ground_object = Some(collider_hit);
-
-            let hit_distance = hit.time_of_impact * raylen;
-            wheel.raycast_info.suspension_length = hit_distance - wheel.radius;
+            wheel.raycast_info.ground_object = Some(hit.collider);
+            wheel.raycast_info.suspension_length = hit.suspension_length;
 
             // clamp on max suspension travel
             let min_suspension_length = wheel.suspension_rest_length - wheel.max_suspension_travel;
@@ -374,7 +531,7 @@ impl DynamicRayCastVehicleController {
                 .raycast_info
                 .suspension_length
                 .clamp(min_suspension_length, max_suspension_length);
-            wheel.raycast_info.contact_point_ws = ray.point_at(hit.time_of_impact);
+            wheel.raycast_info.contact_point_ws = hit.point;
 
             let denominator = wheel
                 .raycast_info
@@ -407,6 +564,10 @@ impl DynamicRayCastVehicleController {
     /// Updates the vehicle’s velocity based on its suspension, engine force, and brake.
     #[profiling::function]
     pub fn update_vehicle(&mut self, dt: Real, queries: QueryPipelineMut) {
+        if let Some(drivetrain) = &mut self.drivetrain {
+            drivetrain.update(dt, &mut self.wheels);
+        }
+
         let num_wheels = self.wheels.len();
         let chassis = &queries.bodies[self.chassis];
 
@@ -438,11 +599,11 @@ impl DynamicRayCastVehicleController {
             .get_mut_internal_with_modification_tracking(self.chassis)
             .unwrap();
 
-        for wheel in &mut self.wheels {
-            if wheel.engine_force > 0.0 {
-                chassis.wake_up(true);
-            }
+        if self.wheels.iter().any(|wheel| wheel.engine_force != 0.0) {
+            chassis.wake_up(true);
+        }
 
+        for wheel in &mut self.wheels {
             // apply suspension force
             let mut suspension_force = wheel.wheel_suspension_force;
 
@@ -450,6 +611,8 @@ impl DynamicRayCastVehicleController {
                 suspension_force = wheel.max_suspension_force;
             }
 
+            // The tire is pressed on the ground by the force actually applied to the chassis.
+            wheel.load = suspension_force;
             let impulse = wheel.raycast_info.contact_normal_ws * suspension_force * dt;
             chassis.apply_impulse_at_point(impulse, wheel.raycast_info.contact_point_ws, false);
         }
@@ -460,8 +623,19 @@ impl DynamicRayCastVehicleController {
             .bodies
             .get_mut_internal_with_modification_tracking(self.chassis)
             .unwrap();
+        let has_drivetrain = self.drivetrain.is_some();
 
         for wheel in &mut self.wheels {
+            if wheel.simulates_spin(has_drivetrain) {
+                // The wheel’s spin is integrated by the tire model.
+                if !wheel.raycast_info.is_in_contact {
+                    wheel.angular_velocity *= 0.99; //damping of rotation when not in contact
+                }
+                wheel.delta_rotation = wheel.angular_velocity * dt;
+                wheel.rotation += wheel.delta_rotation;
+                continue;
+            }
+
             let vel = chassis.velocity_at_point(wheel.raycast_info.hard_point_ws);
 
             if wheel.raycast_info.is_in_contact {
@@ -478,6 +652,7 @@ impl DynamicRayCastVehicleController {
                 let proj2 = fwd.dot(vel);
 
                 wheel.delta_rotation = (proj2 * dt) / (wheel.radius);
+                wheel.angular_velocity = proj2 / wheel.radius;
                 wheel.rotation += wheel.delta_rotation;
             } else {
                 wheel.rotation += wheel.delta_rotation;
@@ -497,6 +672,61 @@ impl DynamicRayCastVehicleController {
         &mut self.wheels
     }
 
+    /// A spherical joint hitching a trailer to this vehicle’s chassis.
+    ///
+    /// The trailer is usually another (unpowered) [`DynamicRayCastVehicleController`] with as
+    /// many axles as needed, and can tow trailers of its own. Insert the returned joint into
+    /// an [`ImpulseJointSet`](crate::dynamics::ImpulseJointSet) between [`Self::chassis`] (the
+    /// first body) and the trailer’s chassis (the second body):
+    /// - `hitch_cs` is the hitch point relative to this vehicle’s chassis.
+    /// - `trailer_hitch_cs` is the same point relative to the trailer’s chassis, which must use the
+    ///   same up and forward axes as this vehicle.
+    /// - `max_yaw_angle`, `max_pitch_angle` and `max_roll_angle` limit the rotation of the
+    ///   trailer relative to the vehicle around the up, lateral and forward axes, preventing the
+    ///   trailer from jackknifing into the vehicle.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use rapier3d::control::DynamicRayCastVehicleController;
+    /// # let mut world = PhysicsWorld::default();
+    /// # let (truck, _) = world.insert(RigidBodyBuilder::dynamic(), ColliderBuilder::cuboid(2.0, 0.5, 1.0));
+    /// # let (trailer, _) = world.insert(
+    /// #     RigidBodyBuilder::dynamic().translation(Vector::new(-5.0, 0.0, 0.0)),
+    /// #     ColliderBuilder::cuboid(2.5, 0.5, 1.0),
+    /// # );
+    /// let vehicle = DynamicRayCastVehicleController::new(truck);
+    /// let hitch = vehicle.trailer_hitch(
+    ///     Vector::new(-2.25, 0.0, 0.0),
+    ///     Vector::new(2.75, 0.0, 0.0),
+    ///     1.2,
+    ///     0.3,
+    ///     0.2,
+    /// );
+    /// world.insert_impulse_joint(truck, trailer, hitch);
+    /// ```
+    pub fn trailer_hitch(
+        &self,
+        hitch_cs: Vector,
+        trailer_hitch_cs: Vector,
+        max_yaw_angle: Real,
+        max_pitch_angle: Real,
+        max_roll_angle: Real,
+    ) -> SphericalJointBuilder {
+        // A joint frame with the forward, up and lateral axes as its X, Y and Z axes.
+        let forward = Vector::ith(self.index_forward_axis, 1.0);
+        let up = Vector::ith(self.index_up_axis, 1.0);
+        let frame_rotation =
+            Rotation::from_mat3(&Matrix::from_cols(forward, up, forward.cross(up)));
+
+        SphericalJointBuilder::new()
+            .local_frame1(Pose::from_parts(hitch_cs, frame_rotation))
+            .local_frame2(Pose::from_parts(trailer_hitch_cs, frame_rotation))
+            .limits(JointAxis::AngX, [-max_roll_angle, max_roll_angle])
+            .limits(JointAxis::AngY, [-max_yaw_angle, max_yaw_angle])
+            .limits(JointAxis::AngZ, [-max_pitch_angle, max_pitch_angle])
+    }
+
     fn update_suspension(&mut self, chassis_mass: Real) {
         for w_it in 0..self.wheels.len() {
             let wheels = &mut self.wheels[w_it];
@@ -640,7 +870,7 @@ impl DynamicRayCastVehicleController {
                 wheel.skid_info = 1.0;
 
                 if ground_object.is_some() {
-                    let max_imp = wheel.wheel_suspension_force * dt * wheel.friction_slip;
+                    let max_imp = wheel.friction_slip_impulse(dt);
                     let max_imp_side = max_imp;
                     let max_imp_squared = max_imp * max_imp_side;
                     assert!(max_imp_squared >= 0.0);
@@ -671,6 +901,8 @@ impl DynamicRayCastVehicleController {
             }
         }
 
+        self.update_tire_model(bodies, colliders, dt, num_wheels_on_ground);
+
         // apply the impulses
         {
             let chassis = bodies
@@ -710,6 +942,213 @@ impl DynamicRayCastVehicleController {
             }
         }
     }
+
+    /// Computes the slip of every wheel, simulates the spin of the wheels that need it and, for
+    /// the wheels using [`TireModel::MagicFormula`], replaces their friction impulses.
+    fn update_tire_model(
+        &mut self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        dt: Real,
+        num_wheels_on_ground: usize,
+    ) {
+        let chassis = &bodies[self.chassis];
+        let has_drivetrain = self.drivetrain.is_some();
+
+        for (wheel_id, wheel) in self.wheels.iter_mut().enumerate() {
+            let simulates_spin = wheel.simulates_spin(has_drivetrain);
+            let magic_formula = match wheel.tire_model {
+                TireModel::FrictionSlip => None,
+                TireModel::MagicFormula {
+                    longitudinal,
+                    lateral,
+                } => Some((longitudinal, lateral)),
+            };
+            let inv_inertia = crate::utils::inv(wheel.inertia);
+            // The brake acts like a friction: it slows the wheel down, but never spins it
+            // backward.
+            let mut brake_budget = wheel.brake * wheel.radius * inv_inertia;
+            let mut apply_brake = |angular_velocity: &mut Real| {
+                let braking = angular_velocity.clamp(-brake_budget, brake_budget);
+                *angular_velocity -= braking;
+                brake_budget -= braking.abs();
+            };
+
+            if simulates_spin {
+                wheel.angular_velocity += wheel.engine_force * wheel.radius * dt * inv_inertia;
+                apply_brake(&mut wheel.angular_velocity);
+            }
+
+            let Some(ground_object) = wheel.raycast_info.ground_object else {
+                wheel.slip_ratio = 0.0;
+                wheel.slip_angle = 0.0;
+                continue;
+            };
+
+            let contact_point = wheel.raycast_info.contact_point_ws;
+            let ground_velocity = colliders[ground_object]
+                .parent()
+                .map(|h| bodies[h].velocity_at_point(contact_point))
+                .unwrap_or_default();
+            let velocity = chassis.velocity_at_point(contact_point) - ground_velocity;
+            let forward = self.forward_ws[wheel_id];
+            let axle = self.axle[wheel_id];
+            let forward_speed = forward.dot(velocity);
+            let side_speed = axle.dot(velocity);
+
+            wheel.slip_angle = if side_speed == 0.0 {
+                0.0
+            } else {
+                side_speed.atan2(forward_speed.abs())
+            };
+
+            let Some((longitudinal, lateral)) = magic_formula else {
+                wheel.slip_ratio = 0.0;
+                if !simulates_spin {
+                    // The wheel’s spin just follows the ground.
+                    continue;
+                }
+                // The ground reacts to the friction impulse pushing the chassis, then the grip
+                // left within the friction budget drags the wheel’s spin toward the ground speed.
+                wheel.angular_velocity -= wheel.forward_impulse * wheel.radius * inv_inertia;
+                let max_grip_impulse =
+                    (wheel.friction_slip_impulse(dt) - wheel.forward_impulse.abs()).max(0.0);
+                let grip_impulse = ((wheel.angular_velocity * wheel.radius - forward_speed)
+                    * wheel.inertia
+                    * crate::utils::inv(wheel.radius * wheel.radius))
+                .clamp(-max_grip_impulse, max_grip_impulse);
+                wheel.angular_velocity -= grip_impulse * wheel.radius * inv_inertia;
+                apply_brake(&mut wheel.angular_velocity);
+                continue;
+            };
+
+            let rolling_speed = wheel.angular_velocity * wheel.radius;
+            let slip_speed = rolling_speed - forward_speed;
+            wheel.slip_ratio = slip_speed
+                / forward_speed
+                    .abs()
+                    .max(rolling_speed.abs())
+                    .max(MIN_SLIP_SPEED);
+
+            let mut forward_friction = longitudinal.eval(wheel.slip_ratio);
+            let mut side_friction = -lateral.eval(wheel.slip_angle);
+
+            // The friction ellipse: a tire that is braking or accelerating hard has less grip
+            // left for cornering, and vice versa.
+            let ellipse = (forward_friction * crate::utils::inv(longitudinal.peak)).powi(2)
+                + (side_friction * crate::utils::inv(lateral.peak)).powi(2);
+            if ellipse > 1.0 {
+                let scale = 1.0 / ellipse.sqrt();
+                forward_friction *= scale;
+                side_friction *= scale;
+            }
+
+            // The slip quantities are ill-defined at low speeds, so never apply more than the
+            // impulses cancelling the slip. Like the rolling friction, they are shared among
+            // the wheels on the ground.
+            let num_wheels_on_ground = num_wheels_on_ground as Real;
+            let forward_denominator = num_wheels_on_ground
+                * impulse_denominator(chassis, contact_point, forward)
+                + wheel.radius * wheel.radius * inv_inertia;
+            let max_forward_impulse = slip_speed.abs() * crate::utils::inv(forward_denominator);
+            let max_side_impulse = side_speed.abs()
+                * crate::utils::inv(
+                    num_wheels_on_ground * impulse_denominator(chassis, contact_point, axle),
+                );
+
+            wheel.forward_impulse = (forward_friction * wheel.load * dt)
+                .clamp(-max_forward_impulse, max_forward_impulse);
+            wheel.side_impulse =
+                (side_friction * wheel.load * dt).clamp(-max_side_impulse, max_side_impulse);
+            // The ground drags the wheel’s spin toward the ground speed, which the brake may
+            // still resist.
+            wheel.angular_velocity -= wheel.forward_impulse * wheel.radius * inv_inertia;
+            apply_brake(&mut wheel.angular_velocity);
+        }
+    }
+}
+
+/// The ground found under a wheel.
+struct WheelGroundHit {
+    collider: ColliderHandle,
+    normal: Vector,
+    point: Vector,
+    suspension_length: Real,
+}
+
+/// Detects the ground with a ray from the wheel’s hard point, reaching the bottom of the wheel
+/// at rest.
+fn cast_wheel_ray(queries: &QueryPipeline, wheel: &Wheel) -> Option<WheelGroundHit> {
+    let raylen = wheel.suspension_rest_length + wheel.radius;
+    let rayvector = wheel.wheel_direction_ws * raylen;
+    let source = wheel.raycast_info.hard_point_ws;
+    let ray = Ray::new(source, rayvector);
+    let (collider_hit, mut hit) = queries.cast_ray_and_get_normal(&ray, 1.0, true)?;
+
+    if hit.time_of_impact == 0.0 {
+        let collider = &queries.colliders[collider_hit];
+        let up_ray = Ray::new(source + rayvector, -rayvector);
+        if let Some(hit2) =
+            collider
+                .shape
+                .cast_ray_and_get_normal(collider.position(), &up_ray, 1.0, false)
+        {
+            hit.normal = -hit2.normal;
+        }
+
+        if hit.normal == Vector::ZERO {
+            // If the hit is still not defined, set the normal.
+            hit.normal = -wheel.wheel_direction_ws;
+        }
+    }
+
+    Some(WheelGroundHit {
+        collider: collider_hit,
+        normal: hit.normal,
+        point: ray.point_at(hit.time_of_impact),
+        suspension_length: hit.time_of_impact * raylen - wheel.radius,
+    })
+}
+
+/// Detects the ground by sweeping the wheel’s shape from its hard point to its rest position.
+///
+/// The shape is oriented so that its local `y` axis is the wheel’s axle.
+fn cast_wheel_shape(
+    queries: &QueryPipeline,
+    wheel: &Wheel,
+    shape: &dyn Shape,
+) -> Option<WheelGroundHit> {
+    let axle = wheel.wheel_axle_ws.normalize_or_zero();
+    let rotation = if axle == Vector::ZERO {
+        Rotation::IDENTITY
+    } else {
+        Rotation::from_rotation_arc(Vector::Y, axle)
+    };
+    let shape_pos = Pose::from_parts(wheel.raycast_info.hard_point_ws, rotation);
+    let (collider_hit, hit) = queries.cast_shape(
+        &shape_pos,
+        wheel.wheel_direction_ws * wheel.suspension_rest_length,
+        shape,
+        ShapeCastOptions {
+            target_distance: 0.0,
+            // A wheel starting inside the ground is fully compressed.
+            stop_at_penetration: true,
+            max_time_of_impact: 1.0,
+            compute_impact_geometry_on_penetration: true,
+        },
+    )?;
+    let normal = if hit.normal1 == Vector::ZERO {
+        -wheel.wheel_direction_ws
+    } else {
+        hit.normal1
+    };
+
+    Some(WheelGroundHit {
+        collider: collider_hit,
+        normal,
+        point: hit.witness1,
+        suspension_length: hit.time_of_impact * wheel.suspension_rest_length,
+    })
 }
 
 struct WheelContactPoint<'a> {
@@ -729,13 +1168,6 @@ impl<'a> WheelContactPoint<'a> {
         friction_direction_world: Vector,
         max_impulse: Real,
     ) -> Self {
-        fn impulse_denominator(body: &RigidBody, pos: Vector, n: Vector) -> Real {
-            let dpt = pos - body.center_of_mass();
-            let gcross = dpt.gcross(n);
-            let v = (body.mprops.effective_world_inv_inertia * gcross).gcross(dpt);
-            // TODO: take the effective inv mass into account instead of the inv_mass?
-            body.mprops.local_mprops.inv_mass + n.dot(v)
-        }
         let denom0 = impulse_denominator(body0, friction_position_world, friction_direction_world);
         let denom1 = body1
             .map(|body1| {
@@ -773,6 +1205,14 @@ impl<'a> WheelContactPoint<'a> {
     }
 }
 
+pub(super) fn impulse_denominator(body: &RigidBody, pos: Vector, n: Vector) -> Real {
+    let dpt = pos - body.center_of_mass();
+    let gcross = dpt.gcross(n);
+    let v = (body.mprops.effective_world_inv_inertia * gcross).gcross(dpt);
+    // TODO: take the effective inv mass into account instead of the inv_mass?
+    body.mprops.local_mprops.inv_mass + n.dot(v)
+}
+
 fn resolve_single_bilateral(
     body1: &RigidBody,
     pt1: Vector,
@@ -0,0 +1,348 @@
+//! A controller for tracked vehicles, steered by driving their tracks at different speeds.
+use crate::alloc_prelude::*;
+
+#[cfg(not(feature = "std"))]
+use simba::scalar::ComplexField;
+
+use crate::control::ray_cast_vehicle_controller::impulse_denominator;
+use crate::dynamics::RigidBodyHandle;
+use crate::geometry::{ColliderHandle, Ray};
+use crate::math::{Real, Vector, VectorExt};
+use crate::pipeline::QueryPipelineMut;
+
+/// A controller to simulate tracked vehicles (tanks, bulldozers) using ray-casting for the road
+/// wheels.
+///
+/// Each [`Track`] is a row of suspension contacts, one per road wheel, detecting the ground with
+/// a ray-cast like the wheels of a
+/// [`DynamicRayCastVehicleController`](super::DynamicRayCastVehicleController). All the contacts
+/// of a track share the same belt: they are driven at the same [`Track::speed`] (up to the
+/// track’s [`Track::max_drive_force`]) and grip the ground with the same friction coefficient.
+///
+/// The vehicle is steered by driving its tracks at different speeds (skid steering, see
+/// [`Self::skid_steer`]): the contacts then slide sideways on the ground, so the turn rate depends
+/// on the tracks’ friction and on the vehicle’s length relative to its width.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub struct TrackedVehicleController {
+    tracks: Vec<Track>,
+    /// Handle of the vehicle’s chassis.
+    pub chassis: RigidBodyHandle,
+    /// The chassis’ local _up_ direction (`0 = x, 1 = y, 2 = z`)
+    pub index_up_axis: usize,
+    /// The chassis’ local _forward_ direction (`0 = x, 1 = y, 2 = z`)
+    pub index_forward_axis: usize,
+}
+
+/// Parameters affecting the physical behavior of a track.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrackTuning {
+    /// The stiffness of each road wheel’s suspension, per unit of chassis mass.
+    pub suspension_stiffness: Real,
+    /// The damping of each road wheel’s suspension, per unit of chassis mass.
+    pub suspension_damping: Real,
+    /// The maximum force applied by each road wheel’s suspension.
+    pub max_suspension_force: Real,
+    /// The friction coefficient between the track and the ground.
+    pub friction: Real,
+    /// The maximum force the engine can apply to drive the track at its target speed.
+    pub max_drive_force: Real,
+}
+
+impl Default for TrackTuning {
+    fn default() -> Self {
+        Self {
+            suspension_stiffness: 20.0,
+            suspension_damping: 2.0,
+            max_suspension_force: 6000.0,
+            friction: 1.0,
+            max_drive_force: 1.0e4,
+        }
+    }
+}
+
+/// A track of a [`TrackedVehicleController`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Track {
+    contacts: Vec<TrackContact>,
+    /// The rest length of the road wheels’ suspension springs.
+    pub suspension_rest_length: Real,
+    /// The radius of the road wheels (including the thickness of the belt).
+    pub wheel_radius: Real,
+    /// The stiffness of each road wheel’s suspension, per unit of chassis mass.
+    pub suspension_stiffness: Real,
+    /// The damping of each road wheel’s suspension, per unit of chassis mass.
+    pub suspension_damping: Real,
+    /// The maximum force applied by each road wheel’s suspension.
+    pub max_suspension_force: Real,
+    /// The friction coefficient between the track and the ground.
+    pub friction: Real,
+    /// The maximum force the engine can apply to drive the track at its target speed.
+    pub max_drive_force: Real,
+    /// The target speed of the track’s belt relative to the ground, positive forward.
+    pub speed: Real,
+    drive_force: Real,
+}
+
+/// A road wheel of a [`Track`], and the ground it found.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct TrackContact {
+    /// The position of the road wheel’s suspension, relative to the chassis.
+    pub chassis_connection_cs: Vector,
+    /// Is the road wheel in contact with the ground?
+    pub is_in_contact: bool,
+    /// The suspension length for the road wheel.
+    pub suspension_length: Real,
+    /// The (world-space) point hit by the road wheel’s ray-cast.
+    pub contact_point_ws: Vector,
+    /// The (world-space) contact normal between the road wheel and the ground.
+    pub contact_normal_ws: Vector,
+    /// The collider hit by the ray-cast.
+    pub ground_object: Option<ColliderHandle>,
+    /// The normal force pressing this part of the track on the ground: the suspension force
+    /// applied to the chassis during the last update.
+    pub load: Real,
+    ground_velocity: Vector,
+}
+
+impl Track {
+    /// The road wheels of this track, and the ground they found at the last update.
+    pub fn contacts(&self) -> &[TrackContact] {
+        &self.contacts
+    }
+
+    /// The force applied along the ground by the track on the chassis during the last update.
+    ///
+    /// This is limited by [`Self::max_drive_force`] and by the track’s friction.
+    pub fn drive_force(&self) -> Real {
+        self.drive_force
+    }
+}
+
+impl TrackedVehicleController {
+    /// Creates a new tracked vehicle represented by the given rigid-body.
+    ///
+    /// Tracks have to be attached afterwards calling [`Self::add_track`].
+    pub fn new(chassis: RigidBodyHandle) -> Self {
+        Self {
+            tracks: vec![],
+            chassis,
+            index_up_axis: 1,
+            index_forward_axis: 0,
+        }
+    }
+
+    /// Adds a track to this vehicle.
+    ///
+    /// `road_wheels_cs` are the positions of the road wheels’ suspensions relative to the
+    /// chassis. The suspensions extend along the chassis’ down direction.
+    pub fn add_track(
+        &mut self,
+        road_wheels_cs: &[Vector],
+        suspension_rest_length: Real,
+        wheel_radius: Real,
+        tuning: &TrackTuning,
+    ) -> &mut Track {
+        self.tracks.push(Track {
+            contacts: road_wheels_cs
+                .iter()
+                .map(|&chassis_connection_cs| TrackContact {
+                    chassis_connection_cs,
+                    suspension_length: suspension_rest_length,
+                    ..Default::default()
+                })
+                .collect(),
+            suspension_rest_length,
+            wheel_radius,
+            suspension_stiffness: tuning.suspension_stiffness,
+            suspension_damping: tuning.suspension_damping,
+            max_suspension_force: tuning.max_suspension_force,
+            friction: tuning.friction,
+            max_drive_force: tuning.max_drive_force,
+            speed: 0.0,
+            drive_force: 0.0,
+        });
+        self.tracks.last_mut().unwrap()
+    }
+
+    /// Reference to all the tracks attached to this vehicle.
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    /// Mutable reference to all the tracks attached to this vehicle.
+    pub fn tracks_mut(&mut self) -> &mut [Track] {
+        &mut self.tracks
+    }
+
+    /// Sets the speed of every track so the vehicle moves forward at `speed` while turning at
+    /// `yaw_rate` (in radians per second, counterclockwise around the chassis’ up direction).
+    ///
+    /// A zero `speed` turns the vehicle in place. The actual turn rate is usually smaller than
+    /// `yaw_rate` since the tracks slide sideways when turning.
+    pub fn skid_steer(&mut self, speed: Real, yaw_rate: Real) {
+        let up = Vector::ith(self.index_up_axis, 1.0);
+        let forward = Vector::ith(self.index_forward_axis, 1.0);
+        let right = forward.cross(up);
+
+        for track in &mut self.tracks {
+            let offset = track
+                .contacts
+                .iter()
+                .map(|contact| contact.chassis_connection_cs.dot(right))
+                .sum::<Real>()
+                * crate::utils::inv(track.contacts.len() as Real);
+            // A yaw rate `w` moves a point `offset` to the right forward at `w * offset`.
+            track.speed = speed + yaw_rate * offset;
+        }
+    }
+
+    /// Updates the vehicle’s velocity based on its suspension and the speed of its tracks.
+    #[profiling::function]
+    pub fn update_vehicle(&mut self, dt: Real, queries: QueryPipelineMut) {
+        let chassis = queries
+            .bodies
+            .get_mut_internal_with_modification_tracking(self.chassis)
+            .unwrap();
+
+        if self.tracks.iter().any(|track| track.speed != 0.0) {
+            chassis.wake_up(true);
+        }
+
+        // A parked tank, asleep with its tracks stopped, isn't simulated: the suspension loads
+        // would pile up in its velocity without the gravity they balance, and throw it up once
+        // it wakes up. Its contacts and drive forces keep their last values until then.
+        if chassis.is_sleeping() {
+            return;
+        }
+
+        let chassis = &queries.bodies[self.chassis];
+        let chassis_pos = *chassis.position();
+        let up = chassis_pos.rotation * Vector::ith(self.index_up_axis, 1.0);
+        let forward = chassis_pos.rotation * Vector::ith(self.index_forward_axis, 1.0);
+        let chassis_mass = chassis.mass();
+        let query_pipeline = queries.as_ref();
+        let mut num_contacts_on_ground = 0;
+
+        // Find the ground and compute the suspension forces.
+        for track in &mut self.tracks {
+            let ray_length = track.suspension_rest_length + track.wheel_radius;
+
+            for contact in &mut track.contacts {
+                let source = chassis_pos * contact.chassis_connection_cs;
+                let ray = Ray::new(source, -up * ray_length);
+                contact.ground_object = None;
+                contact.is_in_contact = false;
+                contact.load = 0.0;
+                contact.suspension_length = track.suspension_rest_length;
+                contact.contact_normal_ws = up;
+                contact.contact_point_ws = ray.point_at(1.0);
+                contact.ground_velocity = Vector::ZERO;
+
+                let Some((handle, hit)) = query_pipeline.cast_ray_and_get_normal(&ray, 1.0, true)
+                else {
+                    continue;
+                };
+
+                num_contacts_on_ground += 1;
+                contact.is_in_contact = true;
+                contact.ground_object = Some(handle);
+                contact.contact_point_ws = ray.point_at(hit.time_of_impact);
+                if hit.normal != Vector::ZERO {
+                    contact.contact_normal_ws = hit.normal;
+                }
+                contact.suspension_length =
+                    (hit.time_of_impact * ray_length - track.wheel_radius).max(0.0);
+                contact.ground_velocity = query_pipeline.colliders[handle]
+                    .parent()
+                    .map(|h| query_pipeline.bodies[h].velocity_at_point(contact.contact_point_ws))
+                    .unwrap_or_default();
+
+                let normal_velocity = contact.contact_normal_ws.dot(
+                    chassis.velocity_at_point(contact.contact_point_ws) - contact.ground_velocity,
+                );
+                let compression = track.suspension_rest_length - contact.suspension_length;
+                contact.load = ((track.suspension_stiffness * compression
+                    - track.suspension_damping * normal_velocity)
+                    * chassis_mass)
+                    .clamp(0.0, track.max_suspension_force);
+            }
+        }
+
+        let chassis = queries
+            .bodies
+            .get_mut_internal_with_modification_tracking(self.chassis)
+            .unwrap();
+
+        for contact in self.tracks.iter().flat_map(|track| &track.contacts) {
+            if contact.is_in_contact {
+                chassis.apply_impulse_at_point(
+                    contact.contact_normal_ws * contact.load * dt,
+                    contact.contact_point_ws,
+                    false,
+                );
+            }
+        }
+
+        // Drive the tracks: the impulses making every contact of a track move at the belt’s
+        // speed along the ground without sliding sideways, shared among all the contacts on the
+        // ground, and limited by the drive force and the friction.
+        let mut impulses = vec![];
+        for track in &mut self.tracks {
+            impulses.clear();
+            let mut drive_impulse = 0.0;
+
+            for contact in track.contacts.iter().filter(|c| c.is_in_contact) {
+                let normal = contact.contact_normal_ws;
+                let point = contact.contact_point_ws;
+                let contact_forward = (forward - normal * normal.dot(forward)).normalize_or_zero();
+                let contact_side = normal.cross(contact_forward);
+                let velocity = chassis.velocity_at_point(point) - contact.ground_velocity;
+
+                let forward_impulse = -(contact_forward.dot(velocity) - track.speed)
+                    * crate::utils::inv(
+                        num_contacts_on_ground as Real
+                            * impulse_denominator(chassis, point, contact_forward),
+                    );
+                let side_impulse = -contact_side.dot(velocity)
+                    * crate::utils::inv(
+                        num_contacts_on_ground as Real
+                            * impulse_denominator(chassis, point, contact_side),
+                    );
+                drive_impulse += forward_impulse;
+                impulses.push((
+                    contact_forward * forward_impulse,
+                    contact_side * side_impulse,
+                ));
+            }
+
+            let max_drive_impulse = track.max_drive_force * dt;
+            let drive_scale = if drive_impulse.abs() > max_drive_impulse {
+                max_drive_impulse / drive_impulse.abs()
+            } else {
+                1.0
+            };
+
+            track.drive_force = 0.0;
+            for (contact, (forward_impulse, side_impulse)) in track
+                .contacts
+                .iter()
+                .filter(|c| c.is_in_contact)
+                .zip(impulses.iter())
+            {
+                let mut impulse = *forward_impulse * drive_scale + *side_impulse;
+                let max_impulse = track.friction * contact.load * dt;
+                let impulse_norm = impulse.length();
+                if impulse_norm > max_impulse {
+                    impulse *= max_impulse / impulse_norm;
+                }
+
+                track.drive_force += impulse.dot(forward) * crate::utils::inv(dt);
+                chassis.apply_impulse_at_point(impulse, contact.contact_point_ws, false);
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! An engine, clutch, gearbox and differentials driving the wheels of a
+//! [`DynamicRayCastVehicleController`](super::DynamicRayCastVehicleController).
+use crate::alloc_prelude::*;
+
+#[cfg(not(feature = "std"))]
+use simba::scalar::ComplexField;
+
+use crate::control::Wheel;
+use crate::math::Real;
+use na::RealField;
+
+/// The drivetrain of a [`DynamicRayCastVehicleController`](super::DynamicRayCastVehicleController).
+///
+/// Instead of setting each [`Wheel::engine_force`] and [`Wheel::brake`] by hand, set the
+/// driver’s inputs ([`Self::throttle`], [`Self::clutch`], [`Self::brake`], [`Self::handbrake`]
+/// and, with a manual gearbox, [`Gearbox::gear`]). Every step, the drivetrain:
+/// - Computes the [`VehicleEngine`] torque at the engine’s current RPM.
+/// - Transmits it to the gearbox through the clutch, which slips when the torque syncing the
+///   engine with the gearbox exceeds its capacity.
+/// - Multiplies it by the [`Gearbox`] ratio (shifting gears first if it is automatic).
+/// - Splits it between the [`DrivenAxle`]s, and between the wheels of each axle with its
+///   [`Differential`].
+/// - Overwrites the `engine_force` and `brake` of every wheel of the vehicle accordingly.
+///
+/// The engine’s RPM is integrated from the torque balance between the engine and the clutch, and
+/// the clutch follows the spin of the driven wheels. Once a vehicle has a drivetrain, its wheels’
+/// spin is integrated from the drive, brake and tire torques under every
+/// [`TireModel`](super::TireModel): the wheels spin up, lock, and feed back into the engine.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Drivetrain {
+    /// The engine.
+    pub engine: VehicleEngine,
+    /// The gearbox.
+    pub gearbox: Gearbox,
+    /// The axles driven by the engine.
+    pub axles: Vec<DrivenAxle>,
+    /// The maximum torque the clutch transmits when fully engaged.
+    pub clutch_max_torque: Real,
+    /// The braking torque applied to every wheel when [`Self::brake`] is `1.0`.
+    pub max_brake_torque: Real,
+    /// The braking torque applied to the [`Self::handbrake_wheels`] when [`Self::handbrake`] is
+    /// `1.0`.
+    pub max_handbrake_torque: Real,
+    /// The indices of the wheels locked by the handbrake, usually the rear ones.
+    pub handbrake_wheels: Vec<usize>,
+    /// The throttle input, in `[0, 1]`.
+    pub throttle: Real,
+    /// The clutch engagement, in `[0, 1]`: `0.0` when the clutch pedal is pressed.
+    pub clutch: Real,
+    /// The brake input, in `[0, 1]`.
+    pub brake: Real,
+    /// The handbrake input, in `[0, 1]`.
+    pub handbrake: Real,
+    engine_angular_velocity: Real,
+    clutch_torque: Real,
+}
+
+/// A combustion engine.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VehicleEngine {
+    /// The torque at full throttle, as `(rpm, torque)` points sorted by RPM.
+    ///
+    /// The torque is interpolated linearly between the points, and constant beyond them.
+    pub torque_curve: Vec<(Real, Real)>,
+    /// The RPM the engine idles at. The engine never turns slower, so it never stalls.
+    pub idle_rpm: Real,
+    /// The rev limiter: the engine doesn’t produce any torque above this RPM.
+    pub max_rpm: Real,
+    /// The angular inertia of the engine’s rotating parts.
+    pub inertia: Real,
+    /// The engine braking torque at [`Self::max_rpm`] when the throttle is released.
+    ///
+    /// The engine braking is proportional to the RPM.
+    pub braking_torque: Real,
+}
+
+impl Default for VehicleEngine {
+    fn default() -> Self {
+        Self {
+            torque_curve: vec![
+                (0.0, 150.0),
+                (1000.0, 200.0),
+                (4500.0, 300.0),
+                (7000.0, 220.0),
+            ],
+            idle_rpm: 800.0,
+            max_rpm: 7000.0,
+            inertia: 0.3,
+            braking_torque: 50.0,
+        }
+    }
+}
+
+impl VehicleEngine {
+    /// The torque produced at full throttle at the given RPM.
+    pub fn torque_at(&self, rpm: Real) -> Real {
+        let Some(first) = self.torque_curve.first() else {
+            return 0.0;
+        };
+
+        if rpm <= first.0 {
+            return first.1;
+        }
+
+        for segment in self.torque_curve.windows(2) {
+            let ((rpm0, torque0), (rpm1, torque1)) = (segment[0], segment[1]);
+            if rpm <= rpm1 {
+                let t = (rpm - rpm0) * crate::utils::inv(rpm1 - rpm0);
+                return torque0 + (torque1 - torque0) * t;
+            }
+        }
+
+        self.torque_curve[self.torque_curve.len() - 1].1
+    }
+}
+
+/// How the gear of a [`Gearbox`] is selected.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GearboxMode {
+    /// The gear is only changed by setting [`Gearbox::gear`].
+    Manual,
+    /// Forward gears are shifted automatically, based on the RPM matching the driven wheels’
+    /// speed. Neutral and reverse are still selected by setting [`Gearbox::gear`].
+    ///
+    /// To avoid shifting back and forth, the RPM after shifting up must stay above
+    /// `downshift_rpm`.
+    Automatic {
+        /// Shifts up above this RPM.
+        upshift_rpm: Real,
+        /// Shifts down below this RPM.
+        downshift_rpm: Real,
+    },
+}
+
+/// A gearbox and final drive.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gearbox {
+    /// The ratios of the forward gears, from the first gear up.
+    pub ratios: Vec<Real>,
+    /// The ratio of the reverse gear.
+    pub reverse_ratio: Real,
+    /// The ratio of the final drive, multiplying the ratio of every gear.
+    pub final_drive: Real,
+    /// How gears are selected.
+    pub mode: GearboxMode,
+    /// The current gear: `-1` for reverse, `0` for neutral, and `1` and above for the forward
+    /// gears.
+    pub gear: i32,
+}
+
+impl Default for Gearbox {
+    fn default() -> Self {
+        Self {
+            ratios: vec![3.5, 2.2, 1.5, 1.1, 0.9],
+            reverse_ratio: 3.3,
+            final_drive: 3.7,
+            mode: GearboxMode::Automatic {
+                upshift_rpm: 6000.0,
+                downshift_rpm: 3000.0,
+            },
+            gear: 0,
+        }
+    }
+}
+
+impl Gearbox {
+    /// The total ratio of the current gear (including the final drive) between the engine’s and
+    /// the wheels’ spin.
+    ///
+    /// This is negative in reverse, and zero in neutral.
+    pub fn ratio(&self) -> Real {
+        match self.gear {
+            0 => 0.0,
+            ..0 => -self.reverse_ratio.abs() * self.final_drive,
+            gear => self.ratios.get(gear as usize - 1).copied().unwrap_or(0.0) * self.final_drive,
+        }
+    }
+
+    fn shift_automatically(&mut self, wheels_angular_velocity: Real) {
+        let GearboxMode::Automatic {
+            upshift_rpm,
+            downshift_rpm,
+        } = self.mode
+        else {
+            return;
+        };
+
+        if self.gear <= 0 {
+            return;
+        }
+
+        let rpm = angular_velocity_to_rpm(wheels_angular_velocity * self.ratio());
+        if rpm > upshift_rpm && (self.gear as usize) < self.ratios.len() {
+            self.gear += 1;
+        } else if rpm < downshift_rpm && self.gear > 1 {
+            self.gear -= 1;
+        }
+    }
+}
+
+/// How a [`DrivenAxle`] splits its torque between its wheels.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum Differential {
+    /// Both wheels get the same torque, and can spin at different speeds.
+    ///
+    /// Corners smoothly, but a wheel losing grip spins freely.
+    #[default]
+    Open,
+    /// A viscous limited-slip differential: torque is moved from the faster wheel to the
+    /// slower one, proportionally to the difference of their spins.
+    LimitedSlip {
+        /// The torque moved per unit of angular velocity difference.
+        stiffness: Real,
+    },
+    /// Both wheels spin at the same speed.
+    Locked,
+}
+
+/// A pair of wheels driven by the engine through a [`Differential`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DrivenAxle {
+    /// The index of the left wheel in the vehicle’s wheels.
+    pub left_wheel: usize,
+    /// The index of the right wheel in the vehicle’s wheels.
+    pub right_wheel: usize,
+    /// How the torque is split between both wheels.
+    pub differential: Differential,
+    /// The part of the engine’s torque sent to this axle, relative to the other axles’.
+    ///
+    /// For example, two axles with a share of `1.0` each get half the torque.
+    pub torque_share: Real,
+}
+
+impl DrivenAxle {
+    /// An axle driving the given wheels through `differential`, with a torque share of `1.0`.
+    pub fn new(left_wheel: usize, right_wheel: usize, differential: Differential) -> Self {
+        Self {
+            left_wheel,
+            right_wheel,
+            differential,
+            torque_share: 1.0,
+        }
+    }
+}
+
+impl Drivetrain {
+    /// A drivetrain with the given engine and gearbox, driving `axles`.
+    ///
+    /// The engine starts idling with the clutch engaged, and the brakes default to values
+    /// suitable for a car of about a ton. Select a gear in [`Gearbox::gear`] to start driving:
+    /// the default [`Gearbox`] is in neutral.
+    pub fn new(engine: VehicleEngine, gearbox: Gearbox, axles: Vec<DrivenAxle>) -> Self {
+        Self {
+            engine_angular_velocity: rpm_to_angular_velocity(engine.idle_rpm),
+            engine,
+            gearbox,
+            axles,
+            clutch_max_torque: 600.0,
+            max_brake_torque: 1500.0,
+            max_handbrake_torque: 1500.0,
+            handbrake_wheels: vec![],
+            throttle: 0.0,
+            clutch: 1.0,
+            brake: 0.0,
+            handbrake: 0.0,
+            clutch_torque: 0.0,
+        }
+    }
+
+    /// The engine’s current RPM.
+    pub fn rpm(&self) -> Real {
+        angular_velocity_to_rpm(self.engine_angular_velocity)
+    }
+
+    /// The torque transmitted by the clutch to the gearbox during the last step.
+    pub fn clutch_torque(&self) -> Real {
+        self.clutch_torque
+    }
+
+    /// Computes the torques of the engine, clutch and differentials, and sets the
+    /// `engine_force` and `brake` of the `wheels` accordingly.
+    pub(crate) fn update(&mut self, dt: Real, wheels: &mut [Wheel]) {
+        let throttle = self.throttle.clamp(0.0, 1.0);
+        let inv_total_share =
+            crate::utils::inv(self.axles.iter().map(|axle| axle.torque_share).sum());
+
+        // The spin of the driven wheels, and their inertia.
+        let mut wheels_angular_velocity = 0.0;
+        let mut wheels_inertia = 0.0;
+        for axle in &self.axles {
+            let (left, right) = (&wheels[axle.left_wheel], &wheels[axle.right_wheel]);
+            wheels_angular_velocity += (left.angular_velocity + right.angular_velocity)
+                * 0.5
+                * axle.torque_share
+                * inv_total_share;
+            wheels_inertia += left.inertia + right.inertia;
+        }
+
+        self.gearbox.shift_automatically(wheels_angular_velocity);
+        let ratio = self.gearbox.ratio();
+
+        // Engine.
+        let rpm = self.rpm();
+        let mut engine_torque = if rpm < self.engine.max_rpm {
+            throttle * self.engine.torque_at(rpm)
+        } else {
+            0.0
+        };
+        engine_torque -= (1.0 - throttle)
+            * self.engine.braking_torque
+            * (rpm * crate::utils::inv(self.engine.max_rpm)).max(0.0);
+
+        // Clutch: the torque syncing the engine with the gearbox input, up to its capacity.
+        let inv_engine_inertia = crate::utils::inv(self.engine.inertia);
+        let inv_gearbox_inertia = ratio * ratio * crate::utils::inv(wheels_inertia);
+        self.clutch_torque = if ratio == 0.0 {
+            0.0
+        } else {
+            let capacity = self.clutch.clamp(0.0, 1.0) * self.clutch_max_torque;
+            let sync_torque = (self.engine_angular_velocity - wheels_angular_velocity * ratio
+                + engine_torque * dt * inv_engine_inertia)
+                * crate::utils::inv(dt * (inv_engine_inertia + inv_gearbox_inertia));
+            sync_torque.clamp(-capacity, capacity)
+        };
+
+        self.engine_angular_velocity +=
+            (engine_torque - self.clutch_torque) * dt * inv_engine_inertia;
+        self.engine_angular_velocity = self.engine_angular_velocity.clamp(
+            rpm_to_angular_velocity(self.engine.idle_rpm),
+            rpm_to_angular_velocity(self.engine.max_rpm),
+        );
+
+        // Differentials.
+        for wheel in wheels.iter_mut() {
+            wheel.engine_force = 0.0;
+        }
+
+        let output_torque = self.clutch_torque * ratio;
+        for axle in &self.axles {
+            let torque = output_torque * axle.torque_share * inv_total_share;
+            let transfer = axle.differential.transfer_torque(
+                dt,
+                &wheels[axle.left_wheel],
+                &wheels[axle.right_wheel],
+            );
+            let left = &mut wheels[axle.left_wheel];
+            left.engine_force += (torque * 0.5 - transfer) * crate::utils::inv(left.radius);
+            let right = &mut wheels[axle.right_wheel];
+            right.engine_force += (torque * 0.5 + transfer) * crate::utils::inv(right.radius);
+        }
+
+        // Brakes. `Wheel::brake` is the braking impulse at the contact point.
+        let brake_torque = self.brake.clamp(0.0, 1.0) * self.max_brake_torque;
+        for wheel in wheels.iter_mut() {
+            wheel.brake = brake_torque * dt * crate::utils::inv(wheel.radius);
+        }
+
+        let handbrake_torque = self.handbrake.clamp(0.0, 1.0) * self.max_handbrake_torque;
+        for &i in &self.handbrake_wheels {
+            let wheel = &mut wheels[i];
+            wheel.brake += handbrake_torque * dt * crate::utils::inv(wheel.radius);
+        }
+    }
+}
+
+impl Differential {
+    /// The torque moved from the left wheel to the right wheel.
+    fn transfer_torque(&self, dt: Real, left: &Wheel, right: &Wheel) -> Real {
+        let angular_velocity_diff = left.angular_velocity - right.angular_velocity;
+        // The torque making both wheels spin at the same speed.
+        let lock_torque = angular_velocity_diff
+            * crate::utils::inv(
+                dt * (crate::utils::inv(left.inertia) + crate::utils::inv(right.inertia)),
+            );
+
+        match *self {
+            Differential::Open => 0.0,
+            Differential::LimitedSlip { stiffness } => {
+                let max_transfer = lock_torque.abs();
+                (stiffness * angular_velocity_diff).clamp(-max_transfer, max_transfer)
+            }
+            Differential::Locked => lock_torque,
+        }
+    }
+}
+
+fn angular_velocity_to_rpm(angular_velocity: Real) -> Real {
+    angular_velocity * 60.0 / Real::two_pi()
+}
+
+fn rpm_to_angular_velocity(rpm: Real) -> Real {
+    rpm * Real::two_pi() / 60.0
+}
@@ -18,6 +18,11 @@ use core::ops;
 #[cfg(feature = "alloc")]
 use core::slice;
 
+#[cfg(feature = "serde-serialize")]
+use crate::data::change_log::ChangeLog;
+#[cfg(feature = "serde-serialize")]
+use crate::data::delta::{ChangeTracker, SlotsDelta};
+
 /// The `Arena` allows inserting and removing elements that are referred to by
 /// `Index`.
 ///
@@ -789,6 +794,73 @@ impl<T> Arena<T> {
             _ => None,
         }
     }
+
+    /// Maps every element of this arena with `f`, keeping their indices (and the order of the
+    /// free slots reused by the next insertions).
+    ///
+    /// Returns `None` if `f` returns `None` for any element.
+    pub(crate) fn try_map<U>(&self, mut f: impl FnMut(&T) -> Option<U>) -> Option<Arena<U>> {
+        let items = self
+            .items
+            .iter()
+            .map(|entry| match entry {
+                Entry::Free { next_free } => Some(Entry::Free {
+                    next_free: *next_free,
+                }),
+                Entry::Occupied { generation, value } => Some(Entry::Occupied {
+                    generation: *generation,
+                    value: f(value)?,
+                }),
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Arena {
+            items,
+            generation: self.generation,
+            free_list_head: self.free_list_head,
+            len: self.len,
+        })
+    }
+}
+
+/// The slots of an [`Arena`] that changed since the previous delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ArenaDelta<T> {
+    items: SlotsDelta<Entry<T>>,
+    generation: u32,
+    free_list_head: Option<u32>,
+    len: usize,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Clone> ArenaDelta<T> {
+    /// The number of slots this delta holds.
+    pub fn num_changed(&self) -> usize {
+        self.items.num_changed()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Clone> Arena<T> {
+    /// The slots marked in `log`, the change log of this arena, since the previous call with
+    /// the same `tracker`.
+    pub(crate) fn delta(&self, tracker: &mut ChangeTracker, log: &mut ChangeLog) -> ArenaDelta<T> {
+        ArenaDelta {
+            items: tracker.diff(log, &self.items),
+            generation: self.generation,
+            free_list_head: self.free_list_head,
+            len: self.len,
+        }
+    }
+
+    /// Brings this arena from the state of the previous delta snapshot to the state of `delta`,
+    /// marking the slots it sets in `log`.
+    pub(crate) fn apply_delta(&mut self, delta: &ArenaDelta<T>, log: &mut ChangeLog) {
+        delta.items.apply_logged(&mut self.items, log);
+        self.generation = delta.generation;
+        self.free_list_head = delta.free_list_head;
+        self.len = delta.len;
+    }
 }
 
 #[cfg(feature = "alloc")]
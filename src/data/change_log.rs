@@ -0,0 +1,104 @@
+//! Per-structure change recording, for the delta snapshots.
+
+#[cfg(feature = "serde-serialize")]
+use crate::alloc_prelude::*;
+use crate::data::arena::Index;
+
+/// Records the slots of an indexed container modified since the previous delta snapshot.
+///
+/// The structure owning the container (a rigid-body set, a joint set, the narrow-phase, etc.)
+/// marks the slots it modifies, so that a delta snapshot only visits those instead of
+/// comparing all of them (see [`ChangeTracker`](crate::data::delta::ChangeTracker)). Marking a
+/// slot is O(1) and a snapshot takes the slots marked since the previous one, so the log only
+/// belongs to the world it is part of, and to the tracker its snapshots are taken with.
+///
+/// Nothing is recorded before the first snapshot (a keyframe, holding every slot anyway), and
+/// nothing at all without the `serde-serialize` feature. The log isn't serialized.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ChangeLog {
+    #[cfg(feature = "serde-serialize")]
+    recording: bool,
+    /// One bit per slot, set for the slots listed in `marked`.
+    #[cfg(feature = "serde-serialize")]
+    flags: Vec<u64>,
+    /// The slots marked since the previous snapshot, in marking order.
+    #[cfg(feature = "serde-serialize")]
+    marked: Vec<u32>,
+}
+
+impl ChangeLog {
+    /// Marks the slot `i` as modified.
+    #[inline]
+    pub fn mark(&mut self, i: u32) {
+        #[cfg(feature = "serde-serialize")]
+        if self.recording {
+            self.mark_recorded(i);
+        }
+        #[cfg(not(feature = "serde-serialize"))]
+        let _ = i;
+    }
+
+    /// Marks the slot of the arena index `index` as modified.
+    #[inline]
+    pub fn mark_index(&mut self, index: Index) {
+        self.mark(index.into_raw_parts().0);
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ChangeLog {
+    fn mark_recorded(&mut self, i: u32) {
+        let word = i as usize / 64;
+        let bit = 1 << (i % 64);
+        if word >= self.flags.len() {
+            self.flags.resize(word + 1, 0);
+        }
+        if self.flags[word] & bit == 0 {
+            self.flags[word] |= bit;
+            self.marked.push(i);
+        }
+    }
+
+    /// Starts recording, forgetting the slots marked so far.
+    pub fn restart(&mut self) {
+        self.recording = true;
+        let _ = self.take();
+    }
+
+    /// The slots marked since the last call, in increasing order, which are unmarked.
+    pub fn take(&mut self) -> Vec<u32> {
+        let mut marked = core::mem::take(&mut self.marked);
+        for i in &marked {
+            self.flags[*i as usize / 64] &= !(1 << (i % 64));
+        }
+        marked.sort_unstable();
+        marked
+    }
+}
+
+/// Records whether a structure that can't be tracked per slot was modified since the previous
+/// delta snapshot.
+///
+/// Nothing is recorded without the `serde-serialize` feature, and the flag isn't serialized.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct ChangeFlag {
+    #[cfg(feature = "serde-serialize")]
+    changed: bool,
+}
+
+impl ChangeFlag {
+    /// Marks the structure as modified.
+    #[inline]
+    pub fn mark(&mut self) {
+        #[cfg(feature = "serde-serialize")]
+        {
+            self.changed = true;
+        }
+    }
+
+    /// Whether the structure was marked since the last call.
+    #[cfg(feature = "serde-serialize")]
+    pub fn take(&mut self) -> bool {
+        core::mem::take(&mut self.changed)
+    }
+}
@@ -1,5 +1,10 @@
 use crate::alloc_prelude::*;
 use crate::data::arena::Index;
+#[cfg(feature = "serde-serialize")]
+use crate::data::{
+    change_log::ChangeLog,
+    delta::{ChangeTracker, SlotsDelta},
+};
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Default)]
@@ -8,6 +13,10 @@ pub struct Coarena<T> {
     data: Vec<(u32, T)>,
 }
 
+/// The elements of a [`Coarena`] that changed since the previous delta snapshot.
+#[cfg(feature = "serde-serialize")]
+pub(crate) type CoarenaDelta<T> = SlotsDelta<(u32, T)>;
+
 impl<T> Coarena<T> {
     /// A coarena with no element.
     pub fn new() -> Self {
@@ -158,3 +167,22 @@ impl<T> Coarena<T> {
         (&mut elt1.1, &mut elt2.1)
     }
 }
+
+#[cfg(feature = "serde-serialize")]
+impl<T: Clone> Coarena<T> {
+    /// The elements marked in `log`, the change log of this coarena, since the previous call
+    /// with the same `tracker`.
+    pub(crate) fn delta(
+        &self,
+        tracker: &mut ChangeTracker,
+        log: &mut ChangeLog,
+    ) -> CoarenaDelta<T> {
+        tracker.diff(log, &self.data)
+    }
+
+    /// Brings this coarena from the state of the previous delta snapshot to the state of
+    /// `delta`, marking the elements it sets in `log`.
+    pub(crate) fn apply_delta(&mut self, delta: &CoarenaDelta<T>, log: &mut ChangeLog) {
+        delta.apply_logged(&mut self.data, log);
+    }
+}
@@ -0,0 +1,606 @@
+//! Change tracking for delta snapshots.
+//!
+//! A delta snapshot only holds the parts of a structure that changed since the previous
+//! snapshot taken with the same tracker. Changes are found in one of two ways:
+//! - the structures owning indexed containers (the rigid-body and collider sets, which build
+//!   on the modified-objects lists they already keep for the physics pipeline, and the joint
+//!   sets, narrow-phase and broad-phase) mark the slots they modify in a [`ChangeLog`] of their
+//!   own, so a snapshot only visits the slots marked since the previous one;
+//! - the other parts, rewritten in place by the pipeline's internal passes, are compared
+//!   through a fingerprint (a 128-bit hash) of their serialized form, per element where they
+//!   are made of several. Two states are then considered equal exactly when they would
+//!   serialize to the same bytes.
+
+use crate::alloc_prelude::*;
+use crate::data::change_log::{ChangeFlag, ChangeLog};
+use core::fmt;
+use serde::Serialize;
+use serde::ser::{self, Serializer};
+
+const FNV128_OFFSET_BASIS: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const FNV128_PRIME: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// A 128-bit FNV-1a hash of the serialized form of values.
+///
+/// It implements a [`Serializer`] that hashes what it is given instead of writing it anywhere,
+/// so fingerprinting doesn't allocate.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Fingerprinter(u128);
+
+impl Fingerprinter {
+    pub fn new() -> Self {
+        Self(FNV128_OFFSET_BASIS)
+    }
+
+    pub fn finish(&self) -> u128 {
+        self.0
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u128::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV128_PRIME);
+        }
+    }
+
+    pub fn write<T: Serialize + ?Sized>(&mut self, value: &T) {
+        // Hashing never fails: only a `Serialize` impl can report an error, and the hash of
+        // what was written before it still identifies the value.
+        let _ = value.serialize(self);
+    }
+}
+
+/// The fingerprint of the serialized form of `value`.
+pub(crate) fn fingerprint<T: Serialize + ?Sized>(value: &T) -> u128 {
+    let mut fingerprinter = Fingerprinter::new();
+    fingerprinter.write(value);
+    fingerprinter.finish()
+}
+
+/// Tracks the fingerprints of the slots of a vector-like container between snapshots.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SlotTracker {
+    fingerprints: Vec<u128>,
+}
+
+impl SlotTracker {
+    /// The slots that changed since the last call, with `fingerprint` computing the fingerprint
+    /// of a slot.
+    pub fn diff<T: Clone>(
+        &mut self,
+        slots: &[T],
+        mut fingerprint: impl FnMut(&T) -> u128,
+    ) -> SlotsDelta<T> {
+        self.fingerprints.truncate(slots.len());
+        let mut changed = Vec::new();
+        for (i, slot) in slots.iter().enumerate() {
+            let new_fingerprint = fingerprint(slot);
+            match self.fingerprints.get_mut(i) {
+                Some(old_fingerprint) if *old_fingerprint == new_fingerprint => continue,
+                Some(old_fingerprint) => *old_fingerprint = new_fingerprint,
+                None => self.fingerprints.push(new_fingerprint),
+            }
+            changed.push((i as u32, slot.clone()));
+        }
+        SlotsDelta {
+            len: slots.len(),
+            changed,
+        }
+    }
+
+    /// The slots that changed since the last call, fingerprinted from their serialized form.
+    pub fn diff_serialized<T: Clone + Serialize>(&mut self, slots: &[T]) -> SlotsDelta<T> {
+        self.diff(slots, fingerprint)
+    }
+}
+
+/// The slots of a vector-like container that changed since the previous snapshot.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SlotsDelta<T> {
+    len: usize,
+    changed: Vec<(u32, T)>,
+}
+
+impl<T: Clone> SlotsDelta<T> {
+    /// The number of slots this delta holds.
+    pub fn num_changed(&self) -> usize {
+        self.changed.len()
+    }
+
+    /// The slots this delta holds, with their indices.
+    pub fn changed(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.changed.iter().map(|(i, slot)| (*i as usize, slot))
+    }
+
+    /// Brings `slots` from the state of the previous snapshot to the state of this one.
+    ///
+    /// Panics if `slots` isn't in the state of the previous snapshot (the slots created since
+    /// then can't be appended).
+    pub fn apply(&self, slots: &mut Vec<T>) {
+        slots.truncate(self.len);
+        for (i, slot) in &self.changed {
+            let i = *i as usize;
+            if i < slots.len() {
+                slots[i] = slot.clone();
+            } else {
+                assert_eq!(
+                    i,
+                    slots.len(),
+                    "delta snapshots must be applied in order, starting with a keyframe"
+                );
+                slots.push(slot.clone());
+            }
+        }
+        assert_eq!(
+            slots.len(),
+            self.len,
+            "delta snapshots must be applied in order, starting with a keyframe"
+        );
+    }
+
+    /// Same as [`Self::apply`], also marking the slots it sets in `log`, the change log of
+    /// `slots`.
+    pub fn apply_logged(&self, slots: &mut Vec<T>, log: &mut ChangeLog) {
+        self.apply(slots);
+        for (i, _) in &self.changed {
+            log.mark(*i);
+        }
+    }
+}
+
+/// Tracks the snapshots of a structure recording its changes in a [`ChangeLog`] or a
+/// [`ChangeFlag`].
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct ChangeTracker {
+    keyframe_taken: bool,
+    /// The number of slots at the previous snapshot.
+    len: usize,
+}
+
+impl ChangeTracker {
+    /// Starts a new snapshot, returning whether it is a keyframe.
+    fn begin(&mut self) -> bool {
+        !core::mem::replace(&mut self.keyframe_taken, true)
+    }
+
+    /// Whether `flag` was marked since the last call (always true the first time).
+    pub fn changed(&mut self, flag: &mut ChangeFlag) -> bool {
+        let changed = flag.take();
+        self.begin() || changed
+    }
+
+    /// The slots marked in `log` since the last call, or all of them the first time.
+    ///
+    /// The slots the container grew by since the last call are included too, so growing it
+    /// (e.g. reserving free slots) doesn't need to be marked.
+    pub fn diff<T: Clone>(&mut self, log: &mut ChangeLog, slots: &[T]) -> SlotsDelta<T> {
+        let new_slots = if self.begin() {
+            log.restart();
+            0..slots.len()
+        } else {
+            self.len.min(slots.len())..slots.len()
+        };
+        let old_len = new_slots.start;
+        let changed = log
+            .take()
+            .into_iter()
+            .filter(|i| (*i as usize) < old_len)
+            .chain(new_slots.map(|i| i as u32))
+            .map(|i| (i, slots[i as usize].clone()))
+            .collect();
+        self.len = slots.len();
+        SlotsDelta {
+            len: slots.len(),
+            changed,
+        }
+    }
+}
+
+/// Tracks the fingerprint of a whole value between snapshots.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct ValueTracker(Option<u128>);
+
+impl ValueTracker {
+    /// A copy of `value` if it changed since the last call.
+    pub fn diff<T: Clone + Serialize>(&mut self, value: &T) -> Option<T> {
+        self.diff_with(value, fingerprint(value))
+    }
+
+    /// A copy of `value` if its fingerprint, computed by the caller, changed since the last call.
+    pub fn diff_with<T: Clone>(&mut self, value: &T, new_fingerprint: u128) -> Option<T> {
+        if self.0 == Some(new_fingerprint) {
+            None
+        } else {
+            self.0 = Some(new_fingerprint);
+            Some(value.clone())
+        }
+    }
+}
+
+/// Sets `target` to the value stored in a delta, if it holds one.
+pub(crate) fn apply_value<T: Clone>(target: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *target = value.clone();
+    }
+}
+
+/// The error type of [`Fingerprinter`], only produced by `Serialize` impls that fail.
+#[derive(Debug)]
+pub(crate) struct FingerprintError;
+
+impl fmt::Display for FingerprintError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the value failed to serialize")
+    }
+}
+
+impl core::error::Error for FingerprintError {}
+
+impl ser::Error for FingerprintError {
+    fn custom<M: fmt::Display>(_: M) -> Self {
+        FingerprintError
+    }
+}
+
+/// A sequence, tuple, map or struct being fingerprinted: its elements, then their count.
+pub(crate) struct Compound<'a> {
+    fingerprinter: &'a mut Fingerprinter,
+    len: u64,
+}
+
+impl Compound<'_> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FingerprintError> {
+        self.len += 1;
+        value.serialize(&mut *self.fingerprinter)
+    }
+
+    fn end(self) -> Result<(), FingerprintError> {
+        self.fingerprinter.write_bytes(&self.len.to_le_bytes());
+        Ok(())
+    }
+}
+
+impl<'a> Serializer for &'a mut Fingerprinter {
+    type Ok = ();
+    type Error = FingerprintError;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<(), FingerprintError> {
+        self.write_bytes(&[v as u8]);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), FingerprintError> {
+        self.write_bytes(&[v]);
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), FingerprintError> {
+        self.write_bytes(&v.to_bits().to_le_bytes());
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), FingerprintError> {
+        self.serialize_u32(v as u32)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), FingerprintError> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), FingerprintError> {
+        self.write_bytes(&(v.len() as u64).to_le_bytes());
+        self.write_bytes(v);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), FingerprintError> {
+        self.serialize_u8(0)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), FingerprintError> {
+        self.serialize_u8(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), FingerprintError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), FingerprintError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+    ) -> Result<(), FingerprintError> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), FingerprintError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), FingerprintError> {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Compound<'a>, FingerprintError> {
+        Ok(Compound {
+            fingerprinter: self,
+            len: 0,
+        })
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_seq(None)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_seq(None)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_u32(variant_index)?;
+        self.serialize_seq(None)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_seq(None)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_seq(None)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        variant_index: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Compound<'a>, FingerprintError> {
+        self.serialize_u32(variant_index)?;
+        self.serialize_seq(None)
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut *self.fingerprinter)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn skip_field(&mut self, _: &'static str) -> Result<(), Self::Error> {
+        // Distinguishes a skipped field from a present one.
+        self.len += 1 << 32;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = FingerprintError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        self.element(value)
+    }
+
+    fn skip_field(&mut self, _: &'static str) -> Result<(), Self::Error> {
+        self.len += 1 << 32;
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Compound::end(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SlotTracker, ValueTracker, fingerprint};
+    use crate::alloc_prelude::*;
+
+    #[test]
+    fn fingerprints_tell_serialized_forms_apart() {
+        assert_eq!(fingerprint(&(1u32, 2.0f32)), fingerprint(&(1u32, 2.0f32)));
+        assert_ne!(fingerprint(&0.0f32), fingerprint(&-0.0f32));
+        // Element boundaries are part of the fingerprint.
+        assert_ne!(
+            fingerprint(&vec![vec![1u8], vec![]]),
+            fingerprint(&vec![vec![], vec![1u8]])
+        );
+        assert_ne!(fingerprint(&Some(0u8)), fingerprint(&None::<u8>));
+    }
+
+    #[test]
+    fn slot_deltas_rebuild_the_tracked_vector() {
+        let mut tracker = SlotTracker::default();
+        let mut states = vec![vec![1, 2, 3], vec![1, 5, 3, 4, 6], vec![1, 5], vec![1, 5]];
+        let mut restored = vec![9, 9, 9, 9, 9, 9, 9];
+
+        let keyframe = tracker.diff_serialized(&states[0]);
+        assert_eq!(keyframe.num_changed(), 3);
+        keyframe.apply(&mut restored);
+        assert_eq!(restored, states[0]);
+
+        for (i, state) in states.drain(..).enumerate().skip(1) {
+            let delta = tracker.diff_serialized(&state);
+            assert_eq!(delta.num_changed(), [3, 3, 0, 0][i]);
+            delta.apply(&mut restored);
+            assert_eq!(restored, state);
+        }
+
+        let mut value = ValueTracker::default();
+        assert_eq!(value.diff(&1u32), Some(1));
+        assert_eq!(value.diff(&1u32), None);
+        assert_eq!(value.diff(&2u32), Some(2));
+    }
+}
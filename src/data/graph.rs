@@ -8,6 +8,9 @@ use crate::alloc_prelude::*;
 use core::cmp::max;
 use core::ops::{Index, IndexMut};
 
+#[cfg(feature = "serde-serialize")]
+use crate::data::delta::{SlotTracker, SlotsDelta};
+
 /// Node identifier.
 #[derive(Copy, Clone, Default, PartialEq, PartialOrd, Eq, Ord, Hash, Debug)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -784,3 +787,52 @@ where
         self.index == rhs.index && self.weight == rhs.weight
     }
 }
+
+/// The nodes and edges of a [`Graph`] that changed since the previous delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct GraphDelta<N, E> {
+    nodes: SlotsDelta<Node<N>>,
+    edges: SlotsDelta<Edge<E>>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<N: Clone, E: Clone> GraphDelta<N, E> {
+    /// The number of edges this delta holds.
+    pub fn num_changed_edges(&self) -> usize {
+        self.edges.num_changed()
+    }
+
+    /// The indices of the edges this delta holds.
+    pub fn changed_edges(&self) -> impl Iterator<Item = usize> + '_ {
+        self.edges.changed().map(|(i, _)| i)
+    }
+}
+
+/// Remembers what a [`Graph`] looked like at its last delta snapshot.
+///
+/// Graph edges are rewritten in place by the pipeline's internal passes, so they are compared
+/// by fingerprint, edge per edge.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GraphDeltaTracker {
+    nodes: SlotTracker,
+    edges: SlotTracker,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl<N: Clone + serde::Serialize, E: Clone + serde::Serialize> Graph<N, E> {
+    /// The nodes and edges that changed since the previous call with the same `tracker`.
+    pub(crate) fn delta(&self, tracker: &mut GraphDeltaTracker) -> GraphDelta<N, E> {
+        GraphDelta {
+            nodes: tracker.nodes.diff_serialized(&self.nodes),
+            edges: tracker.edges.diff_serialized(&self.edges),
+        }
+    }
+
+    /// Brings this graph from the state of the previous delta snapshot to the state of `delta`.
+    pub(crate) fn apply_delta(&mut self, delta: &GraphDelta<N, E>) {
+        delta.nodes.apply(&mut self.nodes);
+        delta.edges.apply(&mut self.edges);
+    }
+}
@@ -5,17 +5,25 @@ pub use self::arena::Arena;
 pub use self::arena::Index;
 #[cfg(feature = "alloc")]
 pub use self::coarena::Coarena;
+#[cfg(feature = "serde-serialize")]
+pub(crate) use self::coarena::CoarenaDelta;
 #[cfg(feature = "alloc")]
 pub use self::modified_objects::{HasModifiedFlag, ModifiedObjects};
+pub use self::state_hasher::StateHasher;
 
 pub mod arena;
 #[cfg(feature = "alloc")]
+pub(crate) mod change_log;
+#[cfg(feature = "alloc")]
 mod coarena;
+#[cfg(feature = "serde-serialize")]
+pub(crate) mod delta;
 #[cfg(feature = "alloc")]
 pub(crate) mod graph;
 #[cfg(feature = "alloc")]
 mod modified_objects;
 #[cfg(feature = "alloc")]
 pub mod pubsub;
+mod state_hasher;
 #[cfg(feature = "alloc")]
 pub(crate) mod union_find;
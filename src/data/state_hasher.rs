@@ -0,0 +1,131 @@
+use crate::data::Index;
+use crate::math::{Pose, Real, Rotation, SPATIAL_DIM, SpatialVector, Vector};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The hasher behind the `state_hash` methods (e.g. [`PhysicsWorld::state_hash`]).
+///
+/// It is a 64-bit FNV-1a hash fed with a sequence of 64-bit words, each one written as its
+/// eight little-endian bytes. Every value is converted to words as follows:
+/// - integers and handles' indices/generations are zero-extended to `u64`, a handle being the
+///   single word `(generation << 32) | index`;
+/// - booleans are `0` or `1`;
+/// - a [`Real`] is its IEEE-754 bit pattern (`Real::to_bits`) zero-extended to `u64`, so `0.0`
+///   and `-0.0` hash differently and `f32` and `f64` builds produce different hashes;
+/// - a vector is its components in order (`x`, `y`, then `z` in 3D);
+/// - a spatial vector (joint impulses or coordinates) is its linear then angular components;
+/// - a rotation is `re`, `im` in 2D and `x`, `y`, `z`, `w` in 3D;
+/// - a pose is its translation followed by its rotation.
+///
+/// This definition is part of the API: with the `enhanced-determinism` feature enabled, two
+/// bit-identical states give the same hash on every platform and with every release of a
+/// given major version.
+///
+/// [`PhysicsWorld::state_hash`]: crate::pipeline::PhysicsWorld::state_hash
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StateHasher(u64);
+
+impl Default for StateHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StateHasher {
+    /// A hasher that didn't hash anything yet.
+    pub fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    /// The hash of everything written so far.
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+
+    /// Hashes a 64-bit word.
+    pub fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Hashes a `usize`, as a 64-bit word.
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64)
+    }
+
+    /// Hashes a boolean, as the word `0` or `1`.
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u64(u64::from(value))
+    }
+
+    /// Hashes an arena index, as the word `(generation << 32) | index`.
+    pub fn write_index(&mut self, index: Index) {
+        let (id, generation) = index.into_raw_parts();
+        self.write_u64((u64::from(generation) << 32) | u64::from(id))
+    }
+
+    /// Hashes the bit pattern of a real number.
+    pub fn write_real(&mut self, value: Real) {
+        self.write_u64(u64::from(value.to_bits()))
+    }
+
+    /// Hashes the bit patterns of a sequence of real numbers.
+    pub fn write_reals<'a>(&mut self, values: impl IntoIterator<Item = &'a Real>) {
+        for value in values {
+            self.write_real(*value);
+        }
+    }
+
+    /// Hashes the components of a vector.
+    pub fn write_vector(&mut self, value: Vector) {
+        self.write_reals(&value.to_array())
+    }
+
+    /// Hashes the components of a spatial vector, linear part first.
+    pub fn write_spatial_vector(&mut self, value: &SpatialVector) {
+        for i in 0..SPATIAL_DIM {
+            self.write_real(value[i]);
+        }
+    }
+
+    /// Hashes the components of a rotation.
+    pub fn write_rotation(&mut self, value: Rotation) {
+        #[cfg(feature = "dim2")]
+        self.write_reals(&[value.re, value.im]);
+        #[cfg(feature = "dim3")]
+        self.write_reals(&[value.x, value.y, value.z, value.w]);
+    }
+
+    /// Hashes the translation, then the rotation, of a pose.
+    pub fn write_pose(&mut self, value: &Pose) {
+        self.write_vector(value.translation);
+        self.write_rotation(value.rotation);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StateHasher;
+
+    #[test]
+    fn state_hasher_is_fnv1a_over_little_endian_words() {
+        // The reference FNV-1a 64 hash of the eight bytes `01 00 00 00 00 00 00 00`.
+        let mut hasher = StateHasher::new();
+        hasher.write_u64(1);
+        let mut expected: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in [1u8, 0, 0, 0, 0, 0, 0, 0] {
+            expected = (expected ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3);
+        }
+        assert_eq!(hasher.finish(), expected);
+        assert_eq!(StateHasher::new().finish(), 0xcbf2_9ce4_8422_2325);
+
+        let mut a = StateHasher::new();
+        a.write_real(0.0);
+        let mut b = StateHasher::new();
+        b.write_real(-0.0);
+        assert_ne!(a.finish(), b.finish());
+    }
+}
@@ -1,5 +1,7 @@
 use crate::alloc_prelude::*;
-use crate::dynamics::{IntegrationParameters, IslandManager, RigidBodySet};
+#[cfg(doc)]
+use crate::dynamics::CcdMode;
+use crate::dynamics::{IntegrationParameters, IslandManager, RigidBodyHandle, RigidBodySet};
 use crate::geometry::{
     BroadPhaseBvh, Collider, ColliderHandle, ColliderSet, CollisionEvent, NarrowPhase,
 };
@@ -37,6 +39,17 @@ pub struct CCDSolver {
 /// itself — `None` past [`FIXED_TARGETS_LIST_MAX`], where the sweep queries the full BVH.
 type FixedTargetsCache = (Real, Option<Vec<(ColliderHandle, Aabb)>>);
 
+/// An impact found by [`CCDSolver::find_impacts`].
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct PredictedImpact {
+    /// The fast body.
+    pub body: RigidBodyHandle,
+    /// The body it hits, `None` for a parentless collider.
+    pub target: Option<RigidBodyHandle>,
+    /// The time of impact, in `[0, dt)`.
+    pub toi: Real,
+}
+
 impl CCDSolver {
     /// Initializes a new CCD solver
     pub fn new() -> Self {
@@ -89,6 +102,37 @@ impl CCDSolver {
         ccd_active
     }
 
+    /// Updates the set of bodies that need CCD before the solve of a
+    /// [`CcdMode::ImpactSubstepping`] substep.
+    ///
+    /// Unlike the pre-solve [`Self::update_ccd_active_flags`], the motion is estimated from the
+    /// current velocities rather than from the last solve's: the impacts must be found before
+    /// they are solved, including those of a body the user just launched.
+    ///
+    /// Returns `true` if any rigid-body must have CCD resolved.
+    pub(crate) fn update_impact_ccd_active_flags(
+        &self,
+        islands: &IslandManager,
+        bodies: &mut RigidBodySet,
+        dt: Real,
+    ) -> bool {
+        let mut ccd_active = false;
+
+        for handle in islands.active_bodies() {
+            let rb = bodies.index_mut_internal(handle);
+
+            if rb.is_dynamic() {
+                let moving_fast =
+                    rb.ccd
+                        .is_moving_fast(dt, &rb.vels, Some(&rb.forces), rb.mprops.max_extent());
+                rb.ccd.ccd_active = moving_fast;
+                ccd_active = ccd_active || moving_fast;
+            }
+        }
+
+        ccd_active
+    }
+
     /// Find the first time a CCD-active body has a non-sensor collider hitting another
     /// non-sensor collider, for the multi-substep splitter.
     ///
@@ -106,6 +150,40 @@ impl CCDSolver {
         narrow_phase: &NarrowPhase,
         hooks: &dyn PhysicsHooks,
     ) -> Option<Real> {
+        let fast_bodies: Vec<_> = islands
+            .active_bodies()
+            .filter(|h| bodies[*h].ccd.ccd_active)
+            .collect();
+        self.find_impacts(
+            dt,
+            params,
+            &fast_bodies,
+            bodies,
+            colliders,
+            broad_phase,
+            narrow_phase,
+            hooks,
+        )
+        .into_iter()
+        .map(|impact| impact.toi)
+        .reduce(Real::min)
+    }
+
+    /// Sweeps each of the `fast_bodies` along its predicted motion over `dt`, and returns the
+    /// impacts of those hitting a non-sensor collider.
+    #[profiling::function]
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn find_impacts(
+        &mut self,
+        dt: Real, // NOTE: this doesn’t necessarily match the `params.dt`.
+        params: &IntegrationParameters,
+        fast_bodies: &[RigidBodyHandle],
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        broad_phase: &mut BroadPhaseBvh,
+        narrow_phase: &NarrowPhase,
+        hooks: &dyn PhysicsHooks,
+    ) -> Vec<PredictedImpact> {
         // NOTE: broad-phase AABBs are NOT enlarged to the swept volumes: only the fast body's
         // query box is swept (per collider, below); targets keep their regular fat AABBs.
         // Swept AABBs written into the tree would leak into the next step (pair explosion).
@@ -118,12 +196,8 @@ impl CCDSolver {
         let (bvh, dispatcher) = (query_pipeline.bvh, query_pipeline.dispatcher);
 
         let linear_slop = params.allowed_linear_error();
-        let fast_bodies: Vec<_> = islands
-            .active_bodies()
-            .filter(|h| bodies[*h].ccd.ccd_active)
-            .collect();
 
-        let fractions = map_bodies_parallel(&fast_bodies, hooks, |handle, hooks| {
+        let results = map_bodies_parallel(fast_bodies, hooks, |handle, hooks| {
             let rb1 = &bodies[handle];
             // `next_position` isn't solved yet: sweep to the forces/velocities integration.
             let predicted_body_pos =
@@ -141,11 +215,17 @@ impl CCDSolver {
                 linear_slop,
                 PseudoHitMode::Ignore,
             )
-            .fraction
         });
 
-        let min_fraction = fractions.into_iter().fold(1.0, Real::min);
-        (min_fraction < 1.0).then_some(min_fraction * dt)
+        results
+            .into_iter()
+            .filter(|result| result.fraction < 1.0)
+            .map(|result| PredictedImpact {
+                body: result.handle,
+                target: result.target,
+                toi: result.fraction * dt,
+            })
+            .collect()
     }
 
     /// Runs the continuous-collision pass on all fast bodies and clamps their `next_position`
@@ -275,6 +275,8 @@ pub(super) struct BodyContinuousResult {
     pub(super) handle: RigidBodyHandle,
     /// The earliest solid impact fraction in `[0, 1]`; `1.0` if the body sweeps freely.
     pub(super) fraction: Real,
+    /// The body hit at `fraction`, `None` if the body sweeps freely or hits a parentless collider.
+    pub(super) target: Option<RigidBodyHandle>,
     pub(super) pseudo_hits: Vec<PseudoHit>,
 }
 
@@ -518,6 +520,7 @@ pub(super) fn sweep_fast_body(
 ) -> BodyContinuousResult {
     let rb1 = &bodies[handle];
     let mut fraction: Real = 1.0;
+    let mut target = None;
     let mut pseudo_hits = Vec::new();
 
     for ch1 in &rb1.colliders.0 {
@@ -590,6 +593,7 @@ pub(super) fn sweep_fast_body(
                     });
                 } else {
                     fraction = hit_fraction;
+                    target = bh2;
                 }
             }
         };
@@ -613,6 +617,7 @@ pub(super) fn sweep_fast_body(
     BodyContinuousResult {
         handle,
         fraction,
+        target,
         pseudo_hits,
     }
 }
@@ -0,0 +1,542 @@
+//! Force fields: environmental forces evaluated by the physics pipeline for every awake
+//! dynamic body, at each solver substep.
+
+use crate::alloc_prelude::*;
+use core::any::Any;
+
+use crate::data::arena::Arena;
+use crate::dynamics::{RigidBody, RigidBodyHandle};
+use crate::geometry::{Collider, ColliderHandle, ColliderSet};
+use crate::math::{AngVector, Pose, Real, Vector};
+#[cfg(feature = "dim3")]
+use crate::na::RealField;
+use crate::utils::CrossProduct;
+use parry::utils::hashmap::HashMap;
+
+/// The unique handle of a force field added to a [`ForceFieldSet`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[repr(transparent)]
+pub struct ForceFieldHandle(pub crate::data::arena::Index);
+
+impl ForceFieldHandle {
+    /// Converts this handle into its (index, generation) components.
+    pub fn into_raw_parts(self) -> (u32, u32) {
+        self.0.into_raw_parts()
+    }
+
+    /// Reconstructs an handle from its (index, generation) components.
+    pub fn from_raw_parts(id: u32, generation: u32) -> Self {
+        Self(crate::data::arena::Index::from_raw_parts(id, generation))
+    }
+}
+
+/// The state of a rigid-body a [`ForceField`] is evaluated on.
+///
+/// The pose and velocities are the ones of the current solver substep; they generally differ
+/// from the ones stored in [`Self::body`], which are the values at the beginning of the
+/// timestep.
+pub struct ForceFieldBody<'a> {
+    /// The handle of the rigid-body.
+    pub handle: RigidBodyHandle,
+    /// The rigid-body, as it was at the beginning of the timestep.
+    pub body: &'a RigidBody,
+    /// The collider set, for fields depending on the rigid-body's colliders.
+    pub colliders: &'a ColliderSet,
+    /// The world-space pose of the rigid-body.
+    pub position: Pose,
+    /// The world-space center of mass of the rigid-body.
+    pub center_of_mass: Vector,
+    /// The linear velocity of the rigid-body's center of mass.
+    pub linvel: Vector,
+    /// The angular velocity of the rigid-body.
+    pub angvel: AngVector,
+}
+
+impl ForceFieldBody<'_> {
+    /// The mass of the rigid-body.
+    pub fn mass(&self) -> Real {
+        self.body.mass()
+    }
+
+    /// The velocity of the given world-space point attached to the rigid-body.
+    pub fn velocity_at_point(&self, point: Vector) -> Vector {
+        self.linvel + self.angvel.gcross(point - self.center_of_mass)
+    }
+
+    /// The world-space pose of one of the rigid-body's colliders.
+    pub fn collider_position(&self, collider: &Collider) -> Pose {
+        match collider.position_wrt_parent() {
+            Some(pos_wrt_parent) => self.position * *pos_wrt_parent,
+            None => *collider.position(),
+        }
+    }
+}
+
+/// The force and torque accumulated by the force fields acting on a rigid-body.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FieldForce {
+    /// The force applied at the rigid-body's center of mass.
+    pub force: Vector,
+    /// The torque applied around the rigid-body's center of mass.
+    pub torque: AngVector,
+    center_of_mass: Vector,
+}
+
+impl FieldForce {
+    /// A zero force on a rigid-body with the given world-space center of mass.
+    pub fn new(center_of_mass: Vector) -> Self {
+        Self {
+            force: Vector::ZERO,
+            torque: AngVector::default(),
+            center_of_mass,
+        }
+    }
+
+    /// Adds a force applied at the rigid-body's center of mass.
+    pub fn add_force(&mut self, force: Vector) {
+        self.force += force;
+    }
+
+    /// Adds a torque.
+    pub fn add_torque(&mut self, torque: AngVector) {
+        self.torque += torque;
+    }
+
+    /// Adds a force applied at the given world-space point.
+    pub fn add_force_at_point(&mut self, force: Vector, point: Vector) {
+        self.force += force;
+        self.torque += (point - self.center_of_mass).gcross(force);
+    }
+}
+
+/// An environmental force applied by the physics pipeline to every awake dynamic body.
+///
+/// Force fields are registered in the pipeline's [`ForceFieldSet`] and evaluated at each
+/// solver substep (including the extra substeps of the bodies with
+/// [`RigidBody::additional_solver_iterations`]) with the substep's pose and velocities of the
+/// body, so velocity-dependent fields like drag stay stable. They are evaluated in parallel
+/// when the `parallel` feature is enabled, hence the `Send + Sync` requirement.
+///
+/// Bodies attached to a multibody aren't affected by force fields.
+pub trait ForceField: Any + Send + Sync {
+    /// Adds to `force` the force and torque this field applies to `body`.
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce);
+
+    /// A boxed copy of this field, or `None` (the default) if it can't be copied.
+    ///
+    /// Copyable fields are part of the initial state of a
+    /// [`Replay`](crate::pipeline::Replay); the built-in fields are all copyable.
+    fn clone_dyn(&self) -> Option<Box<dyn ForceField>> {
+        None
+    }
+}
+
+/// The set of force fields applied by a [`PhysicsPipeline`](crate::pipeline::PhysicsPipeline).
+///
+/// Force fields are part of the pipeline's workspace: they are not serialized with a
+/// [`PhysicsWorld`](crate::pipeline::PhysicsWorld).
+#[derive(Default)]
+pub struct ForceFieldSet {
+    fields: Arena<Box<dyn ForceField>>,
+}
+
+impl ForceFieldSet {
+    /// An empty set of force fields.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of force fields in this set.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Does this set contain no force field?
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Is the given handle the one of a force field of this set?
+    pub fn contains(&self, handle: ForceFieldHandle) -> bool {
+        self.fields.contains(handle.0)
+    }
+
+    /// Adds a force field to this set.
+    pub fn insert(&mut self, field: impl ForceField + 'static) -> ForceFieldHandle {
+        ForceFieldHandle(self.fields.insert(Box::new(field)))
+    }
+
+    /// Removes a force field from this set.
+    pub fn remove(&mut self, handle: ForceFieldHandle) -> Option<Box<dyn ForceField>> {
+        self.fields.remove(handle.0)
+    }
+
+    /// Removes all the force fields from this set.
+    pub fn clear(&mut self) {
+        self.fields.clear();
+    }
+
+    /// The force field with the given handle.
+    pub fn get(&self, handle: ForceFieldHandle) -> Option<&dyn ForceField> {
+        self.fields.get(handle.0).map(|field| &**field)
+    }
+
+    /// The force field with the given handle.
+    pub fn get_mut(&mut self, handle: ForceFieldHandle) -> Option<&mut (dyn ForceField + 'static)> {
+        self.fields.get_mut(handle.0).map(|field| &mut **field)
+    }
+
+    /// The force field with the given handle, if it has the type `T`.
+    pub fn field<T: ForceField>(&self, handle: ForceFieldHandle) -> Option<&T> {
+        let field: &dyn Any = &**self.fields.get(handle.0)?;
+        field.downcast_ref()
+    }
+
+    /// The force field with the given handle, if it has the type `T`.
+    ///
+    /// Use this to modify the parameters of a built-in field, e.g., to change the wind
+    /// velocity of a [`WindField`] over time.
+    pub fn field_mut<T: ForceField>(&mut self, handle: ForceFieldHandle) -> Option<&mut T> {
+        let field: &mut dyn Any = &mut **self.fields.get_mut(handle.0)?;
+        field.downcast_mut()
+    }
+
+    /// Iterates through all the force fields of this set.
+    pub fn iter(&self) -> impl Iterator<Item = (ForceFieldHandle, &dyn ForceField)> {
+        self.fields
+            .iter()
+            .map(|(id, field)| (ForceFieldHandle(id), &**field))
+    }
+
+    /// A copy of this set with the same handles, or `None` if one of its fields can't be copied
+    /// (see [`ForceField::clone_dyn`]).
+    pub fn try_clone(&self) -> Option<Self> {
+        let fields = self.fields.try_map(|field| field.clone_dyn())?;
+        Some(Self { fields })
+    }
+
+    /// The total force and torque applied by all the fields of this set to `body`.
+    pub fn compute_force(&self, body: &ForceFieldBody) -> FieldForce {
+        let mut force = FieldForce::new(body.center_of_mass);
+        for (_, field) in self.fields.iter() {
+            field.apply(body, &mut force);
+        }
+        force
+    }
+}
+
+/// The gravity field of a point mass (a planet, for example), pulling bodies toward its center
+/// with an acceleration inversely proportional to the squared distance.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointGravityField {
+    /// The world-space center of attraction.
+    pub center: Vector,
+    /// The gravitational parameter of the attractor (the gravitational constant times its
+    /// mass): the acceleration at a distance `d` is `strength / d²`.
+    pub strength: Real,
+    /// Distances to the center smaller than this are clamped to it, to avoid the singularity
+    /// at the center.
+    pub min_distance: Real,
+}
+
+impl PointGravityField {
+    /// A point gravity field with the given center and gravitational parameter.
+    pub fn new(center: Vector, strength: Real) -> Self {
+        Self {
+            center,
+            strength,
+            min_distance: 1.0e-3,
+        }
+    }
+
+    /// The gravitational acceleration of this field at the given world-space point.
+    pub fn acceleration_at(&self, point: Vector) -> Vector {
+        let dpos = self.center - point;
+        let distance = dpos.length().max(self.min_distance);
+        if distance <= 0.0 {
+            return Vector::ZERO;
+        }
+        dpos * (self.strength / (distance * distance * distance))
+    }
+}
+
+impl ForceField for PointGravityField {
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce) {
+        let acceleration = self.acceleration_at(body.center_of_mass);
+        force.add_force(acceleration * (body.mass() * body.body.gravity_scale()));
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn ForceField>> {
+        Some(Box::new(*self))
+    }
+}
+
+/// A uniform wind, dragging bodies toward its velocity.
+///
+/// The drag force is `drag * (velocity - v)` where `v` is the velocity of the body's center
+/// of mass, so light bodies are blown away faster than heavy ones. See [`AerodynamicField`]
+/// for a drag depending on the shape of the colliders.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WindField {
+    /// The velocity of the wind.
+    pub velocity: Vector,
+    /// The drag coefficient (force per unit of relative velocity).
+    pub drag: Real,
+}
+
+impl WindField {
+    /// A wind with the given velocity and drag coefficient.
+    pub fn new(velocity: Vector, drag: Real) -> Self {
+        Self { velocity, drag }
+    }
+}
+
+impl ForceField for WindField {
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce) {
+        force.add_force((self.velocity - body.linvel) * self.drag);
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn ForceField>> {
+        Some(Box::new(*self))
+    }
+}
+
+/// A vortex (a tornado or a whirlpool) making bodies swirl around its axis.
+///
+/// All the accelerations decrease linearly with the distance to the axis, down to zero at
+/// [`Self::radius`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct VortexField {
+    /// A world-space point on the vortex axis.
+    pub center: Vector,
+    /// The unit direction of the vortex axis. Bodies swirl counterclockwise around it.
+    #[cfg(feature = "dim3")]
+    pub axis: Vector,
+    /// The distance to the axis beyond which the vortex has no effect.
+    pub radius: Real,
+    /// The acceleration orthogonal to the axis and to the direction of the axis, making bodies
+    /// swirl.
+    pub tangential_acceleration: Real,
+    /// The acceleration toward the axis.
+    pub inward_acceleration: Real,
+    /// The acceleration along the axis.
+    #[cfg(feature = "dim3")]
+    pub axial_acceleration: Real,
+}
+
+impl VortexField {
+    /// A vortex with the given axis and radius, without any acceleration.
+    #[cfg(feature = "dim2")]
+    pub fn new(center: Vector, radius: Real) -> Self {
+        Self {
+            center,
+            radius,
+            tangential_acceleration: 0.0,
+            inward_acceleration: 0.0,
+        }
+    }
+
+    /// A vortex with the given axis and radius, without any acceleration.
+    #[cfg(feature = "dim3")]
+    pub fn new(center: Vector, axis: Vector, radius: Real) -> Self {
+        Self {
+            center,
+            axis: axis.normalize_or_zero(),
+            radius,
+            tangential_acceleration: 0.0,
+            inward_acceleration: 0.0,
+            axial_acceleration: 0.0,
+        }
+    }
+}
+
+impl ForceField for VortexField {
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce) {
+        let dpos = body.center_of_mass - self.center;
+        #[cfg(feature = "dim3")]
+        let dpos = dpos - self.axis * self.axis.dot(dpos);
+        let distance = dpos.length();
+        if distance >= self.radius || distance == 0.0 {
+            return;
+        }
+
+        let radial = dpos / distance;
+        #[cfg(feature = "dim2")]
+        let tangent = Vector::new(-radial.y, radial.x);
+        #[cfg(feature = "dim3")]
+        let tangent = self.axis.cross(radial);
+
+        #[allow(unused_mut)] // mut needed in 3D.
+        let mut acceleration =
+            tangent * self.tangential_acceleration - radial * self.inward_acceleration;
+        #[cfg(feature = "dim3")]
+        {
+            acceleration += self.axis * self.axial_acceleration;
+        }
+        let falloff = 1.0 - distance / self.radius;
+        force.add_force(acceleration * (body.mass() * falloff));
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn ForceField>> {
+        Some(Box::new(*self))
+    }
+}
+
+/// The aerodynamic coefficients of a collider, see [`AerodynamicField`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AerodynamicProfile {
+    /// The drag coefficient, applied to the area of the collider projected orthogonally to
+    /// the airflow.
+    pub drag_coefficient: Real,
+    /// The lift coefficient, applied to the area of the collider projected along its
+    /// [`Self::lift_axis`].
+    pub lift_coefficient: Real,
+    /// The unit normal of the lifting surface (a wing's upper side), in the collider's local
+    /// frame.
+    pub lift_axis: Vector,
+}
+
+impl AerodynamicProfile {
+    /// A profile with the given drag coefficient and no lift.
+    pub fn new(drag_coefficient: Real) -> Self {
+        Self {
+            drag_coefficient,
+            lift_coefficient: 0.0,
+            lift_axis: Vector::Y,
+        }
+    }
+
+    /// Sets the lift coefficient and the local normal of the lifting surface.
+    pub fn with_lift(mut self, lift_coefficient: Real, lift_axis: Vector) -> Self {
+        self.lift_coefficient = lift_coefficient;
+        self.lift_axis = lift_axis.normalize_or_zero();
+        self
+    }
+}
+
+/// Aerodynamic drag and lift applied to each collider based on its projected area.
+///
+/// For each (non-sensor) collider with a profile, with `u` the velocity of the air relative to
+/// the center of the collider's bounding box and `ρ` the air density:
+/// - The drag is `½ ρ C_d A |u| u`, where `A` is the area of the collider projected
+///   orthogonally to `u`.
+/// - The lift is `ρ C_l A_l |u|² cos(θ) n⊥`, where `n` is the lift axis, `A_l` the area of the
+///   collider projected along `n`, `cos(θ) = n·u/|u|`, and `n⊥` the component of `n`
+///   orthogonal to `u`. This is a flat-plate model, maximal at 45° of incidence.
+///
+/// Both forces are applied at the center of the collider's bounding box. Projected areas are
+/// exact for balls and computed from the local bounding box for other shapes. In 2D, areas are
+/// lengths.
+#[derive(Clone, Debug, Default)]
+pub struct AerodynamicField {
+    /// The density of the air (mass per unit volume, e.g., `1.2` in SI units).
+    pub air_density: Real,
+    /// The velocity of the air.
+    pub wind_velocity: Vector,
+    /// The profile of the colliders without a profile of their own. If `None`, these colliders
+    /// aren't affected by the field.
+    pub default_profile: Option<AerodynamicProfile>,
+    profiles: HashMap<ColliderHandle, AerodynamicProfile>,
+}
+
+impl AerodynamicField {
+    /// An aerodynamic field with the given air density, affecting only the colliders with a
+    /// profile.
+    pub fn new(air_density: Real) -> Self {
+        Self {
+            air_density,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the profile of a collider.
+    pub fn set_profile(&mut self, collider: ColliderHandle, profile: AerodynamicProfile) {
+        let _ = self.profiles.insert(collider, profile);
+    }
+
+    /// Removes the profile of a collider, making it use [`Self::default_profile`].
+    pub fn remove_profile(&mut self, collider: ColliderHandle) -> Option<AerodynamicProfile> {
+        crate::utils::hashmap_remove(&mut self.profiles, &collider)
+    }
+
+    /// The profile applied to a collider.
+    pub fn profile(&self, collider: ColliderHandle) -> Option<&AerodynamicProfile> {
+        self.profiles
+            .get(&collider)
+            .or(self.default_profile.as_ref())
+    }
+}
+
+impl ForceField for AerodynamicField {
+    fn apply(&self, body: &ForceFieldBody, force: &mut FieldForce) {
+        for handle in body.body.colliders() {
+            let Some(profile) = self.profile(*handle) else {
+                continue;
+            };
+            let Some(co) = body.colliders.get(*handle) else {
+                continue;
+            };
+            if co.is_sensor() || !co.is_enabled() {
+                continue;
+            }
+
+            let pos = body.collider_position(co);
+            let aabb = co.shape().compute_local_aabb();
+            let center = pos * aabb.center();
+            let airflow = self.wind_velocity - body.velocity_at_point(center);
+            let speed = airflow.length();
+            if speed == 0.0 {
+                continue;
+            }
+            let airflow_dir = airflow / speed;
+            let local_airflow_dir = pos.rotation.inverse() * airflow_dir;
+
+            let drag_area = projected_area(co, aabb.half_extents(), local_airflow_dir);
+            let mut total =
+                airflow * (0.5 * self.air_density * profile.drag_coefficient * drag_area * speed);
+
+            if profile.lift_coefficient != 0.0 {
+                let lift_axis = pos.rotation * profile.lift_axis;
+                let cos = lift_axis.dot(airflow_dir);
+                let lift_area = projected_area(co, aabb.half_extents(), profile.lift_axis);
+                total += (lift_axis - airflow_dir * cos)
+                    * (self.air_density
+                        * profile.lift_coefficient
+                        * lift_area
+                        * speed
+                        * speed
+                        * cos);
+            }
+
+            force.add_force_at_point(total, center);
+        }
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn ForceField>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// The area of a collider projected along the unit local direction `dir`.
+fn projected_area(co: &Collider, half_extents: Vector, dir: Vector) -> Real {
+    if let Some(ball) = co.shape().as_ball() {
+        #[cfg(feature = "dim2")]
+        return 2.0 * ball.radius;
+        #[cfg(feature = "dim3")]
+        return Real::pi() * ball.radius * ball.radius;
+    }
+
+    let dir = dir.abs();
+    #[cfg(feature = "dim2")]
+    return 2.0 * (dir.x * half_extents.y + dir.y * half_extents.x);
+    #[cfg(feature = "dim3")]
+    return 4.0
+        * (dir.x * half_extents.y * half_extents.z
+            + dir.y * half_extents.x * half_extents.z
+            + dir.z * half_extents.x * half_extents.y);
+}
@@ -0,0 +1,160 @@
+//! Gravity providers: per-body gravity computed by the physics pipeline, e.g., for planetoids
+//! or space games where "down" depends on where a body is.
+
+use crate::alloc_prelude::*;
+
+#[cfg(not(feature = "std"))]
+use simba::scalar::ComplexField;
+
+use crate::dynamics::{PointGravityField, RigidBody, RigidBodyHandle};
+use crate::math::{Real, Vector};
+
+/// Computes the gravitational acceleration of each dynamic body, replacing the world gravity
+/// given to [`PhysicsPipeline::step`](crate::pipeline::PhysicsPipeline::step).
+///
+/// The [`gravity_provider`](crate::pipeline::PhysicsPipeline::gravity_provider) of a pipeline
+/// is evaluated once per step, before the solver runs, for each awake dynamic body without a
+/// [gravity override](RigidBody::gravity_override). It is also evaluated for the sleeping
+/// bodies, to wake up the ones whose gravity direction changed (see
+/// [`IntegrationParameters::gravity_wake_angle`](crate::dynamics::IntegrationParameters::gravity_wake_angle)).
+/// The result is still multiplied by the body's [gravity scale](RigidBody::gravity_scale).
+///
+/// Providers are evaluated in parallel when the `parallel` feature is enabled, hence the
+/// `Send + Sync` requirement. Closures taking the body handle, the body and the world gravity
+/// are providers, and [`GravitySources`] adds up several independent sources.
+pub trait GravityProvider: Send + Sync {
+    /// The gravitational acceleration of `body`, given the world gravity `world_gravity`.
+    fn gravity(&self, handle: RigidBodyHandle, body: &RigidBody, world_gravity: Vector) -> Vector;
+
+    /// A boxed copy of this provider, or `None` (the default) if it can't be copied.
+    ///
+    /// A copyable provider is part of the initial state of a
+    /// [`Replay`](crate::pipeline::Replay). Closures can't be copied; [`PointGravityField`]
+    /// can, and so can [`GravitySources`] made of copyable sources.
+    fn clone_dyn(&self) -> Option<Box<dyn GravityProvider>> {
+        None
+    }
+}
+
+impl<F> GravityProvider for F
+where
+    F: Fn(RigidBodyHandle, &RigidBody, Vector) -> Vector + Send + Sync,
+{
+    fn gravity(&self, handle: RigidBodyHandle, body: &RigidBody, world_gravity: Vector) -> Vector {
+        self(handle, body, world_gravity)
+    }
+}
+
+impl GravityProvider for PointGravityField {
+    fn gravity(&self, _: RigidBodyHandle, body: &RigidBody, _: Vector) -> Vector {
+        self.acceleration_at(body.center_of_mass())
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn GravityProvider>> {
+        Some(Box::new(*self))
+    }
+}
+
+/// Several independent gravity sources (planets, for example), whose accelerations add up.
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// # let mut world = PhysicsWorld::default();
+/// let mut sources = GravitySources::new();
+/// sources.push(PointGravityField::new(Vector::new(-50.0, 0.0, 0.0), 1.0e4));
+/// sources.push(PointGravityField::new(Vector::new(50.0, 0.0, 0.0), 2.0e4));
+/// world.set_gravity_provider(sources);
+/// ```
+#[derive(Default)]
+pub struct GravitySources {
+    /// Whether the world gravity is added to the accelerations of the sources (default:
+    /// `false`).
+    pub include_world_gravity: bool,
+    sources: Vec<Box<dyn GravityProvider>>,
+}
+
+impl GravitySources {
+    /// An empty set of gravity sources, ignoring the world gravity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a gravity source.
+    pub fn push(&mut self, source: impl GravityProvider + 'static) {
+        self.sources.push(Box::new(source));
+    }
+
+    /// Adds a gravity source.
+    pub fn with(mut self, source: impl GravityProvider + 'static) -> Self {
+        self.push(source);
+        self
+    }
+
+    /// The number of gravity sources.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Is there no gravity source?
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Removes all the gravity sources.
+    pub fn clear(&mut self) {
+        self.sources.clear();
+    }
+}
+
+impl GravityProvider for GravitySources {
+    fn gravity(&self, handle: RigidBodyHandle, body: &RigidBody, world_gravity: Vector) -> Vector {
+        let init = if self.include_world_gravity {
+            world_gravity
+        } else {
+            Vector::ZERO
+        };
+        self.sources.iter().fold(init, |acc, source| {
+            acc + source.gravity(handle, body, world_gravity)
+        })
+    }
+
+    fn clone_dyn(&self) -> Option<Box<dyn GravityProvider>> {
+        let sources = self
+            .sources
+            .iter()
+            .map(|source| source.clone_dyn())
+            .collect::<Option<_>>()?;
+        Some(Box::new(Self {
+            include_world_gravity: self.include_world_gravity,
+            sources,
+        }))
+    }
+}
+
+/// The gravitational acceleration of `body` before its gravity scale: its override if it has
+/// one, or the one of `provider`, or `world_gravity`. Providers only apply to dynamic bodies.
+pub(crate) fn body_gravity(
+    provider: Option<&dyn GravityProvider>,
+    handle: RigidBodyHandle,
+    body: &RigidBody,
+    world_gravity: Vector,
+) -> Vector {
+    match (body.forces.gravity_override, provider) {
+        (Some(gravity), _) => gravity,
+        (None, Some(provider)) if body.is_dynamic() => {
+            provider.gravity(handle, body, world_gravity)
+        }
+        _ => world_gravity,
+    }
+}
+
+/// Did the direction of a gravitational acceleration turn by more than `max_angle` going from
+/// `old` to `new`? Gravity appearing or vanishing counts as a direction change.
+pub(crate) fn gravity_direction_changed(old: Vector, new: Vector, max_angle: Real) -> bool {
+    let (old_len, new_len) = (old.length(), new.length());
+    if old_len == 0.0 || new_len == 0.0 {
+        return (old_len == 0.0) != (new_len == 0.0);
+    }
+    old.dot(new) < old_len * new_len * max_angle.cos()
+}
@@ -29,6 +29,33 @@ pub enum FrictionModel {
     Coulomb,
 }
 
+/// How the continuous collision detection (CCD) handles the impacts of fast bodies.
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum CcdMode {
+    /// Fast bodies are moved back to their first time of impact, and the rest of their motion
+    /// for this step is dropped (their velocities are untouched).
+    ///
+    /// With [`IntegrationParameters::max_ccd_substeps`] `> 1`, the whole timestep is split at the
+    /// earliest impact of all the fast bodies and the full solver runs again for the remaining
+    /// time, up to that many times.
+    #[default]
+    MotionClamping,
+    /// The islands of the fast bodies about to hit something are substepped through their
+    /// impacts: their timestep is split at the earliest impact, and the full solver (contacts,
+    /// joints, restitution and friction) runs again from there for the remaining time, so fast
+    /// bodies bounce and slide instead of stopping at the impact.
+    ///
+    /// The rest of the world is solved once over the whole step, before these substeps, and
+    /// acts as fixed walls at its final pose during them. Worlds with multibodies split the
+    /// whole world's step instead.
+    ///
+    /// Each impact is resolved over an extra substep of [`IntegrationParameters::min_ccd_dt`].
+    /// [`IntegrationParameters::max_ccd_substeps`] bounds the number of impacts resolved this
+    /// way per step. Impacts past that budget are clamped like [`Self::MotionClamping`].
+    ImpactSubstepping,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 // TODO: we should be able to combine this with MotorModel.
@@ -267,8 +294,13 @@ pub struct IntegrationParameters {
     /// Maximum number of CCD substeps performed by the solver (default: `1`).
     ///
     /// Also the global CCD on/off switch: `0` disables **all** CCD for the world (including the
-    /// automatic CCD of fast dynamic bodies vs fixed colliders).
+    /// automatic CCD of fast dynamic bodies vs fixed colliders). With
+    /// [`CcdMode::ImpactSubstepping`], it bounds the number of impacts resolved per step instead.
     pub max_ccd_substeps: usize,
+    /// How the impacts found by the continuous collision detection are resolved (default:
+    /// [`CcdMode::MotionClamping`]).
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub ccd_mode: CcdMode,
     /// If enabled, contact manifolds of a collider pair sharing (nearly) the same normal are merged
     /// into one "cluster" manifold before constraint generation (default: `true`, 3D only), so at
     /// most 4 contact points are solved per contact plane — a large solver win on composite shapes
@@ -301,6 +333,22 @@ pub struct IntegrationParameters {
     /// The type of friction constraints used in the simulation.
     #[cfg(feature = "dim3")]
     pub friction_model: FrictionModel,
+    /// Angle, in radians, by which the direction of a sleeping body's gravity must turn for the
+    /// body to wake up (default: `0.02`, a bit more than one degree).
+    ///
+    /// The gravity of a body is its [gravity override](crate::dynamics::RigidBody::gravity_override),
+    /// or the one computed by the pipeline's [`GravityProvider`](crate::dynamics::GravityProvider),
+    /// or the world gravity. Changes of magnitude alone never wake bodies up.
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(default = "default_gravity_wake_angle")
+    )]
+    pub gravity_wake_angle: Real,
+}
+
+#[cfg(feature = "serde-serialize")]
+fn default_gravity_wake_angle() -> Real {
+    IntegrationParameters::default().gravity_wake_angle
 }
 
 impl IntegrationParameters {
@@ -395,6 +443,7 @@ impl Default for IntegrationParameters {
             normalized_prediction_distance: 0.02,
             normalized_max_linear_velocity: 400.0,
             max_ccd_substeps: 1,
+            ccd_mode: CcdMode::default(),
             contact_clustering: true,
             contact_recycling: true,
             normalized_contact_recycle_distance: 0.05,
@@ -403,6 +452,7 @@ impl Default for IntegrationParameters {
             length_unit: 1.0,
             #[cfg(feature = "dim3")]
             friction_model: FrictionModel::default(),
+            gravity_wake_angle: 0.02,
         }
     }
 }
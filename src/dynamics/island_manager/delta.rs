@@ -0,0 +1,152 @@
+//! Delta snapshots of the island manager.
+
+use super::persistent::{JointLinkKey, PersistentIsland, Removal, serialize_joint_link_locs};
+use super::{Island, IslandManager, PersistentIslands};
+use crate::alloc_prelude::*;
+use crate::data::delta::{Fingerprinter, SlotTracker, SlotsDelta, ValueTracker, apply_value};
+use parry::utils::VecMap;
+use parry::utils::hashmap::HashMap;
+
+/// The parts of an [`IslandManager`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`IslandManager::delta_snapshot`] and restored with
+/// [`IslandManager::apply_delta`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct IslandManagerDelta {
+    active_set_epoch: u32,
+    islands: Option<VecMap<Island>>,
+    awake_island: Option<usize>,
+    free_islands: Option<Vec<usize>>,
+    persistent_islands: Option<VecMap<PersistentIsland>>,
+    persistent_free_islands: Option<Vec<u32>>,
+    contact_link_locs: SlotsDelta<(u32, u32)>,
+    joint_link_locs: Option<JointLinkLocs>,
+    removal_journal: Option<Vec<Removal>>,
+    split_island: Option<u32>,
+    sleep_scan_stamp: u32,
+    bootstrapped: bool,
+}
+
+/// Serialized like `PersistentIslands::joint_link_locs`, by key.
+#[derive(Clone, Serialize, Deserialize)]
+struct JointLinkLocs(
+    #[serde(
+        serialize_with = "serialize_joint_link_locs",
+        deserialize_with = "crate::utils::serde::deserialize_from_vec_tuple"
+    )]
+    HashMap<JointLinkKey, (u32, u32)>,
+);
+
+/// Remembers what an [`IslandManager`] looked like at its last delta snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct IslandManagerDeltaTracker {
+    islands: ValueTracker,
+    free_islands: ValueTracker,
+    persistent_islands: ValueTracker,
+    persistent_free_islands: ValueTracker,
+    contact_link_locs: SlotTracker,
+    joint_link_locs: ValueTracker,
+    removal_journal: ValueTracker,
+}
+
+impl IslandManagerDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl IslandManager {
+    /// The parts of this island manager that changed since the previous snapshot taken with
+    /// `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe. Afterward, the island lists are
+    /// only stored if they changed, which they don't once every island is asleep.
+    pub fn delta_snapshot(&self, tracker: &mut IslandManagerDeltaTracker) -> IslandManagerDelta {
+        // Destructured so that a new field can't be forgotten here.
+        let IslandManager {
+            active_set_epoch,
+            islands,
+            awake_island,
+            free_islands,
+            solve_groups: _,
+            substep_groups_workspace: _,
+            persistent,
+            sleep_events: _,
+        } = self;
+        let PersistentIslands {
+            islands: persistent_islands,
+            free_islands: persistent_free_islands,
+            contact_link_locs,
+            joint_link_locs,
+            removal_journal,
+            local_split: _,
+            split_island,
+            split_scratch: _,
+            sleep_scan: _,
+            sleep_scan_touched: _,
+            sleep_scan_stamp,
+            bootstrapped,
+        } = persistent;
+        let mut joint_link_locs_fingerprint = Fingerprinter::new();
+        let _ = serialize_joint_link_locs(joint_link_locs, &mut joint_link_locs_fingerprint);
+
+        IslandManagerDelta {
+            active_set_epoch: *active_set_epoch,
+            islands: tracker.islands.diff(islands),
+            awake_island: *awake_island,
+            free_islands: tracker.free_islands.diff(free_islands),
+            persistent_islands: tracker.persistent_islands.diff(persistent_islands),
+            persistent_free_islands: tracker
+                .persistent_free_islands
+                .diff(persistent_free_islands),
+            contact_link_locs: tracker.contact_link_locs.diff_serialized(contact_link_locs),
+            joint_link_locs: tracker
+                .joint_link_locs
+                .diff_with(joint_link_locs, joint_link_locs_fingerprint.finish())
+                .map(JointLinkLocs),
+            removal_journal: tracker.removal_journal.diff(removal_journal),
+            split_island: *split_island,
+            sleep_scan_stamp: *sleep_scan_stamp,
+            bootstrapped: *bootstrapped,
+        }
+    }
+
+    /// Brings this island manager from the state of the previous delta snapshot to the state of
+    /// `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The island
+    /// manager then serializes to the same bytes as the one the snapshots were taken from, and
+    /// its internal buffers are reset the way deserialization does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this island manager isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &IslandManagerDelta) {
+        self.active_set_epoch = delta.active_set_epoch;
+        apply_value(&mut self.islands, &delta.islands);
+        self.awake_island = delta.awake_island;
+        apply_value(&mut self.free_islands, &delta.free_islands);
+        self.solve_groups = Default::default();
+        self.substep_groups_workspace = Default::default();
+        self.sleep_events = Default::default();
+
+        let persistent = &mut self.persistent;
+        apply_value(&mut persistent.islands, &delta.persistent_islands);
+        apply_value(&mut persistent.free_islands, &delta.persistent_free_islands);
+        delta
+            .contact_link_locs
+            .apply(&mut persistent.contact_link_locs);
+        if let Some(JointLinkLocs(locs)) = &delta.joint_link_locs {
+            persistent.joint_link_locs = locs.clone();
+        }
+        apply_value(&mut persistent.removal_journal, &delta.removal_journal);
+        persistent.split_island = delta.split_island;
+        persistent.sleep_scan_stamp = delta.sleep_scan_stamp;
+        persistent.bootstrapped = delta.bootstrapped;
+        persistent.local_split = Default::default();
+        persistent.split_scratch = Default::default();
+        persistent.sleep_scan = Default::default();
+        persistent.sleep_scan_touched = Default::default();
+    }
+}
@@ -48,6 +48,10 @@ pub struct IslandManager {
     /// The persistent islands: connected components of the touching-contact/joint
     /// graph, maintained incrementally.
     pub(crate) persistent: super::PersistentIslands,
+    /// Sleep/wake-up transitions of the bodies with sleep events enabled, reported to the
+    /// event handler at the end of the next step.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) sleep_events: Vec<super::SleepEvent>,
 }
 
 impl IslandManager {
@@ -227,6 +231,23 @@ impl IslandManager {
         self.persistent.body_island(bodies, handle)
     }
 
+    /// Pushes the bodies of the persistent island of `handle` into `out`: the body alone if it
+    /// isn't in any island, nothing if it is fixed or doesn't exist.
+    pub(crate) fn push_persistent_island_bodies(
+        &self,
+        bodies: &RigidBodySet,
+        handle: RigidBodyHandle,
+        out: &mut Vec<RigidBodyHandle>,
+    ) {
+        match bodies.get(handle) {
+            Some(rb) if !rb.is_fixed() => match self.persistent.body_island(bodies, handle) {
+                Some(id) => out.extend_from_slice(&self.persistent.islands[id as usize].bodies),
+                None => out.push(handle),
+            },
+            _ => {}
+        }
+    }
+
     /// Handles of dynamic and kinematic rigid-bodies that are currently active (i.e. not sleeping).
     #[inline]
     pub fn active_bodies(&self) -> impl Iterator<Item = RigidBodyHandle> + '_ {
@@ -235,6 +256,16 @@ impl IslandManager {
             .flat_map(|i| self.islands[i].bodies.iter().copied())
     }
 
+    /// Handles of the dynamic and kinematic rigid-bodies that are currently sleeping, chunk by
+    /// chunk.
+    pub(crate) fn sleeping_bodies(&self) -> impl Iterator<Item = RigidBodyHandle> + '_ {
+        let awake_island = self.awake_island;
+        self.islands
+            .iter()
+            .filter(move |(id, _)| Some(*id) != awake_island)
+            .flat_map(|(_, island)| island.bodies.iter().copied())
+    }
+
     /// The awake island's body slice (same content and order as
     /// [`Self::active_bodies`]), for callers that want to chunk the active set in
     /// parallel.
@@ -1,9 +1,14 @@
+#[cfg(feature = "serde-serialize")]
+pub use delta::{IslandManagerDelta, IslandManagerDeltaTracker};
 pub use manager::IslandManager;
+pub use sleep::SleepEvent;
 
 pub(crate) use island::Island;
 pub(crate) use persistent::{INVALID_ISLAND, ImpulseJointIslandEvent, PersistentIslands};
 pub(crate) use substep_groups::SolveGroup;
 
+#[cfg(feature = "serde-serialize")]
+mod delta;
 mod global_split;
 mod island;
 mod local_split;
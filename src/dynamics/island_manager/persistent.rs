@@ -103,7 +103,7 @@ pub(super) struct Removal {
 /// map's insertion history (a hash map's iteration order is history- and target-dependent,
 /// which would make two snapshots of the same state differ).
 #[cfg(feature = "serde-serialize")]
-fn serialize_joint_link_locs<S: serde::Serializer>(
+pub(super) fn serialize_joint_link_locs<S: serde::Serializer>(
     locs: &HashMap<JointLinkKey, (u32, u32)>,
     s: S,
 ) -> Result<S::Ok, S::Error> {
@@ -129,7 +129,7 @@ fn serialize_joint_link_locs<S: serde::Serializer>(
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub(crate) struct PersistentIslands {
     pub(crate) islands: VecMap<PersistentIsland>,
-    free_islands: Vec<u32>,
+    pub(super) free_islands: Vec<u32>,
     /// `contact_link_locs[edge_id]` = (island id, index in `contact_links`); `INVALID_LOC` when
     /// the edge isn't linked (not touching). Mirrors the contact graph's edges vec: grown on
     /// link, swap-removed through [`Self::contact_edge_removed`].
@@ -157,9 +157,9 @@ pub(crate) struct PersistentIslands {
     /// Stamped so the scan is O(active bodies), never O(total islands) — sleeping islands are
     /// never touched.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
-    sleep_scan: Vec<(u32, bool)>,
+    pub(super) sleep_scan: Vec<(u32, bool)>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
-    sleep_scan_touched: Vec<u32>,
+    pub(super) sleep_scan_touched: Vec<u32>,
     pub(super) sleep_scan_stamp: u32,
     /// Whether the structures were initialized from the current world state
     /// (they are not serialized; the first update after construction or
@@ -1,9 +1,37 @@
 use crate::alloc_prelude::*;
-use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::dynamics::{RigidBodyActiveEvents, RigidBodyHandle, RigidBodySet};
 use crate::geometry::NarrowPhase;
 
 use super::{Island, IslandManager};
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Event emitted when a rigid-body with [`RigidBodyActiveEvents::SLEEP_EVENTS`] enabled falls
+/// asleep or wakes up.
+///
+/// Bodies sleep and wake up with their whole island, so an event is emitted for every body of
+/// the island that has these events enabled. Events are emitted at the end of the step, including
+/// for wake-ups caused by a user edit (like [`IslandManager::wake_up`]) since the last step.
+pub enum SleepEvent {
+    /// The rigid-body fell asleep: it is no longer simulated until woken up.
+    FellAsleep(RigidBodyHandle),
+    /// The rigid-body woke up: it is simulated again.
+    WokeUp(RigidBodyHandle),
+}
+
+impl SleepEvent {
+    /// The rigid-body that fell asleep or woke up.
+    pub fn body(self) -> RigidBodyHandle {
+        match self {
+            Self::FellAsleep(handle) | Self::WokeUp(handle) => handle,
+        }
+    }
+
+    /// Is this a [`SleepEvent::FellAsleep`] event?
+    pub fn fell_asleep(self) -> bool {
+        matches!(self, Self::FellAsleep(_))
+    }
+}
+
 impl IslandManager {
     /// Wakes up a sleeping body, forcing it back into the active simulation.
     ///
@@ -75,6 +103,61 @@ impl IslandManager {
         }
     }
 
+    /// Puts the whole island of a body to sleep, e.g. right after loading a level whose bodies
+    /// start at rest.
+    ///
+    /// The island is the connected component of touching contacts and joints the body belongs
+    /// to, as of the last step (before the first step, only joints connect bodies). Its bodies
+    /// are put to sleep like with [`RigidBody::sleep`](crate::dynamics::RigidBody::sleep): their
+    /// velocities are zeroed right away, and they leave the simulation at the start of the next
+    /// step unless something wakes them up in the meantime.
+    ///
+    /// Does nothing for fixed or removed bodies, nor for already sleeping islands.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut world = PhysicsWorld::new();
+    /// # world.insert(RigidBodyBuilder::fixed(), ColliderBuilder::cuboid(10.0, 0.5, 10.0));
+    /// # let (body, _) = world.insert(RigidBodyBuilder::dynamic().translation(Vector::new(0.0, 1.0, 0.0)), ColliderBuilder::cuboid(0.5, 0.5, 0.5));
+    /// world.step();
+    /// // Everything touching `body`, directly or not, stops being simulated.
+    /// world.islands.sleep_island_of(&mut world.bodies, body);
+    /// world.step();
+    /// assert!(world.bodies[body].is_sleeping());
+    /// ```
+    pub fn sleep_island_of(&mut self, bodies: &mut RigidBodySet, handle: RigidBodyHandle) {
+        if bodies.get(handle).is_none_or(|rb| rb.is_fixed()) {
+            return;
+        }
+
+        let Some(mut island_id) = self.persistent.body_island(bodies, handle) else {
+            // Not registered yet (inserted since the last step): it is alone in its island.
+            if let Some(rb) = bodies.get_mut(handle) {
+                rb.sleep();
+            }
+            return;
+        };
+        let island = &self.persistent.islands[island_id as usize];
+        if island.sleeping {
+            return;
+        }
+        if island.constraint_remove_count > 0 && island.bodies.len() > 1 {
+            // The island may have come apart since it was built: don't put the bodies that no
+            // longer touch this one to sleep.
+            self.persistent.split_island_now(bodies, island_id);
+            island_id = bodies[handle].ids.island_id;
+        }
+
+        // The manual sleep pins their sleep eligibility, so the next step's whole-island sleep
+        // decision commits them.
+        for member in self.persistent.islands[island_id as usize].bodies.clone() {
+            if let Some(rb) = bodies.get_mut(member) {
+                rb.sleep();
+            }
+        }
+    }
+
     /// Puts `chunks` (disjoint subsets of the awake island's bodies, all
     /// sleep-eligible) to sleep: in place if they cover the entire awake
     /// island, otherwise by extracting each chunk into a new sleeping island.
@@ -86,6 +169,10 @@ impl IslandManager {
         active_island_len: usize,
         mut chunks: Vec<Vec<RigidBodyHandle>>,
     ) {
+        for handle in chunks.iter().flatten() {
+            self.push_sleep_event(bodies, SleepEvent::FellAsleep(*handle));
+        }
+
         if chunks.len() == 1 && chunks[0].len() == active_island_len {
             // The whole island is asleep. No need to insert a new one.
             // Put all its bodies to sleep.
@@ -151,6 +238,12 @@ impl IslandManager {
                 for handle in &island.bodies {
                     if let Some(rb) = bodies.get_mut(*handle) {
                         rb.wake_up(false);
+                        if rb
+                            .active_events
+                            .contains(RigidBodyActiveEvents::SLEEP_EVENTS)
+                        {
+                            self.sleep_events.push(SleepEvent::WokeUp(*handle));
+                        }
                     }
                 }
             }
@@ -169,6 +262,12 @@ impl IslandManager {
                         continue;
                     };
                     rb.wake_up(false);
+                    if rb
+                        .active_events
+                        .contains(RigidBodyActiveEvents::SLEEP_EVENTS)
+                    {
+                        self.sleep_events.push(SleepEvent::WokeUp(*handle));
+                    }
                     rb.ids.active_island_id = awake_id as u32;
                     rb.ids.active_set_id = (target.bodies.len()) as u32;
                     target.bodies.push(*handle);
@@ -176,4 +275,19 @@ impl IslandManager {
             }
         }
     }
+
+    /// Records `event` if its body has sleep events enabled.
+    fn push_sleep_event(&mut self, bodies: &RigidBodySet, event: SleepEvent) {
+        if bodies.get(event.body()).is_some_and(|rb| {
+            rb.active_events
+                .contains(RigidBodyActiveEvents::SLEEP_EVENTS)
+        }) {
+            self.sleep_events.push(event);
+        }
+    }
+
+    /// Takes the sleep events recorded since the last call.
+    pub(crate) fn take_sleep_events(&mut self) -> Vec<SleepEvent> {
+        core::mem::take(&mut self.sleep_events)
+    }
 }
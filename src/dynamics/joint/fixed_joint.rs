@@ -1,5 +1,5 @@
 use crate::dynamics::integration_parameters::SpringCoefficients;
-use crate::dynamics::{GenericJoint, GenericJointBuilder, JointAxesMask};
+use crate::dynamics::{GenericJoint, GenericJointBuilder, JointAxesMask, JointBreakAction};
 use crate::math::{Pose, Real, Vector};
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -105,6 +105,36 @@ impl FixedJoint {
         self.data.softness = softness;
         self
     }
+
+    /// The maximum linear force this joint can apply before it breaks.
+    #[must_use]
+    pub fn break_force(&self) -> Real {
+        self.data.break_force
+    }
+
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    pub fn set_break_force(&mut self, max_force: Real) -> &mut Self {
+        self.data.set_break_force(max_force);
+        self
+    }
+
+    /// The maximum torque this joint can apply (measured at its anchor) before it breaks.
+    #[must_use]
+    pub fn break_torque(&self) -> Real {
+        self.data.break_torque
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    pub fn set_break_torque(&mut self, max_torque: Real) -> &mut Self {
+        self.data.set_break_torque(max_torque);
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    pub fn set_break_action(&mut self, action: JointBreakAction) -> &mut Self {
+        self.data.set_break_action(action);
+        self
+    }
 }
 
 impl From<FixedJoint> for GenericJoint {
@@ -166,6 +196,27 @@ impl FixedJointBuilder {
         self
     }
 
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    #[must_use]
+    pub fn break_force(mut self, max_force: Real) -> Self {
+        self.0.set_break_force(max_force);
+        self
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    #[must_use]
+    pub fn break_torque(mut self, max_torque: Real) -> Self {
+        self.0.set_break_torque(max_torque);
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    #[must_use]
+    pub fn break_action(mut self, action: JointBreakAction) -> Self {
+        self.0.set_break_action(action);
+        self
+    }
+
     /// Build the fixed joint.
     #[must_use]
     pub fn build(self) -> FixedJoint {
@@ -262,6 +262,19 @@ pub enum JointEnabled {
     Disabled,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// What happens to an impulse joint once it exceeds its [`GenericJoint::break_force`] or
+/// [`GenericJoint::break_torque`].
+pub enum JointBreakAction {
+    /// The joint is disabled (see [`GenericJoint::set_enabled`]) but stays in the joint set,
+    /// so it can be re-enabled later.
+    #[default]
+    Disable,
+    /// The joint is removed from the joint set; its handle becomes invalid.
+    Remove,
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, PartialEq)]
 /// A generic joint.
@@ -301,10 +314,34 @@ pub struct GenericJoint {
     pub contacts_enabled: bool,
     /// Whether the joint is enabled.
     pub enabled: JointEnabled,
+    /// The maximum linear force this joint can apply before it breaks.
+    ///
+    /// Only impulse joints can break. Defaults to `Real::MAX` (unbreakable).
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(default = "default_break_threshold")
+    )]
+    pub break_force: Real,
+    /// The maximum torque this joint can apply (measured at its anchor) before it breaks.
+    ///
+    /// Only impulse joints can break. Defaults to `Real::MAX` (unbreakable).
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(default = "default_break_threshold")
+    )]
+    pub break_torque: Real,
+    /// What happens to this joint once it breaks.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub break_action: JointBreakAction,
     /// User-defined data associated to this joint.
     pub user_data: u128,
 }
 
+#[cfg(feature = "serde-serialize")]
+fn default_break_threshold() -> Real {
+    Real::MAX
+}
+
 impl Default for GenericJoint {
     fn default() -> Self {
         Self {
@@ -319,6 +356,9 @@ impl Default for GenericJoint {
             softness: SpringCoefficients::joint_defaults(),
             contacts_enabled: true,
             enabled: JointEnabled::Enabled,
+            break_force: Real::MAX,
+            break_torque: Real::MAX,
+            break_action: JointBreakAction::Disable,
             user_data: 0,
         }
     }
@@ -409,6 +449,33 @@ impl GenericJoint {
         }
     }
 
+    /// Can this joint break, i.e., is either of its break thresholds finite?
+    pub fn is_breakable(&self) -> bool {
+        self.break_force < Real::MAX || self.break_torque < Real::MAX
+    }
+
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    ///
+    /// Once the force applied by the solver exceeds this threshold, the joint is disabled or
+    /// removed (depending on [`Self::break_action`]) at the end of the step and a
+    /// [`JointBreakEvent`](crate::dynamics::JointBreakEvent) is emitted.
+    pub fn set_break_force(&mut self, max_force: Real) -> &mut Self {
+        self.break_force = max_force;
+        self
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    pub fn set_break_torque(&mut self, max_torque: Real) -> &mut Self {
+        self.break_torque = max_torque;
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    pub fn set_break_action(&mut self, action: JointBreakAction) -> &mut Self {
+        self.break_action = action;
+        self
+    }
+
     /// Add the specified axes to the set of axes locked by this joint.
     pub fn lock_axes(&mut self, axes: JointAxesMask) -> &mut Self {
         self.locked_axes |= axes;
@@ -838,6 +905,27 @@ impl GenericJointBuilder {
         self
     }
 
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    #[must_use]
+    pub fn break_force(mut self, max_force: Real) -> Self {
+        self.0.set_break_force(max_force);
+        self
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    #[must_use]
+    pub fn break_torque(mut self, max_torque: Real) -> Self {
+        self.0.set_break_torque(max_torque);
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    #[must_use]
+    pub fn break_action(mut self, action: JointBreakAction) -> Self {
+        self.0.set_break_action(action);
+        self
+    }
+
     /// An arbitrary user-defined 128-bit integer associated to the joints built by this builder.
     pub fn user_data(mut self, data: u128) -> Self {
         self.0.user_data = data;
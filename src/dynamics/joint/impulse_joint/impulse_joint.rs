@@ -1,5 +1,8 @@
-use crate::dynamics::{GenericJoint, ImpulseJointHandle, RigidBodyHandle};
-use crate::math::SpatialVector;
+use crate::dynamics::{
+    GenericJoint, ImpulseJointHandle, JointAxis, RigidBody, RigidBodyHandle, RigidBodySet,
+};
+use crate::math::{AngVector, DIM, Pose, Real, Rotation, SpatialVector, Vector, VectorExt};
+use crate::utils::CrossProduct;
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq)]
@@ -36,6 +39,50 @@ pub struct ImpulseJoint {
     /// The solver graph color assigned to this joint.
     #[cfg_attr(feature = "serde-serialize", serde(default = "default_solver_color"))]
     pub(crate) solver_color: u8,
+    /// The wrench applied to the attached bodies by the last substep, accumulated over
+    /// this joint's constraint rows by the impulse writeback.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) solver_wrench: JointSolverWrench,
+    /// The force applied by this joint to the second body during the last step it was
+    /// solved, in world-space.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_force: Vector,
+    /// The torque applied by this joint to the second body during the last step it was
+    /// solved, in world-space and measured at the second body's anchor.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_torque: AngVector,
+    /// The length of the substep the reaction impulses were read from.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_dt: Real,
+}
+
+/// The impulses applied by a joint's constraint rows during one substep.
+///
+/// `linear` and `angular1` are applied to the first body (the latter about its center of
+/// mass); the second body receives `-linear` and `-angular2` (about its own center of mass).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub(crate) struct JointSolverWrench {
+    pub linear: Vector,
+    pub angular1: AngVector,
+    pub angular2: AngVector,
+    /// Set when some of the joint's rows don't expose their world-space Jacobians (rows
+    /// involving a multibody link). The wrench is then rebuilt from the per-axis impulses.
+    pub incomplete: bool,
+}
+
+impl JointSolverWrench {
+    #[inline]
+    pub fn add_row(
+        &mut self,
+        lin_jac: Vector,
+        ang_jac1: AngVector,
+        ang_jac2: AngVector,
+        impulse: Real,
+    ) {
+        self.linear += lin_jac * impulse;
+        self.angular1 += ang_jac1 * impulse;
+        self.angular2 += ang_jac2 * impulse;
+    }
 }
 
 #[cfg(feature = "serde-serialize")]
@@ -60,4 +107,175 @@ impl ImpulseJoint {
     pub fn body2(&self) -> RigidBodyHandle {
         self.body2
     }
+
+    /// The force applied by this joint to its second body during the last step it was
+    /// solved, in world-space.
+    ///
+    /// The first body receives the opposite force. This includes the contributions of
+    /// the locked axes, limits and motors.
+    #[inline]
+    pub fn reaction_force(&self) -> Vector {
+        self.reaction_force
+    }
+
+    /// The torque applied by this joint to its second body during the last step it was
+    /// solved, in world-space and measured at the second body's anchor.
+    #[inline]
+    pub fn reaction_torque(&self) -> AngVector {
+        self.reaction_torque
+    }
+
+    /// The force applied by this joint to its second body during the last step it was
+    /// solved, expressed in the second body's joint frame (`local_frame2`).
+    ///
+    /// This is what a force sensor mounted at the second body's anchor would measure.
+    pub fn local_reaction_force(&self, bodies: &RigidBodySet) -> Vector {
+        self.frame2_rotation(bodies).inverse() * self.reaction_force
+    }
+
+    /// The torque applied by this joint to its second body during the last step it was
+    /// solved, expressed in the second body's joint frame (`local_frame2`).
+    pub fn local_reaction_torque(&self, bodies: &RigidBodySet) -> AngVector {
+        #[cfg(feature = "dim2")]
+        {
+            let _ = bodies;
+            self.reaction_torque
+        }
+        #[cfg(feature = "dim3")]
+        {
+            self.frame2_rotation(bodies).inverse() * self.reaction_torque
+        }
+    }
+
+    /// The force (or torque, for angular axes) applied by the motor of `axis` during the
+    /// last step it was solved.
+    ///
+    /// This is the motor impulse divided by the substep length, and is zero if `axis`
+    /// has no motor.
+    pub fn motor_force(&self, axis: JointAxis) -> Real {
+        self.data
+            .motor(axis)
+            .map(|motor| motor.impulse * crate::utils::inv(self.reaction_dt))
+            .unwrap_or(0.0)
+    }
+
+    /// The force (or torque, for angular axes) applied by the limits of `axis` during the
+    /// last step it was solved.
+    ///
+    /// This is the limit impulse divided by the substep length, and is zero if `axis`
+    /// has no limits or if they were not reached.
+    pub fn limit_force(&self, axis: JointAxis) -> Real {
+        self.data
+            .limits(axis)
+            .map(|limits| limits.impulse * crate::utils::inv(self.reaction_dt))
+            .unwrap_or(0.0)
+    }
+
+    /// The current position of the joint along `axis`.
+    ///
+    /// For linear axes, this is the offset of the second body's anchor from the first body's
+    /// anchor, along that axis of the first body's joint frame (`local_frame1`). For angular
+    /// axes, this is the angle, in `[-π, π]`, the second body's joint frame is rotated by about
+    /// that axis, which is only meaningful if it is the joint's only free rotation axis (e.g.
+    /// for a revolute joint).
+    pub fn axis_position(&self, bodies: &RigidBodySet, axis: JointAxis) -> Real {
+        let frame1 = self.world_frame(bodies, self.body1, self.data.local_frame1);
+        let frame2 = self.world_frame(bodies, self.body2, self.data.local_frame2);
+        let i = axis as usize;
+
+        if i < DIM {
+            return (frame1.rotation.inverse() * (frame2.translation - frame1.translation))[i];
+        }
+
+        let ang_err = frame1.rotation.inverse() * frame2.rotation;
+        #[cfg(feature = "dim2")]
+        {
+            ang_err.angle()
+        }
+        #[cfg(feature = "dim3")]
+        {
+            let angle = ang_err.xyz()[i - DIM].clamp(-1.0, 1.0).asin() * 2.0;
+            if ang_err.w < 0.0 { -angle } else { angle }
+        }
+    }
+
+    /// The current velocity of the joint along `axis`, i.e., the time derivative of
+    /// [`Self::axis_position`].
+    ///
+    /// For linear axes, this is the velocity of the second body's anchor relative to the first
+    /// body's anchor, minus the part due to the rotation of the first body's joint frame (the
+    /// `ω₁ × r` term, `r` being the offset between both anchors), projected on that axis of the
+    /// first body's joint frame. For angular axes, this is the relative angular velocity of both
+    /// bodies along that axis, which matches the derivative of [`Self::axis_position`] under the
+    /// same conditions as the angle itself.
+    pub fn axis_velocity(&self, bodies: &RigidBodySet, axis: JointAxis) -> Real {
+        let frame1 = self.world_frame(bodies, self.body1, self.data.local_frame1);
+        let frame2 = self.world_frame(bodies, self.body2, self.data.local_frame2);
+        let velocities = |handle, anchor| {
+            bodies
+                .get(handle)
+                .map(|rb: &RigidBody| (rb.velocity_at_point(anchor), rb.angvel()))
+                .unwrap_or_default()
+        };
+        let (linvel1, angvel1) = velocities(self.body1, frame1.translation);
+        let (linvel2, angvel2) = velocities(self.body2, frame2.translation);
+        let i = axis as usize;
+
+        if i < DIM {
+            // The axes of frame1 rotate with the first body, which must be accounted for
+            // since the offset is expressed in that frame.
+            let offset = frame2.translation - frame1.translation;
+            let frame_vel = angvel1.gcross(offset);
+            return (linvel2 - linvel1 - frame_vel).dot(frame1.rotation * Vector::ith(i, 1.0));
+        }
+
+        #[cfg(feature = "dim2")]
+        {
+            angvel2 - angvel1
+        }
+        #[cfg(feature = "dim3")]
+        {
+            (angvel2 - angvel1).dot(frame1.rotation * Vector::ith(i - DIM, 1.0))
+        }
+    }
+
+    fn world_frame(&self, bodies: &RigidBodySet, handle: RigidBodyHandle, local: Pose) -> Pose {
+        bodies
+            .get(handle)
+            .map(|rb| *rb.position() * local)
+            .unwrap_or(local)
+    }
+
+    fn frame2_rotation(&self, bodies: &RigidBodySet) -> Rotation {
+        bodies
+            .get(self.body2)
+            .map(|rb| rb.position().rotation * self.data.local_frame2.rotation)
+            .unwrap_or(Rotation::IDENTITY)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Event emitted when an impulse joint exceeds its [`GenericJoint::break_force`] or
+/// [`GenericJoint::break_torque`].
+///
+/// By the time the event is received, the joint was already disabled or removed (see
+/// [`JointBreakEvent::removed`]).
+pub struct JointBreakEvent {
+    /// The joint that broke.
+    pub joint: ImpulseJointHandle,
+    /// The first body the joint was attached to.
+    pub body1: RigidBodyHandle,
+    /// The second body the joint was attached to.
+    pub body2: RigidBodyHandle,
+    /// The world-space force the joint applied to the second body when it broke.
+    pub force: Vector,
+    /// The world-space torque the joint applied to the second body when it broke, measured
+    /// at the second body's anchor.
+    pub torque: AngVector,
+    /// The linear impulse applied by the joint over the step it broke (`force * dt`).
+    pub linear_impulse: Vector,
+    /// The angular impulse applied by the joint over the step it broke (`torque * dt`).
+    pub angular_impulse: AngVector,
+    /// Was the joint removed from its set (`true`) or only disabled (`false`)?
+    pub removed: bool,
 }
@@ -4,8 +4,16 @@ use parry::utils::hashset::HashSet;
 use super::ImpulseJoint;
 use crate::geometry::{InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex};
 
-use crate::data::Coarena;
 use crate::data::arena::Arena;
+use crate::data::change_log::ChangeLog;
+use crate::data::{Coarena, StateHasher};
+#[cfg(feature = "serde-serialize")]
+use crate::data::{
+    CoarenaDelta,
+    arena::ArenaDelta,
+    delta::{ChangeTracker, ValueTracker, apply_value},
+    graph::{GraphDelta, GraphDeltaTracker},
+};
 use crate::dynamics::{
     GenericJoint, ImpulseJointHandle, IslandManager, RigidBodyHandle, RigidBodySet,
 };
@@ -65,6 +73,12 @@ pub struct ImpulseJointSet {
     /// identical, so the caller's previous output is reused untouched.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     selection_epochs: Option<(u32, u32)>,
+    /// The `rb_graph_ids` entries modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    rb_graph_id_changes: ChangeLog,
+    /// The `joint_ids` entries modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    joint_id_changes: ChangeLog,
 }
 
 impl ImpulseJointSet {
@@ -79,6 +93,8 @@ impl ImpulseJointSet {
             island_events: Vec::new(),
             assembly_epoch: 0,
             selection_epochs: None,
+            rb_graph_id_changes: ChangeLog::default(),
+            joint_id_changes: ChangeLog::default(),
         }
     }
 
@@ -287,6 +303,25 @@ impl ImpulseJointSet {
             .map(|e| (e.weight.handle, &e.weight))
     }
 
+    /// A hash of the joints' solver state, for determinism checks.
+    ///
+    /// With [`StateHasher`]'s encoding, this hashes the number of joints then, for each joint
+    /// in increasing handle index order: its handle, the handles of its two rigid-bodies and
+    /// the components of its [`ImpulseJoint::impulses`].
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_usize(self.len());
+        for (handle, id) in self.joint_ids.iter() {
+            if let Some(joint) = self.joint_graph.graph.edge_weight(*id) {
+                hasher.write_index(handle);
+                hasher.write_index(joint.body1.0);
+                hasher.write_index(joint.body2.0);
+                hasher.write_spatial_vector(&joint.impulses);
+            }
+        }
+        hasher.finish()
+    }
+
     /// Iterates over all joints with mutable access.
     ///
     /// Each iteration yields `(joint_handle, &mut joint)`.
@@ -337,6 +372,7 @@ impl ImpulseJointSet {
         let joint_enabled = data.is_enabled();
         self.bump_assembly_epoch();
         let handle = self.joint_ids.insert(0.into());
+        self.joint_id_changes.mark_index(handle);
         let joint = ImpulseJoint {
             body1,
             body2,
@@ -345,9 +381,15 @@ impl ImpulseJointSet {
             handle: ImpulseJointHandle(handle),
             solver_body_ids: [u32::MAX; 2],
             solver_color: crate::geometry::contact_pair::SOLVER_COLOR_UNCOLORED,
+            solver_wrench: Default::default(),
+            reaction_force: Default::default(),
+            reaction_torque: Default::default(),
+            reaction_dt: 0.0,
         };
 
         let default_id = InteractionGraph::<(), ()>::invalid_graph_index();
+        self.rb_graph_id_changes.mark_index(joint.body1.0);
+        self.rb_graph_id_changes.mark_index(joint.body2.0);
         let mut graph_index1 = *self
             .rb_graph_ids
             .ensure_element_exist(joint.body1.0, default_id);
@@ -448,11 +490,14 @@ impl ImpulseJointSet {
         let mut joint = self.joint_graph.graph.remove_edge(edge_id)?;
         if let Some(swapped) = self.joint_graph.graph.edge_weight(edge_id) {
             self.joint_ids[swapped.handle.0] = edge_id;
+            self.joint_id_changes.mark_index(swapped.handle.0);
         }
 
         // Ensure both new endpoints have graph nodes (same dance `insert`
         // does for first-time endpoints).
         let default_id = InteractionGraph::<(), ()>::invalid_graph_index();
+        self.rb_graph_id_changes.mark_index(new_body1.0);
+        self.rb_graph_id_changes.mark_index(new_body2.0);
         let mut graph_index1 = *self
             .rb_graph_ids
             .ensure_element_exist(new_body1.0, default_id);
@@ -472,6 +517,7 @@ impl ImpulseJointSet {
         joint.body2 = new_body2;
         let new_edge_id = self.joint_graph.add_edge(graph_index1, graph_index2, joint);
         self.joint_ids[handle.0] = new_edge_id;
+        self.joint_id_changes.mark_index(handle.0);
 
         if wake_up {
             self.to_wake_up.insert(old_body1);
@@ -595,6 +641,7 @@ impl ImpulseJointSet {
     pub fn remove(&mut self, handle: ImpulseJointHandle, wake_up: bool) -> Option<ImpulseJoint> {
         self.bump_assembly_epoch();
         let id = self.joint_ids.remove(handle.0)?;
+        self.joint_id_changes.mark_index(handle.0);
         let endpoints = self.joint_graph.graph.edge_endpoints(id)?;
 
         if wake_up {
@@ -609,6 +656,7 @@ impl ImpulseJointSet {
 
         if let Some(edge) = self.joint_graph.graph.edge_weight(id) {
             self.joint_ids[edge.handle.0] = id;
+            self.joint_id_changes.mark_index(edge.handle.0);
         }
 
         self.island_events
@@ -633,6 +681,7 @@ impl ImpulseJointSet {
             .rb_graph_ids
             .remove(handle.0, InteractionGraph::<(), ()>::invalid_graph_index())
         {
+            self.rb_graph_id_changes.mark_index(handle.0);
             if InteractionGraph::<(), ()>::is_graph_index_valid(deleted_id) {
                 // We have to delete each joint one by one in order to:
                 // - Wake-up the attached bodies.
@@ -646,11 +695,13 @@ impl ImpulseJointSet {
                 for (h1, h2, to_delete_handle) in to_delete {
                     deleted.push(to_delete_handle);
                     let to_delete_edge_id = self.joint_ids.remove(to_delete_handle.0).unwrap();
+                    self.joint_id_changes.mark_index(to_delete_handle.0);
                     self.joint_graph.graph.remove_edge(to_delete_edge_id);
 
                     // Update the id of the edge which took the place of the deleted one.
                     if let Some(j) = self.joint_graph.graph.edge_weight_mut(to_delete_edge_id) {
                         self.joint_ids[j.handle.0] = to_delete_edge_id;
+                        self.joint_id_changes.mark_index(j.handle.0);
                     }
 
                     // Wake up the attached bodies.
@@ -666,6 +717,7 @@ impl ImpulseJointSet {
                     // One rigid-body joint graph index may have been invalidated
                     // so we need to update it.
                     self.rb_graph_ids.insert(other.0, deleted_id);
+                    self.rb_graph_id_changes.mark_index(other.0);
                 }
             }
         }
@@ -673,3 +725,103 @@ impl ImpulseJointSet {
         deleted
     }
 }
+
+/// The parts of an [`ImpulseJointSet`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`ImpulseJointSet::delta_snapshot`] and restored with
+/// [`ImpulseJointSet::apply_delta`].
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ImpulseJointSetDelta {
+    rb_graph_ids: CoarenaDelta<RigidBodyGraphIndex>,
+    joint_ids: ArenaDelta<TemporaryInteractionIndex>,
+    joint_graph: GraphDelta<RigidBodyHandle, ImpulseJoint>,
+    to_wake_up: Option<HashSet<RigidBodyHandle>>,
+    to_join: Option<HashSet<(RigidBodyHandle, RigidBodyHandle)>>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ImpulseJointSetDelta {
+    /// The number of joints this delta holds.
+    pub fn num_changed_joints(&self) -> usize {
+        self.joint_graph.num_changed_edges()
+    }
+}
+
+/// Remembers what an [`ImpulseJointSet`] looked like at its last delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Default)]
+pub struct ImpulseJointSetDeltaTracker {
+    rb_graph_ids: ChangeTracker,
+    joint_ids: ChangeTracker,
+    joint_graph: GraphDeltaTracker,
+    to_wake_up: ValueTracker,
+    to_join: ValueTracker,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ImpulseJointSetDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ImpulseJointSet {
+    /// The parts of this set that changed since the previous snapshot taken with `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole set. Afterward,
+    /// only the joints whose serialized form changed are stored (the joints between sleeping
+    /// bodies cost nothing), together with the handle-to-graph mappings modified since then.
+    ///
+    /// The changes are recorded by the set itself, so its snapshots must all be taken with the
+    /// same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(
+        &mut self,
+        tracker: &mut ImpulseJointSetDeltaTracker,
+    ) -> ImpulseJointSetDelta {
+        // Destructured so that a new field can't be forgotten here.
+        let ImpulseJointSet {
+            rb_graph_ids,
+            joint_ids,
+            joint_graph,
+            to_wake_up,
+            to_join,
+            island_events: _,
+            assembly_epoch: _,
+            selection_epochs: _,
+            rb_graph_id_changes,
+            joint_id_changes,
+        } = self;
+
+        ImpulseJointSetDelta {
+            rb_graph_ids: rb_graph_ids.delta(&mut tracker.rb_graph_ids, rb_graph_id_changes),
+            joint_ids: joint_ids.delta(&mut tracker.joint_ids, joint_id_changes),
+            joint_graph: joint_graph.graph.delta(&mut tracker.joint_graph),
+            to_wake_up: tracker.to_wake_up.diff(to_wake_up),
+            to_join: tracker.to_join.diff(to_join),
+        }
+    }
+
+    /// Brings this set from the state of the previous delta snapshot to the state of `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The set
+    /// then serializes to the same bytes as the one the snapshots were taken from, and its
+    /// internal caches are reset the way deserialization does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this set isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &ImpulseJointSetDelta) {
+        self.rb_graph_ids
+            .apply_delta(&delta.rb_graph_ids, &mut self.rb_graph_id_changes);
+        self.joint_ids
+            .apply_delta(&delta.joint_ids, &mut self.joint_id_changes);
+        self.joint_graph.graph.apply_delta(&delta.joint_graph);
+        apply_value(&mut self.to_wake_up, &delta.to_wake_up);
+        apply_value(&mut self.to_join, &delta.to_join);
+        self.island_events.clear();
+        self.bump_assembly_epoch();
+    }
+}
@@ -1,7 +1,9 @@
-pub use self::impulse_joint::ImpulseJoint;
+pub use self::impulse_joint::{ImpulseJoint, JointBreakEvent};
 pub use self::impulse_joint_handle::ImpulseJointHandle;
 #[cfg(feature = "alloc")]
 pub use self::impulse_joint_set::ImpulseJointSet;
+#[cfg(feature = "serde-serialize")]
+pub use self::impulse_joint_set::{ImpulseJointSetDelta, ImpulseJointSetDeltaTracker};
 #[cfg(feature = "alloc")]
 pub(crate) use self::impulse_joint_set::{JointGraphEdge, JointIndex};
 
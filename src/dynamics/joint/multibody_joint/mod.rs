@@ -3,12 +3,14 @@
 #[cfg(feature = "alloc")]
 pub use self::multibody::{Multibody, MultibodyDofCoupling};
 #[cfg(feature = "alloc")]
-pub use self::multibody_ik::InverseKinematicsOption;
+pub use self::multibody_ik::{ImpulseJointTree, InverseKinematicsOption, InverseKinematicsTarget};
 #[cfg(feature = "alloc")]
 pub use self::multibody_joint::MultibodyJoint;
 pub use self::multibody_joint_handle::{MultibodyIndex, MultibodyJointHandle};
 #[cfg(feature = "alloc")]
 pub use self::multibody_joint_set::{MultibodyJointSet, MultibodyLinkId};
+#[cfg(feature = "serde-serialize")]
+pub use self::multibody_joint_set::{MultibodyJointSetDelta, MultibodyJointSetDeltaTracker};
 #[cfg(feature = "alloc")]
 pub use self::multibody_link::MultibodyLink;
 #[cfg(feature = "alloc")]
@@ -62,6 +62,38 @@ fn concat_rb_mass_matrix(
     result
 }
 
+/// The velocity-dependent acceleration `J̇·q̇` of a link’s center of mass (Eqn 42 to 45).
+///
+/// `parent` holds the angular velocity and velocity-dependent acceleration of the parent link,
+/// if any, `joint_velocity` is the world-space velocity of the link’s joint, and `angvel` the
+/// angular velocity of the link.
+fn bias_acceleration(
+    link: &MultibodyLink,
+    parent: Option<(AngVector, RigidBodyVelocity<Real>)>,
+    joint_velocity: &RigidBodyVelocity<Real>,
+    angvel: AngVector,
+) -> RigidBodyVelocity<Real> {
+    let mut acc = RigidBodyVelocity::zero();
+
+    if let Some((parent_angvel, parent_acc)) = parent {
+        acc += parent_acc;
+        // The 2.0 originates from the two identical terms of Jdot (the terms become
+        // identical once they are multiplied by the generalized velocities).
+        acc.linvel += 2.0 * parent_angvel.gcross(joint_velocity.linvel);
+        #[cfg(feature = "dim3")]
+        {
+            acc.angvel += parent_angvel.cross(joint_velocity.angvel);
+        }
+
+        acc.linvel += parent_angvel.gcross(parent_angvel.gcross(link.shift02));
+        acc.linvel += parent_acc.angvel.gcross(link.shift02);
+    }
+
+    acc.linvel += angvel.gcross(angvel.gcross(link.shift23));
+    acc.linvel += acc.angvel.gcross(link.shift23);
+    acc
+}
+
 /// A holonomic coupling between two generalized coordinates of a single
 /// [`Multibody`], `q2 = coeff · q1 + offset`, enforced as a velocity-level
 /// equality constraint. This is how MuJoCo's `<equality><joint>` (a polynomial
@@ -497,31 +529,12 @@ impl Multibody {
             let link = &self.links[i];
             let rb = &bodies[link.rigid_body];
 
-            let mut acc = RigidBodyVelocity::zero();
-
-            if i != 0 {
+            let parent = (i != 0).then(|| {
                 let parent_id = link.parent_internal_id;
-                let parent_link = &self.links[parent_id];
-                let parent_rb = &bodies[parent_link.rigid_body];
-
-                acc += self.workspace.accs[parent_id];
-                // The 2.0 originates from the two identical terms of Jdot (the terms become
-                // identical once they are multiplied by the generalized velocities).
-                acc.linvel += 2.0 * parent_rb.vels.angvel.gcross(link.joint_velocity.linvel);
-                #[cfg(feature = "dim3")]
-                {
-                    acc.angvel += parent_rb.vels.angvel.cross(link.joint_velocity.angvel);
-                }
-
-                acc.linvel += parent_rb
-                    .vels
-                    .angvel
-                    .gcross(parent_rb.vels.angvel.gcross(link.shift02));
-                acc.linvel += self.workspace.accs[parent_id].angvel.gcross(link.shift02);
-            }
-
-            acc.linvel += rb.vels.angvel.gcross(rb.vels.angvel.gcross(link.shift23));
-            acc.linvel += acc.angvel.gcross(link.shift23);
+                let parent_rb = &bodies[self.links[parent_id].rigid_body];
+                (parent_rb.vels.angvel, self.workspace.accs[parent_id])
+            });
+            let acc = bias_acceleration(link, parent, &link.joint_velocity, rb.vels.angvel);
 
             self.workspace.accs[i] = acc;
 
@@ -712,6 +725,53 @@ impl Multibody {
         }
     }
 
+    /// Computes the wrench each joint applied to its link during the last substep, with the
+    /// Newton–Euler equations of the subtree rooted at that link.
+    ///
+    /// Must be called right after the solve: the links' rigid-bodies still hold their
+    /// velocities from the start of the last substep, while `self.velocities` and the body
+    /// jacobians are the solved ones.
+    pub(crate) fn update_joint_reactions(&mut self, dt: Real, bodies: &RigidBodySet) {
+        let inv_dt = crate::utils::inv(dt);
+        // The force each link needs, and its torque about the world origin.
+        let mut wrenches: Vec<(Vector, AngVector)> = Vec::with_capacity(self.links.len());
+
+        for i in 0..self.links.len() {
+            let link = &self.links[i];
+            let rb = &bodies[link.rigid_body];
+            let new_vels = RigidBodyVelocity::from_slice(
+                (&self.body_jacobians[i] * &self.velocities).as_slice(),
+            );
+            let acc = (new_vels - rb.vels) * inv_dt;
+            let rb_inertia = rb.mprops.effective_angular_inertia();
+
+            #[cfg(feature = "dim3")]
+            let gyroscopic = new_vels.angvel.cross(rb_inertia * new_vels.angvel);
+            #[cfg(feature = "dim2")]
+            let gyroscopic = 0.0;
+
+            let force = rb.mprops.effective_mass() * acc.linvel - rb.forces.force;
+            let torque = rb_inertia * acc.angvel + gyroscopic - rb.forces.torque;
+            let com = link.local_to_world * rb.mprops.local_mprops.local_com;
+            wrenches.push((force, torque + com.gcross(force)));
+        }
+
+        // Children come after their parent: accumulate the subtrees bottom-up.
+        for i in (0..self.links.len()).rev() {
+            let (force, torque) = wrenches[i];
+            let link = &mut self.links[i];
+            let anchor = link.local_to_world * link.joint.data.local_frame2.translation;
+            link.joint.reaction_force = force;
+            link.joint.reaction_torque = torque - anchor.gcross(force);
+
+            if i != 0 {
+                let parent = &mut wrenches[link.parent_internal_id];
+                parent.0 += force;
+                parent.1 += torque;
+            }
+        }
+    }
+
     fn update_body_jacobians(&mut self) {
         for i in 0..self.links.len() {
             let link = &self.links[i];
@@ -1064,6 +1124,201 @@ impl Multibody {
         out
     }
 
+    /// Changes the generalized velocity by `velocity_changes`, as generalized impulses.
+    ///
+    /// Each degree of freedom `i` receives the impulse `velocity_changes[i] / (M⁻¹)[i, i]`, its
+    /// velocity change scaled by its effective inertia, and every degree of freedom reacts to these
+    /// impulses through the mass matrix `M` factorized at the last step, like it would to joint
+    /// torques. The degrees of freedom this mass matrix doesn’t cover (the kinematic ones, or all
+    /// of them before the first step) have their velocity changed directly.
+    pub(crate) fn apply_velocity_changes_as_impulses(&mut self, velocity_changes: &DVector) {
+        let n = self.ndofs;
+        let factorized = self.augmented_mass.nrows() == n;
+        let mut dvel = DVector::zeros(n);
+        let mut column = DVector::zeros(n);
+        for (i, change) in velocity_changes.iter().enumerate() {
+            if *change == 0.0 {
+                continue;
+            }
+
+            column.fill(0.0);
+            if factorized {
+                // `M⁻¹·e_i`, in the kinematic-reduced ordering of the factorization.
+                column[i] = 1.0;
+                self.augmented_mass_indices
+                    .with_rearranged_rows_mut(&mut column, |b| {
+                        self.inv_augmented_mass.solve_mut(b);
+                    });
+            }
+
+            if column[i] > 0.0 {
+                dvel.axpy(*change / column[i], &column, 1.0);
+            } else {
+                dvel[i] += *change;
+            }
+        }
+        self.velocities += dvel;
+    }
+
+    /// The joint-space mass matrix `M(q)` at the current configuration, including armature but
+    /// excluding joint damping and springs.
+    ///
+    /// Its rows and columns follow the ordering of the [generalized velocities](Self::generalized_velocity),
+    /// so the kinetic energy of the multibody is `½·q̇ᵀ·M·q̇`. Re-runs forward kinematics so the
+    /// result matches the current generalized coordinates.
+    pub fn mass_matrix(&mut self, bodies: &RigidBodySet) -> DMatrix<Real> {
+        self.forward_kinematics(bodies, false);
+
+        let mut mass = DMatrix::zeros(self.ndofs, self.ndofs);
+        if self.ndofs == 0 {
+            return mass;
+        }
+
+        for (link, body_jacobian) in self.links.iter().zip(self.body_jacobians.iter()) {
+            let rb = &bodies[link.rigid_body];
+            let rb_inertia = rb.mprops.effective_angular_inertia().into_matrix();
+            #[allow(clippy::useless_conversion)] // Needed in 3D, no-op in 2D
+            let rb_mass_matrix =
+                concat_rb_mass_matrix(rb.mprops.effective_mass(), rb_inertia.into());
+            mass.quadform(1.0, &rb_mass_matrix, body_jacobian, 1.0);
+        }
+
+        for i in 0..self.ndofs {
+            mass[(i, i)] += self.armature[i];
+        }
+
+        mass
+    }
+
+    /// The inverse dynamics of this multibody: the generalized forces
+    /// `τ = M(q)·q̈ + C(q, q̇)·q̇ + g(q)` needed to reach the given generalized `accelerations`
+    /// from the current generalized coordinates and velocities, under `gravity`.
+    ///
+    /// `M` is the [mass matrix](Self::mass_matrix), `C(q, q̇)·q̇` the
+    /// [velocity-dependent forces](Self::coriolis_torques) and `g` the
+    /// [gravity-compensation torques](Self::gravity_compensation_torques). The accelerations and
+    /// the result follow the ordering of the [generalized velocities](Self::generalized_velocity).
+    /// Forces applied by the user, contacts, joint damping, springs and motors are not accounted
+    /// for. Re-runs forward kinematics so the result matches the current generalized coordinates.
+    pub fn inverse_dynamics(
+        &mut self,
+        bodies: &RigidBodySet,
+        gravity: Vector,
+        accelerations: &[Real],
+    ) -> DVector {
+        self.generalized_forces(bodies, Some(accelerations), true, gravity)
+    }
+
+    /// The coriolis, centrifugal and gyroscopic forces `C(q, q̇)·q̇` at the current generalized
+    /// coordinates and velocities.
+    ///
+    /// This is the part of the [inverse dynamics](Self::inverse_dynamics) that depends on the
+    /// generalized velocities only.
+    pub fn coriolis_torques(&mut self, bodies: &RigidBodySet) -> DVector {
+        self.generalized_forces(bodies, None, true, Vector::ZERO)
+    }
+
+    /// The generalized forces `g(q)` holding this multibody still against `gravity` at the
+    /// current generalized coordinates.
+    ///
+    /// The gravity scale of each link is taken into account. This is the part of the
+    /// [inverse dynamics](Self::inverse_dynamics) that depends on the gravity only.
+    pub fn gravity_compensation_torques(
+        &mut self,
+        bodies: &RigidBodySet,
+        gravity: Vector,
+    ) -> DVector {
+        self.generalized_forces(bodies, None, false, gravity)
+    }
+
+    // Newton-Euler: each link needs the wrench `Mᵢ·(Jᵢ·q̈ + J̇ᵢ·q̇) + ωᵢ × Iᵢ·ωᵢ − mᵢ·g`, which
+    // maps to the generalized forces `Jᵢᵀ·wrenchᵢ`.
+    fn generalized_forces(
+        &mut self,
+        bodies: &RigidBodySet,
+        accelerations: Option<&[Real]>,
+        velocity_dependent: bool,
+        gravity: Vector,
+    ) -> DVector {
+        self.forward_kinematics(bodies, false);
+
+        let mut forces = DVector::zeros(self.ndofs);
+        if self.ndofs == 0 {
+            return forces;
+        }
+
+        let accelerations = accelerations.map(|accelerations| {
+            assert_eq!(
+                accelerations.len(),
+                self.ndofs,
+                "The number of accelerations must match the multibody’s number of degrees of freedom."
+            );
+            DVectorView::from_slice(accelerations, self.ndofs)
+        });
+        if let Some(accelerations) = &accelerations {
+            forces.cmpy(1.0, &self.armature, accelerations, 0.0);
+        }
+
+        // Angular velocities of the links, computed from the generalized velocities since the
+        // rigid-bodies’ velocities may be outdated.
+        let mut angvels: Vec<AngVector> = Vec::with_capacity(self.links.len());
+
+        for i in 0..self.links.len() {
+            let link = &self.links[i];
+            let rb = &bodies[link.rigid_body];
+            let rb_mass = rb.mprops.effective_mass();
+            let rb_inertia = rb.mprops.effective_angular_inertia();
+
+            let mut acc = RigidBodyVelocity::zero();
+            #[allow(unused_mut)] // mut needed in 3D.
+            let mut gyroscopic = AngVector::default();
+
+            if velocity_dependent {
+                let mut joint_velocity = link
+                    .joint
+                    .jacobian_mul_coordinates(&self.velocities.as_slice()[link.assembly_id..]);
+                let mut angvel = joint_velocity.angvel;
+                let mut parent = None;
+
+                if i != 0 {
+                    let parent_id = link.parent_internal_id;
+                    let parent_link = &self.links[parent_id];
+                    joint_velocity = joint_velocity.transformed(
+                        &(parent_link.local_to_world.rotation
+                            * link.joint.data.local_frame1.rotation),
+                    );
+                    angvel = angvels[parent_id] + joint_velocity.angvel;
+                    parent = Some((angvels[parent_id], self.workspace.accs[parent_id]));
+                }
+
+                acc = bias_acceleration(link, parent, &joint_velocity, angvel);
+                #[cfg(feature = "dim3")]
+                {
+                    gyroscopic = angvel.cross(rb_inertia * angvel);
+                }
+                self.workspace.accs[i] = acc;
+                angvels.push(angvel);
+            }
+
+            let mut wrench = *Force::new(
+                rb_mass * (acc.linvel - gravity * rb.forces.gravity_scale),
+                rb_inertia * acc.angvel + gyroscopic,
+            )
+            .as_vector();
+
+            if let Some(accelerations) = &accelerations {
+                #[allow(clippy::useless_conversion)] // Needed in 3D, no-op in 2D
+                let rb_mass_matrix =
+                    concat_rb_mass_matrix(rb_mass, rb_inertia.into_matrix().into());
+                wrench += rb_mass_matrix * (&self.body_jacobians[i] * accelerations);
+            }
+
+            forces.gemv_tr(1.0, &self.body_jacobians[i], &wrench, 1.0);
+        }
+
+        forces
+    }
+
     /// Adds a holonomic coupling between two of this multibody's generalized
     /// coordinates (`q2 = coeff·q1 + offset`), enforced as a velocity-level
     /// equality constraint each step. See [`MultibodyDofCoupling`].
@@ -1183,6 +1438,27 @@ impl Multibody {
         self.velocities.rows_mut(0, self.ndofs)
     }
 
+    /// The generalized coordinates of this multibody, in the same order as its
+    /// [generalized velocities](Self::generalized_velocity).
+    ///
+    /// This gathers the [`MultibodyJoint::coords`] of each link's free axes. For joints with
+    /// three free angular axes, the angular coordinates are the integrated joint angular
+    /// velocities rather than a parametrization of [`MultibodyJoint::joint_rot`].
+    pub fn generalized_coordinates(&self) -> DVector {
+        let mut coords = DVector::zeros(self.ndofs);
+        for link in self.links.iter() {
+            let locked = link.joint.data.locked_axes.bits();
+            let mut idx = link.assembly_id;
+            for a in 0..SPATIAL_DIM {
+                if (locked >> a) & 1 == 0 {
+                    coords[idx] = link.joint.coords[a];
+                    idx += 1;
+                }
+            }
+        }
+        coords
+    }
+
     #[inline]
     pub(crate) fn integrate(&mut self, dt: Real) {
         for rb in self.links.iter_mut() {
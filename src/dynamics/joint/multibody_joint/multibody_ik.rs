@@ -1,7 +1,11 @@
 use crate::alloc_prelude::*;
-use crate::dynamics::{JointAxesMask, Multibody, MultibodyLink, RigidBodySet};
+use crate::dynamics::{
+    ImpulseJointHandle, ImpulseJointSet, JointAxesMask, Multibody, MultibodyJoint, MultibodyLink,
+    RigidBodyHandle, RigidBodySet,
+};
 use crate::math::{ANG_DIM, DIM, DVector, Jacobian, Pose, Real, SPATIAL_DIM};
-use na::{self, SMatrix, SVector};
+use crate::utils::{CrossProductMatrix, vect_to_na};
+use na::{self, DMatrix, SMatrix, SVector};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 /// Options for the jacobian-based Inverse Kinematics solver for multibodies.
@@ -25,6 +29,14 @@ pub struct InverseKinematicsOption {
     /// If errors on both linear and angular parts fall below this
     /// threshold, the iterative resolution will stop.
     pub epsilon_angular: Real,
+    /// Should the solution be clamped to the joint limits?
+    ///
+    /// Only the limits of linear axes, and of joints with a single free angular axis, are
+    /// enforced.
+    pub enforce_joint_limits: bool,
+    /// The fraction of the posture error corrected at each iteration of
+    /// [`Multibody::inverse_kinematics_multi`], if a posture is given.
+    pub posture_weight: Real,
 }
 
 impl Default for InverseKinematicsOption {
@@ -35,6 +47,39 @@ impl Default for InverseKinematicsOption {
             constrained_axes: JointAxesMask::all(),
             epsilon_linear: 1.0e-3,
             epsilon_angular: 1.0e-3,
+            enforce_joint_limits: false,
+            posture_weight: 0.1,
+        }
+    }
+}
+
+/// A target of the multi-target inverse-kinematics solver [`Multibody::inverse_kinematics_multi`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct InverseKinematicsTarget {
+    /// The id of the multibody link to move.
+    pub link_id: usize,
+    /// The frame moved toward the target pose, relative to the link’s rigid-body.
+    pub local_frame: Pose,
+    /// The target world-space pose of the frame.
+    pub pose: Pose,
+    /// The axes of the target pose the solver will solve for.
+    pub constrained_axes: JointAxesMask,
+    /// The priority of this target relative to the other targets.
+    ///
+    /// If the targets can’t all be reached, the solver minimizes the sum of their squared errors
+    /// multiplied by their weights.
+    pub weight: Real,
+}
+
+impl InverseKinematicsTarget {
+    /// A target moving the origin of the link `link_id` toward `pose`, along all the axes.
+    pub fn new(link_id: usize, pose: Pose) -> Self {
+        Self {
+            link_id,
+            local_frame: Pose::IDENTITY,
+            pose,
+            constrained_axes: JointAxesMask::all(),
+            weight: 1.0,
         }
     }
 }
@@ -105,6 +150,17 @@ impl Multibody {
             .iter()
             .map(|id| joint_can_move(&self.links[*id]))
             .collect();
+        let limits = if options.enforce_joint_limits {
+            self.joint_limits(
+                branch
+                    .iter()
+                    .zip(can_move.iter())
+                    .filter(|(_, can_move)| **can_move)
+                    .map(|(id, _)| &self.links[*id]),
+            )
+        } else {
+            vec![]
+        };
 
         for _ in 0..options.max_iters {
             let pose = self.forward_kinematics_single_branch(
@@ -124,45 +180,7 @@ impl Multibody {
                 }
             }
 
-            let delta_lin = target_pose.translation - pose.translation;
-            #[cfg(feature = "dim2")]
-            let delta_ang = (target_pose.rotation * pose.rotation.inverse()).angle();
-            #[cfg(feature = "dim3")]
-            let delta_ang = (target_pose.rotation * pose.rotation.inverse()).to_scaled_axis();
-
-            #[cfg(feature = "dim2")]
-            let mut delta = na::vector![delta_lin.x, delta_lin.y, delta_ang];
-            #[cfg(feature = "dim3")]
-            let mut delta = na::vector![
-                delta_lin.x,
-                delta_lin.y,
-                delta_lin.z,
-                delta_ang.x,
-                delta_ang.y,
-                delta_ang.z
-            ];
-
-            if !options.constrained_axes.contains(JointAxesMask::LIN_X) {
-                delta[0] = 0.0;
-            }
-            if !options.constrained_axes.contains(JointAxesMask::LIN_Y) {
-                delta[1] = 0.0;
-            }
-            #[cfg(feature = "dim3")]
-            if !options.constrained_axes.contains(JointAxesMask::LIN_Z) {
-                delta[2] = 0.0;
-            }
-            if !options.constrained_axes.contains(JointAxesMask::ANG_X) {
-                delta[DIM] = 0.0;
-            }
-            #[cfg(feature = "dim3")]
-            if !options.constrained_axes.contains(JointAxesMask::ANG_Y) {
-                delta[DIM + 1] = 0.0;
-            }
-            #[cfg(feature = "dim3")]
-            if !options.constrained_axes.contains(JointAxesMask::ANG_Z) {
-                delta[DIM + 2] = 0.0;
-            }
+            let delta = pose_delta(&pose, target_pose, options.constrained_axes);
 
             // TODO: measure convergence on the error variation instead?
             if delta.rows(0, DIM).norm() <= options.epsilon_linear
@@ -177,8 +195,360 @@ impl Multibody {
                 options.damping,
                 displacements,
             );
+
+            for limit in &limits {
+                displacements[limit.dof] = limit.clamp_displacement(displacements[limit.dof]);
+            }
+        }
+    }
+
+    /// Computes the displacement needed to have several links reach their targets simultaneously.
+    ///
+    /// The targets are solved for in the least-squares sense, weighted by
+    /// [`InverseKinematicsTarget::weight`], with the same damped least-squares iterations as
+    /// [`Self::inverse_kinematics`]. If `posture` is given, the generalized coordinates are also
+    /// pulled toward it by [`InverseKinematicsOption::posture_weight`] at each iteration, within the
+    /// null-space of the targets so that it doesn’t disturb them. If
+    /// [`InverseKinematicsOption::enforce_joint_limits`] is set, the degrees of freedom reaching
+    /// their limits are frozen there while the remaining ones keep solving for the targets.
+    ///
+    /// If `displacement` is given non-zero, the current pose of the rigid-body is considered to be
+    /// obtained from its current generalized coordinates summed with the `displacement` vector.
+    /// The `displacements` vector is overwritten with the new displacement.
+    ///
+    /// Any joint for which `joint_can_move` returns `false` will have its corresponding
+    /// displacement constrained to 0.
+    #[profiling::function]
+    pub fn inverse_kinematics_multi(
+        &self,
+        bodies: &RigidBodySet,
+        targets: &[InverseKinematicsTarget],
+        posture: Option<&[Real]>,
+        options: &InverseKinematicsOption,
+        joint_can_move: impl Fn(&MultibodyLink) -> bool,
+        displacements: &mut DVector,
+    ) {
+        let ndofs = self.ndofs();
+        let mut movable = vec![false; ndofs];
+        for link in self.links.iter().filter(|link| joint_can_move(link)) {
+            movable[link.assembly_id..link.assembly_id + link.joint.ndofs()].fill(true);
+        }
+        let limits = if options.enforce_joint_limits {
+            self.joint_limits(self.links.iter().filter(|link| joint_can_move(link)))
+        } else {
+            vec![]
+        };
+        let posture = posture.map(|posture| {
+            assert_eq!(
+                posture.len(),
+                ndofs,
+                "The posture must match the multibody’s number of degrees of freedom."
+            );
+            DVector::from_row_slice(posture)
+        });
+
+        let coords = self.generalized_coordinates();
+        let branches: Vec<_> = targets
+            .iter()
+            .map(|target| self.kinematic_branch(target.link_id))
+            .collect();
+        let mut link_jacobian = Jacobian::zeros(0);
+        let mut jacobian = DMatrix::zeros(targets.len() * SPATIAL_DIM, ndofs);
+        let mut errors = DVector::zeros(targets.len() * SPATIAL_DIM);
+
+        for _ in 0..options.max_iters {
+            let mut converged = true;
+
+            for (k, (target, branch)) in targets.iter().zip(branches.iter()).enumerate() {
+                let link_pose = self.forward_kinematics_single_branch(
+                    bodies,
+                    branch,
+                    Some(displacements.as_slice()),
+                    Some(&mut link_jacobian),
+                );
+                let pose = link_pose * target.local_frame;
+                let link_rb = &bodies[self.links[target.link_id].rigid_body];
+                let com = link_pose * link_rb.local_center_of_mass();
+
+                // Move the jacobian from the link’s center of mass to the target frame.
+                {
+                    let (mut jacobian_v, jacobian_w) =
+                        link_jacobian.rows_range_pair_mut(0..DIM, DIM..SPATIAL_DIM);
+                    let shift_tr = vect_to_na(pose.translation - com).gcross_matrix_tr();
+                    jacobian_v.gemm(1.0, &shift_tr, &jacobian_w, 1.0);
+                }
+
+                let delta = pose_delta(&pose, &target.pose, target.constrained_axes);
+                converged &= delta.rows(0, DIM).norm() <= options.epsilon_linear
+                    && delta.rows(DIM, ANG_DIM).norm() <= options.epsilon_angular;
+
+                let sqrt_weight = target.weight.sqrt();
+                let mut rows = jacobian.rows_mut(k * SPATIAL_DIM, SPATIAL_DIM);
+                rows.copy_from(&link_jacobian);
+                rows *= sqrt_weight;
+                for i in 0..SPATIAL_DIM {
+                    if (target.constrained_axes.bits() >> i) & 1 == 0 {
+                        rows.row_mut(i).fill(0.0);
+                    }
+                }
+                errors
+                    .rows_mut(k * SPATIAL_DIM, SPATIAL_DIM)
+                    .copy_from(&(delta * sqrt_weight));
+            }
+
+            if converged && posture.is_none() {
+                break;
+            }
+
+            let posture_step = posture
+                .as_ref()
+                .map(|posture| (posture - &coords - &*displacements) * options.posture_weight);
+
+            // Solve with all the movable dofs, then freeze the ones exceeding their limits at
+            // these limits and solve again with the others, until no more limits are exceeded.
+            let mut free = movable.clone();
+            let step = loop {
+                for (j, _) in free.iter().enumerate().filter(|(_, free)| !**free) {
+                    jacobian.column_mut(j).fill(0.0);
+                }
+                let step = damped_least_squares(
+                    &jacobian,
+                    &errors,
+                    posture_step.as_ref(),
+                    &free,
+                    options.damping,
+                );
+
+                let violated: Vec<_> = limits
+                    .iter()
+                    .filter(|limit| free[limit.dof])
+                    .filter_map(|limit| {
+                        let disp = displacements[limit.dof];
+                        let clamped = limit.clamp_displacement(disp + step[limit.dof]);
+                        (clamped != disp + step[limit.dof]).then_some((limit.dof, clamped))
+                    })
+                    .collect();
+                if violated.is_empty() {
+                    break step;
+                }
+
+                for (dof, clamped) in violated {
+                    errors.axpy(displacements[dof] - clamped, &jacobian.column(dof), 1.0);
+                    displacements[dof] = clamped;
+                    free[dof] = false;
+                }
+            };
+
+            *displacements += &step;
+
+            if converged && step.amax() <= options.epsilon_angular {
+                break;
+            }
         }
     }
+
+    /// The limits of the generalized coordinates of the given links.
+    ///
+    /// The angular coordinates of a joint with several free angular axes don’t map to
+    /// independent angles, so their limits are ignored.
+    fn joint_limits<'a>(&self, links: impl Iterator<Item = &'a MultibodyLink>) -> Vec<DofLimit> {
+        let mut limits = vec![];
+        for link in links {
+            let data = &link.joint.data;
+            let locked = data.locked_axes.bits();
+            let num_free_ang_dofs = ANG_DIM - (locked >> DIM).count_ones() as usize;
+            let mut dof = link.assembly_id;
+            for a in 0..SPATIAL_DIM {
+                if (locked >> a) & 1 == 0 {
+                    if (data.limit_axes.bits() >> a) & 1 != 0 && (a < DIM || num_free_ang_dofs == 1)
+                    {
+                        limits.push(DofLimit {
+                            dof,
+                            coord: link.joint.coords[a],
+                            min: data.limits[a].min,
+                            max: data.limits[a].max,
+                        });
+                    }
+                    dof += 1;
+                }
+            }
+        }
+        limits
+    }
+}
+
+/// A tree of rigid-bodies attached by impulse joints, mirrored by a kinematic [`Multibody`] so
+/// that the multibody inverse-kinematics solvers apply to it.
+///
+/// This is typically used on robots inserted with impulse joints by the URDF or MJCF loaders.
+/// The mirror is a snapshot of the bodies’ poses when the tree is created: build a new tree
+/// after the bodies moved.
+#[derive(Clone, Debug)]
+pub struct ImpulseJointTree {
+    multibody: Multibody,
+    joints: Vec<Option<ImpulseJointHandle>>,
+}
+
+impl ImpulseJointTree {
+    /// Builds the tree of all the dynamic rigid-bodies reachable from `root` through impulse
+    /// joints.
+    ///
+    /// The root itself can be of any type. Joints closing a loop are ignored.
+    pub fn new(
+        bodies: &RigidBodySet,
+        impulse_joints: &ImpulseJointSet,
+        root: RigidBodyHandle,
+    ) -> Self {
+        let mut multibody = Multibody::with_root(root, false);
+        let mut joints = vec![None];
+        let mut stack = vec![0];
+
+        while let Some(parent_id) = stack.pop() {
+            let parent = multibody.links[parent_id].rigid_body;
+
+            for (_, _, handle, joint) in impulse_joints.attached_joints(parent) {
+                let mut data = joint.data;
+                let child = if joint.body1() == parent {
+                    joint.body2()
+                } else {
+                    data.flip();
+                    joint.body1()
+                };
+
+                if multibody.links.iter().any(|link| link.rigid_body == child)
+                    || !bodies.get(child).is_some_and(|rb| rb.is_dynamic())
+                {
+                    continue;
+                }
+
+                let frame1 = *bodies[parent].position() * data.local_frame1;
+                let frame2 = *bodies[child].position() * data.local_frame2;
+                let mut mb_joint = MultibodyJoint::new(data, true);
+                mb_joint.set_coords_from_frames_pose(frame1.inverse() * frame2);
+
+                let link = multibody.add_link(Some(parent_id), mb_joint, child);
+                stack.push(link.internal_id);
+                joints.push(Some(handle));
+            }
+        }
+
+        multibody.forward_kinematics(bodies, true);
+        Self { multibody, joints }
+    }
+
+    /// The multibody mirroring this tree, on which inverse kinematics can be solved.
+    pub fn multibody(&self) -> &Multibody {
+        &self.multibody
+    }
+
+    /// The id of the multibody link mirroring the given rigid-body, if it is part of the tree.
+    pub fn link_id(&self, body: RigidBodyHandle) -> Option<usize> {
+        self.multibody
+            .links()
+            .position(|link| link.rigid_body == body)
+    }
+
+    /// The impulse joint mirrored by the given multibody link, or `None` for the root.
+    pub fn joint(&self, link_id: usize) -> Option<ImpulseJointHandle> {
+        self.joints.get(link_id).copied().flatten()
+    }
+
+    /// Moves the tree by the given generalized displacements (as computed by the multibody’s
+    /// inverse-kinematics solvers), and teleports its rigid-bodies to their new poses.
+    pub fn apply_displacements(&mut self, bodies: &mut RigidBodySet, displacements: &[Real]) {
+        self.multibody.apply_displacements(displacements);
+        self.multibody.forward_kinematics(bodies, false);
+        self.multibody.update_rigid_bodies(bodies, true);
+    }
+}
+
+/// The limits of a generalized coordinate.
+struct DofLimit {
+    dof: usize,
+    coord: Real,
+    min: Real,
+    max: Real,
+}
+
+impl DofLimit {
+    /// Clamps a displacement of the coordinate so that it stays within its limits.
+    fn clamp_displacement(&self, displacement: Real) -> Real {
+        (self.coord + displacement).clamp(self.min, self.max) - self.coord
+    }
+}
+
+/// The damped least-squares step `z + J⁺·(e − J·z)` reducing the errors `e`, where `J⁺` is the
+/// damped pseudo-inverse of `J`, and `z` an optional secondary step restricted to the free dofs.
+fn damped_least_squares(
+    jacobian: &DMatrix<Real>,
+    errors: &DVector,
+    secondary_step: Option<&DVector>,
+    free: &[bool],
+    damping: Real,
+) -> DVector {
+    let mut secondary_step = secondary_step
+        .cloned()
+        .unwrap_or_else(|| DVector::zeros(jacobian.ncols()));
+    for (j, _) in free.iter().enumerate().filter(|(_, free)| !**free) {
+        secondary_step[j] = 0.0;
+    }
+
+    let nrows = jacobian.nrows();
+    let identity = DMatrix::identity(nrows, nrows);
+    let jj = jacobian * jacobian.transpose() + &identity * (damping * damping);
+    let inv_jj = jj.pseudo_inverse(1.0e-5).unwrap_or(identity);
+    let residual = errors - jacobian * &secondary_step;
+    secondary_step.gemv_tr(1.0, jacobian, &(inv_jj * residual), 1.0);
+    secondary_step
+}
+
+/// The displacement moving `pose` to `target`, restricted to the `constrained_axes`.
+fn pose_delta(
+    pose: &Pose,
+    target: &Pose,
+    constrained_axes: JointAxesMask,
+) -> SVector<Real, SPATIAL_DIM> {
+    let delta_lin = target.translation - pose.translation;
+    #[cfg(feature = "dim2")]
+    let delta_ang = (target.rotation * pose.rotation.inverse()).angle();
+    #[cfg(feature = "dim3")]
+    let delta_ang = (target.rotation * pose.rotation.inverse()).to_scaled_axis();
+
+    #[cfg(feature = "dim2")]
+    let mut delta = na::vector![delta_lin.x, delta_lin.y, delta_ang];
+    #[cfg(feature = "dim3")]
+    let mut delta = na::vector![
+        delta_lin.x,
+        delta_lin.y,
+        delta_lin.z,
+        delta_ang.x,
+        delta_ang.y,
+        delta_ang.z
+    ];
+
+    if !constrained_axes.contains(JointAxesMask::LIN_X) {
+        delta[0] = 0.0;
+    }
+    if !constrained_axes.contains(JointAxesMask::LIN_Y) {
+        delta[1] = 0.0;
+    }
+    #[cfg(feature = "dim3")]
+    if !constrained_axes.contains(JointAxesMask::LIN_Z) {
+        delta[2] = 0.0;
+    }
+    if !constrained_axes.contains(JointAxesMask::ANG_X) {
+        delta[DIM] = 0.0;
+    }
+    #[cfg(feature = "dim3")]
+    if !constrained_axes.contains(JointAxesMask::ANG_Y) {
+        delta[DIM + 1] = 0.0;
+    }
+    #[cfg(feature = "dim3")]
+    if !constrained_axes.contains(JointAxesMask::ANG_Z) {
+        delta[DIM + 2] = 0.0;
+    }
+
+    delta
 }
 
 #[cfg(test)]
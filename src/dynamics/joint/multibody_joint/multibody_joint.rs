@@ -1,11 +1,11 @@
-use crate::dynamics::solver::GenericJointConstraint;
+use crate::dynamics::solver::{GenericJointConstraint, WritebackId};
 use crate::dynamics::{
-    FixedJointBuilder, GenericJoint, IntegrationParameters, Multibody, MultibodyLink,
+    FixedJointBuilder, GenericJoint, IntegrationParameters, JointAxis, Multibody, MultibodyLink,
     RigidBodyVelocity, joint,
 };
 use crate::math::{
-    ANG_DIM, DIM, DVector, JacobianViewMut, Pose, Real, Rotation, SPATIAL_DIM, SpatialVector,
-    Vector,
+    ANG_DIM, AngVector, DIM, DVector, JacobianViewMut, Pose, Real, Rotation, SPATIAL_DIM,
+    SpatialVector, Vector,
 };
 use parry::math::VectorExt;
 
@@ -36,6 +36,17 @@ pub struct MultibodyJoint {
     /// (same convention as [`Self::coords`]). Only meaningful where
     /// `spring_stiffness` is non-zero.
     pub(crate) spring_ref: SpatialVector,
+    /// The force applied by this joint to its link during the last step it was solved, in
+    /// world-space.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_force: Vector,
+    /// The torque applied by this joint to its link during the last step it was solved, in
+    /// world-space and measured at the link's anchor.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_torque: AngVector,
+    /// The length of the substep the motor and limit impulses were read from.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) reaction_dt: Real,
 }
 
 impl MultibodyJoint {
@@ -48,6 +59,9 @@ impl MultibodyJoint {
             joint_rot: Rotation::IDENTITY,
             spring_stiffness: Default::default(),
             spring_ref: Default::default(),
+            reaction_force: Vector::ZERO,
+            reaction_torque: Default::default(),
+            reaction_dt: 0.0,
         }
     }
 
@@ -65,6 +79,50 @@ impl MultibodyJoint {
         (self.spring_stiffness[axis], self.spring_ref[axis])
     }
 
+    /// The force applied by this joint to its link during the last step it was solved, in
+    /// world-space.
+    ///
+    /// This is the force the joint transmits from the parent link to the whole subtree rooted
+    /// at this link: it balances the motion of the subtree's links and the forces applied to
+    /// them (gravity and user forces). Contacts touching the subtree are not subtracted, so
+    /// they show up in this force as well.
+    #[inline]
+    pub fn reaction_force(&self) -> Vector {
+        self.reaction_force
+    }
+
+    /// The torque applied by this joint to its link during the last step it was solved, in
+    /// world-space and measured at the link's anchor (`local_frame2`).
+    #[inline]
+    pub fn reaction_torque(&self) -> AngVector {
+        self.reaction_torque
+    }
+
+    /// The generalized force applied by the motor of `axis` during the last step it was
+    /// solved: a force for linear axes, a torque for angular axes.
+    ///
+    /// This is the motor impulse divided by the substep length, and is zero if `axis`
+    /// has no motor. Locked axes are not degrees of freedom of a multibody, so no reaction
+    /// force is computed for them.
+    pub fn motor_force(&self, axis: JointAxis) -> Real {
+        self.data
+            .motor(axis)
+            .map(|motor| motor.impulse * crate::utils::inv(self.reaction_dt))
+            .unwrap_or(0.0)
+    }
+
+    /// The generalized force applied by the limits of `axis` during the last step it was
+    /// solved: a force for linear axes, a torque for angular axes.
+    ///
+    /// This is the limit impulse divided by the substep length, and is zero if `axis` has
+    /// no limits or if they were not reached.
+    pub fn limit_force(&self, axis: JointAxis) -> Real {
+        self.data
+            .limits(axis)
+            .map(|limits| limits.impulse * crate::utils::inv(self.reaction_dt))
+            .unwrap_or(0.0)
+    }
+
     pub(crate) fn free(pos: Pose) -> Self {
         let mut result = Self::new(GenericJoint::default(), false);
         result.set_free_pos(pos);
@@ -93,6 +151,58 @@ impl MultibodyJoint {
         self.joint_rot = pos.rotation;
     }
 
+    /// Sets the coordinates of this joint so that its second frame has the pose `frames_pose`
+    /// relative to its first frame.
+    ///
+    /// The components of `frames_pose` along locked axes are ignored.
+    pub(crate) fn set_coords_from_frames_pose(&mut self, frames_pose: Pose) {
+        let locked_bits = self.data.locked_axes.bits();
+        self.coords = Default::default();
+        self.joint_rot = Rotation::IDENTITY;
+
+        for i in 0..DIM {
+            if (locked_bits & (1 << i)) == 0 {
+                self.coords[i] = frames_pose.translation[i];
+            }
+        }
+
+        let locked_ang_bits = locked_bits >> DIM;
+        let num_free_ang_dofs = ANG_DIM - locked_ang_bits.count_ones() as usize;
+        match num_free_ang_dofs {
+            0 => { /* No free dofs. */ }
+            1 => {
+                let dof_id = (!locked_ang_bits).trailing_zeros() as usize;
+                #[cfg(feature = "dim2")]
+                {
+                    self.coords[DIM + dof_id] = frames_pose.rotation.angle();
+                    self.joint_rot = rotation_from_angle(self.coords[DIM + dof_id]);
+                }
+                #[cfg(feature = "dim3")]
+                {
+                    self.coords[DIM + dof_id] = frames_pose.rotation.to_scaled_axis()[dof_id];
+                    self.joint_rot = Rotation::from_axis_angle(
+                        Vector::ith(dof_id, 1.0),
+                        self.coords[DIM + dof_id],
+                    );
+                }
+            }
+            #[cfg(feature = "dim3")]
+            _ => {
+                let scaled_axis = frames_pose.rotation.to_scaled_axis();
+                let mut free_scaled_axis = Vector::ZERO;
+                for i in 0..ANG_DIM {
+                    if (locked_ang_bits & (1 << i)) == 0 {
+                        self.coords[DIM + i] = scaled_axis[i];
+                        free_scaled_axis[i] = scaled_axis[i];
+                    }
+                }
+                self.joint_rot = Rotation::from_scaled_axis(free_scaled_axis);
+            }
+            #[cfg(feature = "dim2")]
+            _ => unreachable!(),
+        }
+    }
+
     /// The joint’s angular coordinates converted to a rotation.
     pub fn joint_rot(&self) -> Rotation {
         self.joint_rot
@@ -305,6 +415,37 @@ impl MultibodyJoint {
         num_constraints
     }
 
+    /// Stores the impulses of the constraints generated by [`Self::velocity_constraints`]
+    /// into this joint's motors and limits, once solved with the substep length `dt`.
+    pub(crate) fn writeback_impulses(&mut self, constraints: &[GenericJointConstraint], dt: Real) {
+        let locked_bits = self.data.locked_axes.bits();
+        // The constraints are indexed by free DoF, not by axis.
+        let mut free_axes = (0..SPATIAL_DIM).filter(|i| (locked_bits & (1 << i)) == 0);
+        let mut dof_axes = [0; SPATIAL_DIM];
+        for dof_axis in &mut dof_axes {
+            *dof_axis = free_axes.next().unwrap_or(SPATIAL_DIM);
+        }
+
+        for i in 0..SPATIAL_DIM {
+            self.data.limits[i].impulse = 0.0;
+            self.data.motors[i].impulse = 0.0;
+        }
+
+        for constraint in constraints {
+            match constraint.writeback_id {
+                WritebackId::Limit(dof) => {
+                    self.data.limits[dof_axes[dof]].impulse = constraint.impulse
+                }
+                WritebackId::Motor(dof) => {
+                    self.data.motors[dof_axes[dof]].impulse = constraint.impulse
+                }
+                WritebackId::Dof(_) => {}
+            }
+        }
+
+        self.reaction_dt = dt;
+    }
+
     /// Initialize and generate velocity constraints to enforce, e.g., multibody_joint limits and motors.
     pub fn velocity_constraints(
         &self,
@@ -423,3 +564,32 @@ impl MultibodyJoint {
         num_constraints
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn frames_pose_only_sets_the_free_angular_coords() {
+        use crate::dynamics::{GenericJointBuilder, JointAxesMask};
+        use crate::math::{Pose, Rotation, Vector};
+
+        // A two-axis joint: it can only rotate about its X and Y axes.
+        let joint = GenericJointBuilder::new(JointAxesMask::LIN_AXES | JointAxesMask::ANG_Z);
+        let mut mb_joint = super::MultibodyJoint::new(joint.build(), true);
+
+        let rotation = Rotation::from_scaled_axis(Vector::new(0.3, -0.2, 0.4));
+        mb_joint.set_coords_from_frames_pose(Pose::from_rotation(rotation));
+
+        let scaled_axis = rotation.to_scaled_axis();
+        let coords = mb_joint.coords();
+        approx::assert_relative_eq!(coords[3], scaled_axis.x);
+        approx::assert_relative_eq!(coords[4], scaled_axis.y);
+        assert_eq!(coords[5], 0.0);
+
+        // The joint rotation must not contain any rotation about the locked axis.
+        let joint_axis = mb_joint.joint_rot().to_scaled_axis();
+        approx::assert_relative_eq!(joint_axis.x, scaled_axis.x, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(joint_axis.y, scaled_axis.y, epsilon = 1.0e-6);
+        approx::assert_relative_eq!(joint_axis.z, 0.0, epsilon = 1.0e-6);
+    }
+}
@@ -1,7 +1,15 @@
 use crate::alloc_prelude::*;
 use parry::utils::hashset::HashSet;
 
-use crate::data::{Arena, Coarena, Index};
+use crate::data::change_log::ChangeLog;
+use crate::data::{Arena, Coarena, Index, StateHasher};
+#[cfg(feature = "serde-serialize")]
+use crate::data::{
+    CoarenaDelta,
+    arena::ArenaDelta,
+    delta::{ChangeTracker, ValueTracker, apply_value},
+    graph::{GraphDelta, GraphDeltaTracker},
+};
 use crate::dynamics::joint::MultibodyLink;
 use crate::dynamics::{
     GenericJoint, Multibody, MultibodyIndex, MultibodyJoint, MultibodyJointHandle, RigidBodyHandle,
@@ -73,6 +81,12 @@ pub struct MultibodyJointSet {
     /// solver contact graph detect that its two-body vs. generic (multibody)
     /// manifold classification may be stale and must be rebuilt.
     pub(crate) topology_epoch: u32,
+    /// The multibodies modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    multibody_changes: ChangeLog,
+    /// The `rb2mb` entries modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    rb2mb_changes: ChangeLog,
 }
 
 impl MultibodyJointSet {
@@ -86,6 +100,8 @@ impl MultibodyJointSet {
             to_join: HashSet::default(),
             island_chain_events: Vec::new(),
             topology_epoch: 0,
+            multibody_changes: ChangeLog::default(),
+            rb2mb_changes: ChangeLog::default(),
         }
     }
 
@@ -109,6 +125,27 @@ impl MultibodyJointSet {
             })
     }
 
+    /// A hash of the multibody joints' state, for determinism checks.
+    ///
+    /// With [`StateHasher`]'s encoding, this hashes the number of joints then, for each joint
+    /// in the order of [`Self::iter`] (increasing handle index): its handle, the components of
+    /// its generalized coordinates, then its generalized velocities.
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_usize(self.iter().count());
+        for (handle, _, multibody, link) in self.iter() {
+            hasher.write_index(handle.0);
+            hasher.write_spatial_vector(&link.joint.coords);
+            hasher.write_reals(
+                multibody
+                    .generalized_velocity()
+                    .rows(link.assembly_id, link.joint.ndofs())
+                    .iter(),
+            );
+        }
+        hasher.finish()
+    }
+
     /// Inserts a new kinematic multibody joint into this set.
     pub fn insert_kinematic(
         &mut self,
@@ -143,6 +180,7 @@ impl MultibodyJointSet {
     ) -> Option<MultibodyJointHandle> {
         let link1 = self.rb2mb.get(body1.0).copied().unwrap_or_else(|| {
             let mb_handle = self.multibodies.insert(Multibody::with_root(body1, true));
+            self.multibody_changes.mark_index(mb_handle);
             MultibodyLinkId {
                 graph_id: self.connectivity_graph.graph.add_node(body1),
                 multibody: MultibodyIndex(mb_handle),
@@ -152,6 +190,7 @@ impl MultibodyJointSet {
 
         let link2 = self.rb2mb.get(body2.0).copied().unwrap_or_else(|| {
             let mb_handle = self.multibodies.insert(Multibody::with_root(body2, true));
+            self.multibody_changes.mark_index(mb_handle);
             MultibodyLinkId {
                 graph_id: self.connectivity_graph.graph.add_node(body2),
                 multibody: MultibodyIndex(mb_handle),
@@ -169,12 +208,17 @@ impl MultibodyJointSet {
             .add_edge(link1.graph_id, link2.graph_id, ());
         self.rb2mb.insert(body1.0, link1);
         self.rb2mb.insert(body2.0, link2);
+        self.rb2mb_changes.mark_index(body1.0);
+        self.rb2mb_changes.mark_index(body2.0);
         self.topology_epoch = self.topology_epoch.wrapping_add(1);
 
         let mb2 = self.multibodies.remove(link2.multibody.0).unwrap();
+        self.multibody_changes.mark_index(link2.multibody.0);
+        self.multibody_changes.mark_index(link1.multibody.0);
         let multibody1 = &mut self.multibodies[link1.multibody.0];
 
         for mb_link2 in mb2.links() {
+            self.rb2mb_changes.mark_index(mb_link2.rigid_body.0);
             let link = self.rb2mb.get_mut(mb_link2.rigid_body.0).unwrap();
             link.multibody = link1.multibody;
             link.id += multibody1.num_links();
@@ -204,6 +248,7 @@ impl MultibodyJointSet {
         if let Some(removed) = self.rb2mb.get(handle.0).copied() {
             self.topology_epoch = self.topology_epoch.wrapping_add(1);
             let multibody = self.multibodies.remove(removed.multibody.0).unwrap();
+            self.multibody_changes.mark_index(removed.multibody.0);
             self.island_chain_events.push(removed.multibody);
 
             // Remove the edge from the connectivity graph.
@@ -235,14 +280,18 @@ impl MultibodyJointSet {
                             .rb2mb
                             .remove(isolated_link.rigid_body.0, Default::default())
                             .unwrap();
+                        self.rb2mb_changes.mark_index(isolated_link.rigid_body.0);
                         if let Some(other) = self.connectivity_graph.remove_node(isolated.graph_id)
                         {
                             // Update graph index due to the `remove_node` swap-remove.
                             self.rb2mb.get_mut(other.0).unwrap().graph_id = isolated.graph_id;
+                            self.rb2mb_changes.mark_index(other.0);
                         }
                     } else {
                         let mb_id = self.multibodies.insert(multibody);
+                        self.multibody_changes.mark_index(mb_id);
                         for link in self.multibodies[mb_id].links() {
+                            self.rb2mb_changes.mark_index(link.rigid_body.0);
                             let ids = self.rb2mb.get_mut(link.rigid_body.0).unwrap();
                             ids.multibody = MultibodyIndex(mb_id);
                             ids.id = link.internal_id;
@@ -261,6 +310,7 @@ impl MultibodyJointSet {
             self.topology_epoch = self.topology_epoch.wrapping_add(1);
             // Remove the multibody.
             let multibody = self.multibodies.remove(removed.multibody.0).unwrap();
+            self.multibody_changes.mark_index(removed.multibody.0);
             self.island_chain_events.push(removed.multibody);
             for link in multibody.links() {
                 let rb_handle = link.rigid_body;
@@ -271,9 +321,11 @@ impl MultibodyJointSet {
 
                 // Remove the rigid-body <-> multibody mapping for this link.
                 let removed = self.rb2mb.remove(rb_handle.0, Default::default()).unwrap();
+                self.rb2mb_changes.mark_index(rb_handle.0);
                 // Remove the node (and all it’s edges) from the connectivity graph.
                 if let Some(other) = self.connectivity_graph.remove_node(removed.graph_id) {
                     self.rb2mb.get_mut(other.0).unwrap().graph_id = removed.graph_id;
+                    self.rb2mb_changes.mark_index(other.0);
                 }
             }
         }
@@ -318,7 +370,9 @@ impl MultibodyJointSet {
     /// `MultibodyJointSet`.
     pub fn get_multibody_mut(&mut self, index: MultibodyIndex) -> Option<&mut Multibody> {
         // TODO: modification tracking.
-        self.multibodies.get_mut(index.0)
+        let multibody = self.multibodies.get_mut(index.0)?;
+        self.multibody_changes.mark_index(index.0);
+        Some(multibody)
     }
 
     /// Gets a mutable reference to a multibody, based on its temporary index.
@@ -326,7 +380,9 @@ impl MultibodyJointSet {
     /// This method will bypass any modification-detection automatically done by the
     /// `MultibodyJointSet`.
     pub fn get_multibody_mut_internal(&mut self, index: MultibodyIndex) -> Option<&mut Multibody> {
-        self.multibodies.get_mut(index.0)
+        let multibody = self.multibodies.get_mut(index.0)?;
+        self.multibody_changes.mark_index(index.0);
+        Some(multibody)
     }
 
     /// Gets a reference to the multibody identified by its `handle`.
@@ -340,6 +396,7 @@ impl MultibodyJointSet {
     pub fn get_mut(&mut self, handle: MultibodyJointHandle) -> Option<(&mut Multibody, usize)> {
         let link = self.rb2mb.get(handle.0)?;
         let multibody = self.multibodies.get_mut(link.multibody.0)?;
+        self.multibody_changes.mark_index(link.multibody.0);
         Some((multibody, link.id))
     }
 
@@ -353,6 +410,7 @@ impl MultibodyJointSet {
         // TODO: modification tracking?
         let link = self.rb2mb.get(handle.0)?;
         let multibody = self.multibodies.get_mut(link.multibody.0)?;
+        self.multibody_changes.mark_index(link.multibody.0);
         Some((multibody, link.id))
     }
 
@@ -470,6 +528,111 @@ impl core::ops::Index<MultibodyIndex> for MultibodyJointSet {
     }
 }
 
+/// The parts of a [`MultibodyJointSet`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`MultibodyJointSet::delta_snapshot`] and restored with
+/// [`MultibodyJointSet::apply_delta`].
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultibodyJointSetDelta {
+    multibodies: ArenaDelta<Multibody>,
+    rb2mb: CoarenaDelta<MultibodyLinkId>,
+    connectivity_graph: GraphDelta<RigidBodyHandle, ()>,
+    to_wake_up: Option<HashSet<RigidBodyHandle>>,
+    to_join: Option<HashSet<(RigidBodyHandle, RigidBodyHandle)>>,
+    topology_epoch: u32,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl MultibodyJointSetDelta {
+    /// The number of multibodies this delta holds.
+    pub fn num_changed_multibodies(&self) -> usize {
+        self.multibodies.num_changed()
+    }
+}
+
+/// Remembers what a [`MultibodyJointSet`] looked like at its last delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Default)]
+pub struct MultibodyJointSetDeltaTracker {
+    multibodies: ChangeTracker,
+    rb2mb: ChangeTracker,
+    connectivity_graph: GraphDeltaTracker,
+    to_wake_up: ValueTracker,
+    to_join: ValueTracker,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl MultibodyJointSetDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl MultibodyJointSet {
+    /// The parts of this set that changed since the previous snapshot taken with `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole set. Afterward,
+    /// only the multibodies accessed mutably since the previous snapshot are stored, together
+    /// with the link mappings and connectivity edges modified since then.
+    ///
+    /// The changes are recorded by the set itself, so its snapshots must all be taken with the
+    /// same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(
+        &mut self,
+        tracker: &mut MultibodyJointSetDeltaTracker,
+    ) -> MultibodyJointSetDelta {
+        // Destructured so that a new field can't be forgotten here.
+        let MultibodyJointSet {
+            multibodies,
+            rb2mb,
+            connectivity_graph,
+            to_wake_up,
+            to_join,
+            island_chain_events: _,
+            topology_epoch,
+            multibody_changes,
+            rb2mb_changes,
+        } = self;
+
+        MultibodyJointSetDelta {
+            multibodies: multibodies.delta(&mut tracker.multibodies, multibody_changes),
+            rb2mb: rb2mb.delta(&mut tracker.rb2mb, rb2mb_changes),
+            connectivity_graph: connectivity_graph
+                .graph
+                .delta(&mut tracker.connectivity_graph),
+            to_wake_up: tracker.to_wake_up.diff(to_wake_up),
+            to_join: tracker.to_join.diff(to_join),
+            topology_epoch: *topology_epoch,
+        }
+    }
+
+    /// Brings this set from the state of the previous delta snapshot to the state of `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The set
+    /// then serializes to the same bytes as the one the snapshots were taken from, and its
+    /// internal caches are reset the way deserialization does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this set isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &MultibodyJointSetDelta) {
+        self.multibodies
+            .apply_delta(&delta.multibodies, &mut self.multibody_changes);
+        self.rb2mb
+            .apply_delta(&delta.rb2mb, &mut self.rb2mb_changes);
+        self.connectivity_graph
+            .graph
+            .apply_delta(&delta.connectivity_graph);
+        apply_value(&mut self.to_wake_up, &delta.to_wake_up);
+        apply_value(&mut self.to_join, &delta.to_join);
+        self.topology_epoch = delta.topology_epoch;
+        self.island_chain_events.clear();
+    }
+}
+
 // impl Index<MultibodyJointHandle> for MultibodyJointSet {
 //     type Output = Multibody;
 //
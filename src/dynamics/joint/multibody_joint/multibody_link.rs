@@ -4,7 +4,7 @@ use core::ops::{Deref, DerefMut};
 #[cfg(doc)]
 use crate::dynamics::Multibody;
 use crate::dynamics::{MultibodyJoint, RigidBodyHandle};
-use crate::math::{Pose, Real, Vector};
+use crate::math::{AngVector, Pose, Real, Rotation, Vector};
 use crate::prelude::RigidBodyVelocity;
 
 /// One link of a multibody.
@@ -115,6 +115,32 @@ impl MultibodyLink {
     pub fn local_to_parent(&self) -> &Pose {
         &self.local_to_parent
     }
+
+    /// The force applied by this link's joint to it during the last step it was solved,
+    /// expressed in the link's joint frame (`local_frame2`).
+    ///
+    /// This is what a force sensor mounted at the link's anchor would measure. See
+    /// [`MultibodyJoint::reaction_force`].
+    pub fn local_reaction_force(&self) -> Vector {
+        self.frame2_rotation().inverse() * self.joint.reaction_force
+    }
+
+    /// The torque applied by this link's joint to it during the last step it was solved,
+    /// expressed in the link's joint frame (`local_frame2`).
+    pub fn local_reaction_torque(&self) -> AngVector {
+        #[cfg(feature = "dim2")]
+        {
+            self.joint.reaction_torque
+        }
+        #[cfg(feature = "dim3")]
+        {
+            self.frame2_rotation().inverse() * self.joint.reaction_torque
+        }
+    }
+
+    fn frame2_rotation(&self) -> Rotation {
+        self.local_to_world.rotation * self.joint.data.local_frame2.rotation
+    }
 }
 
 // FIXME: keep this even if we already have the Index2 traits?
@@ -57,7 +57,7 @@ pub fn unit_joint_limit_constraint(
         solver_vel2: multibody.solver_id,
         ndofs2: ndofs,
         j_id2: *j_id,
-        joint_id: usize::MAX, // TODO: we don’t support impulse writeback for internal constraints yet.
+        joint_id: usize::MAX, // Written back by `JointGenericInternalConstraintBuilder`.
         impulse: 0.0,
         impulse_bounds,
         inv_lhs: crate::utils::inv(lhs + cfm_gain),
@@ -130,7 +130,7 @@ pub fn unit_joint_motor_constraint(
         solver_vel2: multibody.solver_id,
         ndofs2: ndofs,
         j_id2: *j_id,
-        joint_id: usize::MAX, // TODO: we don’t support impulse writeback for internal constraints yet.
+        joint_id: usize::MAX, // Written back by `JointGenericInternalConstraintBuilder`.
         impulse: 0.0,
         impulse_bounds,
         cfm_coeff: motor_params.cfm_coeff,
@@ -138,7 +138,7 @@ pub fn unit_joint_motor_constraint(
         inv_lhs: crate::utils::inv(lhs + cfm_gain),
         rhs: rhs_wo_bias,
         rhs_wo_bias,
-        writeback_id: WritebackId::Limit(dof_id),
+        writeback_id: WritebackId::Motor(dof_id),
     };
 
     constraints[*insert_at] = constraint;
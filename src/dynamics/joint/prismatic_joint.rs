@@ -1,6 +1,6 @@
 use crate::dynamics::integration_parameters::SpringCoefficients;
 use crate::dynamics::joint::{GenericJoint, GenericJointBuilder, JointAxesMask};
-use crate::dynamics::{JointAxis, MotorModel};
+use crate::dynamics::{JointAxis, JointBreakAction, MotorModel};
 use crate::math::{Real, Vector};
 
 use super::{JointLimits, JointMotor};
@@ -190,6 +190,36 @@ impl PrismaticJoint {
         self.data.softness = softness;
         self
     }
+
+    /// The maximum linear force this joint can apply before it breaks.
+    #[must_use]
+    pub fn break_force(&self) -> Real {
+        self.data.break_force
+    }
+
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    pub fn set_break_force(&mut self, max_force: Real) -> &mut Self {
+        self.data.set_break_force(max_force);
+        self
+    }
+
+    /// The maximum torque this joint can apply (measured at its anchor) before it breaks.
+    #[must_use]
+    pub fn break_torque(&self) -> Real {
+        self.data.break_torque
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    pub fn set_break_torque(&mut self, max_torque: Real) -> &mut Self {
+        self.data.set_break_torque(max_torque);
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    pub fn set_break_action(&mut self, action: JointBreakAction) -> &mut Self {
+        self.data.set_break_action(action);
+        self
+    }
 }
 
 impl From<PrismaticJoint> for GenericJoint {
@@ -303,6 +333,27 @@ impl PrismaticJointBuilder {
         self
     }
 
+    /// Sets the maximum linear force this joint can apply before it breaks.
+    #[must_use]
+    pub fn break_force(mut self, max_force: Real) -> Self {
+        self.0.set_break_force(max_force);
+        self
+    }
+
+    /// Sets the maximum torque this joint can apply (measured at its anchor) before it breaks.
+    #[must_use]
+    pub fn break_torque(mut self, max_torque: Real) -> Self {
+        self.0.set_break_torque(max_torque);
+        self
+    }
+
+    /// Sets what happens to this joint once it breaks.
+    #[must_use]
+    pub fn break_action(mut self, action: JointBreakAction) -> Self {
+        self.0.set_break_action(action);
+        self
+    }
+
     /// Builds the prismatic joint.
     #[must_use]
     pub fn build(self) -> PrismaticJoint {
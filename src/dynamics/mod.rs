@@ -3,13 +3,24 @@
 #[cfg(feature = "alloc")]
 pub use self::ccd::CCDSolver;
 pub use self::coefficient_combine_rule::CoefficientCombineRule;
+#[cfg(feature = "alloc")]
+pub use self::force_field::{
+    AerodynamicField, AerodynamicProfile, FieldForce, ForceField, ForceFieldBody, ForceFieldHandle,
+    ForceFieldSet, PointGravityField, VortexField, WindField,
+};
+#[cfg(feature = "alloc")]
+pub use self::gravity_provider::{GravityProvider, GravitySources};
+#[cfg(feature = "alloc")]
+pub(crate) use self::gravity_provider::{body_gravity, gravity_direction_changed};
 #[cfg(feature = "dim3")]
 pub use self::integration_parameters::FrictionModel;
-pub use self::integration_parameters::{IntegrationParameters, SpringCoefficients};
-#[cfg(feature = "alloc")]
-pub use self::island_manager::IslandManager;
+pub use self::integration_parameters::{CcdMode, IntegrationParameters, SpringCoefficients};
 #[cfg(feature = "alloc")]
 pub(crate) use self::island_manager::{INVALID_ISLAND, ImpulseJointIslandEvent, PersistentIslands};
+#[cfg(feature = "alloc")]
+pub use self::island_manager::{IslandManager, SleepEvent};
+#[cfg(feature = "serde-serialize")]
+pub use self::island_manager::{IslandManagerDelta, IslandManagerDeltaTracker};
 
 #[cfg(feature = "alloc")]
 pub(crate) use self::joint::JointGraphEdge;
@@ -22,17 +33,23 @@ pub use self::rigid_body_handle::RigidBodyHandle;
 #[cfg(feature = "alloc")]
 pub(crate) use self::rigid_body_set::ModifiedRigidBodies;
 #[cfg(feature = "alloc")]
-pub(crate) use self::solver::StagedIslandSolver;
+pub(crate) use self::solver::{SolveScope, StagedIslandSolver};
 pub use parry::mass_properties::MassProperties;
 
 #[cfg(feature = "alloc")]
 pub use self::rigid_body::{RigidBody, RigidBodyBuilder};
 #[cfg(feature = "alloc")]
 pub use self::rigid_body_set::{BodyPair, RigidBodySet};
+#[cfg(feature = "serde-serialize")]
+pub use self::rigid_body_set::{RigidBodySetDelta, RigidBodySetDeltaTracker};
 
 #[cfg(feature = "alloc")]
 mod ccd;
 mod coefficient_combine_rule;
+#[cfg(feature = "alloc")]
+mod force_field;
+#[cfg(feature = "alloc")]
+mod gravity_provider;
 mod integration_parameters;
 #[cfg(feature = "alloc")]
 mod island_manager;
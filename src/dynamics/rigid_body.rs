@@ -5,9 +5,10 @@ use simba::scalar::ComplexField;
 #[cfg(doc)]
 use super::IntegrationParameters;
 use crate::dynamics::{
-    LockedAxes, MassProperties, RigidBodyActivation, RigidBodyAdditionalMassProps, RigidBodyCcd,
-    RigidBodyChanges, RigidBodyColliders, RigidBodyDamping, RigidBodyDominance, RigidBodyForces,
-    RigidBodyIds, RigidBodyMassProps, RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
+    LockedAxes, MassProperties, RigidBodyActivation, RigidBodyActiveEvents,
+    RigidBodyAdditionalMassProps, RigidBodyCcd, RigidBodyChanges, RigidBodyColliders,
+    RigidBodyDamping, RigidBodyDominance, RigidBodyForces, RigidBodyIds, RigidBodyMassProps,
+    RigidBodyPosition, RigidBodyType, RigidBodyVelocity,
 };
 use crate::geometry::{
     ColliderHandle, ColliderMassProps, ColliderParent, ColliderPosition, ColliderSet, ColliderShape,
@@ -65,6 +66,8 @@ pub struct RigidBody {
     pub(crate) dominance: RigidBodyDominance,
     pub(crate) enabled: bool,
     pub(crate) additional_solver_iterations: usize,
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) active_events: RigidBodyActiveEvents,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
 }
@@ -94,6 +97,7 @@ impl RigidBody {
             enabled: true,
             user_data: 0,
             additional_solver_iterations: 0,
+            active_events: RigidBodyActiveEvents::empty(),
         }
     }
 
@@ -137,6 +141,7 @@ impl RigidBody {
             dominance,
             enabled,
             additional_solver_iterations,
+            active_events,
             user_data,
         } = other;
 
@@ -152,6 +157,7 @@ impl RigidBody {
         self.dominance = *dominance;
         self.enabled = *enabled;
         self.additional_solver_iterations = *additional_solver_iterations;
+        self.active_events = *active_events;
         self.user_data = *user_data;
 
         self.changes = RigidBodyChanges::all();
@@ -175,6 +181,21 @@ impl RigidBody {
         self.additional_solver_iterations = additional_iterations;
     }
 
+    /// Which events are enabled for this rigid-body.
+    ///
+    /// See [`RigidBodyActiveEvents`].
+    pub fn active_events(&self) -> RigidBodyActiveEvents {
+        self.active_events
+    }
+
+    /// Enables/disables event generation for this rigid-body.
+    ///
+    /// Set to `RigidBodyActiveEvents::SLEEP_EVENTS` to be notified when it falls asleep or wakes
+    /// up, through [`EventHandler::handle_sleep_event`](crate::pipeline::EventHandler::handle_sleep_event).
+    pub fn set_active_events(&mut self, active_events: RigidBodyActiveEvents) {
+        self.active_events = active_events;
+    }
+
     /// The activation status of this rigid-body.
     pub fn activation(&self) -> &RigidBodyActivation {
         &self.activation
@@ -748,6 +769,47 @@ impl RigidBody {
         }
     }
 
+    /// The gravitational acceleration applied to this body instead of the world gravity (and
+    /// of the pipeline's [`GravityProvider`](crate::dynamics::GravityProvider)), if any.
+    ///
+    /// It is still multiplied by the [gravity scale](Self::gravity_scale).
+    pub fn gravity_override(&self) -> Option<Vector> {
+        self.forces.gravity_override
+    }
+
+    /// Sets the gravitational acceleration applied to this body instead of the world gravity,
+    /// e.g., to make a body walk on the walls of a space station. `None` restores the world
+    /// gravity (or the one of the pipeline's gravity provider).
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut bodies = RigidBodySet::new();
+    /// # let body = bodies.insert(RigidBodyBuilder::dynamic());
+    /// // Fall toward +X instead of -Y.
+    /// bodies[body].set_gravity_override(Some(Vector::new(9.81, 0.0, 0.0)), true);
+    /// ```
+    pub fn set_gravity_override(&mut self, gravity: Option<Vector>, wake_up: bool) {
+        if self.forces.gravity_override != gravity {
+            if wake_up && self.activation.sleeping {
+                self.changes.insert(RigidBodyChanges::SLEEP);
+                self.activation.sleeping = false;
+            }
+
+            self.forces.gravity_override = gravity;
+        }
+    }
+
+    /// The gravitational acceleration, before the [gravity scale](Self::gravity_scale), applied
+    /// to this body at the last step it was awake: its override, the one computed by the
+    /// pipeline's [`GravityProvider`](crate::dynamics::GravityProvider), or the world gravity.
+    ///
+    /// This gives the local "down" direction of bodies living in non-uniform gravity. `None`
+    /// if the body was never simulated.
+    pub fn applied_gravity(&self) -> Option<Vector> {
+        self.forces.applied_gravity
+    }
+
     /// The dominance group of this rigid-body.
     pub fn dominance_group(&self) -> i8 {
         self.dominance.0
@@ -1441,7 +1503,8 @@ impl RigidBody {
 
     /// Calculates the gravitational potential energy of this body.
     ///
-    /// Returns `mass * gravity * height`. Useful for energy conservation checks.
+    /// Returns `mass * gravity * height`. Useful for energy conservation checks. The body's
+    /// [gravity override](Self::gravity_override), if any, replaces `gravity`.
     pub fn gravitational_potential_energy(&self, dt: Real, gravity: Vector) -> Real {
         let world_com = self.mprops.local_mprops.world_com(&self.pos.position);
 
@@ -1449,6 +1512,7 @@ impl RigidBody {
         // to sync up the potential energy with the kinetic energy:
         let world_com = world_com - self.vels.linvel * (dt / 2.0);
 
+        let gravity = self.forces.gravity_override.unwrap_or(gravity);
         -self.mass() * self.forces.gravity_scale * gravity.dot(world_com)
     }
 
@@ -1497,6 +1561,9 @@ pub struct RigidBodyBuilder {
     pub angvel: AngVector,
     /// The scale factor applied to the gravity affecting the rigid-body to be built, `1.0` by default.
     pub gravity_scale: Real,
+    /// The gravitational acceleration applied to the rigid-body to be built instead of the world
+    /// gravity, `None` by default.
+    pub gravity_override: Option<Vector>,
     /// Damping factor for gradually slowing down the translational motion of the rigid-body, `0.0` by default.
     pub linear_damping: Real,
     /// Damping factor for gradually slowing down the angular motion of the rigid-body, `0.0` by default.
@@ -1510,6 +1577,12 @@ pub struct RigidBodyBuilder {
     pub can_sleep: bool,
     /// Whether the rigid-body is to be created asleep.
     pub sleeping: bool,
+    /// The contact impulse needed to wake up the rigid-body to be built while it sleeps.
+    /// See [`RigidBodyActivation::wake_impulse_threshold`].
+    pub wake_impulse_threshold: Real,
+    /// Whether the rigid-body to be built wakes up the sleeping bodies it starts touching.
+    /// See [`RigidBodyActivation::wakes_neighbors`].
+    pub wakes_neighbors: bool,
     /// Whether full ("bullet") Continuous Collision-Detection is enabled for the rigid-body to be
     /// built. Fast dynamic bodies always sweep fixed colliders; this also sweeps kinematic and
     /// dynamic bodies. CCD prevents tunneling but may allow limited interpenetration of colliders.
@@ -1541,6 +1614,8 @@ pub struct RigidBodyBuilder {
     pub additional_solver_iterations: usize,
     /// Are gyroscopic forces enabled for this rigid-body?
     pub gyroscopic_forces_enabled: bool,
+    /// Events enabled for this rigid-body.
+    pub active_events: RigidBodyActiveEvents,
 }
 
 impl Default for RigidBodyBuilder {
@@ -1562,6 +1637,7 @@ impl RigidBodyBuilder {
             linvel: Vector::ZERO,
             angvel,
             gravity_scale: 1.0,
+            gravity_override: None,
             linear_damping: 0.0,
             angular_damping: 0.0,
             body_type,
@@ -1569,6 +1645,8 @@ impl RigidBodyBuilder {
             additional_mass_properties: RigidBodyAdditionalMassProps::default(),
             can_sleep: true,
             sleeping: false,
+            wake_impulse_threshold: 0.0,
+            wakes_neighbors: true,
             ccd_enabled: false,
             soft_ccd_prediction: 0.0,
             allow_fast_rotation: false,
@@ -1577,6 +1655,7 @@ impl RigidBodyBuilder {
             user_data: 0,
             additional_solver_iterations: 0,
             gyroscopic_forces_enabled: true,
+            active_events: RigidBodyActiveEvents::empty(),
         }
     }
 
@@ -1653,12 +1732,27 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets which events are enabled for this rigid-body.
+    ///
+    /// See [`RigidBody::set_active_events`].
+    pub fn active_events(mut self, active_events: RigidBodyActiveEvents) -> Self {
+        self.active_events = active_events;
+        self
+    }
+
     /// Sets the scale applied to the gravity force affecting the rigid-body to be created.
     pub fn gravity_scale(mut self, scale_factor: Real) -> Self {
         self.gravity_scale = scale_factor;
         self
     }
 
+    /// Sets the gravitational acceleration applied to the rigid-body to be created instead of
+    /// the world gravity (still multiplied by its gravity scale).
+    pub fn gravity_override(mut self, gravity: Vector) -> Self {
+        self.gravity_override = Some(gravity);
+        self
+    }
+
     /// Sets the dominance group (advanced collision priority system).
     ///
     /// Higher dominance groups can push lower ones but not vice versa.
@@ -1946,6 +2040,23 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the contact impulse an awake body must apply to this body, while it sleeps, to wake
+    /// it up (default: `0.0`, any touch wakes it).
+    ///
+    /// Useful to keep a large sleeping pile asleep when something lands lightly on it.
+    pub fn wake_impulse_threshold(mut self, threshold: Real) -> Self {
+        self.wake_impulse_threshold = threshold;
+        self
+    }
+
+    /// Sets whether this body wakes up the sleeping bodies it starts touching (default: `true`).
+    ///
+    /// Set to `false` for, e.g., a prop that shouldn't wake a sleeping stack it brushes against.
+    pub fn wakes_neighbors(mut self, wakes_neighbors: bool) -> Self {
+        self.wakes_neighbors = wakes_neighbors;
+        self
+    }
+
     /// Are gyroscopic forces enabled for this rigid-body?
     ///
     /// Enabling gyroscopic forces allows more realistic behaviors like gyroscopic precession,
@@ -1974,6 +2085,7 @@ impl RigidBodyBuilder {
         rb.body_type = self.body_type;
         rb.user_data = self.user_data;
         rb.additional_solver_iterations = self.additional_solver_iterations;
+        rb.active_events = self.active_events;
 
         if self.additional_mass_properties
             != RigidBodyAdditionalMassProps::MassProps(MassProperties::default())
@@ -1986,6 +2098,7 @@ impl RigidBodyBuilder {
         rb.damping.linear_damping = self.linear_damping;
         rb.damping.angular_damping = self.angular_damping;
         rb.forces.gravity_scale = self.gravity_scale;
+        rb.forces.gravity_override = self.gravity_override;
         #[cfg(feature = "dim3")]
         {
             rb.forces.gyroscopic_forces_enabled = self.gyroscopic_forces_enabled;
@@ -1995,6 +2108,8 @@ impl RigidBodyBuilder {
         rb.enable_ccd(self.ccd_enabled);
         rb.set_soft_ccd_prediction(self.soft_ccd_prediction);
         rb.set_allow_fast_rotation(self.allow_fast_rotation);
+        rb.activation.wake_impulse_threshold = self.wake_impulse_threshold;
+        rb.activation.wakes_neighbors = self.wakes_neighbors;
 
         if self.can_sleep && self.sleeping {
             rb.sleep();
@@ -113,6 +113,36 @@ impl Default for RigidBodyChanges {
     }
 }
 
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+    /// Flags that control which physics events are generated for a rigid-body.
+    ///
+    /// By default, rigid-bodies don't generate events. This is the rigid-body counterpart of
+    /// [`ActiveEvents`](crate::pipeline::ActiveEvents), which is set on colliders.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// // Get notified when this body falls asleep or wakes up.
+    /// let body = RigidBodyBuilder::dynamic()
+    ///     .active_events(RigidBodyActiveEvents::SLEEP_EVENTS)
+    ///     .build();
+    /// ```
+    pub struct RigidBodyActiveEvents: u32 {
+        /// Enables [`SleepEvent`](crate::dynamics::SleepEvent)s for this rigid-body.
+        ///
+        /// You'll receive events when this body falls asleep or is woken up.
+        const SLEEP_EVENTS = 0b0001;
+    }
+}
+
+impl Default for RigidBodyActiveEvents {
+    fn default() -> Self {
+        RigidBodyActiveEvents::empty()
+    }
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy, PartialEq)]
 /// The position of this rigid-body.
@@ -975,6 +1005,15 @@ pub struct RigidBodyForces {
     /// Gravity is multiplied by this scaling factor before it's
     /// applied to this rigid-body.
     pub gravity_scale: Real,
+    /// Gravitational acceleration applied to this rigid-body instead of the world gravity (and
+    /// of the pipeline's [`GravityProvider`](crate::dynamics::GravityProvider)), before
+    /// [`Self::gravity_scale`].
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub gravity_override: Option<Vector>,
+    /// The gravitational acceleration, before [`Self::gravity_scale`], applied to this
+    /// rigid-body at the last step it was awake. `None` if it was never simulated.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) applied_gravity: Option<Vector>,
     /// Forces applied by the user.
     pub user_force: Vector,
     /// Torque applied by the user.
@@ -991,6 +1030,8 @@ impl Default for RigidBodyForces {
             force: Vector::ZERO,
             torque: 0.0,
             gravity_scale: 1.0,
+            gravity_override: None,
+            applied_gravity: None,
             user_force: Vector::ZERO,
             user_torque: 0.0,
         };
@@ -1000,6 +1041,8 @@ impl Default for RigidBodyForces {
             force: Vector::ZERO,
             torque: AngVector::ZERO,
             gravity_scale: 1.0,
+            gravity_override: None,
+            applied_gravity: None,
             user_force: Vector::ZERO,
             user_torque: AngVector::ZERO,
             gyroscopic_forces_enabled: true,
@@ -1026,8 +1069,12 @@ impl RigidBodyForces {
     }
 
     /// Adds to `self` the gravitational force that would result in a gravitational acceleration
-    /// equal to `gravity`.
+    /// equal to `gravity` (before [`Self::gravity_scale`]).
+    ///
+    /// The [`Self::gravity_override`] isn't taken into account here: `gravity` is assumed to be
+    /// this body's gravity already.
     pub fn compute_effective_force_and_torque(&mut self, gravity: Vector, mass: Vector) {
+        self.applied_gravity = Some(gravity);
         self.force = self.user_force + gravity * mass * self.gravity_scale;
         self.torque = self.user_torque;
     }
@@ -1320,11 +1367,36 @@ pub struct RigidBodyActivation {
     /// Is this body currently sleeping?
     pub sleeping: bool,
 
+    /// Contact impulse an awake body must apply to this body, while it sleeps, to wake it up.
+    ///
+    /// Until then, the sleeping body (and its island) acts as a fixed obstacle for the bodies that
+    /// started touching it. The impulse is the one the solver applied over a single step, so a
+    /// body merely resting on this one applies about `mass * gravity * dt`. Default: `0.0`, any
+    /// touch wakes the body up right away.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub wake_impulse_threshold: Real,
+
+    /// Whether this body wakes up the sleeping bodies it starts touching.
+    ///
+    /// If `false`, touching a sleeping body leaves its island asleep (it then acts as a fixed
+    /// obstacle for this body), whatever the contact impulse. Joints and explicit wake-ups are not
+    /// affected. Default: `true`.
+    #[cfg_attr(
+        feature = "serde-serialize",
+        serde(default = "default_wakes_neighbors")
+    )]
+    pub wakes_neighbors: bool,
+
     /// Pose at the previous step: the sleep check measures actual per-step displacement,
     /// solver position corrections included (those never show up in the velocities).
     pub(crate) sleep_prev_pose: Pose,
 }
 
+#[cfg(feature = "serde-serialize")]
+fn default_wakes_neighbors() -> bool {
+    true
+}
+
 impl Default for RigidBodyActivation {
     fn default() -> Self {
         Self::active()
@@ -1360,6 +1432,8 @@ impl RigidBodyActivation {
             time_until_sleep: Self::default_time_until_sleep(),
             time_since_can_sleep: 0.0,
             sleeping: false,
+            wake_impulse_threshold: 0.0,
+            wakes_neighbors: true,
             sleep_prev_pose: Pose::IDENTITY,
         }
     }
@@ -1372,6 +1446,8 @@ impl RigidBodyActivation {
             time_until_sleep: Self::default_time_until_sleep(),
             time_since_can_sleep: Self::default_time_until_sleep(),
             sleeping: true,
+            wake_impulse_threshold: 0.0,
+            wakes_neighbors: true,
             sleep_prev_pose: Pose::IDENTITY,
         }
     }
@@ -1408,6 +1484,14 @@ impl RigidBodyActivation {
         self.time_since_can_sleep = self.time_until_sleep;
     }
 
+    /// Whether this body, awake, wakes up the sleeping body `other` it touches, given the
+    /// contact impulse applied to `other` during the last step (zero when they just started
+    /// touching).
+    pub(crate) fn wakes_up(&self, other: &Self, impulse: Real) -> bool {
+        self.wakes_neighbors
+            && (other.wake_impulse_threshold <= 0.0 || impulse > other.wake_impulse_threshold)
+    }
+
     /// Does this body have a sufficiently low kinetic energy for a long enough
     /// duration to be eligible for sleeping?
     pub fn is_eligible_for_sleep(&self) -> bool {
@@ -1,4 +1,5 @@
-use crate::data::{Arena, HasModifiedFlag, ModifiedObjects};
+use crate::data::change_log::ChangeLog;
+use crate::data::{Arena, HasModifiedFlag, ModifiedObjects, StateHasher};
 use crate::dynamics::{
     ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBody, RigidBodyBuilder,
     RigidBodyChanges, RigidBodyHandle,
@@ -6,6 +7,11 @@ use crate::dynamics::{
 use crate::geometry::ColliderSet;
 use core::ops::{Index, IndexMut};
 
+#[cfg(feature = "serde-serialize")]
+use crate::data::arena::ArenaDelta;
+#[cfg(feature = "serde-serialize")]
+use crate::data::delta::{ChangeTracker, ValueTracker, apply_value};
+
 #[cfg(doc)]
 use crate::pipeline::PhysicsPipeline;
 
@@ -76,6 +82,10 @@ pub struct RigidBodySet {
     pub(crate) modified_bodies: ModifiedRigidBodies,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     pub(crate) default_fixed: RigidBody,
+    /// The bodies modified since the previous delta snapshot: the ones added to
+    /// `modified_bodies`, plus the ones the physics pipeline modifies internally.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    changes: ChangeLog,
 }
 
 impl RigidBodySet {
@@ -88,6 +98,7 @@ impl RigidBodySet {
             bodies: Arena::new(),
             modified_bodies: ModifiedObjects::default(),
             default_fixed: RigidBodyBuilder::fixed().build(),
+            changes: ChangeLog::default(),
         }
     }
 
@@ -107,6 +118,7 @@ impl RigidBodySet {
             bodies: Arena::with_capacity(capacity),
             modified_bodies: ModifiedRigidBodies::with_capacity(capacity),
             default_fixed: RigidBodyBuilder::fixed().build(),
+            changes: ChangeLog::default(),
         }
     }
 
@@ -155,6 +167,7 @@ impl RigidBodySet {
         rb.changes.set(RigidBodyChanges::all(), true);
 
         let handle = RigidBodyHandle(self.bodies.insert(rb));
+        self.changes.mark_index(handle.0);
         // Using push_unchecked because this is a brand new rigid-body with the MODIFIED
         // flags set but isn’t in the modified_bodies yet.
         self.modified_bodies
@@ -208,6 +221,7 @@ impl RigidBodySet {
         remove_attached_colliders: bool,
     ) -> Option<RigidBody> {
         let rb = self.bodies.remove(handle.0)?;
+        self.changes.mark_index(handle.0);
         /*
          * Update active sets.
          */
@@ -263,6 +277,7 @@ impl RigidBodySet {
         let (rb, handle) = self.bodies.get_unknown_gen_mut(i)?;
         let handle = RigidBodyHandle(handle);
         self.modified_bodies.push_once(handle, rb);
+        self.changes.mark(i);
         Some((rb, handle))
     }
 
@@ -295,6 +310,7 @@ impl RigidBodySet {
     pub fn get_mut(&mut self, handle: RigidBodyHandle) -> Option<&mut RigidBody> {
         let result = self.bodies.get_mut(handle.0)?;
         self.modified_bodies.push_once(handle, result);
+        self.changes.mark_index(handle.0);
         Some(result)
     }
 
@@ -329,22 +345,43 @@ impl RigidBodySet {
             let (mut rb1, mut rb2) = self.bodies.get2_mut(handle1.0, handle2.0);
             if let Some(rb1) = rb1.as_deref_mut() {
                 self.modified_bodies.push_once(handle1, rb1);
+                self.changes.mark_index(handle1.0);
             }
             if let Some(rb2) = rb2.as_deref_mut() {
                 self.modified_bodies.push_once(handle2, rb2);
+                self.changes.mark_index(handle2.0);
             }
             (rb1, rb2)
         }
     }
 
     pub(crate) fn get_mut_internal(&mut self, handle: RigidBodyHandle) -> Option<&mut RigidBody> {
-        self.bodies.get_mut(handle.0)
+        let result = self.bodies.get_mut(handle.0)?;
+        self.changes.mark_index(handle.0);
+        Some(result)
     }
 
     pub(crate) fn index_mut_internal(&mut self, handle: RigidBodyHandle) -> &mut RigidBody {
+        self.changes.mark_index(handle.0);
         &mut self.bodies[handle.0]
     }
 
+    /// Same as [`Self::index_mut_internal`], without recording the change for the delta
+    /// snapshots, so it can be called from several threads on distinct bodies.
+    ///
+    /// The caller must have recorded the change with [`Self::mark_changed`] beforehand.
+    pub(crate) fn index_mut_internal_unrecorded(
+        &mut self,
+        handle: RigidBodyHandle,
+    ) -> &mut RigidBody {
+        &mut self.bodies[handle.0]
+    }
+
+    /// Records that the body `handle` is modified, for the delta snapshots.
+    pub(crate) fn mark_changed(&mut self, handle: RigidBodyHandle) {
+        self.changes.mark_index(handle.0);
+    }
+
     // Just a very long name instead of `.get_mut` to make sure
     // this is really the method we wanted to use instead of `get_mut_internal`.
     pub(crate) fn get_mut_internal_with_modification_tracking(
@@ -353,6 +390,7 @@ impl RigidBodySet {
     ) -> Option<&mut RigidBody> {
         let result = self.bodies.get_mut(handle.0)?;
         self.modified_bodies.push_once(handle, result);
+        self.changes.mark_index(handle.0);
         Some(result)
     }
 
@@ -374,6 +412,30 @@ impl RigidBodySet {
         self.bodies.iter().map(|(h, b)| (RigidBodyHandle(h), b))
     }
 
+    /// A hash of the simulation state of the rigid-bodies, for determinism checks.
+    ///
+    /// With [`StateHasher`]'s encoding, this hashes the number of rigid-bodies then, for each
+    /// rigid-body in increasing handle index order: its handle, its world-space pose, its linear
+    /// velocity, its angular velocity and whether it is sleeping.
+    ///
+    /// Two sets with bit-identical body states have the same hash, whatever their other
+    /// properties (forces, mass properties, user data, etc.).
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_usize(self.len());
+        for (handle, rb) in self.bodies.iter() {
+            hasher.write_index(handle);
+            hasher.write_pose(rb.position());
+            hasher.write_vector(rb.linvel());
+            #[cfg(feature = "dim2")]
+            hasher.write_real(rb.angvel());
+            #[cfg(feature = "dim3")]
+            hasher.write_vector(rb.angvel());
+            hasher.write_bool(rb.is_sleeping());
+        }
+        hasher.finish()
+    }
+
     /// Iterates over all rigid bodies with mutable access.
     ///
     /// Each iteration yields a `(handle, &mut RigidBody)` pair. Use this to modify
@@ -395,10 +457,12 @@ impl RigidBodySet {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (RigidBodyHandle, &mut RigidBody)> {
         self.modified_bodies.clear();
         let modified_bodies = &mut self.modified_bodies;
+        let changes = &mut self.changes;
         self.bodies.iter_mut().map(move |(h, b)| {
             // NOTE: using `push_unchecked` because we just cleared `modified_bodies`
             //       before iterating.
             modified_bodies.push_unchecked(RigidBodyHandle(h), b);
+            changes.mark_index(h);
             (RigidBodyHandle(h), b)
         })
     }
@@ -426,6 +490,72 @@ impl RigidBodySet {
     }
 }
 
+/// The parts of a [`RigidBodySet`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`RigidBodySet::delta_snapshot`] and restored with
+/// [`RigidBodySet::apply_delta`].
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RigidBodySetDelta {
+    bodies: ArenaDelta<RigidBody>,
+    modified_bodies: Option<ModifiedRigidBodies>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl RigidBodySetDelta {
+    /// The number of rigid-body slots (occupied or freed) this delta holds.
+    pub fn num_changed_bodies(&self) -> usize {
+        self.bodies.num_changed()
+    }
+}
+
+/// Remembers what a [`RigidBodySet`] looked like at its last delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Default)]
+pub struct RigidBodySetDeltaTracker {
+    bodies: ChangeTracker,
+    modified_bodies: ValueTracker,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl RigidBodySetDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl RigidBodySet {
+    /// The parts of this set that changed since the previous snapshot taken with `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole set. Afterward, a
+    /// rigid-body is part of the delta if it was accessed mutably since the previous snapshot (by
+    /// the user or the physics pipeline), so a sleeping body costs nothing.
+    ///
+    /// The changes are recorded by the set itself, so its snapshots must all be taken with the
+    /// same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(&mut self, tracker: &mut RigidBodySetDeltaTracker) -> RigidBodySetDelta {
+        RigidBodySetDelta {
+            bodies: self.bodies.delta(&mut tracker.bodies, &mut self.changes),
+            modified_bodies: tracker.modified_bodies.diff(&self.modified_bodies),
+        }
+    }
+
+    /// Brings this set from the state of the previous delta snapshot to the state of `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The set
+    /// then serializes to the same bytes as the one the snapshots were taken from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this set isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &RigidBodySetDelta) {
+        self.bodies.apply_delta(&delta.bodies, &mut self.changes);
+        apply_value(&mut self.modified_bodies, &delta.modified_bodies);
+    }
+}
+
 impl Index<RigidBodyHandle> for RigidBodySet {
     type Output = RigidBody;
 
@@ -447,6 +577,7 @@ impl IndexMut<RigidBodyHandle> for RigidBodySet {
     fn index_mut(&mut self, handle: RigidBodyHandle) -> &mut RigidBody {
         let rb = &mut self.bodies[handle.0];
         self.modified_bodies.push_once(handle, rb);
+        self.changes.mark_index(handle.0);
         rb
     }
 }
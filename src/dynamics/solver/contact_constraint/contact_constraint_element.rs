@@ -1,8 +1,9 @@
 use crate::dynamics::solver::SolverVel;
-use crate::math::{DIM, TangentImpulse};
+use crate::geometry::ContactManifold;
+use crate::math::{ANG_DIM, DIM, SIMD_WIDTH, SimdReal, TangentImpulse};
 #[cfg(feature = "dim3")]
-use crate::utils::{AngularInertiaOps, CrossProduct};
-use crate::utils::{ComponentMul, DotProduct, ScalarType};
+use crate::utils::CrossProduct;
+use crate::utils::{AngularInertiaOps, ComponentMul, DotProduct, ScalarType};
 #[cfg(feature = "block-solver")]
 use na::Vector2;
 #[cfg(feature = "block-solver")]
@@ -127,54 +128,111 @@ impl<N: ScalarType> ContactConstraintTangentPart<N> {
         }
 
         #[cfg(feature = "dim3")]
-        {
-            let dvel_0 = tangents1[0].gdot(solver_vel1.linear)
-                + self.torque_dir1[0].gdot(solver_vel1.angular)
-                - tangents1[0].gdot(solver_vel2.linear)
-                + self.torque_dir2[0].gdot(solver_vel2.angular)
-                + self.rhs[0];
-            let dvel_1 = tangents1[1].gdot(solver_vel1.linear)
-                + self.torque_dir1[1].gdot(solver_vel1.angular)
-                - tangents1[1].gdot(solver_vel2.linear)
-                + self.torque_dir2[1].gdot(solver_vel2.angular)
-                + self.rhs[1];
-
-            // Exact coupled 2×2 central-friction solve: `Δλ = -K⁻¹·dvel` with
-            // `K` the tangent effective-mass matrix. A 1D solve along `dvel` leaves an
-            // orthogonal residual when `K` is anisotropic; iterating it rotates energy between
-            // the tangents and pumps the friction-only mode of large stacks into ejection.
-            let k11 = self.r[0];
-            let k22 = self.r[1];
-            let k12 = self.r[2] * N::splat(0.5);
-            let inv_det = crate::utils::simd_inv(k11 * k22 - k12 * k12);
-            let delta_impulse = na::vector![
-                (k22 * dvel_0 - k12 * dvel_1) * inv_det,
-                (k11 * dvel_1 - k12 * dvel_0) * inv_det
-            ];
-            let new_impulse = self.impulse - delta_impulse;
-            let new_impulse = {
-                let _disable_fe_except =
-                        crate::utils::DisableFloatingPointExceptionsFlags::
-                        disable_floating_point_exceptions();
-                new_impulse.simd_cap_magnitude(limit)
-            };
+        self.solve_capped(tangents1, im1, im2, solver_vel1, solver_vel2, |impulse| {
+            impulse.simd_cap_magnitude(limit)
+        });
+    }
 
-            let dlambda = new_impulse - self.impulse;
-            self.impulse = new_impulse;
+    /// Solves this tangent part with a different friction limit along each tangent.
+    ///
+    /// The friction impulse is bounded by the ellipse of semi-axes `limits[0]` (along
+    /// `tangents1[0]`) and `limits[1]` (along `tangents1[1]`).
+    #[cfg(feature = "dim3")]
+    #[inline]
+    pub fn solve_anisotropic(
+        &mut self,
+        tangents1: [&N::Vector; DIM - 1],
+        im1: &N::Vector,
+        im2: &N::Vector,
+        limits: na::Vector2<N>,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+    ) where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        self.solve_capped(tangents1, im1, im2, solver_vel1, solver_vel2, |impulse| {
+            cap_elliptic(impulse, limits)
+        });
+    }
 
-            solver_vel1.linear +=
-                (*tangents1[0] * dlambda[0] + *tangents1[1] * dlambda[1]).component_mul(im1);
-            solver_vel1.angular +=
-                self.ii_torque_dir1[0] * dlambda[0] + self.ii_torque_dir1[1] * dlambda[1];
+    #[cfg(feature = "dim3")]
+    #[inline(always)]
+    fn solve_capped(
+        &mut self,
+        tangents1: [&N::Vector; DIM - 1],
+        im1: &N::Vector,
+        im2: &N::Vector,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+        cap: impl Fn(na::Vector2<N>) -> na::Vector2<N>,
+    ) where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        let dvel_0 = tangents1[0].gdot(solver_vel1.linear)
+            + self.torque_dir1[0].gdot(solver_vel1.angular)
+            - tangents1[0].gdot(solver_vel2.linear)
+            + self.torque_dir2[0].gdot(solver_vel2.angular)
+            + self.rhs[0];
+        let dvel_1 = tangents1[1].gdot(solver_vel1.linear)
+            + self.torque_dir1[1].gdot(solver_vel1.angular)
+            - tangents1[1].gdot(solver_vel2.linear)
+            + self.torque_dir2[1].gdot(solver_vel2.angular)
+            + self.rhs[1];
 
-            solver_vel2.linear +=
-                (*tangents1[0] * -dlambda[0] + *tangents1[1] * -dlambda[1]).component_mul(im2);
-            solver_vel2.angular +=
-                self.ii_torque_dir2[0] * dlambda[0] + self.ii_torque_dir2[1] * dlambda[1];
-        }
+        // Exact coupled 2×2 central-friction solve: `Δλ = -K⁻¹·dvel` with
+        // `K` the tangent effective-mass matrix. A 1D solve along `dvel` leaves an
+        // orthogonal residual when `K` is anisotropic; iterating it rotates energy between
+        // the tangents and pumps the friction-only mode of large stacks into ejection.
+        let k11 = self.r[0];
+        let k22 = self.r[1];
+        let k12 = self.r[2] * N::splat(0.5);
+        let inv_det = crate::utils::simd_inv(k11 * k22 - k12 * k12);
+        let delta_impulse = na::vector![
+            (k22 * dvel_0 - k12 * dvel_1) * inv_det,
+            (k11 * dvel_1 - k12 * dvel_0) * inv_det
+        ];
+        let new_impulse = self.impulse - delta_impulse;
+        let new_impulse = {
+            let _disable_fe_except =
+                    crate::utils::DisableFloatingPointExceptionsFlags::
+                    disable_floating_point_exceptions();
+            cap(new_impulse)
+        };
+
+        let dlambda = new_impulse - self.impulse;
+        self.impulse = new_impulse;
+
+        solver_vel1.linear +=
+            (*tangents1[0] * dlambda[0] + *tangents1[1] * dlambda[1]).component_mul(im1);
+        solver_vel1.angular +=
+            self.ii_torque_dir1[0] * dlambda[0] + self.ii_torque_dir1[1] * dlambda[1];
+
+        solver_vel2.linear +=
+            (*tangents1[0] * -dlambda[0] + *tangents1[1] * -dlambda[1]).component_mul(im2);
+        solver_vel2.angular +=
+            self.ii_torque_dir2[0] * dlambda[0] + self.ii_torque_dir2[1] * dlambda[1];
     }
 }
 
+/// Caps a tangent impulse to the ellipse of semi-axes `limits[0]` and `limits[1]`.
+///
+/// The impulse is mapped to the unit disk, capped there, and mapped back: this is a radial
+/// projection rather than the closest point of the ellipse, which is exact when both limits
+/// are equal. A zero limit pins its component to zero.
+#[cfg(feature = "dim3")]
+#[inline]
+pub(crate) fn cap_elliptic<N: ScalarType>(
+    impulse: na::Vector2<N>,
+    limits: na::Vector2<N>,
+) -> na::Vector2<N> {
+    let unit = na::vector![
+        impulse[0] * crate::utils::simd_inv(limits[0]),
+        impulse[1] * crate::utils::simd_inv(limits[1])
+    ];
+    let unit = unit.simd_cap_magnitude(N::splat(1.0));
+    na::vector![unit[0] * limits[0], unit[1] * limits[1]]
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(crate) struct ContactConstraintNormalPart<N: ScalarType> {
     pub torque_dir1: N::AngVector,
@@ -657,6 +715,42 @@ where
         solver_vel2: &mut SolverVel<N>,
     ) where
         N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        self.solve_capped(tangents1, im1, im2, solver_vel1, solver_vel2, |impulse| {
+            impulse.simd_cap_magnitude(limit)
+        });
+    }
+
+    /// Solves this tangent part with a different friction limit along each tangent (see
+    /// `ContactConstraintTangentPart::solve_anisotropic`).
+    #[inline]
+    pub fn solve_anisotropic(
+        &mut self,
+        tangents1: [&N::Vector; 2],
+        im1: &N::Vector,
+        im2: &N::Vector,
+        limits: na::Vector2<N>,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+    ) where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        self.solve_capped(tangents1, im1, im2, solver_vel1, solver_vel2, |impulse| {
+            cap_elliptic(impulse, limits)
+        });
+    }
+
+    #[inline(always)]
+    fn solve_capped(
+        &mut self,
+        tangents1: [&N::Vector; 2],
+        im1: &N::Vector,
+        im2: &N::Vector,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+        cap: impl Fn(na::Vector2<N>) -> na::Vector2<N>,
+    ) where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
     {
         let (torque_dir1_0, torque_dir1_1) = (self.torque_dir1[0], self.torque_dir1[1]);
         let (torque_dir2_0, torque_dir2_1) = (self.torque_dir2[0], self.torque_dir2[1]);
@@ -689,7 +783,7 @@ where
             let _disable_fe_except =
                     crate::utils::DisableFloatingPointExceptionsFlags::
                     disable_floating_point_exceptions();
-            new_impulse.simd_cap_magnitude(limit)
+            cap(new_impulse)
         };
 
         let dlambda = new_impulse - self.impulse;
@@ -755,6 +849,220 @@ impl<N: ScalarType> ContactConstraintTwistPartSlim<N> {
     }
 }
 
+/// Rolling and spinning resistance of a contact manifold: angular-only rows opposing the
+/// relative angular velocity of the two bodies, with a bounded impulse.
+///
+/// In 3D, the first row resists spinning around the contact normal, and the two others
+/// resist rolling around the contact tangents (their impulse magnitude is bounded jointly).
+/// In 2D, the only row resists rolling.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ContactConstraintRollingPart<N: ScalarType> {
+    pub ii_dir1: [N::AngVector; ANG_DIM],
+    pub ii_dir2: [N::AngVector; ANG_DIM],
+    pub impulse: [N; ANG_DIM],
+    pub r: [N; ANG_DIM],
+}
+
+impl<N: ScalarType> ContactConstraintRollingPart<N> {
+    /// The directions of the rows: the rolling axis in 2D.
+    #[cfg(feature = "dim2")]
+    #[inline]
+    pub fn dirs() -> [N::AngVector; ANG_DIM] {
+        [num::One::one()]
+    }
+
+    /// The directions of the rows: the contact normal `dir1`, then the two tangents.
+    #[cfg(feature = "dim3")]
+    #[inline]
+    pub fn dirs(dir1: &N::Vector, tangent1: &N::Vector) -> [N::AngVector; ANG_DIM] {
+        [
+            (*dir1).into(),
+            (*tangent1).into(),
+            dir1.gcross(*tangent1).into(),
+        ]
+    }
+
+    /// Rows with zero impulses and effective masses.
+    #[inline]
+    pub fn zero() -> Self {
+        Self {
+            ii_dir1: [Default::default(); ANG_DIM],
+            ii_dir2: [Default::default(); ANG_DIM],
+            impulse: [N::zero(); ANG_DIM],
+            r: [N::zero(); ANG_DIM],
+        }
+    }
+
+    /// Initializes the rows along `dirs`, with zero impulses: they are not warm-started
+    /// across steps.
+    #[inline]
+    pub fn new(dirs: [N::AngVector; ANG_DIM], ii1: &N::AngInertia, ii2: &N::AngInertia) -> Self
+    where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        let ii_dir1 = dirs.map(|dir| ii1.transform_vector(dir));
+        let ii_dir2 = dirs.map(|dir| ii2.transform_vector(dir));
+        let r = core::array::from_fn(|k| {
+            crate::utils::simd_inv(ii_dir1[k].gdot(dirs[k]) + ii_dir2[k].gdot(dirs[k]))
+        });
+        Self {
+            ii_dir1,
+            ii_dir2,
+            impulse: [N::zero(); ANG_DIM],
+            r,
+        }
+    }
+
+    #[inline]
+    pub fn warmstart(&self, solver_vel1: &mut SolverVel<N>, solver_vel2: &mut SolverVel<N>) {
+        for k in 0..ANG_DIM {
+            solver_vel1.angular += self.ii_dir1[k] * self.impulse[k];
+            solver_vel2.angular -= self.ii_dir2[k] * self.impulse[k];
+        }
+    }
+
+    /// Solves the rows, bounding the rolling impulse by `roll_limit` and, in 3D, the
+    /// spinning impulse by `spin_limit`.
+    #[inline]
+    pub fn solve(
+        &mut self,
+        dirs: [N::AngVector; ANG_DIM],
+        roll_limit: N,
+        spin_limit: N,
+        solver_vel1: &mut SolverVel<N>,
+        solver_vel2: &mut SolverVel<N>,
+    ) where
+        N::AngVector: DotProduct<N::AngVector, Result = N>,
+    {
+        #[cfg(feature = "dim2")]
+        {
+            let _ = spin_limit;
+            let dvel = dirs[0].gdot(solver_vel1.angular - solver_vel2.angular);
+            let new_impulse =
+                (self.impulse[0] - self.r[0] * dvel).simd_clamp(-roll_limit, roll_limit);
+            let dlambda = new_impulse - self.impulse[0];
+            self.impulse[0] = new_impulse;
+            solver_vel1.angular += self.ii_dir1[0] * dlambda;
+            solver_vel2.angular -= self.ii_dir2[0] * dlambda;
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            // Spinning.
+            let dvel = dirs[0].gdot(solver_vel1.angular - solver_vel2.angular);
+            let new_impulse =
+                (self.impulse[0] - self.r[0] * dvel).simd_clamp(-spin_limit, spin_limit);
+            let dlambda = new_impulse - self.impulse[0];
+            self.impulse[0] = new_impulse;
+            solver_vel1.angular += self.ii_dir1[0] * dlambda;
+            solver_vel2.angular -= self.ii_dir2[0] * dlambda;
+
+            // Rolling, bounded like the tangent friction: by the magnitude of the impulse.
+            let dvel_1 = dirs[1].gdot(solver_vel1.angular - solver_vel2.angular);
+            let dvel_2 = dirs[2].gdot(solver_vel1.angular - solver_vel2.angular);
+            let impulse = na::vector![self.impulse[1], self.impulse[2]];
+            let new_impulse = na::vector![
+                self.impulse[1] - self.r[1] * dvel_1,
+                self.impulse[2] - self.r[2] * dvel_2
+            ];
+            let new_impulse = {
+                let _disable_fe_except =
+                    crate::utils::DisableFloatingPointExceptionsFlags::
+                    disable_floating_point_exceptions();
+                new_impulse.simd_cap_magnitude(roll_limit)
+            };
+            let dlambda = new_impulse - impulse;
+            self.impulse[1] = new_impulse[0];
+            self.impulse[2] = new_impulse[1];
+            solver_vel1.angular += self.ii_dir1[1] * dlambda[0] + self.ii_dir1[2] * dlambda[1];
+            solver_vel2.angular -= self.ii_dir2[1] * dlambda[0] + self.ii_dir2[2] * dlambda[1];
+        }
+    }
+}
+
+/// The rolling and spinning resistance of a SIMD chunk of contact manifolds.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ContactRolling<N: ScalarType> {
+    pub part: ContactConstraintRollingPart<N>,
+    /// The combined rolling friction coefficient of each manifold.
+    pub rolling_friction: N,
+    /// The combined spinning friction coefficient of each manifold.
+    pub spinning_friction: N,
+    /// Does any manifold of the chunk resist rolling or spinning? The rows are skipped
+    /// altogether otherwise.
+    pub enabled: bool,
+}
+
+impl<N: ScalarType> ContactRolling<N> {
+    /// Scales the impulse of the previous substep for warm-starting the next one.
+    #[inline]
+    pub fn update(&mut self, warmstart_coeff: N) {
+        if self.enabled {
+            for impulse in &mut self.part.impulse {
+                *impulse *= warmstart_coeff;
+            }
+        }
+    }
+}
+
+impl ContactRolling<SimdReal> {
+    pub fn generate(
+        manifolds: &[&ContactManifold; SIMD_WIDTH],
+        dirs: [<SimdReal as ScalarType>::AngVector; ANG_DIM],
+        ii1: &<SimdReal as ScalarType>::AngInertia,
+        ii2: &<SimdReal as ScalarType>::AngInertia,
+    ) -> Self {
+        let enabled = manifolds
+            .iter()
+            .any(|m| m.data.rolling_friction > 0.0 || m.data.spinning_friction > 0.0);
+        let part = if enabled {
+            ContactConstraintRollingPart::new(dirs, ii1, ii2)
+        } else {
+            // Never read: the buffers are reused without zeroing, but a disabled part is
+            // skipped by every solver stage.
+            ContactConstraintRollingPart::zero()
+        };
+        Self {
+            part,
+            rolling_friction: SimdReal::from(array![|ii| manifolds[ii].data.rolling_friction]),
+            spinning_friction: SimdReal::from(array![|ii| manifolds[ii].data.spinning_friction]),
+            enabled,
+        }
+    }
+
+    #[inline]
+    pub fn warmstart(
+        &self,
+        solver_vel1: &mut SolverVel<SimdReal>,
+        solver_vel2: &mut SolverVel<SimdReal>,
+    ) {
+        if self.enabled {
+            self.part.warmstart(solver_vel1, solver_vel2);
+        }
+    }
+
+    /// Solves the rolling and spinning rows, bounded by the friction coefficients times
+    /// `normal_impulse`, the total normal impulse of the manifold.
+    #[inline]
+    pub fn solve(
+        &mut self,
+        dirs: [<SimdReal as ScalarType>::AngVector; ANG_DIM],
+        normal_impulse: SimdReal,
+        solver_vel1: &mut SolverVel<SimdReal>,
+        solver_vel2: &mut SolverVel<SimdReal>,
+    ) {
+        if self.enabled {
+            self.part.solve(
+                dirs,
+                self.rolling_friction * normal_impulse,
+                self.spinning_friction * normal_impulse,
+                solver_vel1,
+                solver_vel2,
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,4 +1,7 @@
-use super::{ContactConstraintNormalPart, ContactConstraintTangentPart};
+use super::{
+    ContactConstraintNormalPart, ContactConstraintRollingPart, ContactConstraintTangentPart,
+    ContactRolling,
+};
 #[cfg(feature = "block-solver")]
 use crate::dynamics::solver::contact_constraint::BLOCK_SOLVER_MIN_CONDITION;
 use crate::dynamics::solver::manifold_store::ManifoldStore;
@@ -136,12 +139,22 @@ impl ContactWithCoulombFrictionBuilder {
         // Friction/restitution are per-manifold (see `ContactManifoldData`).
         let friction = SimdReal::from(array![|ii| manifolds[ii].data.friction]);
         let restitution = SimdReal::from(array![|ii| manifolds[ii].data.restitution]);
+        #[cfg(feature = "dim3")]
+        let (tangents1, anisotropic) =
+            super::align_tangents_with_friction_dir(&manifolds, &force_dir1, tangents1, friction);
 
         let manifold_points = array![|ii| &manifolds[ii].data.solver_contacts[..counts[ii]]];
 
         out_constraint.dir1 = force_dir1;
         out_constraint.im1 = poses1.im;
         out_constraint.im2 = poses2.im;
+        #[cfg(feature = "dim2")]
+        let rolling_dirs = ContactConstraintRollingPart::<SimdReal>::dirs();
+        #[cfg(feature = "dim3")]
+        let rolling_dirs =
+            ContactConstraintRollingPart::<SimdReal>::dirs(&force_dir1, &tangents1[0]);
+        out_constraint.rolling =
+            ContactRolling::generate(&manifolds, rolling_dirs, &poses1.ii, &poses2.ii);
         out_constraint.solver_vel1 = ids1;
         out_constraint.solver_vel2 = ids2;
         out_constraint.manifold_id = manifold_id;
@@ -151,6 +164,8 @@ impl ContactWithCoulombFrictionBuilder {
         #[cfg(feature = "dim3")]
         {
             out_constraint.tangent1 = tangents1[0];
+            out_constraint.anisotropic = anisotropic.is_some();
+            out_constraint.limit2 = anisotropic.unwrap_or(friction);
         }
 
         for k in 0..num_points {
@@ -451,6 +466,7 @@ impl ContactWithCoulombFrictionBuilder {
             }
         }
 
+        constraint.rolling.update(warmstart_coeff);
         constraint.cfm_factor = cfm_factor;
     }
 
@@ -541,11 +557,18 @@ pub(crate) struct ContactWithCoulombFriction<N: ScalarType> {
     pub im2: N::Vector,
     pub cfm_factor: N,
     pub limit: N,
+    /// The friction coefficient along the second tangent, if `anisotropic`.
+    #[cfg(feature = "dim3")]
+    pub limit2: N,
+    /// Does any lane have an anisotropic friction (see `ContactManifoldData::friction_dir`)?
+    #[cfg(feature = "dim3")]
+    pub anisotropic: bool,
 
     #[cfg(feature = "dim3")]
     pub tangent1: N::Vector, // One of the friction force directions.
     pub normal_part: [ContactConstraintNormalPart<N>; MAX_MANIFOLD_POINTS],
     pub tangent_part: [ContactConstraintTangentPart<N>; MAX_MANIFOLD_POINTS],
+    pub rolling: ContactRolling<N>,
     pub solver_vel1: [u32; SIMD_WIDTH],
     pub solver_vel2: [u32; SIMD_WIDTH],
     pub manifold_id: [ContactRef; SIMD_WIDTH],
@@ -592,6 +615,8 @@ impl ContactWithCoulombFriction<SimdReal> {
             );
         }
 
+        self.rolling.warmstart(&mut solver_vel1, &mut solver_vel2);
+
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
         bodies.scatter_vels(self.solver_vel2, solver_vel2);
     }
@@ -665,6 +690,18 @@ impl ContactWithCoulombFriction<SimdReal> {
 
             for (tangent_part, normal_part) in tangent_parts.iter_mut().zip(normal_parts.iter()) {
                 let limit = self.limit * normal_part.impulse;
+                #[cfg(feature = "dim3")]
+                if self.anisotropic {
+                    tangent_part.solve_anisotropic(
+                        tangents1,
+                        &self.im1,
+                        &self.im2,
+                        na::vector![limit, self.limit2 * normal_part.impulse],
+                        &mut solver_vel1,
+                        &mut solver_vel2,
+                    );
+                    continue;
+                }
                 tangent_part.solve(
                     tangents1,
                     &self.im1,
@@ -674,6 +711,23 @@ impl ContactWithCoulombFriction<SimdReal> {
                     &mut solver_vel2,
                 );
             }
+
+            if self.rolling.enabled {
+                #[cfg(feature = "dim2")]
+                let rolling_dirs = ContactConstraintRollingPart::<SimdReal>::dirs();
+                #[cfg(feature = "dim3")]
+                let rolling_dirs =
+                    ContactConstraintRollingPart::<SimdReal>::dirs(&self.dir1, &self.tangent1);
+                let normal_impulse = normal_parts
+                    .iter()
+                    .fold(SimdReal::zero(), |acc, part| acc + part.impulse);
+                self.rolling.solve(
+                    rolling_dirs,
+                    normal_impulse,
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            }
         }
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
@@ -1,6 +1,6 @@
 use super::{
-    ContactConstraintNormalPartSlim, ContactConstraintTangentPartSlim,
-    ContactConstraintTwistPartSlim,
+    ContactConstraintNormalPartSlim, ContactConstraintRollingPart,
+    ContactConstraintTangentPartSlim, ContactConstraintTwistPartSlim, ContactRolling,
 };
 #[cfg(feature = "block-solver")]
 use crate::dynamics::solver::contact_constraint::BLOCK_SOLVER_MIN_CONDITION;
@@ -138,6 +138,8 @@ impl ContactWithTwistFrictionBuilder<SimdReal> {
         // Friction/restitution are per-manifold (see `ContactManifoldData`).
         let friction = SimdReal::from(array![|ii| manifolds[ii].data.friction]);
         let restitution = SimdReal::from(array![|ii| manifolds[ii].data.restitution]);
+        let (tangents1, anisotropic) =
+            super::align_tangents_with_friction_dir(&manifolds, &force_dir1, tangents1, friction);
 
         let manifold_points = array![|ii| &manifolds[ii].data.solver_contacts[..counts[ii]]];
 
@@ -151,6 +153,12 @@ impl ContactWithTwistFrictionBuilder<SimdReal> {
         out_constraint.im2 = poses2.im;
         out_constraint.ii1 = poses1.ii;
         out_constraint.ii2 = poses2.ii;
+        out_constraint.rolling = ContactRolling::generate(
+            &manifolds,
+            ContactConstraintRollingPart::<SimdReal>::dirs(&force_dir1, &tangents1[0]),
+            &poses1.ii,
+            &poses2.ii,
+        );
         out_builder.local_n1 = poses1.rotation.inverse() * force_dir1;
         out_builder.restitution = restitution;
         out_constraint.solver_vel1 = ids1;
@@ -160,6 +168,8 @@ impl ContactWithTwistFrictionBuilder<SimdReal> {
         #[cfg(feature = "dim3")]
         {
             out_constraint.tangent1 = tangents1[0];
+            out_constraint.anisotropic = anisotropic.is_some();
+            out_constraint.limit2 = anisotropic.unwrap_or(friction);
         }
 
         let mut friction_center = Default::default();
@@ -518,6 +528,8 @@ impl ContactWithTwistFrictionBuilder<SimdReal> {
             twist_part.impulse *= warmstart_coeff;
         }
 
+        constraint.rolling.update(warmstart_coeff);
+
         constraint.cfm_factor = cfm_factor;
     }
 
@@ -610,6 +622,11 @@ pub(crate) struct ContactWithTwistFriction<N: ScalarType> {
     pub ii2: N::AngInertia,
     pub cfm_factor: N,
     pub limit: N,
+    /// The friction coefficient along the second tangent, if `anisotropic`.
+    pub limit2: N,
+    /// Does any lane have an anisotropic friction (see `ContactManifoldData::friction_dir`)?
+    /// The twist limit only uses the primary coefficient `limit`.
+    pub anisotropic: bool,
 
     #[cfg(feature = "dim3")]
     pub tangent1: N::Vector, // One of the friction force directions.
@@ -621,6 +638,7 @@ pub(crate) struct ContactWithTwistFriction<N: ScalarType> {
     pub twist_part: ContactConstraintTwistPartSlim<N>,
     // Distances between the friction center and the contact point.
     pub twist_dists: [N; MAX_MANIFOLD_POINTS],
+    pub rolling: ContactRolling<N>,
 
     pub solver_vel1: [u32; SIMD_WIDTH],
     pub solver_vel2: [u32; SIMD_WIDTH],
@@ -672,6 +690,7 @@ impl ContactWithTwistFriction<SimdReal> {
                 &mut solver_vel2,
             );
         }
+        self.rolling.warmstart(&mut solver_vel1, &mut solver_vel2);
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
         bodies.scatter_vels(self.solver_vel2, solver_vel2);
@@ -749,6 +768,7 @@ impl ContactWithTwistFriction<SimdReal> {
             }
 
             // Multiply by the friction coefficient.
+            let tangent_limit2 = tangent_limit * self.limit2;
             tangent_limit *= self.limit;
             twist_limit *= self.limit;
 
@@ -766,14 +786,37 @@ impl ContactWithTwistFriction<SimdReal> {
                 );
             }
 
-            self.tangent_part.solve(
-                tangents1,
-                &self.im1,
-                &self.im2,
-                tangent_limit,
-                &mut solver_vel1,
-                &mut solver_vel2,
-            );
+            if self.anisotropic {
+                self.tangent_part.solve_anisotropic(
+                    tangents1,
+                    &self.im1,
+                    &self.im2,
+                    na::vector![tangent_limit, tangent_limit2],
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            } else {
+                self.tangent_part.solve(
+                    tangents1,
+                    &self.im1,
+                    &self.im2,
+                    tangent_limit,
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            }
+
+            if self.rolling.enabled {
+                let normal_impulse = normal_parts
+                    .iter()
+                    .fold(SimdReal::zero(), |acc, part| acc + part.impulse);
+                self.rolling.solve(
+                    ContactConstraintRollingPart::<SimdReal>::dirs(&self.dir1, &self.tangent1),
+                    normal_impulse,
+                    &mut solver_vel1,
+                    &mut solver_vel2,
+                );
+            }
         }
 
         bodies.scatter_vels(self.solver_vel1, solver_vel1);
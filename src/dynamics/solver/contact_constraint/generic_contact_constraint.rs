@@ -3,10 +3,13 @@ use crate::dynamics::{IntegrationParameters, MultibodyJointSet, RigidBodySet};
 use crate::geometry::ContactManifold;
 #[cfg(feature = "dim3")]
 use crate::math::TangentImpulse;
-use crate::math::{DIM, DVector, MAX_MANIFOLD_POINTS, Real};
+use crate::math::{ANG_DIM, DIM, DVector, MAX_MANIFOLD_POINTS, Real};
 use crate::utils::{self, AngularInertiaOps, CrossProduct, DotProduct};
 
-use super::{ContactConstraintNormalPart, ContactConstraintTangentPart};
+use super::{
+    ContactConstraintNormalPart, ContactConstraintRollingPart, ContactConstraintTangentPart,
+    ContactRolling,
+};
 use crate::dynamics::solver::CoulombContactPointInfos;
 use crate::dynamics::solver::manifold_store::ManifoldStore;
 use crate::dynamics::solver::solver_body::SolverBodies;
@@ -107,13 +110,26 @@ impl GenericContactConstraintBuilder {
             &vels1.linvel,
             &vels2.linvel,
         );
+        // Anisotropic friction: the first tangent is the friction direction (see
+        // `align_tangents_with_friction_dir`).
+        #[cfg(feature = "dim3")]
+        let tangents1 = if manifold.data.is_friction_anisotropic() {
+            let friction_dir = manifold.data.friction_dir;
+            [friction_dir, force_dir1.gcross(friction_dir)]
+        } else {
+            tangents1
+        };
+        let rolling_enabled =
+            manifold.data.rolling_friction > 0.0 || manifold.data.spinning_friction > 0.0;
 
         let multibodies_ndof = multibody1.map(|m| m.0.ndofs()).unwrap_or(0)
             + multibody2.map(|m| m.0.ndofs()).unwrap_or(0);
-        // For each solver contact we generate DIM constraints, and each constraints appends
-        // the multibodies jacobian and weighted jacobians
-        let required_jacobian_len =
-            *jacobian_id + manifold.data.solver_contacts.len() * multibodies_ndof * 2 * DIM;
+        // For each solver contact we generate DIM constraints, plus ANG_DIM rolling constraints
+        // for the whole manifold, and each constraints appends the multibodies jacobian and
+        // weighted jacobians
+        let num_rolling_rows = if rolling_enabled { ANG_DIM } else { 0 };
+        let required_jacobian_len = *jacobian_id
+            + (manifold.data.solver_contacts.len() * DIM + num_rolling_rows) * multibodies_ndof * 2;
 
         // Grow the jacobian buffer to fit this constraint: `generate` runs serially in the
         // staged solver's pre-phase (before any worker starts), so growing here is race-free
@@ -143,6 +159,7 @@ impl GenericContactConstraintBuilder {
         #[cfg(feature = "dim3")]
         {
             out_constraint.tangent1 = tangents1[0];
+            out_constraint.limit2 = manifold.data.secondary_friction;
         }
 
         for k in 0..manifold_points.len() {
@@ -375,6 +392,46 @@ impl GenericContactConstraintBuilder {
                 (manifold_point.contact_id[0] & !crate::geometry::NEW_CONTACT_BIT) as u8;
         }
 
+        // Rolling part: its rows follow the rows of the contact points.
+        let mut rolling_part = ContactConstraintRollingPart::<Real>::zero();
+        if rolling_enabled {
+            #[cfg(feature = "dim2")]
+            let rolling_dirs = ContactConstraintRollingPart::<Real>::dirs();
+            #[cfg(feature = "dim3")]
+            let rolling_dirs =
+                ContactConstraintRollingPart::<Real>::dirs(&force_dir1, &tangents1[0]);
+
+            for (k, dir) in rolling_dirs.into_iter().enumerate() {
+                let inv_r1 = if let Some((mb1, link_id1)) = multibody1.as_ref() {
+                    mb1.fill_jacobians(*link_id1, Vector::ZERO, dir, jacobian_id, jacobians)
+                        .0
+                } else if type1.is_dynamic_or_kinematic() {
+                    rolling_part.ii_dir1[k] =
+                        mprops1.effective_world_inv_inertia.transform_vector(dir);
+                    rolling_part.ii_dir1[k].gdot(dir)
+                } else {
+                    0.0
+                };
+                let inv_r2 = if let Some((mb2, link_id2)) = multibody2.as_ref() {
+                    mb2.fill_jacobians(*link_id2, Vector::ZERO, -dir, jacobian_id, jacobians)
+                        .0
+                } else if type2.is_dynamic_or_kinematic() {
+                    rolling_part.ii_dir2[k] =
+                        mprops2.effective_world_inv_inertia.transform_vector(dir);
+                    rolling_part.ii_dir2[k].gdot(dir)
+                } else {
+                    0.0
+                };
+                rolling_part.r[k] = crate::utils::inv(inv_r1 + inv_r2);
+            }
+        }
+        out_constraint.rolling = ContactRolling {
+            part: rolling_part,
+            rolling_friction: manifold.data.rolling_friction,
+            spinning_friction: manifold.data.spinning_friction,
+            enabled: rolling_enabled,
+        };
+
         let ndofs1 = multibody1.map(|mb| mb.0.ndofs()).unwrap_or(0);
         let ndofs2 = multibody2.map(|mb| mb.0.ndofs()).unwrap_or(0);
 
@@ -494,6 +551,8 @@ impl GenericContactConstraintBuilder {
             }
         }
 
+        constraint.rolling.update(params.warmstart_coefficient);
+
         constraint.cfm_factor = cfm_factor;
     }
 
@@ -562,6 +621,10 @@ pub(crate) struct GenericContactConstraint {
     pub im2: Vector,
     pub cfm_factor: Real,
     pub limit: Real,
+    /// The friction coefficient along the second tangent: equal to `limit` unless the friction
+    /// is anisotropic (see `ContactManifoldData::friction_dir`).
+    #[cfg(feature = "dim3")]
+    pub limit2: Real,
     pub solver_vel1: u32,
     pub solver_vel2: u32,
     pub manifold_id: ContactRef,
@@ -569,6 +632,7 @@ pub(crate) struct GenericContactConstraint {
     pub num_contacts: u8,
     pub normal_part: [ContactConstraintNormalPart<Real>; MAX_MANIFOLD_POINTS],
     pub tangent_part: [ContactConstraintTangentPart<Real>; MAX_MANIFOLD_POINTS],
+    pub rolling: ContactRolling<Real>,
 }
 
 impl GenericContactConstraint {
@@ -585,6 +649,8 @@ impl GenericContactConstraint {
             im2: Vector::ZERO,
             cfm_factor: 0.0,
             limit: 0.0,
+            #[cfg(feature = "dim3")]
+            limit2: 0.0,
             solver_vel1: u32::MAX,
             solver_vel2: u32::MAX,
             manifold_id: ContactRef::PADDING,
@@ -592,6 +658,12 @@ impl GenericContactConstraint {
             num_contacts: u8::MAX,
             normal_part: [ContactConstraintNormalPart::zero(); MAX_MANIFOLD_POINTS],
             tangent_part: [ContactConstraintTangentPart::zero(); MAX_MANIFOLD_POINTS],
+            rolling: ContactRolling {
+                part: ContactConstraintRollingPart::zero(),
+                rolling_friction: 0.0,
+                spinning_friction: 0.0,
+                enabled: false,
+            },
         }
     }
 
@@ -635,6 +707,12 @@ impl GenericContactConstraint {
             &mut solver_vel2,
             generic_solver_vels,
         );
+        self.generic_warmstart_rolling(
+            jacobians,
+            &mut solver_vel1,
+            &mut solver_vel2,
+            generic_solver_vels,
+        );
 
         if let GenericRhs::SolverVel(solver_vel1) = solver_vel1 {
             bodies.vels[self.solver_vel1 as usize] = solver_vel1;
@@ -682,6 +760,8 @@ impl GenericContactConstraint {
             self.im1,
             self.im2,
             self.limit,
+            #[cfg(feature = "dim3")]
+            self.limit2,
             self.ndofs1,
             self.ndofs2,
             self.j_id,
@@ -691,6 +771,14 @@ impl GenericContactConstraint {
             solve_restitution,
             solve_friction,
         );
+        if solve_friction {
+            self.generic_solve_rolling(
+                jacobians,
+                &mut solver_vel1,
+                &mut solver_vel2,
+                generic_solver_vels,
+            );
+        }
 
         if let GenericRhs::SolverVel(solver_vel1) = solver_vel1 {
             bodies.vels[self.solver_vel1 as usize] = solver_vel1;
@@ -1,7 +1,12 @@
 use crate::dynamics::solver::SolverVel;
+#[cfg(feature = "dim3")]
+use crate::dynamics::solver::cap_elliptic;
 use crate::dynamics::solver::contact_constraint::GenericContactConstraint;
-use crate::dynamics::solver::{ContactConstraintNormalPart, ContactConstraintTangentPart};
-use crate::math::{AngVector, DIM, DVector, Real, Vector};
+use crate::dynamics::solver::{
+    ContactConstraintNormalPart, ContactConstraintRollingPart, ContactConstraintTangentPart,
+    ContactRolling,
+};
+use crate::math::{ANG_DIM, AngVector, DIM, DVector, Real, Vector};
 use crate::utils::{ComponentMul, DotProduct};
 #[cfg(feature = "dim2")]
 use {crate::utils::OrthonormalBasis, na::SimdPartialOrd};
@@ -38,6 +43,12 @@ fn tangent_j_id(j_id: usize, ndofs1: usize, ndofs2: usize) -> usize {
     j_id + (ndofs1 + ndofs2) * 2
 }
 
+// The rolling rows follow the DIM rows of each contact point.
+#[inline]
+fn rolling_j_id(j_id: usize, ndofs1: usize, ndofs2: usize, num_contacts: usize) -> usize {
+    j_id + j_step(ndofs1, ndofs2) * DIM * num_contacts
+}
+
 impl GenericRhs {
     #[inline]
     fn dvel(
@@ -196,7 +207,7 @@ impl ContactConstraintTangentPart<Real> {
         im2: Vector,
         ndofs1: usize,
         ndofs2: usize,
-        limit: Real,
+        limits: [Real; DIM - 1],
         solver_vel1: &mut GenericRhs,
         solver_vel2: &mut GenericRhs,
         solver_vels: &mut DVector,
@@ -224,7 +235,8 @@ impl ContactConstraintTangentPart<Real> {
                 solver_vels,
             ) + self.rhs[0];
 
-            let new_impulse = (self.impulse[0] - self.r[0] * dvel_0).simd_clamp(-limit, limit);
+            let new_impulse =
+                (self.impulse[0] - self.r[0] * dvel_0).simd_clamp(-limits[0], limits[0]);
             let dlambda = new_impulse - self.impulse[0];
             self.impulse[0] = new_impulse;
 
@@ -287,7 +299,11 @@ impl ContactConstraintTangentPart<Real> {
                 self.impulse[0] - self.r[0] * dvel_0,
                 self.impulse[1] - self.r[1] * dvel_1,
             );
-            let new_impulse = new_impulse.cap_magnitude(limit);
+            let new_impulse = if limits[0] == limits[1] {
+                new_impulse.cap_magnitude(limits[0])
+            } else {
+                cap_elliptic(new_impulse, limits.into())
+            };
 
             let dlambda = new_impulse - self.impulse;
             self.impulse = new_impulse;
@@ -518,6 +534,8 @@ impl GenericContactConstraint {
         im1: Vector,
         im2: Vector,
         limit: Real,
+        // The friction coefficient along `dir1 × tangent1`.
+        #[cfg(feature = "dim3")] limit2: Real,
         // ndofs is 0 for a non-multibody body, or a multibody with zero
         // degrees of freedom.
         ndofs1: usize,
@@ -563,7 +581,10 @@ impl GenericContactConstraint {
             let mut tng_j_id = tangent_j_id(j_id, ndofs1, ndofs2);
 
             for (normal_part, tangent_part) in normal_parts.iter().zip(tangent_parts.iter_mut()) {
-                let limit = limit * normal_part.impulse;
+                #[cfg(feature = "dim2")]
+                let limits = [limit * normal_part.impulse];
+                #[cfg(feature = "dim3")]
+                let limits = [limit * normal_part.impulse, limit2 * normal_part.impulse];
                 tangent_part.generic_solve(
                     tng_j_id,
                     jacobians,
@@ -572,7 +593,7 @@ impl GenericContactConstraint {
                     im2,
                     ndofs1,
                     ndofs2,
-                    limit,
+                    limits,
                     solver_vel1,
                     solver_vel2,
                     solver_vels,
@@ -582,3 +603,229 @@ impl GenericContactConstraint {
         }
     }
 }
+
+impl ContactConstraintRollingPart<Real> {
+    #[inline]
+    fn generic_dvel(
+        &self,
+        k: usize,
+        j_id: usize,
+        jacobians: &DVector,
+        dir: AngVector,
+        ndofs1: usize,
+        ndofs2: usize,
+        solver_vel1: &GenericRhs,
+        solver_vel2: &GenericRhs,
+        solver_vels: &DVector,
+    ) -> Real {
+        let j_id = j_id + j_step(ndofs1, ndofs2) * k;
+        solver_vel1.dvel(
+            j_id1(j_id, ndofs1, ndofs2),
+            ndofs1,
+            jacobians,
+            Vector::ZERO,
+            dir,
+            solver_vels,
+        ) + solver_vel2.dvel(
+            j_id2(j_id, ndofs1, ndofs2),
+            ndofs2,
+            jacobians,
+            Vector::ZERO,
+            -dir,
+            solver_vels,
+        )
+    }
+
+    #[inline]
+    fn generic_apply_impulse(
+        &self,
+        k: usize,
+        j_id: usize,
+        impulse: Real,
+        jacobians: &DVector,
+        ndofs1: usize,
+        ndofs2: usize,
+        solver_vel1: &mut GenericRhs,
+        solver_vel2: &mut GenericRhs,
+        solver_vels: &mut DVector,
+    ) {
+        let j_id = j_id + j_step(ndofs1, ndofs2) * k;
+        solver_vel1.apply_impulse(
+            j_id1(j_id, ndofs1, ndofs2),
+            ndofs1,
+            impulse,
+            jacobians,
+            Vector::ZERO,
+            self.ii_dir1[k],
+            solver_vels,
+            Vector::ZERO,
+        );
+        solver_vel2.apply_impulse(
+            j_id2(j_id, ndofs1, ndofs2),
+            ndofs2,
+            impulse,
+            jacobians,
+            Vector::ZERO,
+            -self.ii_dir2[k],
+            solver_vels,
+            Vector::ZERO,
+        );
+    }
+}
+
+impl GenericContactConstraint {
+    #[cfg(feature = "dim2")]
+    #[inline]
+    fn rolling_dirs(&self) -> [AngVector; ANG_DIM] {
+        ContactConstraintRollingPart::<Real>::dirs()
+    }
+
+    #[cfg(feature = "dim3")]
+    #[inline]
+    fn rolling_dirs(&self) -> [AngVector; ANG_DIM] {
+        ContactConstraintRollingPart::<Real>::dirs(&self.dir1, &self.tangent1)
+    }
+
+    #[inline]
+    pub fn generic_warmstart_rolling(
+        &self,
+        jacobians: &DVector,
+        solver_vel1: &mut GenericRhs,
+        solver_vel2: &mut GenericRhs,
+        solver_vels: &mut DVector,
+    ) {
+        let ContactRolling { part, enabled, .. } = &self.rolling;
+        if !enabled {
+            return;
+        }
+
+        let j_id = rolling_j_id(
+            self.j_id,
+            self.ndofs1,
+            self.ndofs2,
+            self.num_contacts as usize,
+        );
+        for k in 0..ANG_DIM {
+            part.generic_apply_impulse(
+                k,
+                j_id,
+                part.impulse[k],
+                jacobians,
+                self.ndofs1,
+                self.ndofs2,
+                solver_vel1,
+                solver_vel2,
+                solver_vels,
+            );
+        }
+    }
+
+    /// Solves the rolling and spinning rows, bounded like the ones of the rigid-body
+    /// constraints (see `ContactConstraintRollingPart::solve`).
+    #[inline]
+    pub fn generic_solve_rolling(
+        &mut self,
+        jacobians: &DVector,
+        solver_vel1: &mut GenericRhs,
+        solver_vel2: &mut GenericRhs,
+        solver_vels: &mut DVector,
+    ) {
+        if !self.rolling.enabled {
+            return;
+        }
+
+        let (ndofs1, ndofs2) = (self.ndofs1, self.ndofs2);
+        let num_contacts = self.num_contacts as usize;
+        let j_id = rolling_j_id(self.j_id, ndofs1, ndofs2, num_contacts);
+        let dirs = self.rolling_dirs();
+        let normal_impulse = self.normal_part[..num_contacts]
+            .iter()
+            .map(|part| part.impulse)
+            .sum::<Real>();
+        let roll_limit = self.rolling.rolling_friction * normal_impulse;
+        let part = &mut self.rolling.part;
+        let dvel = |part: &ContactConstraintRollingPart<Real>,
+                    k: usize,
+                    solver_vel1: &GenericRhs,
+                    solver_vel2: &GenericRhs,
+                    solver_vels: &DVector| {
+            part.generic_dvel(
+                k,
+                j_id,
+                jacobians,
+                dirs[k],
+                ndofs1,
+                ndofs2,
+                solver_vel1,
+                solver_vel2,
+                solver_vels,
+            )
+        };
+
+        #[cfg(feature = "dim2")]
+        {
+            let dvel = dvel(part, 0, solver_vel1, solver_vel2, solver_vels);
+            let new_impulse = (part.impulse[0] - part.r[0] * dvel).clamp(-roll_limit, roll_limit);
+            let dlambda = new_impulse - part.impulse[0];
+            part.impulse[0] = new_impulse;
+            part.generic_apply_impulse(
+                0,
+                j_id,
+                dlambda,
+                jacobians,
+                ndofs1,
+                ndofs2,
+                solver_vel1,
+                solver_vel2,
+                solver_vels,
+            );
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            // Spinning.
+            let spin_limit = self.rolling.spinning_friction * normal_impulse;
+            let dvel_0 = dvel(part, 0, solver_vel1, solver_vel2, solver_vels);
+            let new_impulse = (part.impulse[0] - part.r[0] * dvel_0).clamp(-spin_limit, spin_limit);
+            let dlambda = new_impulse - part.impulse[0];
+            part.impulse[0] = new_impulse;
+            part.generic_apply_impulse(
+                0,
+                j_id,
+                dlambda,
+                jacobians,
+                ndofs1,
+                ndofs2,
+                solver_vel1,
+                solver_vel2,
+                solver_vels,
+            );
+
+            // Rolling, bounded by the magnitude of the impulse.
+            let dvel_1 = dvel(part, 1, solver_vel1, solver_vel2, solver_vels);
+            let dvel_2 = dvel(part, 2, solver_vel1, solver_vel2, solver_vels);
+            let impulse = na::Vector2::new(part.impulse[1], part.impulse[2]);
+            let new_impulse = na::Vector2::new(
+                part.impulse[1] - part.r[1] * dvel_1,
+                part.impulse[2] - part.r[2] * dvel_2,
+            )
+            .cap_magnitude(roll_limit);
+            let dlambda = new_impulse - impulse;
+            part.impulse[1] = new_impulse[0];
+            part.impulse[2] = new_impulse[1];
+            for k in 1..ANG_DIM {
+                part.generic_apply_impulse(
+                    k,
+                    j_id,
+                    dlambda[k - 1],
+                    jacobians,
+                    ndofs1,
+                    ndofs2,
+                    solver_vel1,
+                    solver_vel2,
+                    solver_vels,
+                );
+            }
+        }
+    }
+}
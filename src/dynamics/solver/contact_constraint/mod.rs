@@ -21,7 +21,11 @@ mod contact_with_twist_friction;
 #[cfg(feature = "dim3")]
 use crate::utils::ScalarType;
 #[cfg(feature = "dim3")]
-use crate::{math::DIM, utils::OrthonormalBasis};
+use crate::{
+    geometry::ContactManifold,
+    math::{DIM, SIMD_WIDTH, SimdReal},
+    utils::{DotProduct, OrthonormalBasis},
+};
 
 #[inline]
 #[cfg(feature = "dim3")]
@@ -45,3 +49,48 @@ pub(crate) fn compute_tangent_contact_directions<N: ScalarType>(
 
     [tangent1, bitangent1]
 }
+
+/// Rotates the tangent basis of the lanes with an anisotropic friction so that their first
+/// tangent is their manifold's `ContactManifoldData::friction_dir`.
+///
+/// Returns the resulting basis, as well as the friction coefficients along the second
+/// tangent if any lane is anisotropic (`None` otherwise, to keep the isotropic friction
+/// solve on the fast path).
+#[inline]
+#[cfg(feature = "dim3")]
+pub(crate) fn align_tangents_with_friction_dir(
+    manifolds: &[&ContactManifold; SIMD_WIDTH],
+    force_dir1: &<SimdReal as ScalarType>::Vector,
+    tangents1: [<SimdReal as ScalarType>::Vector; DIM - 1],
+    friction: SimdReal,
+) -> (
+    [<SimdReal as ScalarType>::Vector; DIM - 1],
+    Option<SimdReal>,
+) {
+    use crate::utils::CrossProduct;
+    use simba::simd::{SimdPartialOrd, SimdValue};
+
+    if !manifolds
+        .iter()
+        .any(|manifold| manifold.data.is_friction_anisotropic())
+    {
+        return (tangents1, None);
+    }
+
+    // The friction direction is either zero (isotropic lane) or a unit vector orthogonal
+    // to the contact normal.
+    let friction_dir = <SimdReal as ScalarType>::Vector::from(gather![|ii| manifolds[ii]
+        .data
+        .friction_dir
+        .into()]);
+    let is_anisotropic = friction_dir
+        .gdot(friction_dir)
+        .simd_gt(SimdReal::splat(0.5));
+    let tangent1 = friction_dir.select(is_anisotropic, tangents1[0]);
+    let secondary_friction = SimdReal::from(array![|ii| manifolds[ii].data.secondary_friction]);
+
+    (
+        [tangent1, force_dir1.gcross(tangent1)],
+        Some(secondary_friction.select(is_anisotropic, friction)),
+    )
+}
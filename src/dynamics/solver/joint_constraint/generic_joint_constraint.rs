@@ -306,7 +306,8 @@ impl GenericJointConstraint {
     }
 
     pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
-        // TODO: we don’t support impulse writeback for internal constraints yet.
+        // Internal (multibody) constraints are written back by their link's builder, see
+        // `JointGenericInternalConstraintBuilder::writeback_impulses`.
         if self.joint_id != JointIndex::MAX {
             let joint = &mut joints_all[self.joint_id].weight;
             match self.writeback_id {
@@ -314,6 +315,8 @@ impl GenericJointConstraint {
                 WritebackId::Limit(i) => joint.data.limits[i].impulse = self.impulse,
                 WritebackId::Motor(i) => joint.data.motors[i].impulse = self.impulse,
             }
+            // The generalized Jacobians aren't world-space wrenches.
+            joint.solver_wrench.incomplete = true;
         }
     }
 
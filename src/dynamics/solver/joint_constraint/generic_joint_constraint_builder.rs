@@ -334,6 +334,27 @@ impl JointGenericInternalConstraintBuilder {
             );
         }
     }
+
+    /// Stores the impulses of this link's own motor and limit constraints into its joint.
+    pub fn writeback_impulses(
+        &self,
+        dt: Real,
+        constraints: &[GenericJointConstraint],
+        multibodies: &mut MultibodyJointSet,
+    ) {
+        let Some(link) = multibodies
+            .get_multibody_mut_internal(self.link.multibody)
+            .and_then(|mb| mb.link_mut(self.link.id))
+        else {
+            return;
+        };
+        // The DoF couplings following them are not written back.
+        let n_own = link.joint.num_velocity_constraints();
+        link.joint.writeback_impulses(
+            &constraints[self.constraint_id..self.constraint_id + n_own],
+            dt,
+        );
+    }
 }
 
 impl JointSolverBody<Real, 1> {
@@ -146,4 +146,14 @@ impl JointConstraintsSet {
             c.writeback_impulses(joints_all);
         }
     }
+
+    /// Stores the impulses of the multibody-internal constraints (the links' motors and
+    /// limits) into the multibody joints.
+    pub fn writeback_internal_impulses(&self, dt: Real, multibodies: &mut MultibodyJointSet) {
+        for builder in &self.generic_velocity_constraints_builder {
+            if let GenericJointConstraintBuilder::Internal(builder) = builder {
+                builder.writeback_impulses(dt, &self.generic_velocity_constraints, multibodies);
+            }
+        }
+    }
 }
@@ -3,7 +3,7 @@ use crate::dynamics::solver::joint_constraint::{AngularLimitParams, JointConstra
 use crate::dynamics::{
     GenericJoint, IntegrationParameters, JointAxesMask, JointGraphEdge, JointIndex,
 };
-use crate::math::{ANG_DIM, DIM, Real, SPATIAL_DIM};
+use crate::math::{ANG_DIM, AngVector, DIM, Real, SPATIAL_DIM, Vector};
 use crate::utils::{ComponentMul, DotProduct, ScalarType, SimdRealCopy};
 
 use crate::dynamics::solver::solver_body::SolverBodies;
@@ -383,6 +383,9 @@ impl JointConstraint<Real, 1> {
             WritebackId::Limit(i) => joint.data.limits[i].impulse = self.impulse,
             WritebackId::Motor(i) => joint.data.motors[i].impulse = self.impulse,
         }
+        joint
+            .solver_wrench
+            .add_row(self.lin_jac, self.ang_jac1, self.ang_jac2, self.impulse);
     }
 }
 
@@ -535,6 +538,33 @@ impl JointConstraint<SimdReal, SIMD_WIDTH> {
                 WritebackId::Limit(i) => joint.data.limits[i].impulse = impulses[ii],
                 WritebackId::Motor(i) => joint.data.motors[i].impulse = impulses[ii],
             }
+
+            // Padding lanes replicate lane 0: its wrench must only be accumulated once.
+            if ii == 0 || self.joint_id[ii] != self.joint_id[0] {
+                joint.solver_wrench.add_row(
+                    lane_vector(&self.lin_jac, ii),
+                    lane_ang_vector(&self.ang_jac1, ii),
+                    lane_ang_vector(&self.ang_jac2, ii),
+                    impulses[ii],
+                );
+            }
         }
     }
 }
+
+/// The `lane`-th lane of a wide vector.
+fn lane_vector(v: &<SimdReal as ScalarType>::Vector, lane: usize) -> Vector {
+    Vector::from_array(core::array::from_fn(|k| v[k].extract(lane)))
+}
+
+/// The `lane`-th lane of a wide angular vector.
+#[cfg(feature = "dim2")]
+fn lane_ang_vector(v: &SimdReal, lane: usize) -> AngVector {
+    v.extract(lane)
+}
+
+/// The `lane`-th lane of a wide angular vector.
+#[cfg(feature = "dim3")]
+fn lane_ang_vector(v: &<SimdReal as ScalarType>::AngVector, lane: usize) -> AngVector {
+    lane_vector(v, lane)
+}
@@ -14,7 +14,7 @@ pub(crate) mod manifold_store;
 mod solver_body;
 pub(crate) mod solver_contact_graph;
 mod staged_island_solver;
-pub(crate) use staged_island_solver::StagedIslandSolver;
+pub(crate) use staged_island_solver::{SolveScope, StagedIslandSolver};
 mod velocity_solver;
 
 // TODO: SAFETY: restrict with bytemuck::Zeroable to make this safe.
@@ -36,12 +36,16 @@ macro_rules! scatter(
 /// `SolverBodies.flags` byte parallel to `vels`/`poses` so the scalar integrate-positions loop
 /// can read it without disturbing the SIMD-gathered `SolverVel`/`SolverPose` layouts.
 pub(crate) const SOLVER_BODY_ALLOW_FAST_ROTATION: u8 = 1;
+/// Per-solver-body flag: the force fields are evaluated for this body at each substep. Set by
+/// the staged solver's body-copy stage (never by [`SolverBodies::copy_from`]).
+pub(crate) const SOLVER_BODY_FORCE_FIELDS: u8 = 2;
 
 #[derive(Default)]
 pub struct SolverBodies {
     pub vels: Vec<SolverVel<Real>>,
     pub poses: Vec<SolverPose<Real>>,
-    /// Per-body flag bytes (see `SOLVER_BODY_ALLOW_FAST_ROTATION`), indexed like `vels`/`poses`.
+    /// Per-body flag bytes (see `SOLVER_BODY_ALLOW_FAST_ROTATION` and
+    /// `SOLVER_BODY_FORCE_FIELDS`), indexed like `vels`/`poses`.
     pub flags: Vec<u8>,
 }
 
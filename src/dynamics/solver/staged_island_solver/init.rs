@@ -9,9 +9,10 @@ use crate::dynamics::solver::manifold_store::ManifoldStore;
 use crate::dynamics::solver::reset_buffer_reusing;
 use crate::dynamics::solver::solver_contact_graph::{ContactRef, SolverContactGraph};
 use crate::dynamics::{
-    IntegrationParameters, IslandManager, JointGraphEdge, JointIndex, MultibodyJointSet,
-    RigidBodySet,
+    ForceFieldSet, IntegrationParameters, IslandManager, JointGraphEdge, JointIndex,
+    MultibodyJointSet, RigidBodySet,
 };
+use crate::geometry::ColliderSet;
 use crate::math::Real;
 use parry::math::SIMD_WIDTH;
 
@@ -21,7 +22,8 @@ use crate::dynamics::FrictionModel;
 use super::sync::StageSync;
 use super::worker::run_worker;
 use super::{
-    CHUNK_BATCH, ChunkSegment, GroupLayout, LAYOUT_REF_WORKERS, SharedCtx, StagedIslandSolver,
+    CHUNK_BATCH, ChunkSegment, GroupLayout, LAYOUT_REF_WORKERS, SharedCtx, SolveScope,
+    StagedIslandSolver,
 };
 
 impl StagedIslandSolver {
@@ -31,10 +33,14 @@ impl StagedIslandSolver {
         &mut self,
         num_workers: usize,
         island_id: usize,
+        scope: SolveScope,
         counters: &mut Counters,
         base_params: &IntegrationParameters,
         islands: &IslandManager,
         bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+        // Evaluated for every awake dynamic body at each substep.
+        force_fields: &ForceFieldSet,
         // The persistent per-color solver contact buckets (+ the
         // generic list), maintained incrementally by the narrow-phase — consumed
         // directly; nothing is selected or sorted per step.
@@ -54,6 +60,9 @@ impl StagedIslandSolver {
         // real substeps buy convergence on high mass ratios, unlike the flat PGS sweeps they
         // replaced. Multibody scenes: one group at max extra (generic tables not group-major yet).
         let island_bodies = islands.island(island_id).bodies();
+        // Like the solve groups, the scoped solves don't handle the multibodies' generic
+        // constraints: the pipeline only scopes the solves of multibody-free scenes.
+        debug_assert!(scope.is_all() || multibodies.iter().next().is_none());
         let multi_group = islands.solve_groups.len() >= 2 && multibodies.iter().next().is_none();
         let max_extra = islands
             .solve_groups
@@ -162,6 +171,42 @@ impl StagedIslandSolver {
 
         let set = &mut self.contact_constraints;
 
+        // A scoped solve leaves out the contacts between bodies it doesn't move: copy the other
+        // refs of each bucket. Reserve the exact upper bound up front: the segments hold raw
+        // pointers into this vec, which must never reallocate below.
+        let scoped_runs = (!scope.is_all()).then(|| {
+            let in_scope = |r: &ContactRef| {
+                store.get(*r).data.solver_body_ids.iter().any(|id| {
+                    island_bodies
+                        .get(*id as usize)
+                        .is_some_and(|handle| scope.contains(*handle))
+                })
+            };
+            self.scoped_chunk_refs.clear();
+            self.scoped_chunk_refs.reserve(num_two_body);
+            let mut runs = Vec::new();
+            for (color, refs) in graph.buckets() {
+                let start = self.scoped_chunk_refs.len();
+                self.scoped_chunk_refs
+                    .extend(refs.iter().filter(|r| in_scope(r)));
+                if self.scoped_chunk_refs.len() > start {
+                    runs.push((color, start..self.scoped_chunk_refs.len()));
+                }
+            }
+            runs
+        });
+        let scoped_buckets: Option<Vec<(u8, &[ContactRef])>> = scoped_runs.map(|runs| {
+            runs.into_iter()
+                .map(|(color, range)| (color, &self.scoped_chunk_refs[range]))
+                .collect()
+        });
+        let buckets = || {
+            graph
+                .buckets()
+                .filter(|_| scoped_buckets.is_none())
+                .chain(scoped_buckets.iter().flatten().copied())
+        };
+
         // Build the SIMD chunk layout, group-major then color by color, straight from the
         // persistent buckets. Colors too small to distribute across workers merge into a
         // per-group serial "overflow" tail. The threshold is calibrated for
@@ -192,7 +237,7 @@ impl StagedIslandSolver {
         if self.groups.len() == 1 {
             // Single group (the common case): the historical zero-copy layout,
             // slicing the persistent buckets directly.
-            for (color, refs) in graph.buckets() {
+            for (color, refs) in buckets() {
                 if (color as usize) < NUM_COLORS - 1
                     && refs.len().div_ceil(SIMD_WIDTH) >= min_color_chunks
                 {
@@ -207,7 +252,7 @@ impl StagedIslandSolver {
             // created only once the vec stops growing (they hold raw pointers into it). The
             // overflow color sorts last, so deferring them preserves the global chunk order.
             let mut overflow_grouped_len = 0usize;
-            for (color, refs) in graph.buckets() {
+            for (color, refs) in buckets() {
                 let is_overflow_color = (color as usize) == NUM_COLORS - 1;
                 if is_overflow_color {
                     // Overflow color: uncolorable pairs / 2nd+ manifolds of a pair CAN share both
@@ -293,7 +338,7 @@ impl StagedIslandSolver {
             let mut runs: Vec<Vec<(u8, u32, u32)>> = alloc::vec![Vec::new(); num_groups];
             let mut overflow_by_group: Vec<Vec<ContactRef>> = alloc::vec![Vec::new(); num_groups];
             let mut split: Vec<Vec<ContactRef>> = alloc::vec![Vec::new(); num_groups];
-            for (color, refs) in graph.buckets() {
+            for (color, refs) in buckets() {
                 let is_overflow_color = (color as usize) == NUM_COLORS - 1;
                 for scratch in &mut split {
                     scratch.clear();
@@ -465,6 +510,12 @@ impl StagedIslandSolver {
          */
         self.sync = StageSync::new(num_workers);
 
+        // The workers write the island bodies back without recording the changes for the delta
+        // snapshots, so record them here.
+        for handle in island_bodies {
+            bodies.mark_changed(*handle);
+        }
+
         let ctx = SharedCtx {
             sync: &self.sync,
             chunk_segments: &self.chunk_segments,
@@ -475,6 +526,7 @@ impl StagedIslandSolver {
             joint_rows: &self.joint_rows,
             joint_chunk_rows: &self.joint_chunk_rows,
             island_bodies,
+            scope,
             has_multibodies: !self.velocity_solver.multibody_roots.is_empty(),
             base_params,
             store,
@@ -491,6 +543,8 @@ impl StagedIslandSolver {
             twist_constraints: set.simd_velocity_twist_constraints.as_mut_ptr(),
             use_twist,
             bodies: bodies as *mut _,
+            colliders,
+            force_fields: (!force_fields.is_empty()).then_some(force_fields),
             multibodies: multibodies as *mut _,
             #[cfg(feature = "dim3")]
             any_gyroscopic: &self.any_gyroscopic as *const _,
@@ -529,6 +583,20 @@ impl StagedIslandSolver {
             }
         }
 
+        // Multibody joints aren't part of the joint graph the workers write back to.
+        if !self.velocity_solver.multibody_roots.is_empty() {
+            // Multibody scenes are always single-group: this is their substep length.
+            let substep_dt = self.groups[0].dt;
+            self.joint_constraints
+                .writeback_internal_impulses(substep_dt, multibodies);
+
+            for link in &self.velocity_solver.multibody_roots {
+                if let Some(multibody) = multibodies.get_multibody_mut_internal(link.multibody) {
+                    multibody.update_joint_reactions(substep_dt, bodies);
+                }
+            }
+        }
+
         counters.solver.velocity_resolution_time.pause();
         // NOTE: impulse and rigid-body writeback now happen as parallel stages of
         //       `run_worker`, so their time is included in the resolution counter.
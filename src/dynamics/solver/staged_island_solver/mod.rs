@@ -20,9 +20,10 @@ use crate::dynamics::solver::manifold_store::ManifoldStore;
 use crate::dynamics::solver::solver_contact_graph::ContactRef;
 use crate::dynamics::solver::{JointConstraintsSet, VelocitySolver};
 use crate::dynamics::{
-    IntegrationParameters, JointGraphEdge, JointIndex, MultibodyJointSet, RigidBodyHandle,
-    RigidBodySet,
+    ForceFieldSet, IntegrationParameters, JointGraphEdge, JointIndex, MultibodyJointSet,
+    RigidBodyHandle, RigidBodySet,
 };
+use crate::geometry::ColliderSet;
 use crate::math::Real;
 use parry::math::SIMD_WIDTH;
 
@@ -57,6 +58,60 @@ fn stage_batch(domain_len: usize, num_workers: usize) -> usize {
     (domain_len / (num_workers * 8)).clamp(CHUNK_BATCH, 64)
 }
 
+/// The awake bodies a solve moves. The others are read-only walls at their current pose, like
+/// the sleeping bodies at the island frontier, and their constraints are left out of the solve:
+/// the impact substeps of `CcdMode::ImpactSubstepping` solve the islands of the impacting bodies
+/// apart from the rest of the world.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum SolveScope<'a> {
+    /// Every awake body.
+    All,
+    /// Only these bodies, sorted by handle.
+    Only(&'a [RigidBodyHandle]),
+    /// Every awake body but these ones, sorted by handle.
+    AllBut(&'a [RigidBodyHandle]),
+}
+
+impl SolveScope<'_> {
+    /// Sorts `handles` the way the scopes expect them.
+    pub fn sort(handles: &mut [RigidBodyHandle]) {
+        handles.sort_unstable_by_key(|handle| handle.0.into_raw_parts());
+    }
+
+    pub fn is_all(self) -> bool {
+        matches!(self, Self::All)
+    }
+
+    /// Whether the body `handle`, if awake, is moved by the solve.
+    pub fn contains(self, handle: RigidBodyHandle) -> bool {
+        let listed = |handles: &[RigidBodyHandle]| {
+            handles
+                .binary_search_by_key(&handle.0.into_raw_parts(), |h| h.0.into_raw_parts())
+                .is_ok()
+        };
+        match self {
+            Self::All => true,
+            Self::Only(handles) => listed(handles),
+            Self::AllBut(handles) => !listed(handles),
+        }
+    }
+
+    /// Whether a constraint between these bodies is part of the solve: at least one of them must
+    /// be a non-fixed body moved by it.
+    pub fn contains_any(
+        self,
+        bodies: &RigidBodySet,
+        handles: impl IntoIterator<Item = Option<RigidBodyHandle>>,
+    ) -> bool {
+        self.is_all()
+            || handles.into_iter().flatten().any(|handle| {
+                bodies
+                    .get(handle)
+                    .is_some_and(|rb| !rb.is_fixed() && self.contains(handle))
+            })
+    }
+}
+
 /// One contiguous run of SIMD chunks over a slice of solver-manifold refs (a persistent color
 /// bucket slice, or a piece of the overflow grouper's output). Chunks are addressed as
 /// (segment, offset) with lanes resolved on demand — no per-step O(manifolds) lane-array copy.
@@ -173,6 +228,8 @@ struct SharedCtx<'a> {
     /// `joint_constraints.simd_velocity_constraints`.
     joint_chunk_rows: &'a [Range<usize>],
     island_bodies: &'a [RigidBodyHandle],
+    /// The island bodies this solve moves; the others are neither integrated nor written back.
+    scope: SolveScope<'a>,
     /// Sleeping bodies filled as read-only (kinematic-like) solver bodies after
     /// the island's own bodies (slot id = `island_bodies.len() + position`).
     /// Never written back.
@@ -197,6 +254,10 @@ struct SharedCtx<'a> {
     use_twist: bool,
 
     bodies: *mut RigidBodySet,
+    /// Read by the force fields only.
+    colliders: &'a ColliderSet,
+    /// The force fields evaluated by the velocity-increment stage, `None` if there are none.
+    force_fields: Option<&'a ForceFieldSet>,
     multibodies: *mut MultibodyJointSet,
     /// Set by the body-copy stage when any island body has gyroscopic forces
     /// enabled (3D); gates the per-substep gyroscopic pass. Published before
@@ -237,6 +298,9 @@ pub(crate) struct StagedIslandSolver {
     /// contiguous copies of the persistent buckets' refs (the single-group
     /// path points straight into the buckets and never fills this).
     grouped_chunk_refs: Vec<ContactRef>,
+    /// Stable storage for the chunk refs of a scoped solve: the persistent buckets' refs
+    /// touching a body it moves, bucket by bucket.
+    scoped_chunk_refs: Vec<ContactRef>,
     joint_colors: ParallelInteractionGroups,
     /// Per parallel joint color: color id and SIMD joint-chunk index range; lanes within a
     /// range touch pairwise-disjoint bodies. Joint coloring shares the contacts' color space
@@ -303,6 +367,7 @@ impl StagedIslandSolver {
             color_ranges: Vec::new(),
             groups: Vec::new(),
             grouped_chunk_refs: Vec::new(),
+            scoped_chunk_refs: Vec::new(),
             overflow_scratch: Vec::new(),
             joint_colors: ParallelInteractionGroups::new(),
             joint_color_ranges: Vec::new(),
@@ -326,4 +391,14 @@ impl StagedIslandSolver {
             sync: StageSync::new(1),
         }
     }
+
+    /// The substep length the last solve used for the solver body `solver_body_id`, or
+    /// `None` if that body wasn't part of it.
+    pub fn substep_dt(&self, solver_body_id: u32) -> Option<Real> {
+        let id = solver_body_id as usize;
+        self.groups
+            .iter()
+            .find(|group| group.bodies.contains(&id))
+            .map(|group| group.dt)
+    }
 }
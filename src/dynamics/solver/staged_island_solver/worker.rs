@@ -11,11 +11,15 @@ use crate::dynamics::solver::contact_constraint::ContactWithCoulombFrictionBuild
 #[cfg(feature = "dim3")]
 use crate::dynamics::solver::contact_constraint::ContactWithTwistFrictionBuilder;
 use crate::dynamics::solver::joint_constraint::GenericJointConstraintBuilder;
-use crate::dynamics::solver::solver_body::SOLVER_BODY_ALLOW_FAST_ROTATION;
+use crate::dynamics::solver::solver_body::{
+    SOLVER_BODY_ALLOW_FAST_ROTATION, SOLVER_BODY_FORCE_FIELDS, SolverBodies, SolverVel,
+};
 use crate::dynamics::solver::solver_contact_graph::ContactRef;
-use crate::dynamics::{JointGraphEdge, RigidBodyType, RigidBodyVelocity};
+use crate::dynamics::{
+    ForceFieldBody, ForceFieldSet, JointGraphEdge, RigidBodyType, RigidBodyVelocity,
+};
 use crate::geometry::ContactManifold;
-use crate::math::Real;
+use crate::math::{Pose, Real};
 use parry::math::SIMD_WIDTH;
 
 use super::solve::solve_pass;
@@ -28,6 +32,38 @@ use super::{BODY_BATCH, SharedCtx, chunk_at, stage_batch};
 #[allow(clippy::unnecessary_cast)]
 const MAX_ROTATION: Real = core::f64::consts::FRAC_PI_4 as Real;
 
+/// Integrates the force fields' force and torque on the solver body `i` over a substep of
+/// length `dt`, evaluated with the body's current substep pose and velocities.
+///
+/// # Safety
+/// No other thread may mutate the rigid-body set, nor access solver body `i`.
+unsafe fn apply_force_fields(
+    ctx: &SharedCtx,
+    force_fields: &ForceFieldSet,
+    solver_bodies: &mut SolverBodies,
+    i: usize,
+    dt: Real,
+) {
+    let handle = ctx.island_bodies[i];
+    let bodies = unsafe { &*ctx.bodies };
+    let rb = &bodies[handle];
+    let pose = solver_bodies.poses[i];
+    let vels = &mut solver_bodies.vels[i];
+    let body = ForceFieldBody {
+        handle,
+        body: rb,
+        colliders: ctx.colliders,
+        // The solver pose is the center-of-mass frame.
+        position: pose.pose() * Pose::from_translation(-rb.mprops.local_mprops.local_com),
+        center_of_mass: pose.translation,
+        linvel: vels.linear,
+        angvel: vels.angular,
+    };
+    let force = force_fields.compute_force(&body);
+    vels.linear += force.force * pose.im * dt;
+    vels.angular += pose.ii * force.torque * dt;
+}
+
 /// The stage machine executed by every worker. See [`SharedCtx`] for the safety contract.
 pub(super) unsafe fn run_worker(ctx: &SharedCtx, worker_id: usize) {
     let sync = ctx.sync;
@@ -64,6 +100,17 @@ pub(super) unsafe fn run_worker(ctx: &SharedCtx, worker_id: usize) {
                 let vs = unsafe { &mut *ctx.velocity_solver };
                 vs.solver_bodies.copy_from(i, rb);
 
+                if !ctx.scope.contains(handle) {
+                    // Not moved by this solve: a read-only wall at its current pose, with no
+                    // velocity, increment or mass (the increments and gyroscopic parameters
+                    // were reset by the serial pre-phase).
+                    vs.solver_bodies.vels[i] = SolverVel::zero();
+                    let pose = &mut vs.solver_bodies.poses[i];
+                    pose.ii = Default::default();
+                    pose.im = Default::default();
+                    continue;
+                }
+
                 // The per-substep external-force increment, baked with the
                 // slot's group substep dt (groups are few; the scan is cheap).
                 let slot_dt = ctx
@@ -75,6 +122,11 @@ pub(super) unsafe fn run_worker(ctx: &SharedCtx, worker_id: usize) {
                 let incr = &mut vs.solver_vels_increment[i];
                 incr.angular = rb.mprops.effective_world_inv_inertia * rb.forces.torque * slot_dt;
                 incr.linear = rb.forces.force * rb.mprops.effective_inv_mass * slot_dt;
+                // The force fields depend on the substep's state: they are evaluated by the
+                // increment stage rather than baked here.
+                if ctx.force_fields.is_some() && rb.is_dynamic() && !rb.is_sleeping() {
+                    vs.solver_bodies.flags[i] |= SOLVER_BODY_FORCE_FIELDS;
+                }
 
                 // Gyroscopic forces (3D) are applied per-substep by the increment
                 // stage; record the body's diagonal local inertia here.
@@ -240,6 +292,24 @@ pub(super) unsafe fn run_worker(ctx: &SharedCtx, worker_id: usize) {
                     let vs = unsafe { &mut *ctx.velocity_solver };
                     let claimed_len = claimed.len();
                     for i in claimed {
+                        // Force fields, evaluated with the state integrated by the
+                        // previous substep.
+                        if let Some(force_fields) = ctx.force_fields {
+                            if vs.solver_bodies.flags[i] & SOLVER_BODY_FORCE_FIELDS != 0 {
+                                // SAFETY: reads the rigid-bodies (no writer during this
+                                //         stage), writes the claimed solver-body slot only.
+                                unsafe {
+                                    apply_force_fields(
+                                        ctx,
+                                        force_fields,
+                                        &mut vs.solver_bodies,
+                                        i,
+                                        params.dt,
+                                    );
+                                }
+                            }
+                        }
+
                         let incr = vs.solver_vels_increment[i];
                         {
                             let vels = &mut vs.solver_bodies.vels[i];
@@ -819,9 +889,12 @@ pub(super) unsafe fn run_worker(ctx: &SharedCtx, worker_id: usize) {
                         continue;
                     }
                 }
+                if !ctx.scope.contains(handle) {
+                    continue;
+                }
 
                 let bodies = unsafe { &mut *ctx.bodies };
-                let rb = bodies.index_mut_internal(handle);
+                let rb = bodies.index_mut_internal_unrecorded(handle);
                 let vs = unsafe { &*ctx.velocity_solver };
                 let solver_vels = &vs.solver_bodies.vels[rb.ids.active_set_id as usize];
                 let solver_poses = &vs.solver_bodies.poses[rb.ids.active_set_id as usize];
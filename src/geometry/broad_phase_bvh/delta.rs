@@ -0,0 +1,188 @@
+//! Delta snapshots of the broad-phase: only the pairs and pair adjacency lists that changed
+//! since the previous snapshot are stored, plus the tree if it was modified.
+
+use super::{BroadPhaseBvh, BvhOptimizationStrategy};
+use crate::alloc_prelude::*;
+use crate::data::CoarenaDelta;
+use crate::data::delta::ChangeTracker;
+use crate::geometry::ColliderHandle;
+use parry::partitioning::Bvh;
+
+/// The parts of a [`BroadPhaseBvh`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`BroadPhaseBvh::delta_snapshot`] and restored with
+/// [`BroadPhaseBvh::apply_delta`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BroadPhaseBvhDelta {
+    tree: Option<Bvh>,
+    pair_adjacency: CoarenaDelta<Vec<ColliderHandle>>,
+    /// The pairs of the colliders whose adjacency list changed, with their creation frame.
+    pairs: Vec<(ColliderHandle, ColliderHandle, u32)>,
+    prev_updated_leaves: Vec<u32>,
+    pending_set_aabb: Vec<ColliderHandle>,
+    changes_since_optimize: u32,
+    reinsert_leaf_updates: bool,
+    frame_index: u32,
+    optimization_strategy: BvhOptimizationStrategy,
+    adaptive_change_detection_margin: bool,
+}
+
+impl BroadPhaseBvhDelta {
+    /// Whether this delta holds the broad-phase's tree.
+    pub fn has_tree(&self) -> bool {
+        self.tree.is_some()
+    }
+
+    /// The number of colliders whose pairs this delta holds.
+    pub fn num_changed_colliders(&self) -> usize {
+        self.pair_adjacency.num_changed()
+    }
+}
+
+/// Remembers what a [`BroadPhaseBvh`] looked like at its last delta snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct BroadPhaseBvhDeltaTracker {
+    tree: ChangeTracker,
+    pair_adjacency: ChangeTracker,
+}
+
+impl BroadPhaseBvhDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// The key of the pair between `handle1` and `handle2` in the pair map.
+fn pair_key(handle1: ColliderHandle, handle2: ColliderHandle) -> (ColliderHandle, ColliderHandle) {
+    if handle1.into_raw_parts().0 < handle2.into_raw_parts().0 {
+        (handle1, handle2)
+    } else {
+        (handle2, handle1)
+    }
+}
+
+impl BroadPhaseBvh {
+    /// The parts of this broad-phase that changed since the previous snapshot taken with
+    /// `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole broad-phase.
+    /// Afterward, only the colliders whose pairs were created or removed since then are stored,
+    /// with their pairs. The tree can't be split into parts, so it is stored whole, but only if
+    /// a leaf moved or the tree was refitted or optimized since then.
+    ///
+    /// The changes are recorded by the broad-phase itself, so its snapshots must all be taken
+    /// with the same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(
+        &mut self,
+        tracker: &mut BroadPhaseBvhDeltaTracker,
+    ) -> BroadPhaseBvhDelta {
+        // Destructured so that a new field can't be forgotten here.
+        let BroadPhaseBvh {
+            tree,
+            workspace: _,
+            pairs,
+            pair_adjacency,
+            updated_colliders: _,
+            #[cfg(not(feature = "parallel"))]
+                candidates_scratch: _,
+            updated_mask: _,
+            stale_pairs: _,
+            prev_updated_leaves,
+            curr_updated_leaves: _,
+            pending_set_aabb,
+            changes_since_optimize,
+            reinsert_leaf_updates,
+            update_scratch: _,
+            #[cfg(feature = "parallel")]
+                update_batch_scratch: _,
+            #[cfg(feature = "parallel")]
+                update_batch_statuses: _,
+            frame_index,
+            optimization_strategy,
+            adaptive_change_detection_margin,
+            deferred_optimize_pending: _,
+            tree_changes,
+            pair_adjacency_changes,
+        } = self;
+
+        let pair_adjacency =
+            pair_adjacency.delta(&mut tracker.pair_adjacency, pair_adjacency_changes);
+        // Every pair creation or removal modifies the adjacency lists of both colliders, so the
+        // pairs that changed are among the ones of the colliders listed in the delta.
+        let mut changed_pairs: Vec<_> = pair_adjacency
+            .changed()
+            .filter(|(_, (generation, _))| *generation != u32::MAX)
+            .flat_map(|(i, (generation, others))| {
+                let handle = ColliderHandle::from_raw_parts(i as u32, *generation);
+                others.iter().map(move |other| pair_key(handle, *other))
+            })
+            .filter_map(|key| Some((key.0, key.1, *pairs.get(&key)?)))
+            .collect();
+        changed_pairs.sort_unstable_by_key(|(handle1, handle2, _)| {
+            (handle1.into_raw_parts().0, handle2.into_raw_parts().0)
+        });
+        changed_pairs.dedup();
+
+        BroadPhaseBvhDelta {
+            tree: tracker.tree.changed(tree_changes).then(|| tree.clone()),
+            pair_adjacency,
+            pairs: changed_pairs,
+            prev_updated_leaves: prev_updated_leaves.clone(),
+            pending_set_aabb: pending_set_aabb.clone(),
+            changes_since_optimize: *changes_since_optimize,
+            reinsert_leaf_updates: *reinsert_leaf_updates,
+            frame_index: *frame_index,
+            optimization_strategy: *optimization_strategy,
+            adaptive_change_detection_margin: *adaptive_change_detection_margin,
+        }
+    }
+
+    /// Brings this broad-phase from the state of the previous delta snapshot to the state of
+    /// `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The
+    /// broad-phase then serializes to the same bytes as the one the snapshots were taken from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this broad-phase isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &BroadPhaseBvhDelta) {
+        // Drop the pairs of the changed colliders, as listed before the delta, then add back
+        // the ones they have after it.
+        for (i, _) in delta.pair_adjacency.changed() {
+            let (Some(generation), Some(others)) = (
+                self.pair_adjacency.get_gen(i as u32),
+                self.pair_adjacency.get_unknown_gen(i as u32),
+            ) else {
+                continue;
+            };
+            let handle = ColliderHandle::from_raw_parts(i as u32, generation);
+            for other in others {
+                crate::utils::hashmap_remove(&mut self.pairs, &pair_key(handle, *other));
+            }
+        }
+        self.pair_adjacency
+            .apply_delta(&delta.pair_adjacency, &mut self.pair_adjacency_changes);
+        self.pairs.extend(
+            delta
+                .pairs
+                .iter()
+                .map(|(handle1, handle2, frame)| ((*handle1, *handle2), *frame)),
+        );
+
+        if let Some(tree) = &delta.tree {
+            self.tree = tree.clone();
+            self.tree_changes.mark();
+        }
+        self.prev_updated_leaves
+            .clone_from(&delta.prev_updated_leaves);
+        self.pending_set_aabb.clone_from(&delta.pending_set_aabb);
+        self.changes_since_optimize = delta.changes_since_optimize;
+        self.reinsert_leaf_updates = delta.reinsert_leaf_updates;
+        self.frame_index = delta.frame_index;
+        self.optimization_strategy = delta.optimization_strategy;
+        self.adaptive_change_detection_margin = delta.adaptive_change_detection_margin;
+        self.deferred_optimize_pending = false;
+    }
+}
@@ -1,13 +1,19 @@
 use crate::alloc_prelude::*;
 use crate::data::Coarena;
+use crate::data::change_log::{ChangeFlag, ChangeLog};
 use crate::dynamics::IntegrationParameters;
 use crate::geometry::{Aabb, ColliderHandle};
 use crate::math::Real;
 use parry::partitioning::{Bvh, BvhLeafUpdateStatus, BvhWorkspace};
 use parry::utils::hashmap::HashMap;
 
+#[cfg(feature = "serde-serialize")]
+mod delta;
 mod update;
 
+#[cfg(feature = "serde-serialize")]
+pub use self::delta::{BroadPhaseBvhDelta, BroadPhaseBvhDeltaTracker};
+
 /// The broad-phase collision detector that quickly filters out distant object pairs.
 ///
 /// The broad-phase is the "first pass" of collision detection. It uses a hierarchical
@@ -100,6 +106,13 @@ pub struct BroadPhaseBvh {
     /// solver; consumed by [`Self::take_deferred_optimize`].
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     deferred_optimize_pending: bool,
+    /// Whether `tree` was modified since the previous delta snapshot (the tree is opaque, so it
+    /// is stored whole whenever it changed).
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    tree_changes: ChangeFlag,
+    /// The `pair_adjacency` entries modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pair_adjacency_changes: ChangeLog,
 }
 
 // TODO: would be interesting to try out:
@@ -226,6 +239,7 @@ impl BroadPhaseBvh {
     pub(crate) fn finish_deferred_optimize(&mut self, task: DeferredBvhOptimize) {
         self.tree = task.tree;
         self.workspace = task.workspace;
+        self.tree_changes.mark();
     }
 
     /// Sets the AABB associated to the given collider.
@@ -254,6 +268,7 @@ impl BroadPhaseBvh {
             // this collider.
             BvhLeafUpdateStatus::Unchanged => {}
             BvhLeafUpdateStatus::UpdatedInPlace | BvhLeafUpdateStatus::Inserted => {
+                self.tree_changes.mark();
                 if !self.reinsert_leaf_updates && status == BvhLeafUpdateStatus::UpdatedInPlace {
                     self.changes_since_optimize = self.changes_since_optimize.saturating_add(1);
                 }
@@ -48,6 +48,7 @@ impl BroadPhaseBvh {
         if self.deferred_optimize_pending {
             self.deferred_optimize_pending = false;
             super::run_bvh_optimize(&mut self.tree, &mut self.workspace);
+            self.tree_changes.mark();
         }
 
         // Removals must be handled first, in case another collider in
@@ -296,6 +297,16 @@ impl BroadPhaseBvh {
             self.tree
                 .refit_partial(&self.prev_updated_leaves, &self.curr_updated_leaves);
         }
+        // Leaf updates, removals, refits and the optimizer all rewrite nodes of the tree: it is
+        // only left untouched when nothing moved during this update nor the previous one.
+        if full_refit
+            || !self.prev_updated_leaves.is_empty()
+            || !self.curr_updated_leaves.is_empty()
+            || !removed_colliders.is_empty()
+            || forced_reinsertion
+        {
+            self.tree_changes.mark();
+        }
         core::mem::swap(&mut self.prev_updated_leaves, &mut self.curr_updated_leaves);
 
         self.deferred_optimize_pending |= defer_optimize;
@@ -420,6 +431,8 @@ impl BroadPhaseBvh {
                 self.pair_adjacency
                     .ensure_element_exist(handle2.0, Vec::new())
                     .push(handle1);
+                self.pair_adjacency_changes.mark_index(handle1.0);
+                self.pair_adjacency_changes.mark_index(handle2.0);
                 events.push(BroadPhasePairEvent::AddPair(ColliderPair::new(
                     handle1, handle2,
                 )));
@@ -452,6 +465,7 @@ impl BroadPhaseBvh {
         // colliders on its own).
         for handle in removed_colliders {
             if let Some(mut others) = self.pair_adjacency.remove(handle.0, Vec::new()) {
+                self.pair_adjacency_changes.mark_index(handle.0);
                 for other in others.drain(..) {
                     self.stale_pairs.push((*handle, other, false));
                 }
@@ -590,6 +604,7 @@ impl BroadPhaseBvh {
                     if let Some(others) = self.pair_adjacency.get_mut(ha.0) {
                         if let Some(pos) = others.iter().position(|h| *h == hb) {
                             others.swap_remove(pos);
+                            self.pair_adjacency_changes.mark_index(ha.0);
                         }
                     }
                 }
@@ -1,12 +1,12 @@
 use crate::alloc_prelude::*;
 use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle, RigidBodySet};
-#[cfg(feature = "dim3")]
-use crate::geometry::HeightFieldFlags;
 use crate::geometry::{
     ActiveCollisionTypes, ColliderChanges, ColliderFlags, ColliderMassProps, ColliderMaterial,
     ColliderParent, ColliderPosition, ColliderShape, ColliderType, InteractionGroups,
     MeshConverter, MeshConverterError, SharedShape,
 };
+#[cfg(feature = "dim3")]
+use crate::geometry::{AnisotropicFriction, HeightFieldFlags};
 use crate::math::{AngVector, DIM, IVector, Pose, Real, Rotation, Vector, rotation_from_angle};
 use crate::parry::transformation::vhacd::VHACDParameters;
 use crate::pipeline::{ActiveEvents, ActiveHooks};
@@ -263,6 +263,56 @@ impl Collider {
         self.material.restitution_combine_rule = rule;
     }
 
+    /// The rolling friction coefficient of this collider.
+    ///
+    /// See [`ColliderMaterial::rolling_friction`].
+    pub fn rolling_friction(&self) -> Real {
+        self.material.rolling_friction
+    }
+
+    /// Sets the rolling friction coefficient of this collider.
+    ///
+    /// Makes balls and wheels slow down while rolling on this surface. It is a length (in
+    /// meters), typically much smaller than the rolling object: `0.001`-`0.05`.
+    /// Works with other collider's rolling friction via the friction combine rule.
+    pub fn set_rolling_friction(&mut self, coefficient: Real) {
+        self.material.rolling_friction = coefficient
+    }
+
+    /// The torsional (spinning) friction coefficient of this collider.
+    ///
+    /// See [`ColliderMaterial::spinning_friction`].
+    pub fn spinning_friction(&self) -> Real {
+        self.material.spinning_friction
+    }
+
+    /// Sets the torsional (spinning) friction coefficient of this collider.
+    ///
+    /// Makes objects spinning on this surface (around the contact normal) slow down. It is
+    /// a length (in meters), roughly the radius of the contact patch.
+    /// Works with other collider's spinning friction via the friction combine rule.
+    pub fn set_spinning_friction(&mut self, coefficient: Real) {
+        self.material.spinning_friction = coefficient
+    }
+
+    /// The anisotropic friction of this collider, if any.
+    ///
+    /// See [`ColliderMaterial::anisotropic_friction`].
+    #[cfg(feature = "dim3")]
+    pub fn anisotropic_friction(&self) -> Option<AnisotropicFriction> {
+        self.material.anisotropic_friction
+    }
+
+    /// Sets the anisotropic friction of this collider.
+    ///
+    /// With `Some`, [`Self::friction`] only applies along the given local direction, and
+    /// the secondary coefficient applies orthogonally to it. `None` makes the friction
+    /// isotropic again.
+    #[cfg(feature = "dim3")]
+    pub fn set_anisotropic_friction(&mut self, anisotropic_friction: Option<AnisotropicFriction>) {
+        self.material.anisotropic_friction = anisotropic_friction
+    }
+
     /// Sets the total force magnitude beyond which a contact force event can be emitted.
     pub fn set_contact_force_event_threshold(&mut self, threshold: Real) {
         self.contact_force_event_threshold = threshold;
@@ -653,6 +703,16 @@ pub struct ColliderBuilder {
     pub restitution: Real,
     /// The rule used to combine two restitution coefficients.
     pub restitution_combine_rule: CoefficientCombineRule,
+    /// The rolling friction coefficient of the collider to be built.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub rolling_friction: Real,
+    /// The torsional (spinning) friction coefficient of the collider to be built.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub spinning_friction: Real,
+    /// The anisotropic friction of the collider to be built.
+    #[cfg(feature = "dim3")]
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub anisotropic_friction: Option<AnisotropicFriction>,
     /// The position of this collider.
     pub position: Pose,
     /// Is this collider a sensor?
@@ -698,6 +758,10 @@ impl ColliderBuilder {
             solver_groups: InteractionGroups::all(),
             friction_combine_rule: CoefficientCombineRule::Average,
             restitution_combine_rule: CoefficientCombineRule::Average,
+            rolling_friction: 0.0,
+            spinning_friction: 0.0,
+            #[cfg(feature = "dim3")]
+            anisotropic_friction: None,
             active_collision_types: ActiveCollisionTypes::default(),
             active_hooks: ActiveHooks::empty(),
             active_events: ActiveEvents::empty(),
@@ -1268,6 +1332,37 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the rolling friction coefficient of this collider.
+    ///
+    /// Resists rolling with a torque of at most `rolling_friction * normal_force`. It is a
+    /// length (in meters), typically `0.001`-`0.05`. Default is `0.0` (rolls forever).
+    pub fn rolling_friction(mut self, rolling_friction: Real) -> Self {
+        self.rolling_friction = rolling_friction;
+        self
+    }
+
+    /// Sets the torsional (spinning) friction coefficient of this collider.
+    ///
+    /// Resists spinning around the contact normal with a torque of at most
+    /// `spinning_friction * normal_force`. It is a length (in meters), roughly the radius
+    /// of the contact patch. Default is `0.0`.
+    pub fn spinning_friction(mut self, spinning_friction: Real) -> Self {
+        self.spinning_friction = spinning_friction;
+        self
+    }
+
+    /// Makes the friction of this collider anisotropic.
+    ///
+    /// The friction coefficient set with [`Self::friction`] applies along `local_dir`
+    /// (expressed in the collider's local frame), and `secondary_friction` applies
+    /// orthogonally to it. For example, a ski gliding along its local `X` axis could use
+    /// `.friction(0.05).anisotropic_friction(Vector::X, 0.8)`.
+    #[cfg(feature = "dim3")]
+    pub fn anisotropic_friction(mut self, local_dir: Vector, secondary_friction: Real) -> Self {
+        self.anisotropic_friction = Some(AnisotropicFriction::new(local_dir, secondary_friction));
+        self
+    }
+
     /// Sets the density (mass per unit volume) of this collider.
     ///
     /// Mass will be computed as: `density × volume`. Common densities:
@@ -1418,6 +1513,10 @@ impl ColliderBuilder {
             restitution: self.restitution,
             friction_combine_rule: self.friction_combine_rule,
             restitution_combine_rule: self.restitution_combine_rule,
+            rolling_friction: self.rolling_friction,
+            spinning_friction: self.spinning_friction,
+            #[cfg(feature = "dim3")]
+            anisotropic_friction: self.anisotropic_friction,
         };
         let flags = ColliderFlags {
             collision_groups: self.collision_groups,
@@ -1,6 +1,8 @@
 use crate::alloc_prelude::*;
 use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle, RigidBodyType};
 use crate::geometry::{InteractionGroups, Shape, SharedShape};
+#[cfg(feature = "dim3")]
+use crate::math::Vector;
 use crate::math::{Pose, Real};
 use crate::pipeline::{ActiveEvents, ActiveHooks};
 use core::ops::{Deref, DerefMut};
@@ -225,6 +227,28 @@ pub struct ColliderMaterial {
     pub friction_combine_rule: CoefficientCombineRule,
     /// The rule applied to combine the restitution coefficients of two colliders.
     pub restitution_combine_rule: CoefficientCombineRule,
+    /// The rolling friction coefficient of this collider.
+    ///
+    /// Resists the relative rolling of two colliders in contact with a torque of at most
+    /// `rolling_friction * normal_force`: it is a length, roughly the depth of the dent a
+    /// rolling object digs into the surface. Should be `>= 0`. Combined with the other
+    /// collider's using [`Self::friction_combine_rule`].
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub rolling_friction: Real,
+    /// The torsional (spinning) friction coefficient of this collider.
+    ///
+    /// Resists the relative spinning of two colliders around their contact normal with a
+    /// torque of at most `spinning_friction * normal_force` (so it is a length too, roughly
+    /// the radius of the contact patch). Should be `>= 0`. Combined with the other
+    /// collider's using [`Self::friction_combine_rule`].
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub spinning_friction: Real,
+    /// Makes the friction of this collider differ along two tangent directions.
+    ///
+    /// If `None` (the default), [`Self::friction`] applies equally in every direction.
+    #[cfg(feature = "dim3")]
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub anisotropic_friction: Option<AnisotropicFriction>,
 }
 
 impl ColliderMaterial {
@@ -236,6 +260,54 @@ impl ColliderMaterial {
             ..Default::default()
         }
     }
+
+    /// The friction coefficient of this collider orthogonally to its primary friction
+    /// direction: [`AnisotropicFriction::secondary_friction`] if it is anisotropic,
+    /// [`Self::friction`] otherwise.
+    #[cfg(feature = "dim3")]
+    pub fn secondary_friction(&self) -> Real {
+        self.anisotropic_friction
+            .map(|aniso| aniso.secondary_friction)
+            .unwrap_or(self.friction)
+    }
+}
+
+/// Friction that differs along two orthogonal tangent directions of a collider, e.g., for
+/// skis, tank treads, or brushed surfaces.
+///
+/// At each contact, [`Self::local_dir`] is projected onto the contact plane:
+/// [`ColliderMaterial::friction`] applies along that projection and
+/// [`Self::secondary_friction`] along the orthogonal tangent, the friction force being
+/// bounded by the ellipse of these two coefficients. If the direction is (nearly) parallel
+/// to the contact normal, the contact falls back to isotropic friction.
+///
+/// When both colliders in contact are anisotropic, the direction of the first collider
+/// of the pair is used. Each coefficient is combined with the other collider's
+/// coefficient along the same direction (its [`ColliderMaterial::secondary_friction`]
+/// for the secondary one) using the friction combine rules.
+#[cfg(feature = "dim3")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct AnisotropicFriction {
+    /// The primary friction direction, expressed in the collider's local frame.
+    ///
+    /// [`ColliderMaterial::friction`] applies along this direction. It doesn't need to be
+    /// normalized.
+    pub local_dir: Vector,
+    /// The friction coefficient orthogonally to [`Self::local_dir`]. Should be `>= 0`.
+    pub secondary_friction: Real,
+}
+
+#[cfg(feature = "dim3")]
+impl AnisotropicFriction {
+    /// Anisotropic friction with `ColliderMaterial::friction` along `local_dir` and
+    /// `secondary_friction` orthogonally to it.
+    pub fn new(local_dir: Vector, secondary_friction: Real) -> Self {
+        Self {
+            local_dir,
+            secondary_friction,
+        }
+    }
 }
 
 impl Default for ColliderMaterial {
@@ -245,6 +317,10 @@ impl Default for ColliderMaterial {
             restitution: 0.0,
             friction_combine_rule: CoefficientCombineRule::default(),
             restitution_combine_rule: CoefficientCombineRule::default(),
+            rolling_friction: 0.0,
+            spinning_friction: 0.0,
+            #[cfg(feature = "dim3")]
+            anisotropic_friction: None,
         }
     }
 }
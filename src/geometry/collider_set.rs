@@ -1,11 +1,17 @@
 use crate::alloc_prelude::*;
 use crate::data::arena::Arena;
+use crate::data::change_log::ChangeLog;
 use crate::data::{HasModifiedFlag, ModifiedObjects};
 use crate::dynamics::{IslandManager, RigidBodyHandle, RigidBodySet};
 use crate::geometry::{Collider, ColliderChanges, ColliderHandle, ColliderParent};
 use crate::math::Pose;
 use core::ops::{Index, IndexMut};
 
+#[cfg(feature = "serde-serialize")]
+use crate::data::arena::ArenaDelta;
+#[cfg(feature = "serde-serialize")]
+use crate::data::delta::{ChangeTracker, ValueTracker, apply_value};
+
 /// A set of modified colliders
 pub type ModifiedColliders = ModifiedObjects<ColliderHandle, Collider>;
 
@@ -50,6 +56,10 @@ pub struct ColliderSet {
     pub(crate) colliders: Arena<Collider>,
     pub(crate) modified_colliders: ModifiedColliders,
     pub(crate) removed_colliders: Vec<ColliderHandle>,
+    /// The colliders modified since the previous delta snapshot: the ones added to
+    /// `modified_colliders`, plus the ones the physics pipeline modifies internally.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    changes: ChangeLog,
 }
 
 impl ColliderSet {
@@ -59,6 +69,7 @@ impl ColliderSet {
             colliders: Arena::new(),
             modified_colliders: Default::default(),
             removed_colliders: Vec::new(),
+            changes: ChangeLog::default(),
         }
     }
 
@@ -70,6 +81,7 @@ impl ColliderSet {
             colliders: Arena::with_capacity(capacity),
             modified_colliders: ModifiedColliders::with_capacity(capacity),
             removed_colliders: Vec::new(),
+            changes: ChangeLog::default(),
         }
     }
 
@@ -131,10 +143,12 @@ impl ColliderSet {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (ColliderHandle, &mut Collider)> {
         self.modified_colliders.clear();
         let modified_colliders = &mut self.modified_colliders;
+        let changes = &mut self.changes;
         self.colliders.iter_mut().map(move |(h, co)| {
             // NOTE: we push unchecked here since we are just re-populating the
             //       `modified_colliders` set that we just cleared before iteration.
             modified_colliders.push_unchecked(ColliderHandle(h), co);
+            changes.mark_index(h);
             (ColliderHandle(h), co)
         })
     }
@@ -176,6 +190,7 @@ impl ColliderSet {
         //       the set yet.
         self.modified_colliders
             .push_unchecked(handle, &mut self.colliders[handle.0]);
+        self.changes.mark_index(handle.0);
         handle
     }
 
@@ -228,6 +243,7 @@ impl ColliderSet {
         //       so it was initialized with the changed flag but isn’t in
         //       the set yet.
         self.modified_colliders.push_unchecked(handle, coll);
+        self.changes.mark_index(handle.0);
 
         parent.add_collider_internal(
             handle,
@@ -340,6 +356,7 @@ impl ColliderSet {
         wake_up: bool,
     ) -> Option<Collider> {
         let collider = self.colliders.remove(handle.0)?;
+        self.changes.mark_index(handle.0);
 
         /*
          * Delete the collider from its parent body.
@@ -384,6 +401,7 @@ impl ColliderSet {
         let (collider, handle) = self.colliders.get_unknown_gen_mut(i)?;
         let handle = ColliderHandle(handle);
         self.modified_colliders.push_once(handle, collider);
+        self.changes.mark(i);
         Some((collider, handle))
     }
 
@@ -402,6 +420,7 @@ impl ColliderSet {
     pub fn get_mut(&mut self, handle: ColliderHandle) -> Option<&mut Collider> {
         let result = self.colliders.get_mut(handle.0)?;
         self.modified_colliders.push_once(handle, result);
+        self.changes.mark_index(handle.0);
         Some(result)
     }
 
@@ -421,20 +440,43 @@ impl ColliderSet {
             let (mut co1, mut co2) = self.colliders.get2_mut(handle1.0, handle2.0);
             if let Some(co1) = co1.as_deref_mut() {
                 self.modified_colliders.push_once(handle1, co1);
+                self.changes.mark_index(handle1.0);
             }
             if let Some(co2) = co2.as_deref_mut() {
                 self.modified_colliders.push_once(handle2, co2);
+                self.changes.mark_index(handle2.0);
             }
             (co1, co2)
         }
     }
 
     pub(crate) fn index_mut_internal(&mut self, handle: ColliderHandle) -> &mut Collider {
+        self.changes.mark_index(handle.0);
         &mut self.colliders[handle.0]
     }
 
+    /// Same as [`Self::index_mut_internal`], without recording the change for the delta
+    /// snapshots, so it can be called from several threads on distinct colliders.
+    ///
+    /// The caller must have recorded the change with [`Self::mark_changed`] beforehand.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn index_mut_internal_unrecorded(
+        &mut self,
+        handle: ColliderHandle,
+    ) -> &mut Collider {
+        &mut self.colliders[handle.0]
+    }
+
+    /// Records that the collider `handle` is modified, for the delta snapshots.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn mark_changed(&mut self, handle: ColliderHandle) {
+        self.changes.mark_index(handle.0);
+    }
+
     pub(crate) fn get_mut_internal(&mut self, handle: ColliderHandle) -> Option<&mut Collider> {
-        self.colliders.get_mut(handle.0)
+        let result = self.colliders.get_mut(handle.0)?;
+        self.changes.mark_index(handle.0);
+        Some(result)
     }
 
     // Just a very long name instead of `.get_mut` to make sure
@@ -446,10 +488,85 @@ impl ColliderSet {
     ) -> Option<&mut Collider> {
         let result = self.colliders.get_mut(handle.0)?;
         self.modified_colliders.push_once(handle, result);
+        self.changes.mark_index(handle.0);
         Some(result)
     }
 }
 
+/// The parts of a [`ColliderSet`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`ColliderSet::delta_snapshot`] and restored with
+/// [`ColliderSet::apply_delta`].
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ColliderSetDelta {
+    colliders: ArenaDelta<Collider>,
+    modified_colliders: Option<ModifiedColliders>,
+    removed_colliders: Option<Vec<ColliderHandle>>,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ColliderSetDelta {
+    /// The number of collider slots (occupied or freed) this delta holds.
+    pub fn num_changed_colliders(&self) -> usize {
+        self.colliders.num_changed()
+    }
+}
+
+/// Remembers what a [`ColliderSet`] looked like at its last delta snapshot.
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, Default)]
+pub struct ColliderSetDeltaTracker {
+    colliders: ChangeTracker,
+    modified_colliders: ValueTracker,
+    removed_colliders: ValueTracker,
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ColliderSetDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl ColliderSet {
+    /// The parts of this set that changed since the previous snapshot taken with `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole set. Afterward, a
+    /// collider is part of the delta if it was accessed mutably since the previous snapshot (by
+    /// the user or the physics pipeline), which includes modifying its shape through
+    /// [`Collider::shape_mut`]. Colliders left untouched aren't visited.
+    ///
+    /// The changes are recorded by the set itself, so its snapshots must all be taken with the
+    /// same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(&mut self, tracker: &mut ColliderSetDeltaTracker) -> ColliderSetDelta {
+        ColliderSetDelta {
+            colliders: self
+                .colliders
+                .delta(&mut tracker.colliders, &mut self.changes),
+            modified_colliders: tracker.modified_colliders.diff(&self.modified_colliders),
+            removed_colliders: tracker.removed_colliders.diff(&self.removed_colliders),
+        }
+    }
+
+    /// Brings this set from the state of the previous delta snapshot to the state of `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The set
+    /// then serializes to the same bytes as the one the snapshots were taken from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this set isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &ColliderSetDelta) {
+        self.colliders
+            .apply_delta(&delta.colliders, &mut self.changes);
+        apply_value(&mut self.modified_colliders, &delta.modified_colliders);
+        apply_value(&mut self.removed_colliders, &delta.removed_colliders);
+    }
+}
+
 impl Index<crate::data::Index> for ColliderSet {
     type Output = Collider;
 
@@ -471,6 +588,7 @@ impl IndexMut<ColliderHandle> for ColliderSet {
     fn index_mut(&mut self, handle: ColliderHandle) -> &mut Collider {
         let collider = &mut self.colliders[handle.0];
         self.modified_colliders.push_once(handle, collider);
+        self.changes.mark_index(handle.0);
         collider
     }
 }
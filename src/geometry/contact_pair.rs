@@ -253,6 +253,15 @@ pub struct ContactPair {
     /// reference pose, or its first update recomputes manifolds (and re-derives the
     /// world-frozen solver anchors) where the uninterrupted run would have recycled.
     pub(crate) recycle_state: Option<ContactRecycleState>,
+    /// Whether this touching pair was kept from waking up its sleeping side (see
+    /// [`RigidBodyActivation::wakes_neighbors`] and
+    /// [`RigidBodyActivation::wake_impulse_threshold`]). Such a pair doesn't link the islands of
+    /// its bodies until it wakes that side up.
+    ///
+    /// [`RigidBodyActivation::wakes_neighbors`]: crate::dynamics::RigidBodyActivation::wakes_neighbors
+    /// [`RigidBodyActivation::wake_impulse_threshold`]: crate::dynamics::RigidBodyActivation::wake_impulse_threshold
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub(crate) wake_deferred: bool,
 }
 
 /// The relative configuration of a contact pair at its last full narrow-phase
@@ -275,6 +284,10 @@ pub(crate) struct ContactRecycleState {
     /// precomputed at the last full update (it depends on whether the pair had
     /// active contacts, which recycling doesn't change).
     pub max_drift: Real,
+    /// Whether a sleeping body acted as a world-attached wall at the last full update: its
+    /// anchors are then in world-space, so the pair isn't recycled.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub sleeping_wall: bool,
 }
 
 /// `cos Δθ` between two world rotations (in 3D, computed from the quaternion dot
@@ -341,6 +354,7 @@ impl ContactPair {
             event_status: PairEventStatus::empty(),
             workspace: None,
             recycle_state: None,
+            wake_deferred: false,
         }
     }
 
@@ -358,6 +372,7 @@ impl ContactPair {
         self.event_status = PairEventStatus::empty();
         self.workspace = None;
         self.recycle_state = None;
+        self.wake_deferred = false;
     }
 
     /// The manifolds actually seen by the constraint solver: the contact clusters if
@@ -579,6 +594,27 @@ pub struct ContactManifoldData {
     /// The effective restitution coefficient of this manifold's contacts.
     #[cfg_attr(feature = "serde-serialize", serde(default))]
     pub restitution: Real,
+    /// The effective rolling friction coefficient of this manifold (see
+    /// [`ColliderMaterial::rolling_friction`](crate::geometry::ColliderMaterial::rolling_friction)).
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub rolling_friction: Real,
+    /// The effective torsional friction coefficient of this manifold (see
+    /// [`ColliderMaterial::spinning_friction`](crate::geometry::ColliderMaterial::spinning_friction)).
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub spinning_friction: Real,
+    /// The world-space tangent direction along which [`Self::friction`] applies, for
+    /// anisotropic friction (see
+    /// [`ColliderMaterial::anisotropic_friction`](crate::geometry::ColliderMaterial::anisotropic_friction)).
+    ///
+    /// Zero if the friction of this manifold is isotropic.
+    #[cfg(feature = "dim3")]
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub friction_dir: Vector,
+    /// The effective friction coefficient orthogonally to [`Self::friction_dir`] on the
+    /// contact plane. Equal to [`Self::friction`] if the friction is isotropic.
+    #[cfg(feature = "dim3")]
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    pub secondary_friction: Real,
 }
 
 /// A single solver contact.
@@ -803,9 +839,49 @@ impl ContactManifoldData {
             user_data: 0,
             friction: 0.0,
             restitution: 0.0,
+            rolling_friction: 0.0,
+            spinning_friction: 0.0,
+            #[cfg(feature = "dim3")]
+            friction_dir: Vector::ZERO,
+            #[cfg(feature = "dim3")]
+            secondary_friction: 0.0,
         }
     }
 
+    /// Is the friction of this manifold anisotropic?
+    #[cfg(feature = "dim3")]
+    #[inline]
+    pub fn is_friction_anisotropic(&self) -> bool {
+        self.friction_dir != Vector::ZERO
+    }
+
+    /// Sets [`Self::friction_dir`] to the projection of `world_dir` onto the contact plane,
+    /// and [`Self::secondary_friction`] accordingly.
+    ///
+    /// The friction becomes isotropic (with [`Self::friction`] in every direction) if
+    /// `world_dir` is `None` or (nearly) parallel to the contact normal.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn set_anisotropic_friction(
+        &mut self,
+        world_dir: Option<Vector>,
+        secondary_friction: Real,
+    ) {
+        let tangent = world_dir
+            .map(|dir| {
+                let dir = dir.normalize_or_zero();
+                dir - self.normal * dir.dot(self.normal)
+            })
+            .filter(|tangent| tangent.length_squared() > 1.0e-6)
+            .map(|tangent| tangent.normalize())
+            .unwrap_or(Vector::ZERO);
+        self.friction_dir = tangent;
+        self.secondary_friction = if tangent == Vector::ZERO {
+            self.friction
+        } else {
+            secondary_friction
+        };
+    }
+
     /// Resolves the world-space contact points (one per body surface) of one solver
     /// contact: body-local anchors ([`SolverContactGeneric::anchor1`]) are resolved through
     /// the bodies' current poses (a world-attached side's anchor already is a world point).
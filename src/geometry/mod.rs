@@ -4,6 +4,8 @@
 pub(crate) use self::broad_phase_bvh::DeferredBvhOptimize;
 #[cfg(feature = "alloc")]
 pub use self::broad_phase_bvh::{BroadPhaseBvh, BvhOptimizationStrategy};
+#[cfg(feature = "serde-serialize")]
+pub use self::broad_phase_bvh::{BroadPhaseBvhDelta, BroadPhaseBvhDeltaTracker};
 pub use self::broad_phase_pair_event::{BroadPhasePairEvent, ColliderPair};
 #[cfg(feature = "alloc")]
 pub use self::collider::{Collider, ColliderBuilder};
@@ -12,6 +14,8 @@ pub use self::collider_components::*;
 pub use self::collider_handle::ColliderHandle;
 #[cfg(feature = "alloc")]
 pub use self::collider_set::{ColliderSet, ModifiedColliders};
+#[cfg(feature = "serde-serialize")]
+pub use self::collider_set::{ColliderSetDelta, ColliderSetDeltaTracker};
 #[cfg(feature = "alloc")]
 pub(crate) use self::contact_pair::ContactRecycleState;
 #[cfg(feature = "alloc")]
@@ -35,6 +39,8 @@ pub use self::interaction_groups::{Group, InteractionGroups, InteractionTestMode
 pub use self::mesh_converter::{MeshConverter, MeshConverterError};
 #[cfg(feature = "alloc")]
 pub use self::narrow_phase::NarrowPhase;
+#[cfg(feature = "serde-serialize")]
+pub use self::narrow_phase::{NarrowPhaseDelta, NarrowPhaseDeltaTracker};
 #[cfg(feature = "alloc")]
 pub use parry::utils::Array2;
 
@@ -258,6 +264,53 @@ impl ContactForceEvent {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+/// Event occurring when two colliders start touching, describing how hard they hit each other.
+///
+/// Unlike [`CollisionEvent::Started`], it carries the impact kinematics measured before the
+/// constraints solver changed the bodies' velocities, and the impulse the solver applied to
+/// separate them, which is what impact sounds and particle effects are usually scaled by.
+///
+/// It is emitted after the velocity solve of the step the colliders started touching, if at least
+/// one of them has [`ActiveEvents::IMPACT_EVENTS`](crate::pipeline::ActiveEvents::IMPACT_EVENTS)
+/// enabled.
+pub struct ImpactEvent {
+    /// The first collider involved in the impact.
+    pub collider1: ColliderHandle,
+    /// The second collider involved in the impact.
+    pub collider2: ColliderHandle,
+    /// The world-space deepest contact point, on the surface of the first collider.
+    pub point: Vector,
+    /// The world-space contact normal at [`Self::point`], pointing from the first collider
+    /// toward the second.
+    pub normal: Vector,
+    /// The velocity of the second collider relative to the first at [`Self::point`], along
+    /// [`Self::normal`], before the constraints solver ran.
+    ///
+    /// Negative when the colliders approach each other: `-normal_relative_velocity` is the
+    /// impact speed.
+    pub normal_relative_velocity: Real,
+    /// The sum of the normal impulses applied by all the contacts between the two colliders
+    /// during the step they started touching.
+    ///
+    /// Always zero with the [`CollisionPipeline`](crate::pipeline::CollisionPipeline), which
+    /// doesn't solve contacts.
+    pub normal_impulse: Real,
+    /// The friction coefficient of the first collider.
+    pub friction1: Real,
+    /// The friction coefficient of the second collider.
+    pub friction2: Real,
+    /// The restitution coefficient of the first collider.
+    pub restitution1: Real,
+    /// The restitution coefficient of the second collider.
+    pub restitution2: Real,
+    /// The friction coefficient the solver used, combined from both colliders' materials.
+    pub combined_friction: Real,
+    /// The restitution coefficient the solver used, combined from both colliders' materials.
+    pub combined_restitution: Real,
+}
+
 #[cfg(feature = "alloc")]
 pub(crate) use self::narrow_phase::ContactManifoldIndex;
 #[cfg(feature = "alloc")]
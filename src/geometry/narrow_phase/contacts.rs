@@ -8,7 +8,7 @@ use super::pair_update::{
 };
 use super::{
     NarrowPhase, assign_pair_solver_color, clear_pair_solver_color, collect_pairs_to_update,
-    pack_color_body_info, strong_wake_sleeping_side, unpack_color_body_info,
+    deferred_wakes, pack_color_body_info, strong_wake_sleeping_side, unpack_color_body_info,
 };
 use crate::alloc_prelude::*;
 use crate::dynamics::{ImpulseJointSet, IslandManager, MultibodyJointSet, RigidBodySet};
@@ -310,7 +310,7 @@ impl NarrowPhase {
         let mut color_todo = core::mem::take(&mut self.solver_color_todo);
         color_todo.clear();
         for &(edge_id, parent1, parent2, any_active_contact) in transitions.iter() {
-            {
+            let record_impact = {
                 let pair = &mut self.contact_graph.graph.edges[edge_id as usize].weight;
                 let co1 = &colliders[pair.collider1];
                 let co2 = &colliders[pair.collider2];
@@ -328,13 +328,22 @@ impl NarrowPhase {
                 if !any_active_contact {
                     clear_pair_solver_color(&mut self.body_solver_color_masks, pair);
                 }
+                any_active_contact && active_events.contains(ActiveEvents::IMPACT_EVENTS)
+            };
+            if record_impact {
+                self.record_impact(edge_id, bodies, colliders, parent1, parent2);
             }
 
-            // Wake rule (whole-island sleep): starts wake unconditionally; stops
-            // never wake (touching implies same island, so a moving support means the
+            // Wake rule (whole-island sleep): starts wake unless the awake side doesn't wake
+            // the sleeping one (the pair then stays out of the islands, see `deferred_wakes`);
+            // stops never wake (touching implies same island, so a moving support means the
             // island is already awake — support loss can't strand a sleeping body).
+            let wake_deferred =
+                any_active_contact && deferred_wakes::defers_wake_up(bodies, parent1, parent2);
             if any_active_contact {
-                strong_wake_sleeping_side(islands, bodies, parent1, parent2);
+                if !wake_deferred {
+                    strong_wake_sleeping_side(islands, bodies, parent1, parent2);
+                }
                 let body_info = |h: Option<crate::dynamics::RigidBodyHandle>| {
                     h.map(|h| {
                         let rb = &bodies[h];
@@ -348,10 +357,20 @@ impl NarrowPhase {
                 ));
             }
 
-            islands.interaction_changed(bodies, parent1, parent2, any_active_contact);
+            islands.interaction_changed(
+                bodies,
+                parent1,
+                parent2,
+                any_active_contact && !wake_deferred,
+            );
+            let pair = &mut self.contact_graph.graph.edges[edge_id as usize].weight;
+            pair.wake_deferred = wake_deferred;
             // Persistent islands: a touching transition links/unlinks the
             // pair's contact edge (link merges the two islands).
-            if any_active_contact {
+            if wake_deferred {
+                self.wake_deferred_pairs
+                    .push((pair.collider1, pair.collider2));
+            } else if any_active_contact {
                 islands
                     .persistent
                     .link_contact(bodies, edge_id, parent1, parent2);
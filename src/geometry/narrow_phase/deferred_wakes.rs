@@ -0,0 +1,121 @@
+//! Deferred wake-ups: touching pairs between an awake or fixed body and a sleeping one that the
+//! former doesn't wake up right away (fixed bodies never do, see also
+//! [`RigidBodyActivation::wakes_neighbors`] and [`RigidBodyActivation::wake_impulse_threshold`]).
+//!
+//! Such a pair is flagged `ContactPair::wake_deferred` and left out of the persistent islands
+//! (the touching predicates the islands read skip it), so the sleeping island stays asleep and
+//! acts as a fixed obstacle for the solver. After each solve, the pair is linked — waking its
+//! sleeping side first if its impulse got large enough — once nothing defers it anymore.
+//!
+//! [`RigidBodyActivation::wakes_neighbors`]: crate::dynamics::RigidBodyActivation::wakes_neighbors
+//! [`RigidBodyActivation::wake_impulse_threshold`]: crate::dynamics::RigidBodyActivation::wake_impulse_threshold
+
+use super::NarrowPhase;
+use crate::dynamics::{IslandManager, RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::Real;
+
+/// What to do with a touching pair between the bodies `parent1` and `parent2`, given the contact
+/// impulse of its last solve.
+enum WakeDecision {
+    /// Keep the sleeping side asleep.
+    Defer,
+    /// Wake up the given sleeping body, then link the pair.
+    WakeUp(RigidBodyHandle),
+    /// Nothing sleeps on one side only anymore: link the pair.
+    Link,
+}
+
+fn wake_decision(
+    bodies: &RigidBodySet,
+    parent1: Option<RigidBodyHandle>,
+    parent2: Option<RigidBodyHandle>,
+    impulse: Real,
+) -> WakeDecision {
+    let body = |h: Option<RigidBodyHandle>| h.and_then(|h| bodies.get(h).map(|rb| (h, rb)));
+    let sleeping = |rb: &RigidBody| rb.is_dynamic() && rb.activation.sleeping;
+    let decide = |(sleeper_handle, sleeper): (RigidBodyHandle, &RigidBody), other: &RigidBody| {
+        // A fixed body never moves, so it never wakes up the bodies resting on it.
+        if !other.is_fixed() && other.activation.wakes_up(&sleeper.activation, impulse) {
+            WakeDecision::WakeUp(sleeper_handle)
+        } else {
+            WakeDecision::Defer
+        }
+    };
+
+    match (body(parent1), body(parent2)) {
+        (Some(b1), Some(b2)) => match (sleeping(b1.1), sleeping(b2.1)) {
+            (true, false) => decide(b1, b2.1),
+            (false, true) => decide(b2, b1.1),
+            (true, true) => WakeDecision::Defer,
+            (false, false) => WakeDecision::Link,
+        },
+        _ => WakeDecision::Link,
+    }
+}
+
+/// Whether a pair between `parent1` and `parent2` that just started touching must leave its
+/// sleeping side asleep.
+pub(super) fn defers_wake_up(
+    bodies: &RigidBodySet,
+    parent1: Option<RigidBodyHandle>,
+    parent2: Option<RigidBodyHandle>,
+) -> bool {
+    matches!(
+        wake_decision(bodies, parent1, parent2, 0.0),
+        WakeDecision::Defer
+    )
+}
+
+impl NarrowPhase {
+    /// Links the wake-deferred pairs that no longer defer anything, waking up their sleeping side
+    /// if the contact impulse of the last solve exceeded its wake threshold. Pairs that stopped
+    /// touching are dropped.
+    pub(crate) fn resolve_deferred_wake_ups(
+        &mut self,
+        islands: &mut IslandManager,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+    ) {
+        if self.wake_deferred_pairs.is_empty() {
+            return;
+        }
+
+        let mut pairs = core::mem::take(&mut self.wake_deferred_pairs);
+        pairs.retain(|&(co1, co2)| {
+            let edge_id = self
+                .graph_indices
+                .get(co1.0)
+                .zip(self.graph_indices.get(co2.0))
+                .and_then(|(gid1, gid2)| {
+                    self.contact_graph
+                        .graph
+                        .find_edge(gid1.contact_graph_index, gid2.contact_graph_index)
+                });
+            let Some(edge_id) = edge_id else {
+                // The pair was removed.
+                return false;
+            };
+            let pair = &mut self.contact_graph.graph.edges[edge_id.index()].weight;
+            if !pair.wake_deferred {
+                // The pair stopped touching, or was linked by a collider change.
+                return false;
+            }
+
+            let parent = |co| colliders.get(co).and_then(|c| c.parent.map(|p| p.handle));
+            let (parent1, parent2) = (parent(co1), parent(co2));
+            match wake_decision(bodies, parent1, parent2, pair.total_impulse_magnitude()) {
+                WakeDecision::Defer => return true,
+                WakeDecision::WakeUp(handle) => islands.wake_up(bodies, handle, true),
+                WakeDecision::Link => {}
+            }
+
+            pair.wake_deferred = false;
+            islands
+                .persistent
+                .link_contact(bodies, edge_id.index() as u32, parent1, parent2);
+            false
+        });
+        self.wake_deferred_pairs = pairs;
+    }
+}
@@ -0,0 +1,176 @@
+//! Delta snapshots of the narrow-phase: only the contact and intersection pairs that changed
+//! since the previous snapshot are stored.
+
+use super::{ColliderGraphIndices, NarrowPhase};
+use crate::alloc_prelude::*;
+use crate::data::CoarenaDelta;
+use crate::data::delta::{ChangeTracker, SlotTracker, SlotsDelta, ValueTracker, apply_value};
+use crate::data::graph::{GraphDelta, GraphDeltaTracker};
+use crate::dynamics::solver::solver_contact_graph::SolverContactGraph;
+use crate::geometry::{ColliderHandle, ContactPair, IntersectionPair};
+
+/// The parts of a [`NarrowPhase`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`NarrowPhase::delta_snapshot`] and restored with
+/// [`NarrowPhase::apply_delta`].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NarrowPhaseDelta {
+    contact_graph: GraphDelta<ColliderHandle, ContactPair>,
+    intersection_graph: GraphDelta<ColliderHandle, IntersectionPair>,
+    graph_indices: CoarenaDelta<ColliderGraphIndices>,
+    body_solver_color_masks: SlotsDelta<u128>,
+    pair_solver_hints: SlotsDelta<u16>,
+    solver_contact_graph: Option<SolverContactGraph>,
+    solver_graph_valid: bool,
+    solver_graph_epoch: u32,
+    solver_graph_mb_epoch: u32,
+    force_event_pairs: Option<Vec<u32>>,
+    force_event_pos: SlotsDelta<u32>,
+    force_event_flagged: Option<Vec<u32>>,
+    force_list_valid: bool,
+    wake_deferred_pairs: Option<Vec<(ColliderHandle, ColliderHandle)>>,
+}
+
+impl NarrowPhaseDelta {
+    /// The number of contact pairs this delta holds.
+    pub fn num_changed_contact_pairs(&self) -> usize {
+        self.contact_graph.num_changed_edges()
+    }
+
+    /// The number of intersection pairs this delta holds.
+    pub fn num_changed_intersection_pairs(&self) -> usize {
+        self.intersection_graph.num_changed_edges()
+    }
+}
+
+/// Remembers what a [`NarrowPhase`] looked like at its last delta snapshot.
+#[derive(Clone, Debug, Default)]
+pub struct NarrowPhaseDeltaTracker {
+    contact_graph: GraphDeltaTracker,
+    intersection_graph: GraphDeltaTracker,
+    graph_indices: ChangeTracker,
+    body_solver_color_masks: SlotTracker,
+    pair_solver_hints: SlotTracker,
+    solver_contact_graph: ValueTracker,
+    force_event_pairs: ValueTracker,
+    force_event_pos: SlotTracker,
+    force_event_flagged: ValueTracker,
+    wake_deferred_pairs: ValueTracker,
+}
+
+impl NarrowPhaseDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NarrowPhase {
+    /// The parts of this narrow-phase that changed since the previous snapshot taken with
+    /// `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole narrow-phase.
+    /// Afterward, only the contact and intersection pairs whose serialized form changed are
+    /// stored (pairs between sleeping colliders cost nothing), together with the
+    /// solver-coloring bookkeeping if it changed.
+    ///
+    /// The collider-to-pair mappings are recorded by the narrow-phase itself, so its snapshots
+    /// must all be taken with the same tracker: a new tracker starts over with a keyframe.
+    pub fn delta_snapshot(&mut self, tracker: &mut NarrowPhaseDeltaTracker) -> NarrowPhaseDelta {
+        // Destructured so that a new field can't be forgotten here.
+        let NarrowPhase {
+            query_dispatcher: _,
+            contact_graph,
+            intersection_graph,
+            graph_indices,
+            graph_index_changes,
+            update_candidates: _,
+            body_solver_color_masks,
+            body_qualify_info: _,
+            awake_body_mask: _,
+            pair_solver_hints,
+            solver_contact_graph,
+            solver_graph_valid,
+            solver_graph_epoch,
+            solver_graph_mb_epoch,
+            solver_graph_dirty: _,
+            force_event_pairs,
+            force_event_pos,
+            force_event_flagged,
+            force_list_valid,
+            pending_impacts: _,
+            wake_deferred_pairs,
+            solver_color_todo: _,
+            retired_pairs: _,
+        } = self;
+
+        NarrowPhaseDelta {
+            contact_graph: contact_graph.graph.delta(&mut tracker.contact_graph),
+            intersection_graph: intersection_graph
+                .graph
+                .delta(&mut tracker.intersection_graph),
+            graph_indices: graph_indices.delta(&mut tracker.graph_indices, graph_index_changes),
+            body_solver_color_masks: tracker
+                .body_solver_color_masks
+                .diff_serialized(body_solver_color_masks),
+            pair_solver_hints: tracker.pair_solver_hints.diff_serialized(pair_solver_hints),
+            solver_contact_graph: tracker.solver_contact_graph.diff(solver_contact_graph),
+            solver_graph_valid: *solver_graph_valid,
+            solver_graph_epoch: *solver_graph_epoch,
+            solver_graph_mb_epoch: *solver_graph_mb_epoch,
+            force_event_pairs: tracker.force_event_pairs.diff(force_event_pairs),
+            force_event_pos: tracker.force_event_pos.diff_serialized(force_event_pos),
+            force_event_flagged: tracker.force_event_flagged.diff(force_event_flagged),
+            force_list_valid: *force_list_valid,
+            wake_deferred_pairs: tracker.wake_deferred_pairs.diff(wake_deferred_pairs),
+        }
+    }
+
+    /// Brings this narrow-phase from the state of the previous delta snapshot to the state of
+    /// `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken, starting with a keyframe. The
+    /// narrow-phase then serializes to the same bytes as the one the snapshots were taken from,
+    /// and its internal buffers are reset the way deserialization does. The query dispatcher is
+    /// kept.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this narrow-phase isn't in the state of the previous snapshot.
+    pub fn apply_delta(&mut self, delta: &NarrowPhaseDelta) {
+        self.contact_graph.graph.apply_delta(&delta.contact_graph);
+        self.intersection_graph
+            .graph
+            .apply_delta(&delta.intersection_graph);
+        for i in delta.contact_graph.changed_edges() {
+            // Not serialized: a deserialized pair starts without it too.
+            self.contact_graph.graph.edges[i]
+                .weight
+                .solver_clusters_prev
+                .clear();
+        }
+        self.graph_indices
+            .apply_delta(&delta.graph_indices, &mut self.graph_index_changes);
+        delta
+            .body_solver_color_masks
+            .apply(&mut self.body_solver_color_masks);
+        delta.pair_solver_hints.apply(&mut self.pair_solver_hints);
+        apply_value(&mut self.solver_contact_graph, &delta.solver_contact_graph);
+        self.solver_graph_valid = delta.solver_graph_valid;
+        self.solver_graph_epoch = delta.solver_graph_epoch;
+        self.solver_graph_mb_epoch = delta.solver_graph_mb_epoch;
+        apply_value(&mut self.force_event_pairs, &delta.force_event_pairs);
+        delta.force_event_pos.apply(&mut self.force_event_pos);
+        apply_value(&mut self.force_event_flagged, &delta.force_event_flagged);
+        self.force_list_valid = delta.force_list_valid;
+        apply_value(&mut self.wake_deferred_pairs, &delta.wake_deferred_pairs);
+
+        self.update_candidates.clear();
+        self.body_qualify_info.clear();
+        self.awake_body_mask.clear();
+        self.solver_graph_dirty.clear();
+        self.pending_impacts.clear();
+        self.solver_color_todo.clear();
+        self.retired_pairs.clear();
+    }
+}
@@ -0,0 +1,98 @@
+//! Impact events: the contact kinematics of pairs that just started touching, captured
+//! before the solver runs and completed with the impulse it applied once it did.
+
+use super::NarrowPhase;
+use crate::dynamics::{RigidBodyHandle, RigidBodySet, SolveScope};
+use crate::geometry::{ColliderSet, ContactPair, ImpactEvent};
+use crate::pipeline::EventHandler;
+use parry::utils::PoseOpt;
+
+impl NarrowPhase {
+    /// Records the impact of the contact pair at `edge_id`, which just started touching, from
+    /// its deepest contact and the current (pre-solve) velocities of its bodies.
+    pub(super) fn record_impact(
+        &mut self,
+        edge_id: u32,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        parent1: Option<RigidBodyHandle>,
+        parent2: Option<RigidBodyHandle>,
+    ) {
+        let pair = &self.contact_graph.graph.edges[edge_id as usize].weight;
+        let (Some(co1), Some(co2)) = (colliders.get(pair.collider1), colliders.get(pair.collider2))
+        else {
+            return;
+        };
+        let Some((manifold, contact)) = pair.find_deepest_contact() else {
+            return;
+        };
+
+        let point = manifold.subshape_pos1().prepend_to(co1.position()) * contact.local_p1;
+        let velocity = |parent: Option<RigidBodyHandle>| {
+            parent
+                .and_then(|h| bodies.get(h))
+                .map(|rb| rb.velocity_at_point(point))
+                .unwrap_or_default()
+        };
+        let normal = manifold.data.normal;
+
+        let event = ImpactEvent {
+            collider1: pair.collider1,
+            collider2: pair.collider2,
+            point,
+            normal,
+            normal_relative_velocity: (velocity(parent2) - velocity(parent1)).dot(normal),
+            // Filled once the solver ran, by `emit_impact_events`.
+            normal_impulse: 0.0,
+            friction1: co1.friction(),
+            friction2: co2.friction(),
+            restitution1: co1.restitution(),
+            restitution2: co2.restitution(),
+            combined_friction: manifold.data.friction,
+            combined_restitution: manifold.data.restitution,
+        };
+        self.pending_impacts.push((edge_id, event));
+    }
+
+    /// Emits the impact events recorded since the last call, with the normal impulses the
+    /// solver applied in the meantime.
+    ///
+    /// The impacts of pairs outside of `scope` weren't solved yet: they are kept for the solve
+    /// that includes them.
+    pub(crate) fn emit_impact_events(
+        &mut self,
+        scope: SolveScope,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        events: &dyn EventHandler,
+    ) {
+        let mut pending = core::mem::take(&mut self.pending_impacts);
+        pending.retain_mut(|(edge_id, event)| {
+            let Some(edge) = self.contact_graph.graph.edges.get(*edge_id as usize) else {
+                return false;
+            };
+            let pair = &edge.weight;
+            // The pair can't have been removed between the contact update and the solver, but
+            // don't report the impulses of another pair if that ever changes.
+            if pair.collider1 != event.collider1 || pair.collider2 != event.collider2 {
+                return false;
+            }
+            if !scope.contains_any(bodies, pair_parents(colliders, pair)) {
+                return true;
+            }
+
+            event.normal_impulse = pair.total_impulse_magnitude();
+            events.handle_impact_event(bodies, colliders, *event);
+            false
+        });
+        self.pending_impacts = pending;
+    }
+}
+
+/// The parents of the colliders of `pair`.
+pub(super) fn pair_parents(
+    colliders: &ColliderSet,
+    pair: &ContactPair,
+) -> [Option<RigidBodyHandle>; 2] {
+    [pair.collider1, pair.collider2].map(|h| colliders.get(h).and_then(|co| co.parent()))
+}
@@ -3,6 +3,10 @@
 //! solver-facing bookkeeping maintained across steps.
 
 mod contacts;
+mod deferred_wakes;
+#[cfg(feature = "serde-serialize")]
+mod delta;
+mod impacts;
 mod intersections;
 mod pair_management;
 mod pair_update;
@@ -13,8 +17,12 @@ mod solver_graph;
 #[cfg(feature = "dim3")]
 mod test;
 
+#[cfg(feature = "serde-serialize")]
+pub use self::delta::{NarrowPhaseDelta, NarrowPhaseDeltaTracker};
+
 use crate::alloc_prelude::*;
 use crate::data::Coarena;
+use crate::data::change_log::ChangeLog;
 use crate::dynamics::solver::solver_contact_graph::{
     GENERIC_BUCKET, SolverContactGraph, bucket_id,
 };
@@ -332,6 +340,9 @@ pub struct NarrowPhase {
     contact_graph: InteractionGraph<ColliderHandle, ContactPair>,
     intersection_graph: InteractionGraph<ColliderHandle, IntersectionPair>,
     graph_indices: Coarena<ColliderGraphIndices>,
+    /// The `graph_indices` entries modified since the previous delta snapshot.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    graph_index_changes: ChangeLog,
     /// Scratch buffer holding the edge indices of pairs to process during a step, so
     /// the per-step loops don’t have to iterate on the whole interaction graphs.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
@@ -392,6 +403,16 @@ pub struct NarrowPhase {
     /// through every transition, so it does NOT need epoch full rebuilds; `false`
     /// (a degenerate state) triggers the from-scratch scan.
     force_list_valid: bool,
+    /// Scratch: impacts of the pairs that started touching since the last solve, as
+    /// `(edge id, event)`, completed with the solver impulses by
+    /// [`Self::emit_impact_events`].
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pending_impacts: Vec<(u32, crate::geometry::ImpactEvent)>,
+    /// The `(collider1, collider2)` of the touching pairs flagged `ContactPair::wake_deferred`,
+    /// checked after each solve by [`Self::resolve_deferred_wake_ups`]. May contain pairs that
+    /// stopped touching or were removed since: they are dropped by that check.
+    #[cfg_attr(feature = "serde-serialize", serde(default))]
+    wake_deferred_pairs: Vec<(ColliderHandle, ColliderHandle)>,
     /// Scratch: begin-touch pairs deferred for greedy coloring in canonical
     /// `(min, max body id)` order (discovery-order independent: ≈ Δ colors instead of ≈ 2Δ).
     /// Entries are `(edge id, packed body infos)`, see [`pack_color_body_info`].
@@ -428,6 +449,7 @@ impl NarrowPhase {
             contact_graph: InteractionGraph::new(),
             intersection_graph: InteractionGraph::new(),
             graph_indices: Coarena::new(),
+            graph_index_changes: ChangeLog::default(),
             update_candidates: Vec::new(),
             retired_pairs: Vec::new(),
             body_solver_color_masks: Vec::new(),
@@ -443,6 +465,8 @@ impl NarrowPhase {
             force_event_pos: Vec::new(),
             force_event_flagged: Vec::new(),
             force_list_valid: false,
+            pending_impacts: Vec::new(),
+            wake_deferred_pairs: Vec::new(),
             solver_color_todo: Vec::new(),
         }
     }
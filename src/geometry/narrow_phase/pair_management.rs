@@ -43,6 +43,7 @@ impl NarrowPhase {
                 .graph_indices
                 .remove(collider.0, ColliderGraphIndices::invalid())
             {
+                self.graph_index_changes.mark_index(collider.0);
                 let intersection_graph_id = prox_id_remap
                     .get(collider)
                     .copied()
@@ -148,6 +149,7 @@ impl NarrowPhase {
         // We have to manage the fact that one other collider will
         // have its graph index changed because of the node's swap-remove.
         if let Some(replacement) = self.intersection_graph.remove_node(intersection_graph_id) {
+            self.graph_index_changes.mark_index(replacement.0);
             if let Some(replacement) = self.graph_indices.get_mut(replacement.0) {
                 replacement.intersection_graph_index = intersection_graph_id;
             } else {
@@ -187,6 +189,7 @@ impl NarrowPhase {
                 }
             })
         {
+            self.graph_index_changes.mark_index(replacement.0);
             if let Some(replacement) = self.graph_indices.get_mut(replacement.0) {
                 replacement.contact_graph_index = contact_graph_id;
             } else {
@@ -300,8 +303,10 @@ impl NarrowPhase {
                                 let parent = |co: ColliderHandle| {
                                     colliders.get(co).and_then(|c| c.parent.map(|p| p.handle))
                                 };
-                                let pair = &self.contact_graph.graph.edges[edge_id].weight;
+                                let pair = &mut self.contact_graph.graph.edges[edge_id].weight;
                                 let (co1, co2) = (pair.collider1, pair.collider2);
+                                // Both sides were woken up above: nothing defers the link anymore.
+                                pair.wake_deferred = false;
                                 islands.persistent.unlink_contact(edge_id as u32);
                                 if touching {
                                     islands.persistent.link_contact(
@@ -575,6 +580,8 @@ impl NarrowPhase {
         {
             // These colliders have no parents - continue.
 
+            self.graph_index_changes.mark_index(pair.collider1.0);
+            self.graph_index_changes.mark_index(pair.collider2.0);
             let (gid1, gid2) = self.graph_indices.ensure_pair_exists(
                 pair.collider1.0,
                 pair.collider2.0,
@@ -118,7 +118,11 @@ pub(super) fn process_pair(
                 | ColliderChanges::LOCAL_MASS_PROPERTIES;
             let hooks_involved = !(co1.flags.active_hooks | co2.flags.active_hooks).is_empty();
 
-            if ((co1.changes | co2.changes) & !recycle_safe).is_empty() && !hooks_involved {
+            // World-space anchors of a sleeping side go stale as soon as it wakes up.
+            if ((co1.changes | co2.changes) & !recycle_safe).is_empty()
+                && !hooks_involved
+                && !state.sleeping_wall
+            {
                 let pos12 = co1.pos.inv_mul(&co2.pos);
                 // Conservative bound on how far any contact point moved in
                 // the pair's local space since the last full update (chord
@@ -341,6 +345,42 @@ pub(super) fn process_pair(
             co1.material.restitution_combine_rule,
             co2.material.restitution_combine_rule,
         );
+        let rolling_friction = CoefficientCombineRule::combine(
+            co1.material.rolling_friction,
+            co2.material.rolling_friction,
+            co1.material.friction_combine_rule,
+            co2.material.friction_combine_rule,
+        );
+        let spinning_friction = CoefficientCombineRule::combine(
+            co1.material.spinning_friction,
+            co2.material.spinning_friction,
+            co1.material.friction_combine_rule,
+            co2.material.friction_combine_rule,
+        );
+        // Anisotropic friction: the first anisotropic collider's direction, in world-space.
+        #[cfg(feature = "dim3")]
+        let friction_dir = co1
+            .material
+            .anisotropic_friction
+            .map(|aniso| co1.pos.rotation * aniso.local_dir)
+            .or_else(|| {
+                co2.material
+                    .anisotropic_friction
+                    .map(|aniso| co2.pos.rotation * aniso.local_dir)
+            });
+        #[cfg(feature = "dim3")]
+        let secondary_friction = CoefficientCombineRule::combine(
+            co1.material.secondary_friction(),
+            co2.material.secondary_friction(),
+            co1.material.friction_combine_rule,
+            co2.material.friction_combine_rule,
+        );
+
+        // A sleeping body this pair leaves asleep (see `deferred_wakes`) has no solver body: the
+        // solver sees it as a world-attached wall, so its anchors must stay in world-space.
+        let sleeping_wall = super::deferred_wakes::defers_wake_up(bodies, rb_handle1, rb_handle2);
+        let sleeping_wall1 = sleeping_wall && rb1.is_some_and(|rb| rb.is_sleeping());
+        let sleeping_wall2 = sleeping_wall && rb2.is_some_and(|rb| rb.is_sleeping());
 
         let zero = RigidBodyDominance(0); // The value doesn't matter, it will be MAX because of the effective groups.
         let dominance1 = rb1.map(|rb| rb.dominance).unwrap_or(zero);
@@ -378,9 +418,14 @@ pub(super) fn process_pair(
                 manifold.data.solver_flags = solver_flags;
                 manifold.data.friction = friction;
                 manifold.data.restitution = restitution;
+                manifold.data.rolling_friction = rolling_friction;
+                manifold.data.spinning_friction = spinning_friction;
                 manifold.data.relative_dominance =
                     dominance1.effective_group(&rb_type1) - dominance2.effective_group(&rb_type2);
                 manifold.data.normal = world_pos1.rotation * manifold.local_n1;
+                manifold
+                    .data
+                    .set_anisotropic_friction(friction_dir, secondary_friction);
             }
         } else if !pair.solver_clusters.is_empty() {
             // Clustering stopped applying to this pair: carry the warm-start
@@ -409,6 +454,8 @@ pub(super) fn process_pair(
             manifold.data.solver_flags = solver_flags;
             manifold.data.friction = friction;
             manifold.data.restitution = restitution;
+            manifold.data.rolling_friction = rolling_friction;
+            manifold.data.spinning_friction = spinning_friction;
             manifold.data.relative_dominance =
                 dominance1.effective_group(&rb_type1) - dominance2.effective_group(&rb_type2);
             manifold.data.normal = world_pos1.rotation * manifold.local_n1;
@@ -530,6 +577,16 @@ pub(super) fn process_pair(
                 manifold.data.user_data = modifiable_user_data;
             }
 
+            // Project the anisotropic friction direction onto the (possibly hook-modified)
+            // contact plane. A friction coefficient overridden by the hook applies isotropically.
+            #[cfg(feature = "dim3")]
+            {
+                let friction_dir = friction_dir.filter(|_| manifold.data.friction == friction);
+                manifold
+                    .data
+                    .set_anisotropic_friction(friction_dir, secondary_friction);
+            }
+
             // Localize solver contacts: bake skins (and hook-written `dist`) into the anchors, then
             // express each in its body's CoM frame (world-attached/dominance-superior sides keep world
             // anchors, matching the solver's identity pose). Riding rigidly lets recycled steps skip refresh.
@@ -541,8 +598,14 @@ pub(super) fn process_pair(
                         .position
                         .prepend_translation(rb.mprops.local_mprops.local_com)
                 };
-                let com_pose1 = rb1.as_ref().filter(|_| rel_dom <= 0).map(com_pose);
-                let com_pose2 = rb2.as_ref().filter(|_| rel_dom >= 0).map(com_pose);
+                let com_pose1 = rb1
+                    .as_ref()
+                    .filter(|_| rel_dom <= 0 && !sleeping_wall1)
+                    .map(com_pose);
+                let com_pose2 = rb2
+                    .as_ref()
+                    .filter(|_| rel_dom >= 0 && !sleeping_wall2)
+                    .map(com_pose);
                 // Split-borrow: the frozen solver arms are written to the
                 // manifold points while iterating the solver contacts.
                 let manifold_points = &mut manifold.points;
@@ -609,6 +672,7 @@ pub(super) fn process_pair(
                 rot2: co2.pos.rotation,
                 max_extent,
                 max_drift,
+                sleeping_wall: sleeping_wall1 || sleeping_wall2,
             });
         }
     }
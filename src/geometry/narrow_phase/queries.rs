@@ -2,6 +2,7 @@
 //! intersection pairs and their interaction graphs.
 
 use super::NarrowPhase;
+use crate::data::StateHasher;
 use crate::geometry::{
     ColliderHandle, ColliderSet, ContactData, ContactManifoldData, ContactPair, InteractionGraph,
     IntersectionPair, TemporaryInteractionIndex,
@@ -181,7 +182,54 @@ impl NarrowPhase {
         self.contact_graph.interactions()
     }
 
-    /// `(edge_id, parent1, parent2)` for every *touching* contact pair. Used to (re)build
+    /// A hash of the contacts' warm-start state, for determinism checks.
+    ///
+    /// With [`StateHasher`]'s encoding, each contact pair with at least one contact point is
+    /// hashed on its own: the handles of its two colliders then, for each of its
+    /// [`ContactPair::manifolds`] followed by each of its [`ContactPair::solver_clusters`], the
+    /// number of contact points then, for each point, its [`ContactData::warmstart_impulse`],
+    /// the components of its [`ContactData::warmstart_tangent_impulse`] and, in 3D, its
+    /// `warmstart_twist_impulse` and `warmstart_tangent_world`.
+    ///
+    /// The result hashes the number of such pairs followed by the wrapping sum of their
+    /// hashes, which makes it independent of the order the pairs are stored in.
+    pub fn state_hash(&self) -> u64 {
+        let mut num_pairs = 0;
+        let mut sum = 0u64;
+        for pair in self.contact_pairs() {
+            let manifolds = pair.manifolds.iter().chain(&pair.solver_clusters);
+            if manifolds.clone().all(|m| m.points.is_empty()) {
+                continue;
+            }
+
+            let mut hasher = StateHasher::new();
+            hasher.write_index(pair.collider1.0);
+            hasher.write_index(pair.collider2.0);
+            for manifold in manifolds {
+                hasher.write_usize(manifold.points.len());
+                for point in &manifold.points {
+                    let data = &point.data;
+                    hasher.write_real(data.warmstart_impulse);
+                    hasher.write_reals(data.warmstart_tangent_impulse.iter());
+                    #[cfg(feature = "dim3")]
+                    {
+                        hasher.write_real(data.warmstart_twist_impulse);
+                        hasher.write_vector(data.warmstart_tangent_world);
+                    }
+                }
+            }
+            num_pairs += 1;
+            sum = sum.wrapping_add(hasher.finish());
+        }
+
+        let mut hasher = StateHasher::new();
+        hasher.write_usize(num_pairs);
+        hasher.write_u64(sum);
+        hasher.finish()
+    }
+
+    /// `(edge_id, parent1, parent2)` for every *touching* contact pair, except the pairs kept
+    /// from waking up their sleeping side (see `deferred_wakes`). Used to (re)build
     /// the persistent islands from scratch (bootstrap after construction or
     /// deserialization) and by their debug validation.
     pub(crate) fn touching_pairs_with_ids<'a>(
@@ -201,7 +249,7 @@ impl NarrowPhase {
             .enumerate()
             .filter_map(move |(edge_id, edge)| {
                 let pair = &edge.weight;
-                if !pair.has_any_active_contact() {
+                if !pair.has_any_active_contact() || pair.wake_deferred {
                     return None;
                 }
                 let parent = |co: crate::geometry::ColliderHandle| {
@@ -227,7 +275,10 @@ impl NarrowPhase {
             .map(|id| id.contact_graph_index)
             .into_iter()
             .flat_map(move |id| self.contact_graph.graph.edges(id))
-            .filter(|edge| edge.weight().has_any_active_contact())
+            .filter(|edge| {
+                let pair = edge.weight();
+                pair.has_any_active_contact() && !pair.wake_deferred
+            })
             .map(move |edge| {
                 let pair = edge.weight();
                 let other = if pair.collider1 == collider {
@@ -8,7 +8,7 @@ use crate::dynamics::solver::manifold_store::ManifoldStoreParts;
 use crate::dynamics::solver::solver_contact_graph::{
     ContactRef, GENERIC_BUCKET, GraphPos, SolverContactGraph, bucket_id,
 };
-use crate::dynamics::{IslandManager, MultibodyJointSet, RigidBodySet};
+use crate::dynamics::{IslandManager, MultibodyJointSet, RigidBodySet, SolveScope};
 use crate::geometry::{
     Collider, ColliderHandle, ColliderSet, ContactManifold, ContactManifoldData, ContactPair,
     InteractionGraph, PairEventStatus, SolverFlags,
@@ -458,10 +458,13 @@ impl NarrowPhase {
     ///
     /// The narrow-phase maintains the exact set of solver-active pairs with force events
     /// enabled, so scenes without them pay nothing here.
+    ///
+    /// Only the pairs involving a body of `scope` were solved, and are inspected.
     #[cfg(feature = "alloc")]
     pub(crate) fn emit_contact_force_events(
         &mut self,
         dt: Real,
+        scope: SolveScope,
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
         events: &dyn EventHandler,
@@ -470,6 +473,9 @@ impl NarrowPhase {
         for i in 0..self.force_event_pairs.len() {
             let edge = self.force_event_pairs[i] as usize;
             let pair = &mut self.contact_graph.graph.edges[edge].weight;
+            if !scope.contains_any(bodies, super::impacts::pair_parents(colliders, pair)) {
+                continue;
+            }
             let threshold = |h| {
                 colliders
                     .get(h)
@@ -4,6 +4,7 @@ use crate::alloc_prelude::*;
 
 use crate::dynamics::{
     ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodyChanges,
+    SolveScope,
 };
 use crate::geometry::{
     BroadPhaseBvh, BroadPhasePairEvent, ColliderChanges, ColliderHandle, ModifiedColliders,
@@ -112,6 +113,11 @@ impl CollisionPipeline {
             hooks,
             events,
         );
+        // No solver here: the impacts are reported right away, without impulses.
+        narrow_phase.emit_impact_events(SolveScope::All, bodies, colliders, events);
+        // Without impulses, the pairs kept from waking a sleeping body only link once something
+        // else woke it up.
+        narrow_phase.resolve_deferred_wake_ups(islands, bodies, colliders);
         narrow_phase.compute_intersections(
             islands,
             bodies,
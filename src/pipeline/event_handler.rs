@@ -1,9 +1,9 @@
 #[cfg(feature = "alloc")]
-use crate::dynamics::RigidBodySet;
+use crate::dynamics::{JointBreakEvent, RigidBodySet, SleepEvent};
 #[cfg(all(feature = "std", feature = "alloc"))]
 use crate::geometry::ContactForceEvent;
 #[cfg(feature = "alloc")]
-use crate::geometry::{ColliderSet, CollisionEvent, ContactPair};
+use crate::geometry::{ColliderSet, CollisionEvent, ContactPair, ImpactEvent};
 #[cfg(feature = "alloc")]
 use crate::math::Real;
 
@@ -40,6 +40,12 @@ bitflags::bitflags! {
         ///
         /// You'll receive events when contact forces surpass `contact_force_event_threshold`.
         const CONTACT_FORCE_EVENTS = 0b0010;
+
+        /// Enables impact events for this collider.
+        ///
+        /// You'll receive an [`ImpactEvent`](crate::geometry::ImpactEvent) with the impact
+        /// speed and impulse when this collider starts touching another.
+        const IMPACT_EVENTS = 0b0100;
     }
 }
 
@@ -54,6 +60,9 @@ impl Default for ActiveEvents {
 /// Implement this trait to get notified when:
 /// - Two colliders start or stop touching ([`handle_collision_event`](Self::handle_collision_event))
 /// - Contact forces exceed a threshold ([`handle_contact_force_event`](Self::handle_contact_force_event))
+/// - Two colliders hit each other ([`handle_impact_event`](Self::handle_impact_event))
+/// - A breakable joint breaks ([`handle_joint_break_event`](Self::handle_joint_break_event))
+/// - A rigid-body falls asleep or wakes up ([`handle_sleep_event`](Self::handle_sleep_event))
 ///
 /// # Common use cases
 /// - Playing sound effects when objects collide
@@ -138,6 +147,59 @@ pub trait EventHandler: crate::utils::MaybeSync {
         contact_pair: &ContactPair,
         total_force_magnitude: Real,
     );
+
+    /// Called after the velocity solve of the step two colliders started touching.
+    ///
+    /// At least one collider must have [`ActiveEvents::IMPACT_EVENTS`] enabled. The event is
+    /// emitted in addition to the [`CollisionEvent::Started`] event, if any.
+    ///
+    /// # Use cases
+    /// - Scale impact sounds and particle effects with the impact speed or impulse
+    /// - Pick effects from the colliding materials
+    ///
+    /// Does nothing by default.
+    fn handle_impact_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: ImpactEvent,
+    ) {
+    }
+
+    /// Called when an impulse joint exceeds its break force or torque.
+    ///
+    /// Only joints with a finite [`break_force`](crate::dynamics::GenericJoint::break_force)
+    /// or [`break_torque`](crate::dynamics::GenericJoint::break_torque) can break. The event
+    /// is emitted at the end of the step, once the joint was disabled or removed from its set
+    /// (see [`JointBreakEvent::removed`]).
+    ///
+    /// Does nothing by default.
+    fn handle_joint_break_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: JointBreakEvent,
+    ) {
+    }
+
+    /// Called when a rigid-body falls asleep or wakes up.
+    ///
+    /// Only rigid-bodies with [`RigidBodyActiveEvents::SLEEP_EVENTS`](crate::dynamics::RigidBodyActiveEvents::SLEEP_EVENTS)
+    /// enabled emit these events. They are emitted at the end of the step, once the island
+    /// manager decided which bodies sleep.
+    ///
+    /// # Use cases
+    /// - Mirror the sleep state of bodies into another system without polling every body
+    /// - Stop animating or networking sleeping objects
+    ///
+    /// Does nothing by default.
+    fn handle_sleep_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        _event: SleepEvent,
+    ) {
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -188,6 +250,9 @@ impl EventHandler for () {
 pub struct ChannelEventCollector {
     collision_event_sender: std::sync::mpsc::Sender<CollisionEvent>,
     contact_force_event_sender: std::sync::mpsc::Sender<ContactForceEvent>,
+    joint_break_event_sender: Option<std::sync::mpsc::Sender<JointBreakEvent>>,
+    sleep_event_sender: Option<std::sync::mpsc::Sender<SleepEvent>>,
+    impact_event_sender: Option<std::sync::mpsc::Sender<ImpactEvent>>,
 }
 
 #[cfg(feature = "std")]
@@ -200,8 +265,41 @@ impl ChannelEventCollector {
         Self {
             collision_event_sender,
             contact_force_event_sender,
+            joint_break_event_sender: None,
+            sleep_event_sender: None,
+            impact_event_sender: None,
         }
     }
+
+    /// Also collect the [`JointBreakEvent`]s, into the given channel.
+    #[must_use]
+    pub fn with_joint_break_event_sender(
+        mut self,
+        joint_break_event_sender: std::sync::mpsc::Sender<JointBreakEvent>,
+    ) -> Self {
+        self.joint_break_event_sender = Some(joint_break_event_sender);
+        self
+    }
+
+    /// Also collect the [`SleepEvent`]s, into the given channel.
+    #[must_use]
+    pub fn with_sleep_event_sender(
+        mut self,
+        sleep_event_sender: std::sync::mpsc::Sender<SleepEvent>,
+    ) -> Self {
+        self.sleep_event_sender = Some(sleep_event_sender);
+        self
+    }
+
+    /// Also collect the [`ImpactEvent`]s, into the given channel.
+    #[must_use]
+    pub fn with_impact_event_sender(
+        mut self,
+        impact_event_sender: std::sync::mpsc::Sender<ImpactEvent>,
+    ) -> Self {
+        self.impact_event_sender = Some(impact_event_sender);
+        self
+    }
 }
 
 #[cfg(feature = "std")]
@@ -227,4 +325,37 @@ impl EventHandler for ChannelEventCollector {
         let result = ContactForceEvent::from_contact_pair(dt, contact_pair, total_force_magnitude);
         let _ = self.contact_force_event_sender.send(result);
     }
+
+    fn handle_impact_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: ImpactEvent,
+    ) {
+        if let Some(sender) = &self.impact_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_joint_break_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: JointBreakEvent,
+    ) {
+        if let Some(sender) = &self.joint_break_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_sleep_event(
+        &self,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        event: SleepEvent,
+    ) {
+        if let Some(sender) = &self.sleep_event_sender {
+            let _ = sender.send(event);
+        }
+    }
 }
@@ -16,7 +16,13 @@ pub use physics_pipeline::{PhysicsPipeline, Quarantine};
 #[cfg(feature = "alloc")]
 pub use physics_world::PhysicsWorld;
 #[cfg(feature = "alloc")]
-pub use query_pipeline::{QueryFilter, QueryFilterFlags, QueryPipeline, QueryPipelineMut};
+pub use query_pipeline::{
+    QueryFilter, QueryFilterFlags, QueryPipeline, QueryPipelineMut, ShapeContact,
+};
+#[cfg(feature = "alloc")]
+pub use replay::{Replay, ReplayError, ReplayFrame, ReplayInput, ReplayRecorder, Replayer};
+#[cfg(feature = "serde-serialize")]
+pub use world_delta::{PhysicsWorldDelta, PhysicsWorldDeltaTracker};
 
 #[cfg(all(feature = "debug-render", feature = "alloc"))]
 pub use self::debug_render_pipeline::{
@@ -36,7 +42,11 @@ mod physics_world;
 #[cfg(feature = "alloc")]
 mod query_pipeline;
 #[cfg(feature = "alloc")]
+mod replay;
+#[cfg(feature = "alloc")]
 mod user_changes;
+#[cfg(feature = "serde-serialize")]
+mod world_delta;
 
 #[cfg(all(feature = "debug-render", feature = "alloc"))]
 mod debug_render_pipeline;
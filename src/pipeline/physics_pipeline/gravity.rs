@@ -0,0 +1,59 @@
+//! Wake-ups of the sleeping bodies whose gravity direction changed.
+
+use crate::alloc_prelude::*;
+
+use crate::dynamics::{
+    IntegrationParameters, IslandManager, RigidBodySet, body_gravity, gravity_direction_changed,
+};
+use crate::math::Vector;
+
+use super::PhysicsPipeline;
+
+impl PhysicsPipeline {
+    /// Wakes up the sleeping dynamic bodies whose gravity direction turned by more than
+    /// [`IntegrationParameters::gravity_wake_angle`] since they last moved.
+    ///
+    /// Without a gravity provider, the gravity of a sleeping body only changes with the world
+    /// gravity or with its override (whose setter wakes the body up on request), so the
+    /// sleeping bodies are only checked when the world gravity changed.
+    pub(super) fn wake_up_on_gravity_change(
+        &mut self,
+        gravity: Vector,
+        integration_parameters: &IntegrationParameters,
+        islands: &mut IslandManager,
+        bodies: &mut RigidBodySet,
+    ) {
+        if self.gravity_provider.is_none() && self.last_world_gravity == Some(gravity) {
+            return;
+        }
+        self.last_world_gravity = Some(gravity);
+
+        let provider = self.gravity_provider.as_deref();
+        let max_angle = integration_parameters.gravity_wake_angle;
+        let mut to_wake_up = vec![];
+        for handle in islands.sleeping_bodies() {
+            let rb = bodies.index_mut_internal(handle);
+            if !rb.is_dynamic() {
+                continue;
+            }
+
+            let new_gravity = body_gravity(provider, handle, rb, gravity);
+            match rb.forces.applied_gravity {
+                Some(old_gravity) => {
+                    if gravity_direction_changed(old_gravity, new_gravity, max_angle) {
+                        to_wake_up.push(handle);
+                    }
+                }
+                // Inserted asleep: this is the gravity it rests under.
+                None => rb.forces.applied_gravity = Some(new_gravity),
+            }
+        }
+
+        for handle in to_wake_up {
+            // Waking up one body wakes up its whole island.
+            if bodies.get(handle).is_some_and(|rb| rb.is_sleeping()) {
+                islands.wake_up(bodies, handle, true);
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Joint reaction wrenches, and the breaking of the joints exceeding their thresholds.
+
+use crate::dynamics::{
+    ImpulseJoint, ImpulseJointHandle, ImpulseJointSet, JointBreakAction, JointBreakEvent,
+    RigidBodySet,
+};
+use crate::geometry::ColliderSet;
+use crate::math::{AngVector, DIM, Real, SPATIAL_DIM, Vector};
+use crate::pipeline::EventHandler;
+use crate::utils::CrossProduct;
+
+use super::PhysicsPipeline;
+
+/// A joint that exceeded its break thresholds, waiting for the end of the step.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct BrokenJoint {
+    handle: ImpulseJointHandle,
+    force: Vector,
+    torque: AngVector,
+    dt: Real,
+}
+
+impl PhysicsPipeline {
+    /// Converts the wrenches written back by the last solve into the solved joints' reaction
+    /// forces, and records the joints exceeding their break thresholds. `scoped` tells whether
+    /// the last solve only solved the scoped joint selection.
+    ///
+    /// The broken joints are only disabled or removed by [`Self::break_joints`], at the end
+    /// of the step: the island bookkeeping of a removed joint must not change mid-step.
+    pub(super) fn update_joint_reactions(
+        &mut self,
+        dt: Real,
+        scoped: bool,
+        bodies: &RigidBodySet,
+        impulse_joints: &mut ImpulseJointSet,
+    ) {
+        let joints = impulse_joints.joints_mut();
+        let joint_indices = if scoped {
+            &self.scoped_joint_indices
+        } else {
+            &self.joint_constraint_indices
+        };
+
+        for &joint_id in joint_indices {
+            let joint = &mut joints[joint_id].weight;
+            // The writeback only stores the last substep's impulses: scale them by that
+            // substep's length, which depends on the joint's solve group.
+            let substep_dt = joint
+                .solver_body_ids
+                .iter()
+                .filter(|id| **id != u32::MAX)
+                .find_map(|id| self.staged_solver.substep_dt(*id))
+                .unwrap_or(dt);
+            let inv_dt = crate::utils::inv(substep_dt);
+            let (impulse, angular_impulse) = reaction_impulses(joint, bodies);
+            joint.solver_wrench = Default::default();
+            joint.reaction_force = impulse * inv_dt;
+            joint.reaction_torque = angular_impulse * inv_dt;
+            joint.reaction_dt = substep_dt;
+
+            if joint.data.is_breakable()
+                && (joint.reaction_force.length() > joint.data.break_force
+                    || ang_magnitude(joint.reaction_torque) > joint.data.break_torque)
+                && !self.broken_joints.iter().any(|b| b.handle == joint.handle)
+            {
+                self.broken_joints.push(BrokenJoint {
+                    handle: joint.handle,
+                    force: joint.reaction_force,
+                    torque: joint.reaction_torque,
+                    dt,
+                });
+            }
+        }
+    }
+
+    /// Disables or removes the joints recorded as broken during this step, and emits their
+    /// [`JointBreakEvent`]s.
+    pub(super) fn break_joints(
+        &mut self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        impulse_joints: &mut ImpulseJointSet,
+        events: &dyn EventHandler,
+    ) {
+        for broken in self.broken_joints.drain(..) {
+            let Some(joint) = impulse_joints.get(broken.handle) else {
+                continue;
+            };
+            let (body1, body2) = (joint.body1, joint.body2);
+
+            let removed = match joint.data.break_action {
+                JointBreakAction::Disable => {
+                    if let Some(joint) = impulse_joints.get_mut(broken.handle, true) {
+                        joint.data.set_enabled(false);
+                    }
+                    false
+                }
+                JointBreakAction::Remove => {
+                    impulse_joints.remove(broken.handle, true);
+                    true
+                }
+            };
+
+            let event = JointBreakEvent {
+                joint: broken.handle,
+                body1,
+                body2,
+                force: broken.force,
+                torque: broken.torque,
+                linear_impulse: broken.force * broken.dt,
+                angular_impulse: broken.torque * broken.dt,
+                removed,
+            };
+            events.handle_joint_break_event(bodies, colliders, event);
+        }
+    }
+}
+
+/// The impulse and angular impulse (at the second body's anchor) applied by `joint` to its
+/// second body during the last substep.
+fn reaction_impulses(joint: &ImpulseJoint, bodies: &RigidBodySet) -> (Vector, AngVector) {
+    let wrench = &joint.solver_wrench;
+    let rb1 = &bodies[joint.body1];
+    let rb2 = &bodies[joint.body2];
+    let anchor2 = (*rb2.position() * joint.data.local_frame2).translation;
+
+    if wrench.incomplete {
+        // Rows involving a multibody link: rebuild the wrench from the per-axis impulses,
+        // along the first body's joint frame. This ignores the (small) coupling introduced
+        // by the rows orthogonalization.
+        let frame1 = *rb1.position() * joint.data.local_frame1;
+        let mut axis_impulses = joint.impulses;
+        for i in 0..SPATIAL_DIM {
+            axis_impulses[i] += joint.data.limits[i].impulse + joint.data.motors[i].impulse;
+        }
+        let linear =
+            frame1.rotation * Vector::from_array(core::array::from_fn(|k| axis_impulses[k]));
+        #[cfg(feature = "dim2")]
+        let angular = axis_impulses[DIM];
+        #[cfg(feature = "dim3")]
+        let angular =
+            frame1.rotation * Vector::from_array(core::array::from_fn(|k| axis_impulses[DIM + k]));
+        // These were applied to the first body.
+        return (-linear, -angular);
+    }
+
+    // Read the wrench from the side of a body that was part of the solve: the angular
+    // Jacobians of a world-attached side aren't expressed at its center of mass.
+    if joint.solver_body_ids[1] != u32::MAX {
+        let impulse = -wrench.linear;
+        let angular = -wrench.angular2 + (rb2.center_of_mass() - anchor2).gcross(impulse);
+        (impulse, angular)
+    } else {
+        let impulse1 = wrench.linear;
+        let angular1 = wrench.angular1 + (rb1.center_of_mass() - anchor2).gcross(impulse1);
+        (-impulse1, -angular1)
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn ang_magnitude(v: AngVector) -> Real {
+    v.abs()
+}
+
+#[cfg(feature = "dim3")]
+fn ang_magnitude(v: AngVector) -> Real {
+    v.length()
+}
@@ -4,8 +4,8 @@ use crate::alloc_prelude::*;
 
 use crate::counters::Counters;
 use crate::dynamics::{
-    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
-    RigidBodySet,
+    CCDSolver, ForceFieldSet, GravityProvider, ImpulseJointSet, IntegrationParameters,
+    IslandManager, MultibodyJointSet, RigidBodySet,
 };
 use crate::geometry::{
     BroadPhaseBvh, BroadPhasePairEvent, ColliderHandle, ColliderSet, ContactManifoldIndex,
@@ -14,6 +14,8 @@ use crate::geometry::{
 use crate::math::Vector;
 use crate::pipeline::{EventHandler, PhysicsHooks};
 
+mod gravity;
+mod joint_reactions;
 mod quarantine;
 pub use quarantine::Quarantine;
 mod solve;
@@ -31,9 +33,10 @@ mod test_staged;
 /// resolving contacts so objects don't overlap, and updating positions and velocities.
 ///
 /// ## Performance note
-/// This structure only contains temporary working memory (scratch buffers). You can create
-/// a new one anytime, but it's more efficient to reuse the same instance across frames
-/// since Rapier can reuse allocated memory.
+/// Apart from its [`force_fields`](Self::force_fields) and
+/// [`gravity_provider`](Self::gravity_provider), this structure only contains temporary working
+/// memory (scratch buffers). You can create a new one anytime, but it's more efficient
+/// to reuse the same instance across frames since Rapier can reuse allocated memory.
 ///
 /// ## How it works (simplified)
 /// Rapier uses a time-stepping approach where each step involves:
@@ -41,16 +44,29 @@ mod test_staged;
 /// 2. **Constraint solving**: Calculate forces to prevent overlaps and enforce joint constraints
 /// 3. **Integration**: Update object positions and velocities based on forces and gravity
 /// 4. **Position correction**: Fix any remaining overlaps that might have occurred
-// NOTE: this contains only workspace data, so there is no point in making this serializable.
+// NOTE: this contains only workspace data, and the (type-erased) force fields and gravity
+//       provider, so there is no point in making this serializable.
 pub struct PhysicsPipeline {
     /// Counters used for benchmarking only.
     pub counters: Counters,
+    /// The force fields applied to the awake dynamic bodies at each solver substep.
+    pub force_fields: ForceFieldSet,
+    /// Computes the gravity of the dynamic bodies without a gravity override, instead of using
+    /// the world gravity given to [`Self::step`].
+    pub gravity_provider: Option<Box<dyn GravityProvider>>,
+    /// The world gravity of the last gravity-change check of the sleeping bodies.
+    last_world_gravity: Option<Vector>,
     joint_constraint_indices: Vec<ContactManifoldIndex>,
     /// Whether [`Self::joint_constraint_indices`] has been filled by this pipeline yet.
     /// The joint set memoizes its selection against the buffer the caller keeps, so a
     /// pipeline that just came into existence must invalidate that memo before its first
     /// selection — otherwise it reuses a buffer it never filled.
     joint_selection_primed: bool,
+    /// The part of [`Self::joint_constraint_indices`] solved by the last scoped solve.
+    scoped_joint_indices: Vec<ContactManifoldIndex>,
+    /// Scratch: the sorted bodies of the islands substepped through their impacts by
+    /// [`CcdMode::ImpactSubstepping`](crate::dynamics::CcdMode::ImpactSubstepping).
+    impact_islands: Vec<crate::dynamics::RigidBodyHandle>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
     /// Colliders moved by the last `advance_to_final_positions` with their fresh broad-phase
     /// AABBs, fed to the broad-phase refresh without the user-modification tracking. AABBs are
@@ -58,6 +74,9 @@ pub struct PhysicsPipeline {
     end_step_collider_aabbs: Vec<(ColliderHandle, crate::geometry::Aabb)>,
     /// Non-finite state detected and neutralized during the last step.
     quarantine: Quarantine,
+    /// Joints that exceeded their break thresholds during this step, disabled or removed at
+    /// its end.
+    broken_joints: Vec<joint_reactions::BrokenJoint>,
     /// Scratch buffer holding the active body handles (parallel body update).
     #[cfg(feature = "parallel")]
     active_body_handles: Vec<crate::dynamics::RigidBodyHandle>,
@@ -105,6 +124,9 @@ impl PhysicsPipeline {
     pub fn new() -> PhysicsPipeline {
         PhysicsPipeline {
             counters: Counters::new(true),
+            force_fields: ForceFieldSet::new(),
+            gravity_provider: None,
+            last_world_gravity: None,
             #[cfg(feature = "parallel")]
             active_body_handles: vec![],
             sleep_observations: Vec::new(),
@@ -116,12 +138,36 @@ impl PhysicsPipeline {
             thread_pool: None,
             joint_constraint_indices: vec![],
             joint_selection_primed: false,
+            scoped_joint_indices: vec![],
+            impact_islands: vec![],
             broad_phase_events: vec![],
             end_step_collider_aabbs: vec![],
             quarantine: Quarantine::default(),
+            broken_joints: vec![],
         }
     }
 
+    /// A new pipeline with copies of the parts of this one that affect the simulation: its
+    /// force fields, its gravity provider, and the world gravity the sleeping bodies were last
+    /// checked against.
+    ///
+    /// The force fields are only copied if all of them can be, so that their handles stay
+    /// valid; the gravity provider only if it can be (see [`ForceField::clone_dyn`] and
+    /// [`GravityProvider::clone_dyn`]).
+    ///
+    /// [`ForceField::clone_dyn`]: crate::dynamics::ForceField::clone_dyn
+    /// [`GravityProvider::clone_dyn`]: crate::dynamics::GravityProvider::clone_dyn
+    pub(crate) fn clone_simulation_state(&self) -> PhysicsPipeline {
+        let mut pipeline = PhysicsPipeline::new();
+        pipeline.force_fields = self.force_fields.try_clone().unwrap_or_default();
+        pipeline.gravity_provider = self
+            .gravity_provider
+            .as_ref()
+            .and_then(|provider| provider.clone_dyn());
+        pipeline.last_world_gravity = self.last_world_gravity;
+        pipeline
+    }
+
     /// Completes the BVH optimization pass deferred by the last broad-phase update (if
     /// any) and puts the optimized tree back into the broad-phase. Must be called before
     /// anything uses the broad-phase tree again.
@@ -5,6 +5,7 @@ use crate::alloc_prelude::*;
 
 use crate::dynamics::{
     ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodySet,
+    SolveScope, body_gravity,
 };
 use crate::geometry::{BroadPhaseBvh, ColliderHandle, ColliderSet, NarrowPhase};
 use crate::math::{Real, Vector};
@@ -166,6 +167,7 @@ impl PhysicsPipeline {
         colliders: &mut ColliderSet,
         impulse_joints: &mut ImpulseJointSet,
         multibody_joints: &mut MultibodyJointSet,
+        scope: SolveScope,
         events: &dyn EventHandler,
     ) {
         // Persistent islands, two tiers: a bounded local dual search settles each removal (proves
@@ -185,7 +187,8 @@ impl PhysicsPipeline {
 
         // Single fused traversal of the active bodies: sleep-energy/candidacy update (must
         // run after the narrow-phase wake-ups, before the sleep traversals below) +
-        // effective external forces. Pass cost is dominated by body cache lines, not math.
+        // effective external forces (gravity providers included). Pass cost is dominated by
+        // body cache lines, not math.
         self.counters.stages.update_time.resume();
         // OR-reduction over the active bodies: does any awake body request
         // extra substeps? Gates the substep-group partition below.
@@ -237,10 +240,15 @@ impl PhysicsPipeline {
             let dt = integration_parameters.dt;
             let length_unit = integration_parameters.length_unit;
             let observations = &mut self.sleep_observations;
+            let gravity_provider = self.gravity_provider.as_deref();
             for handle in islands.active_bodies() {
+                if !scope.contains(handle) {
+                    continue;
+                }
                 let rb = bodies.index_mut_internal(handle);
                 IslandManager::update_body_energy(rb, dt, length_unit);
                 let effective_mass = rb.mprops.effective_mass();
+                let gravity = body_gravity(gravity_provider, handle, rb, gravity);
                 rb.forces
                     .compute_effective_force_and_torque(gravity, effective_mass);
                 any_extra_iterations |= rb.additional_solver_iterations() > 0;
@@ -255,8 +263,13 @@ impl PhysicsPipeline {
             let length_unit = integration_parameters.length_unit;
             self.active_body_handles.clear();
             self.active_body_handles.extend(islands.active_bodies());
+            // Record the changes for the delta snapshots here, the parallel loop can't.
+            for handle in &self.active_body_handles {
+                bodies.mark_changed(*handle);
+            }
             let bodies_ptr = core::sync::atomic::AtomicPtr::new(bodies as *mut RigidBodySet);
             let persistent = &islands.persistent;
+            let gravity_provider = self.gravity_provider.as_deref();
             let chunk_results: Vec<BodyUpdateChunkResult> = self
                 .active_body_handles
                 .par_chunks(256)
@@ -268,9 +281,13 @@ impl PhysicsPipeline {
                     let mut chunk_bid = None;
                     let mut observations = Vec::new();
                     for handle in chunk {
-                        let rb = bodies.index_mut_internal(*handle);
+                        if !scope.contains(*handle) {
+                            continue;
+                        }
+                        let rb = bodies.index_mut_internal_unrecorded(*handle);
                         IslandManager::update_body_energy(rb, dt, length_unit);
                         let effective_mass = rb.mprops.effective_mass();
+                        let gravity = body_gravity(gravity_provider, *handle, rb, gravity);
                         rb.forces
                             .compute_effective_force_and_torque(gravity, effective_mass);
                         any_extra |= rb.additional_solver_iterations() > 0;
@@ -344,6 +361,21 @@ impl PhysicsPipeline {
             bodies,
             &mut self.joint_constraint_indices,
         );
+        // A scoped solve leaves out the joints between bodies it doesn't move. The selection is
+        // filtered into its own buffer: the joint set memoizes it against the unfiltered one.
+        self.scoped_joint_indices.clear();
+        if !scope.is_all() {
+            let joints = impulse_joints.joints_mut();
+            self.scoped_joint_indices.extend(
+                self.joint_constraint_indices
+                    .iter()
+                    .filter(|id| {
+                        let joint = &joints[**id].weight;
+                        scope.contains_any(bodies, [Some(joint.body1), Some(joint.body2)])
+                    })
+                    .copied(),
+            );
+        }
         self.counters
             .stages
             .island_constraints_collection_time
@@ -371,31 +403,48 @@ impl PhysicsPipeline {
             let num_threads = 1;
 
             let joint_assembly_epoch = impulse_joints.assembly_epoch;
+            let joint_indices = if scope.is_all() {
+                &self.joint_constraint_indices
+            } else {
+                &self.scoped_joint_indices
+            };
             self.staged_solver.init_and_solve(
                 num_threads,
                 island_id,
+                scope,
                 &mut self.counters,
                 integration_parameters,
                 islands,
                 bodies,
+                colliders,
+                &self.force_fields,
                 narrow_phase.solver_graph(),
                 &manifold_store,
                 impulse_joints.joints_mut(),
-                &self.joint_constraint_indices,
+                joint_indices,
                 joint_assembly_epoch,
                 multibody_joints,
                 unsafe { contact_color_masks.as_slice() },
             );
+            self.update_joint_reactions(
+                integration_parameters.dt,
+                !scope.is_all(),
+                bodies,
+                impulse_joints,
+            );
         }
 
         // Generate contact force events if needed, and update each pair's
         // above-threshold status (the source of `ContactForceEvent::started`).
         narrow_phase.emit_contact_force_events(
             integration_parameters.dt,
+            scope,
             bodies,
             colliders,
             events,
         );
+        // The impact events of the pairs that started touching before this solve.
+        narrow_phase.emit_impact_events(scope, bodies, colliders, events);
 
         self.counters.stages.solver_time.pause();
     }
@@ -4,8 +4,8 @@
 use crate::alloc_prelude::*;
 
 use crate::dynamics::{
-    CCDSolver, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
-    RigidBodyChanges, RigidBodySet, RigidBodyType,
+    CCDSolver, CcdMode, ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet,
+    RigidBodyChanges, RigidBodySet, RigidBodyType, SolveScope,
 };
 #[cfg(feature = "parallel")]
 use crate::geometry::ColliderHandle;
@@ -155,6 +155,13 @@ impl PhysicsPipeline {
 
             self.active_body_handles.clear();
             self.active_body_handles.extend(islands.active_bodies());
+            // Record the changes for the delta snapshots here, the parallel loop can't.
+            for handle in &self.active_body_handles {
+                bodies.mark_changed(*handle);
+                for co_handle in bodies[*handle].colliders.0.iter() {
+                    colliders.mark_changed(*co_handle);
+                }
+            }
             let bodies_ptr = core::sync::atomic::AtomicPtr::new(bodies as *mut RigidBodySet);
             let colliders_ptr = core::sync::atomic::AtomicPtr::new(colliders as *mut ColliderSet);
 
@@ -179,7 +186,7 @@ impl PhysicsPipeline {
                     let mut quarantined_colliders = Vec::new();
 
                     for handle in chunk {
-                        let rb = bodies.index_mut_internal(*handle);
+                        let rb = bodies.index_mut_internal_unrecorded(*handle);
                         // Non-finite pose containment; see the serial branch.
                         if !rb.pos.next_position.is_finite() {
                             quarantined_bodies.push((*handle, rb.pos.position));
@@ -188,7 +195,7 @@ impl PhysicsPipeline {
                         rb.pos.position = rb.pos.next_position;
 
                         for co_handle in rb.colliders.0.iter() {
-                            let co = colliders.index_mut_internal(*co_handle);
+                            let co = colliders.index_mut_internal_unrecorded(*co_handle);
                             let new_pos =
                                 rb.pos.position * co.parent.as_ref().unwrap().pos_wrt_parent;
                             co.pos = crate::geometry::ColliderPosition(new_pos);
@@ -244,6 +251,7 @@ impl PhysicsPipeline {
         integration_parameters: &IntegrationParameters,
         islands: &IslandManager,
         bodies: &mut RigidBodySet,
+        scope: SolveScope,
     ) {
         // Update kinematic bodies velocities.
         // TODO: what is the best place for this? It should at least be
@@ -251,6 +259,9 @@ impl PhysicsPipeline {
         // there to determine if this kinematic body should wake-up dynamic
         // bodies it is touching.
         for handle in islands.active_bodies() {
+            if !scope.contains(handle) {
+                continue;
+            }
             // TODO PERF: only iterate on kinematic position-based bodies
             let rb = bodies.index_mut_internal(handle);
 
@@ -323,6 +334,10 @@ impl PhysicsPipeline {
             &mut modified_colliders,
         );
 
+        // Sleeping bodies whose gravity turned wake up like the ones woken up by the user: their
+        // island joins this step's simulation.
+        self.wake_up_on_gravity_change(gravity, integration_parameters, islands, bodies);
+
         // Disabled colliders are treated as if they were removed.
         // NOTE: this must be called here, after handle_user_changes_to_rigid_bodies to take into
         //       account colliders disabled because of their parent rigid-body.
@@ -402,14 +417,35 @@ impl PhysicsPipeline {
         let mut remaining_time = integration_parameters.dt;
         let mut integration_parameters = *integration_parameters;
 
-        let (ccd_is_enabled, mut remaining_substeps) =
-            if integration_parameters.max_ccd_substeps == 0 {
-                (false, 1)
+        let (ccd_is_enabled, mut remaining_substeps) = match (
+            integration_parameters.max_ccd_substeps,
+            integration_parameters.ccd_mode,
+        ) {
+            (0, _) => (false, 1),
+            // One substep up to each impact, plus the one after the last impact.
+            (max_impacts, CcdMode::ImpactSubstepping) => (true, max_impacts.saturating_add(1)),
+            (max_substeps, CcdMode::MotionClamping) => (true, max_substeps),
+        };
+        // Set when a substep stopped at an impact of `CcdMode::ImpactSubstepping`.
+        let mut impact_reached = false;
+        // `CcdMode::ImpactSubstepping` only substeps the islands of the bodies about to hit
+        // something, once the rest of the world was solved over the whole step. Multibodies
+        // aren't scoped by the solver: their worlds are split as a whole instead.
+        let substep_impact_islands = ccd_is_enabled
+            && integration_parameters.ccd_mode == CcdMode::ImpactSubstepping
+            && multibody_joints.multibodies.is_empty();
+        let mut impact_islands = core::mem::take(&mut self.impact_islands);
+        impact_islands.clear();
+        // Whether the islands of `impact_islands` are being substepped.
+        let mut impact_islands_found = false;
+
+        while remaining_substeps > 0 {
+            let mut scope = if impact_islands_found {
+                SolveScope::Only(&impact_islands)
             } else {
-                (true, integration_parameters.max_ccd_substeps)
+                SolveScope::All
             };
 
-        while remaining_substeps > 0 {
             // If there are more than one CCD substep, we need to split
             // the timestep into multiple intervals. First, estimate the
             // size of the time slice we will integrate for this substep.
@@ -420,11 +456,94 @@ impl PhysicsPipeline {
             //
             // If there is only one or zero CCD substep, there is no need
             // to split the timestep interval. So we can just skip this part.
-            if ccd_is_enabled && remaining_substeps > 1 {
+            if impact_reached {
+                // The solver applies restitution at the end of its substep: resolve the impact
+                // over the shortest substep, so the bodies then move with their new velocities
+                // for the rest of the step.
+                impact_reached = false;
+                integration_parameters.dt = integration_parameters.min_ccd_dt.min(remaining_time);
+                remaining_time -= integration_parameters.dt;
+
+                if remaining_time <= integration_parameters.min_ccd_dt {
+                    integration_parameters.dt += remaining_time;
+                    remaining_time = 0.0;
+                    remaining_substeps = 0;
+                }
+            } else if substep_impact_islands && remaining_substeps > 1 {
+                // The impacts are predicted from the current velocities: they must be found
+                // before being solved.
+                let ccd_active =
+                    ccd_solver.update_impact_ccd_active_flags(islands, bodies, remaining_time);
+                self.join_deferred_bvh_optimize(broad_phase);
+                let impacts = if ccd_active {
+                    let fast_bodies: Vec<_> = islands
+                        .active_bodies()
+                        .filter(|h| bodies[*h].ccd.ccd_active && scope.contains(*h))
+                        .collect();
+                    ccd_solver.find_impacts(
+                        remaining_time,
+                        &integration_parameters,
+                        &fast_bodies,
+                        bodies,
+                        colliders,
+                        broad_phase,
+                        narrow_phase,
+                        hooks,
+                    )
+                } else {
+                    vec![]
+                };
+
+                if !impact_islands_found && !impacts.is_empty() {
+                    // Solve everything but the impacting islands over the whole step. Their
+                    // substeps come next, with the rest of the world as walls at its final pose.
+                    for impact in &impacts {
+                        islands.push_persistent_island_bodies(
+                            bodies,
+                            impact.body,
+                            &mut impact_islands,
+                        );
+                        if let Some(target) = impact.target {
+                            islands.push_persistent_island_bodies(
+                                bodies,
+                                target,
+                                &mut impact_islands,
+                            );
+                        }
+                    }
+                    SolveScope::sort(&mut impact_islands);
+                    impact_islands.dedup();
+                    impact_islands_found = true;
+                    scope = SolveScope::AllBut(&impact_islands);
+                    integration_parameters.dt = remaining_time;
+                } else if let Some(toi) = impacts.iter().map(|impact| impact.toi).reduce(Real::min)
+                {
+                    // Stop right at the impact for the next substeps to resolve it.
+                    integration_parameters.dt = toi.max(integration_parameters.min_ccd_dt);
+                    impact_reached = true;
+                    remaining_substeps -= 1;
+                    remaining_time -= integration_parameters.dt;
+
+                    // Avoid substep length that are too small.
+                    if remaining_time <= integration_parameters.min_ccd_dt {
+                        integration_parameters.dt += remaining_time;
+                        remaining_time = 0.0;
+                        remaining_substeps = 0;
+                    }
+                } else {
+                    // No impact, don't do any other substep after this one.
+                    integration_parameters.dt = remaining_time;
+                    remaining_time = 0.0;
+                    remaining_substeps = 0;
+                }
+            } else if ccd_is_enabled && remaining_substeps > 1 {
                 // NOTE: Take forces into account when updating the bodies CCD activation flags
                 //       these forces have not been integrated to the body's velocity yet.
-                let ccd_active =
-                    ccd_solver.update_ccd_active_flags(islands, bodies, remaining_time, true);
+                let ccd_active = if integration_parameters.ccd_mode == CcdMode::ImpactSubstepping {
+                    ccd_solver.update_impact_ccd_active_flags(islands, bodies, remaining_time)
+                } else {
+                    ccd_solver.update_ccd_active_flags(islands, bodies, remaining_time, true)
+                };
                 self.join_deferred_bvh_optimize(broad_phase);
                 let first_impact = if ccd_active {
                     ccd_solver.find_first_impact(
@@ -444,7 +563,11 @@ impl PhysicsPipeline {
                 if let Some(toi) = first_impact {
                     let original_interval = remaining_time / (remaining_substeps as Real);
 
-                    if toi < original_interval {
+                    if integration_parameters.ccd_mode == CcdMode::ImpactSubstepping {
+                        // Stop right at the impact for the next substeps to resolve it.
+                        integration_parameters.dt = toi.max(integration_parameters.min_ccd_dt);
+                        impact_reached = true;
+                    } else if toi < original_interval {
                         integration_parameters.dt = original_interval;
                     } else {
                         integration_parameters.dt =
@@ -474,7 +597,7 @@ impl PhysicsPipeline {
             self.counters.ccd.num_substeps += 1;
 
             self.counters.custom.resume();
-            self.interpolate_kinematic_velocities(&integration_parameters, islands, bodies);
+            self.interpolate_kinematic_velocities(&integration_parameters, islands, bodies, scope);
             self.counters.custom.pause();
             self.build_islands_and_solve_velocity_constraints(
                 gravity,
@@ -485,6 +608,7 @@ impl PhysicsPipeline {
                 colliders,
                 impulse_joints,
                 multibody_joints,
+                scope,
                 events,
             );
 
@@ -574,8 +698,27 @@ impl PhysicsPipeline {
         // NOTE: the world mass-properties of the bodies that moved were refreshed by
         //       `advance_to_final_positions`.
 
-        // Re-insert the modified vector we extracted for the borrow-checker.
+        // Re-insert the vectors we extracted for the borrow-checker.
         colliders.set_modified(modified_colliders);
+        self.impact_islands = impact_islands;
+
+        // Joints that exceeded their break thresholds are only disabled or removed now that
+        // the step is complete, like a user edit made between two steps.
+        if !self.broken_joints.is_empty() {
+            self.break_joints(bodies, colliders, impulse_joints, events);
+        }
+
+        // Touching pairs kept from waking up a sleeping body wake it now if they pushed on it hard
+        // enough, like a user wake-up made between two steps.
+        narrow_phase.resolve_deferred_wake_ups(islands, bodies, colliders);
+
+        // Report the sleep transitions of this step, and the wake-ups caused by user edits since
+        // the previous one.
+        for event in islands.take_sleep_events() {
+            if bodies.contains(event.body()) {
+                events.handle_sleep_event(bodies, colliders, event);
+            }
+        }
 
         self.counters.step_completed();
     }
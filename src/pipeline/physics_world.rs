@@ -1,8 +1,9 @@
 use crate::alloc_prelude::*;
+use crate::data::StateHasher;
 use crate::dynamics::{
-    CCDSolver, GenericJoint, ImpulseJoint, ImpulseJointHandle, ImpulseJointSet,
-    IntegrationParameters, IslandManager, Multibody, MultibodyJointHandle, MultibodyJointSet,
-    MultibodyLink, MultibodyLinkId, RigidBody, RigidBodyHandle, RigidBodySet,
+    CCDSolver, ForceFieldSet, GenericJoint, GravityProvider, ImpulseJoint, ImpulseJointHandle,
+    ImpulseJointSet, IntegrationParameters, IslandManager, Multibody, MultibodyJointHandle,
+    MultibodyJointSet, MultibodyLink, MultibodyLinkId, RigidBody, RigidBodyHandle, RigidBodySet,
 };
 use crate::geometry::{
     BroadPhaseBvh, Collider, ColliderHandle, ColliderSet, ContactPair, DefaultBroadPhase,
@@ -11,6 +12,7 @@ use crate::geometry::{
 use crate::math::{Real, Vector};
 use crate::pipeline::{
     EventHandler, PhysicsHooks, PhysicsPipeline, Quarantine, QueryFilter, QueryPipeline,
+    ShapeContact,
 };
 use parry::bounding_volume::{Aabb, BoundingVolume};
 use parry::partitioning::BvhNode;
@@ -59,7 +61,9 @@ use crate::pipeline::{DebugRenderBackend, DebugRenderPipeline};
 /// ```
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct PhysicsWorld {
-    /// Gravity applied to all dynamic bodies each step.
+    /// Gravity applied to all dynamic bodies each step, unless they have a
+    /// [gravity override](RigidBody::gravity_override) or a
+    /// [gravity provider](Self::set_gravity_provider) is set.
     pub gravity: Vector,
     /// Parameters controlling the simulation (timestep, solver iterations, etc.).
     pub integration_parameters: IntegrationParameters,
@@ -161,6 +165,94 @@ impl PhysicsWorld {
         self.physics_pipeline.quarantine()
     }
 
+    /// A cheap hash of the simulation state, for determinism checks (e.g. comparing the
+    /// peers of a lockstep multiplayer game after each step).
+    ///
+    /// With [`StateHasher`]'s encoding, this hashes, in order, the
+    /// [`RigidBodySet::state_hash`] (poses, velocities and sleeping flags),
+    /// [`NarrowPhase::state_hash`] (contact warm-start impulses),
+    /// [`ImpulseJointSet::state_hash`] and [`MultibodyJointSet::state_hash`] (joint states).
+    /// Parameters (gravity, integration parameters, shapes, materials…) aren't hashed.
+    ///
+    /// This definition stays the same across releases, so that, with the
+    /// `enhanced-determinism` feature, two bit-identical simulations give the same hash on every
+    /// platform.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// let new_world = || {
+    ///     let mut world = PhysicsWorld::default();
+    ///     world.insert(RigidBodyBuilder::dynamic(), ColliderBuilder::ball(0.5));
+    ///     world
+    /// };
+    /// let mut world1 = new_world();
+    /// let mut world2 = new_world();
+    ///
+    /// world1.step();
+    /// world2.step();
+    /// assert_eq!(world1.state_hash(), world2.state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = StateHasher::new();
+        hasher.write_u64(self.bodies.state_hash());
+        hasher.write_u64(self.narrow_phase.state_hash());
+        hasher.write_u64(self.impulse_joints.state_hash());
+        hasher.write_u64(self.multibody_joints.state_hash());
+        hasher.finish()
+    }
+
+    /// The force fields applied to the awake dynamic bodies at each solver substep.
+    ///
+    /// Force fields are not part of a snapshot: they live in the
+    /// [`physics_pipeline`](Self::physics_pipeline).
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut world = PhysicsWorld::default();
+    /// let wind = world
+    ///     .force_fields_mut()
+    ///     .insert(WindField::new(Vector::new(5.0, 0.0, 0.0), 0.5));
+    ///
+    /// // Later: change the wind direction.
+    /// if let Some(wind) = world.force_fields_mut().field_mut::<WindField>(wind) {
+    ///     wind.velocity = Vector::new(0.0, 0.0, 5.0);
+    /// }
+    /// ```
+    pub fn force_fields(&self) -> &ForceFieldSet {
+        &self.physics_pipeline.force_fields
+    }
+
+    /// The force fields applied to the awake dynamic bodies at each solver substep.
+    pub fn force_fields_mut(&mut self) -> &mut ForceFieldSet {
+        &mut self.physics_pipeline.force_fields
+    }
+
+    /// Makes `provider` compute the gravity of the dynamic bodies without a gravity override,
+    /// instead of using [`Self::gravity`] (which the provider still receives).
+    ///
+    /// Like force fields, the gravity provider is not part of a snapshot: it lives in the
+    /// [`physics_pipeline`](Self::physics_pipeline).
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut world = PhysicsWorld::default();
+    /// // A spherical planetoid of radius 20 centered at the origin, with 9.81 m/s² at its surface.
+    /// world.set_gravity_provider(|_: RigidBodyHandle, body: &RigidBody, _: Vector| {
+    ///     -body.center_of_mass().normalize_or_zero() * 9.81
+    /// });
+    /// ```
+    pub fn set_gravity_provider(&mut self, provider: impl GravityProvider + 'static) {
+        self.physics_pipeline.gravity_provider = Some(Box::new(provider));
+    }
+
+    /// Removes the gravity provider, making the bodies use [`Self::gravity`] again.
+    pub fn remove_gravity_provider(&mut self) -> Option<Box<dyn GravityProvider>> {
+        self.physics_pipeline.gravity_provider.take()
+    }
+
     // ── Rigid bodies ────────────────────────────────────────────────────
 
     /// Insert a rigid body with an attached collider, and return both handles.
@@ -245,6 +337,12 @@ impl PhysicsWorld {
         }
     }
 
+    /// Put a body and everything it is connected to (through touching contacts and joints) to
+    /// sleep. See [`IslandManager::sleep_island_of`].
+    pub fn sleep_island_of(&mut self, handle: RigidBodyHandle) {
+        self.islands.sleep_island_of(&mut self.bodies, handle);
+    }
+
     // ── Colliders ───────────────────────────────────────────────────────
 
     /// Insert a collider, optionally attached to a rigid body, and return its handle.
@@ -462,6 +560,22 @@ impl PhysicsWorld {
             .cast_shape(shape_pos, shape_vel, shape, options)
     }
 
+    /// Cast (sweep) a shape through the world and return every collider hit, sorted by time
+    /// of impact.
+    ///
+    /// Shorthand for `world.query_pipeline_with_filter(filter).cast_shape_all(...)`.
+    pub fn cast_shape_all<'a>(
+        &'a self,
+        shape_pos: &Pose,
+        shape_vel: Vector,
+        shape: &dyn Shape,
+        options: ShapeCastOptions,
+        filter: QueryFilter<'a>,
+    ) -> Vec<(ColliderHandle, ShapeCastHit)> {
+        self.query_pipeline_with_filter(filter)
+            .cast_shape_all(shape_pos, shape_vel, shape, options)
+    }
+
     /// Cast a shape with a nonlinear motion and return the first collider hit.
     ///
     /// Shorthand for `world.query_pipeline_with_filter(filter).cast_shape_nonlinear(...)`.
@@ -594,6 +708,32 @@ impl PhysicsWorld {
             })
     }
 
+    /// Iterate over every collider whose shape overlaps the given shape positioned at
+    /// `shape_pos`, with the contact between them.
+    ///
+    /// Each item is `(handle, &collider, contact)`; see [`QueryPipeline::contacts_with_shape`].
+    pub fn contacts_with_shape<'a>(
+        &'a self,
+        shape_pos: Pose,
+        shape: &'a dyn Shape,
+        filter: QueryFilter<'a>,
+    ) -> impl Iterator<Item = (ColliderHandle, &'a Collider, ShapeContact)> + 'a {
+        let bvh = &self.broad_phase.tree;
+        let bodies = &self.bodies;
+        let colliders = &self.colliders;
+        let dispatcher = self.narrow_phase.query_dispatcher();
+        let shape_aabb = shape.compute_aabb(&shape_pos);
+        bvh.leaves(move |node: &BvhNode| node.aabb().intersects(&shape_aabb))
+            .filter_map(move |leaf| {
+                let (co, co_handle) = colliders.get_unknown_gen(leaf)?;
+                if filter.test(bodies, co_handle, co) {
+                    let contact = ShapeContact::compute(dispatcher, &shape_pos, shape, co)?;
+                    return Some((co_handle, co, contact));
+                }
+                None
+            })
+    }
+
     /// Iterate over every collider whose stored AABB intersects the given AABB.
     ///
     /// This is *conservative*: the AABBs used are the ones in the broad-phase BVH,
@@ -1,3 +1,4 @@
+use crate::alloc_prelude::*;
 use crate::dynamics::RigidBodyHandle;
 use crate::geometry::{Aabb, Collider, ColliderHandle, PointProjection, Ray, RayIntersection};
 use crate::geometry::{BroadPhaseBvh, InteractionGroups};
@@ -85,6 +86,44 @@ impl QueryPipelineMut<'_> {
     }
 }
 
+/// A contact between a query shape and a collider, as returned by
+/// [`QueryPipeline::contacts_with_shape`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ShapeContact {
+    /// The deepest point of the query shape inside the collider, in world-space.
+    pub point_on_shape: Vector,
+    /// The deepest point of the collider inside the query shape, in world-space.
+    pub point_on_collider: Vector,
+    /// The contact normal, in world-space, pointing from the query shape toward the collider.
+    ///
+    /// Moving the query shape by `-normal * penetration_depth` separates it from the collider.
+    pub normal: Vector,
+    /// How far the query shape and the collider penetrate each other (zero if they only touch).
+    pub penetration_depth: Real,
+}
+
+impl ShapeContact {
+    /// The contact between `shape` at `shape_pos` and `collider`, if they overlap.
+    pub(crate) fn compute(
+        dispatcher: &dyn QueryDispatcher,
+        shape_pos: &Pose,
+        shape: &dyn Shape,
+        collider: &Collider,
+    ) -> Option<Self> {
+        let pos12 = shape_pos.inv_mul(collider.position());
+        let contact = dispatcher
+            .contact(&pos12, shape, collider.shape(), 0.0)
+            .ok()
+            .flatten()?;
+        Some(Self {
+            point_on_shape: *shape_pos * contact.point1,
+            point_on_collider: *collider.position() * contact.point2,
+            normal: shape_pos.rotation * contact.normal1,
+            penetration_depth: (-contact.dist).max(0.0),
+        })
+    }
+}
+
 impl CompositeShape for QueryPipeline<'_> {
     fn map_part_at(
         &self,
@@ -493,6 +532,86 @@ impl<'a> QueryPipeline<'a> {
             .and_then(|hit| self.id_to_handle(hit))
     }
 
+    /// Sweeps a shape through the world and returns every collider it would hit, sorted by
+    /// time of impact.
+    ///
+    /// Unlike [`cast_shape()`](Self::cast_shape), which stops at the first hit, this reports
+    /// all the colliders along the sweep, as if each of them was alone in the world. Useful
+    /// for melee weapon sweeps or piercing projectiles. Hits with the same time of impact (e.g.
+    /// colliders the shape starts in) are sorted by collider handle.
+    ///
+    /// The returned [`ShapeCastHit`]s follow the same conventions as
+    /// [`cast_shape()`](Self::cast_shape).
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # use rapier3d::parry::{query::ShapeCastOptions, shape::Ball};
+    /// # let mut world = PhysicsWorld::default();
+    /// # for i in 0..3 {
+    /// #     world.insert(
+    /// #         RigidBodyBuilder::fixed().translation(Vector::new(i as Real * 2.0, 0.0, 0.0)),
+    /// #         ColliderBuilder::ball(0.5),
+    /// #     );
+    /// # }
+    /// # world.step();
+    /// // Sweep a sword-sized box along +X.
+    /// let blade = Cuboid::new(Vector::new(0.1, 0.5, 0.1));
+    /// let options = ShapeCastOptions::with_max_time_of_impact(10.0);
+    /// let hits = world.query_pipeline().cast_shape_all(
+    ///     &Pose::translation(-2.0, 0.0, 0.0),
+    ///     Vector::X,
+    ///     &blade,
+    ///     options,
+    /// );
+    /// assert_eq!(hits.len(), 3);
+    /// ```
+    #[profiling::function]
+    pub fn cast_shape_all(
+        &self,
+        shape_pos: &Pose,
+        shape_vel: Vector,
+        shape: &dyn Shape,
+        options: ShapeCastOptions,
+    ) -> Vec<(ColliderHandle, ShapeCastHit)> {
+        // A collider can only be hit if the path of the center of the shape's AABB crosses the
+        // collider's AABB enlarged by the half-extents of the shape's AABB.
+        let shape_aabb = shape
+            .compute_aabb(shape_pos)
+            .loosened(options.target_distance);
+        let half_extents = shape_aabb.half_extents();
+        let path = Ray::new(shape_aabb.center(), shape_vel);
+
+        let mut hits: Vec<_> = self
+            .bvh
+            .leaves(|node: &BvhNode| {
+                let aabb = node.aabb();
+                Aabb::new(aabb.mins - half_extents, aabb.maxs + half_extents)
+                    .intersects_local_ray(&path, options.max_time_of_impact)
+            })
+            .filter_map(|leaf| {
+                let (co, co_handle) = self.colliders.get_unknown_gen(leaf)?;
+                if !self.filter.test(self.bodies, co_handle, co) {
+                    return None;
+                }
+                let pos12 = co.position().inv_mul(shape_pos);
+                let vel12 = co.position().rotation.inverse() * shape_vel;
+                let hit = self
+                    .dispatcher
+                    .cast_shapes(&pos12, vel12, co.shape(), shape, options)
+                    .ok()
+                    .flatten()?;
+                Some((co_handle, hit.transform1_by(co.position())))
+            })
+            .collect();
+        hits.sort_by(|(handle1, hit1), (handle2, hit2)| {
+            hit1.time_of_impact
+                .total_cmp(&hit2.time_of_impact)
+                .then_with(|| handle1.0.cmp(&handle2.0))
+        });
+        hits
+    }
+
     /// Casts a shape with an arbitrary continuous motion and retrieve the first collider it hits.
     ///
     /// In the returned [`ShapeCastHit`], `witness1` and `normal1` refer to the hit collider
@@ -560,6 +679,51 @@ impl<'a> QueryPipeline<'a> {
                 None
             })
     }
+
+    /// Retrieve the contact (points, normal and penetration depth) between the given shape and
+    /// each collider it overlaps.
+    ///
+    /// This tests the same colliders as [`intersect_shape()`](Self::intersect_shape), with the
+    /// same filtering, but also computes how they overlap, e.g., to apply area-of-effect
+    /// damage depending on the penetration, or to push the shape out of the colliders.
+    ///
+    /// # Parameters
+    /// * `shape_pos` - The pose of the shape to test.
+    /// * `shape` - The shape to test.
+    ///
+    /// # Example
+    /// ```
+    /// # use rapier3d::prelude::*;
+    /// # let mut world = PhysicsWorld::default();
+    /// # world.insert(RigidBodyBuilder::fixed(), ColliderBuilder::cuboid(10.0, 0.5, 10.0));
+    /// # world.step();
+    /// let blast = Ball::new(1.0);
+    /// for (handle, _, contact) in world
+    ///     .query_pipeline()
+    ///     .contacts_with_shape(Pose::translation(0.0, 1.0, 0.0), &blast)
+    /// {
+    ///     println!("{handle:?} is {} deep inside the blast", contact.penetration_depth);
+    /// }
+    /// ```
+    #[profiling::function]
+    pub fn contacts_with_shape(
+        &'a self,
+        shape_pos: Pose,
+        shape: &'a dyn Shape,
+    ) -> impl Iterator<Item = (ColliderHandle, &'a Collider, ShapeContact)> + 'a {
+        let shape_aabb = shape.compute_aabb(&shape_pos);
+        self.bvh
+            .leaves(move |node: &BvhNode| node.aabb().intersects(&shape_aabb))
+            .filter_map(move |leaf| {
+                let (co, co_handle) = self.colliders.get_unknown_gen(leaf)?;
+                if self.filter.test(self.bodies, co_handle, co) {
+                    let contact = ShapeContact::compute(self.dispatcher, &shape_pos, shape, co)?;
+                    return Some((co_handle, co, contact));
+                }
+
+                None
+            })
+    }
 }
 
 bitflags::bitflags! {
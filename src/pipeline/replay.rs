@@ -0,0 +1,670 @@
+//! Deterministic replays: record the inputs applied to a [`PhysicsWorld`] step by step, then
+//! re-simulate them from the initial state while checking that every step ends in the same state.
+
+use crate::alloc_prelude::*;
+
+use crate::dynamics::{
+    CCDSolver, GenericJoint, ImpulseJointHandle, MultibodyJointHandle, RigidBody, RigidBodyHandle,
+};
+use crate::geometry::{Collider, ColliderHandle};
+use crate::math::{AngVector, Pose, Vector};
+use crate::pipeline::{EventHandler, PhysicsHooks, PhysicsWorld};
+
+/// A user input applied to a [`PhysicsWorld`] between two steps, as recorded by a
+/// [`ReplayRecorder`].
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+pub enum ReplayInput {
+    /// [`RigidBody::add_force`].
+    AddForce {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The force.
+        force: Vector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::add_force_at_point`].
+    AddForceAtPoint {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The force.
+        force: Vector,
+        /// The world-space point the force is applied at.
+        point: Vector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::add_torque`].
+    AddTorque {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The torque.
+        torque: AngVector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::reset_forces`] and [`RigidBody::reset_torques`].
+    ResetForces {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::apply_impulse`].
+    ApplyImpulse {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The impulse.
+        impulse: Vector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::apply_impulse_at_point`].
+    ApplyImpulseAtPoint {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The impulse.
+        impulse: Vector,
+        /// The world-space point the impulse is applied at.
+        point: Vector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::apply_torque_impulse`].
+    ApplyTorqueImpulse {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The torque impulse.
+        torque_impulse: AngVector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::set_linvel`].
+    SetLinvel {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The new linear velocity.
+        linvel: Vector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::set_angvel`].
+    SetAngvel {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The new angular velocity.
+        angvel: AngVector,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::set_position`].
+    SetPosition {
+        /// The affected rigid-body.
+        body: RigidBodyHandle,
+        /// The new world-space pose.
+        position: Pose,
+        /// Whether the rigid-body is woken up.
+        wake_up: bool,
+    },
+    /// [`RigidBody::set_next_kinematic_position`].
+    SetNextKinematicPosition {
+        /// The affected kinematic rigid-body.
+        body: RigidBodyHandle,
+        /// The pose the rigid-body reaches at the end of the next step.
+        position: Pose,
+    },
+    /// [`PhysicsWorld::insert_body`].
+    InsertBody {
+        /// The handle the rigid-body was given.
+        handle: RigidBodyHandle,
+        /// The inserted rigid-body.
+        body: Box<RigidBody>,
+    },
+    /// [`PhysicsWorld::remove_body`].
+    RemoveBody {
+        /// The removed rigid-body.
+        handle: RigidBodyHandle,
+    },
+    /// [`PhysicsWorld::insert_collider`].
+    InsertCollider {
+        /// The handle the collider was given.
+        handle: ColliderHandle,
+        /// The inserted collider.
+        collider: Box<Collider>,
+        /// The rigid-body the collider is attached to.
+        parent: Option<RigidBodyHandle>,
+    },
+    /// [`PhysicsWorld::remove_collider`].
+    RemoveCollider {
+        /// The removed collider.
+        handle: ColliderHandle,
+    },
+    /// [`PhysicsWorld::insert_impulse_joint`].
+    InsertImpulseJoint {
+        /// The handle the joint was given.
+        handle: ImpulseJointHandle,
+        /// The first rigid-body attached to the joint.
+        body1: RigidBodyHandle,
+        /// The second rigid-body attached to the joint.
+        body2: RigidBodyHandle,
+        /// The joint.
+        joint: Box<GenericJoint>,
+    },
+    /// [`PhysicsWorld::remove_impulse_joint`].
+    RemoveImpulseJoint {
+        /// The removed joint.
+        handle: ImpulseJointHandle,
+    },
+    /// [`PhysicsWorld::insert_multibody_joint`].
+    InsertMultibodyJoint {
+        /// The handle the joint was given.
+        handle: MultibodyJointHandle,
+        /// The parent rigid-body of the joint.
+        body1: RigidBodyHandle,
+        /// The child rigid-body of the joint.
+        body2: RigidBodyHandle,
+        /// The joint.
+        joint: Box<GenericJoint>,
+    },
+    /// [`PhysicsWorld::remove_multibody_joint`].
+    RemoveMultibodyJoint {
+        /// The removed joint.
+        handle: MultibodyJointHandle,
+    },
+}
+
+impl ReplayInput {
+    /// Applies this input to `world`.
+    ///
+    /// Returns `false` if this is an insertion and the inserted object got a different handle
+    /// than the recorded one. Inputs targeting a rigid-body that doesn't exist are ignored.
+    pub fn apply(&self, world: &mut PhysicsWorld) -> bool {
+        match self {
+            Self::InsertBody { handle, body } => {
+                return world.insert_body((**body).clone()) == *handle;
+            }
+            Self::RemoveBody { handle } => {
+                let _ = world.remove_body(*handle);
+            }
+            Self::InsertCollider {
+                handle,
+                collider,
+                parent,
+            } => {
+                return world.insert_collider((**collider).clone(), *parent) == *handle;
+            }
+            Self::RemoveCollider { handle } => {
+                let _ = world.remove_collider(*handle);
+            }
+            Self::InsertImpulseJoint {
+                handle,
+                body1,
+                body2,
+                joint,
+            } => {
+                return world.insert_impulse_joint(*body1, *body2, **joint) == *handle;
+            }
+            Self::RemoveImpulseJoint { handle } => {
+                let _ = world.remove_impulse_joint(*handle);
+            }
+            Self::InsertMultibodyJoint {
+                handle,
+                body1,
+                body2,
+                joint,
+            } => {
+                return world.insert_multibody_joint(*body1, *body2, **joint) == Some(*handle);
+            }
+            Self::RemoveMultibodyJoint { handle } => world.remove_multibody_joint(*handle),
+            _ => {
+                if let Some(rb) = world.bodies.get_mut(self.body()) {
+                    self.apply_to_body(rb);
+                }
+            }
+        }
+        true
+    }
+
+    /// The rigid-body targeted by a rigid-body input.
+    fn body(&self) -> RigidBodyHandle {
+        match self {
+            Self::AddForce { body, .. }
+            | Self::AddForceAtPoint { body, .. }
+            | Self::AddTorque { body, .. }
+            | Self::ResetForces { body, .. }
+            | Self::ApplyImpulse { body, .. }
+            | Self::ApplyImpulseAtPoint { body, .. }
+            | Self::ApplyTorqueImpulse { body, .. }
+            | Self::SetLinvel { body, .. }
+            | Self::SetAngvel { body, .. }
+            | Self::SetPosition { body, .. }
+            | Self::SetNextKinematicPosition { body, .. } => *body,
+            Self::InsertBody { handle, .. } | Self::RemoveBody { handle } => *handle,
+            Self::InsertCollider { .. }
+            | Self::RemoveCollider { .. }
+            | Self::InsertImpulseJoint { .. }
+            | Self::RemoveImpulseJoint { .. }
+            | Self::InsertMultibodyJoint { .. }
+            | Self::RemoveMultibodyJoint { .. } => RigidBodyHandle::invalid(),
+        }
+    }
+
+    fn apply_to_body(&self, rb: &mut RigidBody) {
+        match *self {
+            Self::AddForce { force, wake_up, .. } => rb.add_force(force, wake_up),
+            Self::AddForceAtPoint {
+                force,
+                point,
+                wake_up,
+                ..
+            } => rb.add_force_at_point(force, point, wake_up),
+            Self::AddTorque {
+                torque, wake_up, ..
+            } => rb.add_torque(torque, wake_up),
+            Self::ResetForces { wake_up, .. } => {
+                rb.reset_forces(wake_up);
+                rb.reset_torques(wake_up);
+            }
+            Self::ApplyImpulse {
+                impulse, wake_up, ..
+            } => rb.apply_impulse(impulse, wake_up),
+            Self::ApplyImpulseAtPoint {
+                impulse,
+                point,
+                wake_up,
+                ..
+            } => rb.apply_impulse_at_point(impulse, point, wake_up),
+            Self::ApplyTorqueImpulse {
+                torque_impulse,
+                wake_up,
+                ..
+            } => rb.apply_torque_impulse(torque_impulse, wake_up),
+            Self::SetLinvel {
+                linvel, wake_up, ..
+            } => rb.set_linvel(linvel, wake_up),
+            Self::SetAngvel {
+                angvel, wake_up, ..
+            } => rb.set_angvel(angvel, wake_up),
+            Self::SetPosition {
+                position, wake_up, ..
+            } => rb.set_position(position, wake_up),
+            Self::SetNextKinematicPosition { position, .. } => {
+                rb.set_next_kinematic_position(position)
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The inputs applied before one step, and the state hash the step ended with.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct ReplayFrame {
+    /// The inputs applied, in order, before the step.
+    pub inputs: Vec<ReplayInput>,
+    /// The [`PhysicsWorld::state_hash`] at the end of the step.
+    pub state_hash: u64,
+}
+
+/// A recorded simulation: an initial state and the inputs applied before each step.
+///
+/// Built by a [`ReplayRecorder`], and re-simulated by a [`Replayer`]. With the
+/// `serde-serialize` feature, a replay can be saved and loaded with any serde format, e.g., to
+/// check in CI that an `enhanced-determinism` build reproduces a simulation recorded on another
+/// platform.
+///
+/// The initial state is the state serialized with a [`PhysicsWorld`], plus the force fields and
+/// gravity provider of its pipeline when they can be copied (see [`ForceField::clone_dyn`] and
+/// [`GravityProvider::clone_dyn`]); the force fields are copied only if all of them can be.
+/// The force fields and gravity provider that weren't copied, the physics hooks and the event
+/// handler must be set up identically on the replaying side, e.g., with
+/// [`Replayer::world_mut`]. A deserialized replay has no force fields nor gravity provider.
+///
+/// [`ForceField::clone_dyn`]: crate::dynamics::ForceField::clone_dyn
+/// [`GravityProvider::clone_dyn`]: crate::dynamics::GravityProvider::clone_dyn
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Replay {
+    /// The state of the world when the recording started.
+    pub initial_state: PhysicsWorld,
+    /// One frame per recorded step.
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// The number of recorded steps.
+    pub fn num_steps(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Starts re-simulating this replay from a copy of its initial state.
+    pub fn replayer(&self) -> Replayer<'_> {
+        Replayer {
+            replay: self,
+            world: snapshot(&self.initial_state),
+            next_frame: 0,
+        }
+    }
+
+    /// Re-simulates the whole replay, checking the state hash of every step.
+    ///
+    /// Returns the world at the end of the replay, or the first divergence.
+    pub fn run(&self) -> Result<PhysicsWorld, ReplayError> {
+        let mut replayer = self.replayer();
+        while replayer.step()? {}
+        Ok(replayer.world)
+    }
+}
+
+/// Records the inputs applied to a [`PhysicsWorld`] and the state hash after each step.
+///
+/// Every input must go through the recorder (which applies it to the world) for the replay to
+/// reproduce the simulation.
+///
+/// # Example
+/// ```
+/// # use rapier3d::prelude::*;
+/// let mut world = PhysicsWorld::default();
+/// let mut recorder = ReplayRecorder::new(&world);
+/// let ball = recorder.insert_body(&mut world, RigidBodyBuilder::dynamic());
+/// recorder.insert_collider(&mut world, ColliderBuilder::ball(0.5), Some(ball));
+///
+/// for i in 0..10 {
+///     if i == 5 {
+///         recorder.record(
+///             &mut world,
+///             ReplayInput::ApplyImpulse { body: ball, impulse: Vector::Y, wake_up: true },
+///         );
+///     }
+///     recorder.step(&mut world);
+/// }
+///
+/// let replay = recorder.finish();
+/// let replayed = replay.run().expect("the replay diverged");
+/// assert_eq!(replayed.bodies[ball].translation(), world.bodies[ball].translation());
+/// ```
+pub struct ReplayRecorder {
+    replay: Replay,
+    pending: Vec<ReplayInput>,
+}
+
+impl ReplayRecorder {
+    /// Starts recording from the current state of `world`.
+    pub fn new(world: &PhysicsWorld) -> Self {
+        Self {
+            replay: Replay {
+                initial_state: snapshot(world),
+                frames: Vec::new(),
+            },
+            pending: Vec::new(),
+        }
+    }
+
+    /// The replay recorded so far (the inputs recorded since the last step excluded).
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Applies `input` to `world` and records it.
+    ///
+    /// For insertions, the handle stored in `input` is ignored and replaced by the handle the
+    /// object actually gets; prefer [`Self::insert_body`], [`Self::insert_collider`],
+    /// [`Self::insert_impulse_joint`] and [`Self::insert_multibody_joint`] which return it.
+    pub fn record(&mut self, world: &mut PhysicsWorld, input: ReplayInput) {
+        match input {
+            ReplayInput::InsertBody { body, .. } => {
+                let _ = self.insert_body(world, *body);
+            }
+            ReplayInput::InsertCollider {
+                collider, parent, ..
+            } => {
+                let _ = self.insert_collider(world, *collider, parent);
+            }
+            ReplayInput::InsertImpulseJoint {
+                body1,
+                body2,
+                joint,
+                ..
+            } => {
+                let _ = self.insert_impulse_joint(world, body1, body2, *joint);
+            }
+            ReplayInput::InsertMultibodyJoint {
+                body1,
+                body2,
+                joint,
+                ..
+            } => {
+                let _ = self.insert_multibody_joint(world, body1, body2, *joint);
+            }
+            input => {
+                let _ = input.apply(world);
+                self.pending.push(input);
+            }
+        }
+    }
+
+    /// Inserts a rigid-body into `world` and records it.
+    pub fn insert_body(
+        &mut self,
+        world: &mut PhysicsWorld,
+        body: impl Into<RigidBody>,
+    ) -> RigidBodyHandle {
+        let body = body.into();
+        let handle = world.insert_body(body.clone());
+        self.pending.push(ReplayInput::InsertBody {
+            handle,
+            body: Box::new(body),
+        });
+        handle
+    }
+
+    /// Inserts a collider into `world` and records it.
+    pub fn insert_collider(
+        &mut self,
+        world: &mut PhysicsWorld,
+        collider: impl Into<Collider>,
+        parent: Option<RigidBodyHandle>,
+    ) -> ColliderHandle {
+        let collider = collider.into();
+        let handle = world.insert_collider(collider.clone(), parent);
+        self.pending.push(ReplayInput::InsertCollider {
+            handle,
+            collider: Box::new(collider),
+            parent,
+        });
+        handle
+    }
+
+    /// Inserts an impulse joint into `world` and records it.
+    pub fn insert_impulse_joint(
+        &mut self,
+        world: &mut PhysicsWorld,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        joint: impl Into<GenericJoint>,
+    ) -> ImpulseJointHandle {
+        let joint = joint.into();
+        let handle = world.insert_impulse_joint(body1, body2, joint);
+        self.pending.push(ReplayInput::InsertImpulseJoint {
+            handle,
+            body1,
+            body2,
+            joint: Box::new(joint),
+        });
+        handle
+    }
+
+    /// Inserts a multibody joint into `world` and records it.
+    ///
+    /// Nothing is recorded if the joint couldn't be inserted, i.e., if it returns `None`.
+    pub fn insert_multibody_joint(
+        &mut self,
+        world: &mut PhysicsWorld,
+        body1: RigidBodyHandle,
+        body2: RigidBodyHandle,
+        joint: impl Into<GenericJoint>,
+    ) -> Option<MultibodyJointHandle> {
+        let joint = joint.into();
+        let handle = world.insert_multibody_joint(body1, body2, joint)?;
+        self.pending.push(ReplayInput::InsertMultibodyJoint {
+            handle,
+            body1,
+            body2,
+            joint: Box::new(joint),
+        });
+        Some(handle)
+    }
+
+    /// Steps `world` and records the step.
+    pub fn step(&mut self, world: &mut PhysicsWorld) {
+        self.step_with_events(world, &(), &());
+    }
+
+    /// Steps `world` with the given hooks and event handler, and records the step.
+    ///
+    /// The same hooks must be used when replaying.
+    pub fn step_with_events(
+        &mut self,
+        world: &mut PhysicsWorld,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        world.step_with_events(hooks, events);
+        self.replay.frames.push(ReplayFrame {
+            inputs: core::mem::take(&mut self.pending),
+            state_hash: world.state_hash(),
+        });
+    }
+
+    /// Stops recording and returns the replay.
+    ///
+    /// Inputs recorded after the last step are discarded.
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+/// A divergence detected while re-simulating a [`Replay`].
+#[derive(thiserror::Error, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// An object inserted before the step got a different handle than during the recording.
+    #[error("an insertion before step {step} returned a different handle than recorded")]
+    HandleMismatch {
+        /// The index of the step the insertion preceded.
+        step: usize,
+    },
+    /// The state of the world at the end of a step differs from the recorded one.
+    #[error(
+        "the state diverged at step {step} (expected hash {expected_hash:#018x}, got {actual_hash:#018x})"
+    )]
+    StateMismatch {
+        /// The index of the diverging step.
+        step: usize,
+        /// The recorded state hash.
+        expected_hash: u64,
+        /// The state hash of the replay.
+        actual_hash: u64,
+    },
+}
+
+/// Re-simulates a [`Replay`] step by step.
+pub struct Replayer<'a> {
+    replay: &'a Replay,
+    world: PhysicsWorld,
+    next_frame: usize,
+}
+
+impl Replayer<'_> {
+    /// The replayed world.
+    pub fn world(&self) -> &PhysicsWorld {
+        &self.world
+    }
+
+    /// The replayed world, e.g., to register the force fields that couldn't be copied into the
+    /// replay before the first step.
+    ///
+    /// Modifying its simulation state makes the replay diverge.
+    pub fn world_mut(&mut self) -> &mut PhysicsWorld {
+        &mut self.world
+    }
+
+    /// The index of the next step to replay.
+    pub fn next_step(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Replays the next step.
+    ///
+    /// Returns `Ok(false)` once all the steps have been replayed.
+    pub fn step(&mut self) -> Result<bool, ReplayError> {
+        self.step_with_events(&(), &())
+    }
+
+    /// Replays the next step with the given hooks and event handler.
+    pub fn step_with_events(
+        &mut self,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) -> Result<bool, ReplayError> {
+        let Some(frame) = self.replay.frames.get(self.next_frame) else {
+            return Ok(false);
+        };
+        let step = self.next_frame;
+        self.next_frame += 1;
+
+        for input in &frame.inputs {
+            if !input.apply(&mut self.world) {
+                return Err(ReplayError::HandleMismatch { step });
+            }
+        }
+        self.world.step_with_events(hooks, events);
+
+        let actual_hash = self.world.state_hash();
+        if actual_hash != frame.state_hash {
+            return Err(ReplayError::StateMismatch {
+                step,
+                expected_hash: frame.state_hash,
+                actual_hash,
+            });
+        }
+        Ok(true)
+    }
+
+    /// Stops replaying and returns the replayed world.
+    pub fn into_world(self) -> PhysicsWorld {
+        self.world
+    }
+}
+
+/// A copy of the simulation state of `world`: everything a serialized [`PhysicsWorld`] holds,
+/// plus what can be copied of the force fields and gravity provider of its pipeline, with a
+/// fresh CCD workspace.
+fn snapshot(world: &PhysicsWorld) -> PhysicsWorld {
+    // Destructured so that a new field has to be considered here.
+    let PhysicsWorld {
+        gravity,
+        integration_parameters,
+        physics_pipeline,
+        islands,
+        broad_phase,
+        narrow_phase,
+        bodies,
+        colliders,
+        impulse_joints,
+        multibody_joints,
+        ccd_solver: _,
+    } = world;
+    PhysicsWorld {
+        gravity: *gravity,
+        integration_parameters: *integration_parameters,
+        physics_pipeline: physics_pipeline.clone_simulation_state(),
+        islands: islands.clone(),
+        broad_phase: broad_phase.clone(),
+        narrow_phase: narrow_phase.clone(),
+        bodies: bodies.clone(),
+        colliders: colliders.clone(),
+        impulse_joints: impulse_joints.clone(),
+        multibody_joints: multibody_joints.clone(),
+        ccd_solver: CCDSolver::new(),
+    }
+}
@@ -0,0 +1,194 @@
+//! Delta snapshots of a whole [`PhysicsWorld`], for rollback and state synchronization.
+
+use crate::dynamics::{
+    CCDSolver, ImpulseJointSetDelta, ImpulseJointSetDeltaTracker, IntegrationParameters,
+    IslandManagerDelta, IslandManagerDeltaTracker, MultibodyJointSetDelta,
+    MultibodyJointSetDeltaTracker, RigidBodySetDelta, RigidBodySetDeltaTracker,
+};
+use crate::geometry::{
+    BroadPhaseBvhDelta, BroadPhaseBvhDeltaTracker, ColliderSetDelta, ColliderSetDeltaTracker,
+    NarrowPhaseDelta, NarrowPhaseDeltaTracker,
+};
+use crate::math::Vector;
+use crate::pipeline::{PhysicsPipeline, PhysicsWorld};
+
+/// The parts of a [`PhysicsWorld`] that changed since the previous delta snapshot.
+///
+/// Obtained with [`PhysicsWorld::delta_snapshot`] and restored with
+/// [`PhysicsWorld::apply_delta`]. It can be serialized, e.g., to be kept in a rollback buffer
+/// or sent over the network.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PhysicsWorldDelta {
+    index: u64,
+    gravity: Vector,
+    integration_parameters: IntegrationParameters,
+    islands: IslandManagerDelta,
+    broad_phase: BroadPhaseBvhDelta,
+    narrow_phase: NarrowPhaseDelta,
+    bodies: RigidBodySetDelta,
+    colliders: ColliderSetDelta,
+    impulse_joints: ImpulseJointSetDelta,
+    multibody_joints: MultibodyJointSetDelta,
+}
+
+impl PhysicsWorldDelta {
+    /// The number of snapshots taken with the same tracker before this one.
+    ///
+    /// Deltas must be applied in the order of their indices, starting with index 0.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Whether this is the first snapshot taken with its tracker, holding the whole world.
+    ///
+    /// A keyframe can be applied to any world.
+    pub fn is_keyframe(&self) -> bool {
+        self.index == 0
+    }
+
+    /// The part of this delta about the world's rigid-bodies.
+    pub fn bodies(&self) -> &RigidBodySetDelta {
+        &self.bodies
+    }
+
+    /// The part of this delta about the world's colliders.
+    pub fn colliders(&self) -> &ColliderSetDelta {
+        &self.colliders
+    }
+
+    /// The part of this delta about the world's broad-phase.
+    pub fn broad_phase(&self) -> &BroadPhaseBvhDelta {
+        &self.broad_phase
+    }
+
+    /// The part of this delta about the world's contact and intersection pairs.
+    pub fn narrow_phase(&self) -> &NarrowPhaseDelta {
+        &self.narrow_phase
+    }
+
+    /// The part of this delta about the world's impulse joints.
+    pub fn impulse_joints(&self) -> &ImpulseJointSetDelta {
+        &self.impulse_joints
+    }
+
+    /// The part of this delta about the world's multibody joints.
+    pub fn multibody_joints(&self) -> &MultibodyJointSetDelta {
+        &self.multibody_joints
+    }
+}
+
+/// Remembers what a [`PhysicsWorld`] looked like at its last delta snapshot.
+///
+/// A tracker must only be used with a single world. Create a new one to start over with a
+/// keyframe.
+#[derive(Clone, Debug, Default)]
+pub struct PhysicsWorldDeltaTracker {
+    num_snapshots: u64,
+    islands: IslandManagerDeltaTracker,
+    broad_phase: BroadPhaseBvhDeltaTracker,
+    narrow_phase: NarrowPhaseDeltaTracker,
+    bodies: RigidBodySetDeltaTracker,
+    colliders: ColliderSetDeltaTracker,
+    impulse_joints: ImpulseJointSetDeltaTracker,
+    multibody_joints: MultibodyJointSetDeltaTracker,
+}
+
+impl PhysicsWorldDeltaTracker {
+    /// A tracker whose first snapshot will be a keyframe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicsWorld {
+    /// The parts of this world that changed since the previous snapshot taken with `tracker`.
+    ///
+    /// The first snapshot taken with a tracker is a keyframe holding the whole world. The
+    /// following ones only hold what changed since the previous one:
+    /// - the rigid-bodies, colliders and multibodies accessed mutably, by the user or the
+    ///   pipeline (so sleeping parts of the world cost nothing);
+    /// - the contact pairs, intersection pairs, impulse joints and island lists that changed;
+    /// - the broad-phase pairs of the colliders that gained or lost one, and the broad-phase
+    ///   tree if any of its leaves moved;
+    /// - the gravity and integration parameters.
+    ///
+    /// Each set records the elements it modifies or hands out mutably (the ones it also reports
+    /// as modified to the pipeline, plus the ones the pipeline modifies internally), so finding
+    /// them doesn't visit the others. The elements rewritten in place by the pipeline's internal
+    /// passes (contact pairs, joints, islands) are compared one by one through a hash of their
+    /// serialized form instead.
+    ///
+    /// These changes are recorded by the world itself, independently of the other worlds, so
+    /// its snapshots must all be taken with the same tracker: a new tracker starts over with a
+    /// keyframe.
+    ///
+    /// Applying a keyframe then each following delta, in order, with
+    /// [`PhysicsWorld::apply_delta`] gives a world that serializes to the same bytes as this one
+    /// did when the last of them was taken, and that continues the simulation identically.
+    pub fn delta_snapshot(&mut self, tracker: &mut PhysicsWorldDeltaTracker) -> PhysicsWorldDelta {
+        // Destructured so that a new field has to be considered here.
+        let PhysicsWorld {
+            gravity,
+            integration_parameters,
+            physics_pipeline: _,
+            islands,
+            broad_phase,
+            narrow_phase,
+            bodies,
+            colliders,
+            impulse_joints,
+            multibody_joints,
+            ccd_solver: _,
+        } = self;
+
+        let index = tracker.num_snapshots;
+        tracker.num_snapshots += 1;
+        PhysicsWorldDelta {
+            index,
+            gravity: *gravity,
+            integration_parameters: *integration_parameters,
+            islands: islands.delta_snapshot(&mut tracker.islands),
+            broad_phase: broad_phase.delta_snapshot(&mut tracker.broad_phase),
+            narrow_phase: narrow_phase.delta_snapshot(&mut tracker.narrow_phase),
+            bodies: bodies.delta_snapshot(&mut tracker.bodies),
+            colliders: colliders.delta_snapshot(&mut tracker.colliders),
+            impulse_joints: impulse_joints.delta_snapshot(&mut tracker.impulse_joints),
+            multibody_joints: multibody_joints.delta_snapshot(&mut tracker.multibody_joints),
+        }
+    }
+
+    /// Brings this world from the state of the previous delta snapshot to the state of `delta`.
+    ///
+    /// Deltas must be applied in the order they were taken (see [`PhysicsWorldDelta::index`]),
+    /// starting with a keyframe, which can be applied to any world. To roll back, apply the
+    /// keyframe again followed by the deltas up to the desired step.
+    ///
+    /// As with a deserialized world, stepping then continues the simulation exactly as the world
+    /// the snapshots were taken from did. To that end, the physics pipeline and CCD solver are
+    /// replaced by new ones, which only keep the pipeline's force fields (and thread pool).
+    ///
+    /// # Panics
+    ///
+    /// Panics if this world isn't in the state of the previous snapshot (only partially
+    /// detected: applying deltas out of order usually gives a corrupted world instead).
+    pub fn apply_delta(&mut self, delta: &PhysicsWorldDelta) {
+        self.gravity = delta.gravity;
+        self.integration_parameters = delta.integration_parameters;
+        self.islands.apply_delta(&delta.islands);
+        self.broad_phase.apply_delta(&delta.broad_phase);
+        self.narrow_phase.apply_delta(&delta.narrow_phase);
+        self.bodies.apply_delta(&delta.bodies);
+        self.colliders.apply_delta(&delta.colliders);
+        self.impulse_joints.apply_delta(&delta.impulse_joints);
+        self.multibody_joints.apply_delta(&delta.multibody_joints);
+
+        // The pipeline's and CCD solver's caches describe the state before the restore.
+        let mut physics_pipeline = PhysicsPipeline::new();
+        physics_pipeline.force_fields = core::mem::take(&mut self.physics_pipeline.force_fields);
+        physics_pipeline.gravity_provider = self.physics_pipeline.gravity_provider.take();
+        #[cfg(all(feature = "parallel", not(feature = "unsync-callbacks")))]
+        physics_pipeline.set_thread_pool(self.physics_pipeline.thread_pool());
+        self.physics_pipeline = physics_pipeline;
+        self.ccd_solver = CCDSolver::new();
+    }
+}
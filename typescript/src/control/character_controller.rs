@@ -38,11 +38,7 @@ impl RawKinematicCharacterController {
 
         Self {
             controller,
-            result: EffectiveCharacterMovement {
-                translation: Vector::ZERO,
-                grounded: false,
-                is_sliding_down_slope: false,
-            },
+            result: EffectiveCharacterMovement::default(),
             events: vec![],
         }
     }